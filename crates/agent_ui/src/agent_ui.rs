@@ -624,6 +624,7 @@ fn update_command_palette_filter(cx: &mut App) {
                 | EditPredictionProvider::Ollama
                 | EditPredictionProvider::OpenAiCompatibleApi
                 | EditPredictionProvider::Mercury
+                | EditPredictionProvider::LanguageServer
                 | EditPredictionProvider::Experimental(_) => {
                     filter.show_namespace("edit_prediction");
                     filter.hide_namespace("copilot");
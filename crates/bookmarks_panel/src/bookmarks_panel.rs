@@ -0,0 +1,1270 @@
+mod bookmarks_panel_settings;
+
+use std::{ops::Range, path::Path, sync::Arc};
+
+use bookmarks_panel_settings::BookmarksPanelSettings;
+use collections::HashMap;
+use editor::{Editor, EditorEvent};
+use gpui::{
+    Action, App, AsyncWindowContext, ClickEvent, Context, DismissEvent, Entity, EntityId,
+    EventEmitter, FocusHandle, Focusable, KeyContext, MouseButton, MouseDownEvent, Pixels, Point,
+    ScrollStrategy, Subscription, Task, UniformListScrollHandle, WeakEntity, Window, actions,
+    anchored, deferred, uniform_list,
+};
+use language::BufferEvent;
+use menu::{Cancel, Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{
+    Fs, Project,
+    bookmark_store::{BookmarkStore, SerializedBookmark},
+};
+use settings::Settings;
+use ui::{ContextMenu, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{
+    OpenOptions, OpenVisible, Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+actions!(
+    bookmarks_panel,
+    [
+        /// Toggles focus on the bookmarks panel.
+        ToggleFocus,
+        /// Edits the label of the selected bookmark.
+        RenameBookmark,
+        /// Edits the note of the selected bookmark.
+        EditBookmarkNote,
+        /// Moves the selected bookmark up among the bookmarks of its file.
+        MoveBookmarkUp,
+        /// Moves the selected bookmark down among the bookmarks of its file.
+        MoveBookmarkDown,
+        /// Removes the selected bookmark.
+        DeleteBookmark,
+    ]
+);
+
+const BOOKMARKS_PANEL_KEY: &str = "BookmarksPanel";
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<BookmarksPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Lists the bookmarks of the project grouped by file,
+/// letting the user label, annotate, reorder and remove them.
+pub struct BookmarksPanel {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    bookmark_store: Entity<BookmarkStore>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    entries: Vec<ListEntry>,
+    selected: Option<SelectedBookmark>,
+    editing: Option<EditingBookmark>,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    buffer_subscriptions: HashMap<EntityId, Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+enum ListEntry {
+    File {
+        name: SharedString,
+        dir: Option<SharedString>,
+    },
+    Bookmark(BookmarkListEntry),
+}
+
+struct BookmarkListEntry {
+    abs_path: Arc<Path>,
+    bookmark: SerializedBookmark,
+    /// Text of the bookmarked line, known once the file has been opened
+    line_text: Option<SharedString>,
+    is_first_in_file: bool,
+    is_last_in_file: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SelectedBookmark {
+    abs_path: Arc<Path>,
+    row: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EditedField {
+    Label,
+    Note,
+}
+
+struct EditingBookmark {
+    bookmark: SelectedBookmark,
+    field: EditedField,
+    editor: Entity<Editor>,
+    _subscription: Subscription,
+}
+
+impl BookmarksPanel {
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<anyhow::Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                cx.new(|cx| Self::new(workspace, window, cx))
+            })
+        })
+    }
+
+    fn new(workspace: &Workspace, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let project = workspace.project().clone();
+        let bookmark_store = project.read(cx).bookmark_store();
+        let subscriptions = vec![
+            cx.observe_in(&bookmark_store, window, |this, _, _, cx| {
+                this.update_entries(cx);
+            }),
+            cx.observe_global_in::<settings::SettingsStore>(window, |_, _, cx| cx.notify()),
+        ];
+
+        let mut this = Self {
+            workspace: workspace.weak_handle(),
+            project,
+            bookmark_store,
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            entries: Vec::new(),
+            selected: None,
+            editing: None,
+            context_menu: None,
+            buffer_subscriptions: HashMap::default(),
+            _subscriptions: subscriptions,
+        };
+        this.update_entries(cx);
+        this
+    }
+
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        let bookmarks = self.bookmark_store.read(cx).all_serialized_bookmarks(cx);
+        let path_style = self.project.read(cx).path_style(cx);
+
+        let mut entries = Vec::new();
+        let mut buffer_subscriptions = HashMap::default();
+        for (abs_path, file_bookmarks) in bookmarks {
+            let (name, dir) = match self.project.read(cx).find_worktree(&abs_path, cx) {
+                Some((_, relative_path)) => (
+                    relative_path
+                        .file_name()
+                        .map(|name| name.to_string())
+                        .unwrap_or_default(),
+                    relative_path
+                        .parent()
+                        .filter(|parent| !parent.is_empty())
+                        .map(|parent| parent.display(path_style).into_owned()),
+                ),
+                None => (
+                    abs_path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                    abs_path
+                        .parent()
+                        .map(|parent| parent.to_string_lossy().into_owned()),
+                ),
+            };
+            entries.push(ListEntry::File {
+                name: name.into(),
+                dir: dir.map(SharedString::from),
+            });
+
+            let buffer = self
+                .bookmark_store
+                .read(cx)
+                .loaded_buffer(&abs_path)
+                .cloned();
+            if let Some(buffer) = &buffer {
+                let subscription = self.buffer_subscriptions.remove(&buffer.entity_id());
+                buffer_subscriptions.insert(
+                    buffer.entity_id(),
+                    subscription.unwrap_or_else(|| {
+                        cx.subscribe(buffer, |this, _, event: &BufferEvent, cx| {
+                            if let BufferEvent::Edited { .. } = event {
+                                this.update_entries(cx);
+                            }
+                        })
+                    }),
+                );
+            }
+            let snapshot = buffer.map(|buffer| buffer.read(cx).snapshot());
+
+            let bookmark_count = file_bookmarks.len();
+            for (ix, bookmark) in file_bookmarks.into_iter().enumerate() {
+                let line_text = snapshot.as_ref().map(|snapshot| {
+                    let line_start = language::Point::new(bookmark.row, 0);
+                    let line_end =
+                        language::Point::new(bookmark.row, snapshot.line_len(bookmark.row));
+                    SharedString::from(
+                        snapshot
+                            .text_for_range(line_start..line_end)
+                            .collect::<String>()
+                            .trim()
+                            .to_string(),
+                    )
+                });
+                entries.push(ListEntry::Bookmark(BookmarkListEntry {
+                    abs_path: abs_path.clone(),
+                    bookmark,
+                    line_text,
+                    is_first_in_file: ix == 0,
+                    is_last_in_file: ix + 1 == bookmark_count,
+                }));
+            }
+        }
+
+        self.entries = entries;
+        self.buffer_subscriptions = buffer_subscriptions;
+        if self.selected_index().is_none() {
+            self.selected = None;
+        }
+        if let Some(editing) = &self.editing
+            && self.index_of(&editing.bookmark).is_none()
+        {
+            self.editing = None;
+        }
+        cx.notify();
+    }
+
+    fn index_of(&self, bookmark: &SelectedBookmark) -> Option<usize> {
+        self.entries.iter().position(|entry| match entry {
+            ListEntry::Bookmark(entry) => {
+                entry.abs_path == bookmark.abs_path && entry.bookmark.row == bookmark.row
+            }
+            ListEntry::File { .. } => false,
+        })
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.selected
+            .as_ref()
+            .and_then(|selected| self.index_of(selected))
+    }
+
+    fn selected_entry(&self) -> Option<&BookmarkListEntry> {
+        match self.entries.get(self.selected_index()?)? {
+            ListEntry::Bookmark(entry) => Some(entry),
+            ListEntry::File { .. } => None,
+        }
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if let Some(ListEntry::Bookmark(entry)) = self.entries.get(ix) {
+            self.selected = Some(SelectedBookmark {
+                abs_path: entry.abs_path.clone(),
+                row: entry.bookmark.row,
+            });
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+            cx.notify();
+        }
+    }
+
+    fn bookmark_indices(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| matches!(entry, ListEntry::Bookmark(_)))
+            .map(|(ix, _)| ix)
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let next_ix = match self.selected_index() {
+            Some(selected_ix) => self.bookmark_indices().find(|ix| *ix > selected_ix),
+            None => self.bookmark_indices().next(),
+        };
+        if let Some(ix) = next_ix {
+            self.select_index(ix, cx);
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let previous_ix = match self.selected_index() {
+            Some(selected_ix) => self.bookmark_indices().rfind(|ix| *ix < selected_ix),
+            None => self.bookmark_indices().next_back(),
+        };
+        if let Some(ix) = previous_ix {
+            self.select_index(ix, cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.bookmark_indices().next() {
+            self.select_index(ix, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.bookmark_indices().next_back() {
+            self.select_index(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing.is_some() {
+            self.finish_editing(true, window, cx);
+        } else if let Some(entry) = self.selected_entry() {
+            let abs_path = entry.abs_path.clone();
+            let row = entry.bookmark.row;
+            self.open_bookmark(abs_path, row, window, cx);
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing.is_some() {
+            self.finish_editing(false, window, cx);
+        } else {
+            self.selected = None;
+            cx.notify();
+        }
+    }
+
+    fn open_bookmark(
+        &mut self,
+        abs_path: Arc<Path>,
+        row: u32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(open_task) = self
+            .workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_abs_path(
+                    abs_path.to_path_buf(),
+                    OpenOptions {
+                        visible: Some(OpenVisible::None),
+                        ..Default::default()
+                    },
+                    window,
+                    cx,
+                )
+            })
+            .log_err()
+        else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |_, cx| {
+            let item = open_task.await?;
+            if let Some(editor) = item.downcast::<Editor>() {
+                editor.update_in(cx, |editor, window, cx| {
+                    editor.go_to_singleton_buffer_point(language::Point::new(row, 0), window, cx);
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn rename_bookmark(&mut self, _: &RenameBookmark, window: &mut Window, cx: &mut Context<Self>) {
+        self.start_editing(EditedField::Label, window, cx);
+    }
+
+    fn edit_bookmark_note(
+        &mut self,
+        _: &EditBookmarkNote,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.start_editing(EditedField::Note, window, cx);
+    }
+
+    fn start_editing(&mut self, field: EditedField, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let bookmark = SelectedBookmark {
+            abs_path: entry.abs_path.clone(),
+            row: entry.bookmark.row,
+        };
+        let (current_text, placeholder) = match field {
+            EditedField::Label => (entry.bookmark.label.clone(), "Bookmark label…"),
+            EditedField::Note => (entry.bookmark.note.clone(), "Bookmark note…"),
+        };
+
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(placeholder, window, cx);
+            if let Some(current_text) = current_text {
+                editor.set_text(current_text.as_ref(), window, cx);
+                editor.select_all(&editor::actions::SelectAll, window, cx);
+            }
+            editor
+        });
+        let subscription = cx.subscribe_in(&editor, window, |this, _, event, window, cx| {
+            if let EditorEvent::Blurred = event {
+                this.finish_editing(true, window, cx);
+            }
+        });
+        window.focus(&editor.focus_handle(cx), cx);
+
+        self.editing = Some(EditingBookmark {
+            bookmark,
+            field,
+            editor,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    fn finish_editing(&mut self, save: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(editing) = self.editing.take() else {
+            return;
+        };
+        if save {
+            let text = editing.editor.read(cx).text(cx);
+            let SelectedBookmark { abs_path, row } = &editing.bookmark;
+            self.bookmark_store
+                .update(cx, |bookmark_store, cx| match editing.field {
+                    EditedField::Label => {
+                        bookmark_store.set_bookmark_label(abs_path, *row, &text, cx)
+                    }
+                    EditedField::Note => {
+                        bookmark_store.set_bookmark_note(abs_path, *row, &text, cx)
+                    }
+                });
+        }
+        if editing.editor.focus_handle(cx).is_focused(window) {
+            window.focus(&self.focus_handle, cx);
+        }
+        cx.notify();
+    }
+
+    fn move_bookmark_up(&mut self, _: &MoveBookmarkUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_selected_bookmark(-1, cx);
+    }
+
+    fn move_bookmark_down(&mut self, _: &MoveBookmarkDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_selected_bookmark(1, cx);
+    }
+
+    fn move_selected_bookmark(&mut self, offset: isize, cx: &mut Context<Self>) {
+        let Some(selected_ix) = self.selected_index() else {
+            return;
+        };
+        let Some(ListEntry::Bookmark(entry)) = self.entries.get(selected_ix) else {
+            return;
+        };
+        let index_in_file = self.entries[..selected_ix]
+            .iter()
+            .rev()
+            .take_while(|entry| matches!(entry, ListEntry::Bookmark(_)))
+            .count();
+        let Some(new_index) = index_in_file.checked_add_signed(offset) else {
+            return;
+        };
+        let abs_path = entry.abs_path.clone();
+        let row = entry.bookmark.row;
+        self.bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.move_bookmark(&abs_path, row, new_index, cx);
+        });
+        if let Some(ix) = self.selected_index() {
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+        }
+    }
+
+    fn delete_bookmark(&mut self, _: &DeleteBookmark, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(selected_ix) = self.selected_index() else {
+            return;
+        };
+        let Some(ListEntry::Bookmark(entry)) = self.entries.get(selected_ix) else {
+            return;
+        };
+        let abs_path = entry.abs_path.clone();
+        let row = entry.bookmark.row;
+        // Keep a selection close to the removed bookmark
+        let next_selection = self
+            .bookmark_indices()
+            .find(|ix| *ix > selected_ix)
+            .or_else(|| self.bookmark_indices().rfind(|ix| *ix < selected_ix))
+            .and_then(|ix| match &self.entries[ix] {
+                ListEntry::Bookmark(entry) => Some(SelectedBookmark {
+                    abs_path: entry.abs_path.clone(),
+                    row: entry.bookmark.row,
+                }),
+                ListEntry::File { .. } => None,
+            });
+
+        self.selected = next_selection;
+        self.bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.remove_bookmark(&abs_path, row, cx);
+        });
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_index(ix, cx);
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let can_move_up = !entry.is_first_in_file;
+        let can_move_down = !entry.is_last_in_file;
+
+        let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
+            menu.context(self.focus_handle.clone())
+                .action("Go to Bookmark", Box::new(Confirm))
+                .separator()
+                .action("Rename", Box::new(RenameBookmark))
+                .action("Edit Note", Box::new(EditBookmarkNote))
+                .separator()
+                .action_disabled_when(!can_move_up, "Move Up", Box::new(MoveBookmarkUp))
+                .action_disabled_when(!can_move_down, "Move Down", Box::new(MoveBookmarkDown))
+                .separator()
+                .action("Delete", Box::new(DeleteBookmark))
+        });
+        window.focus(&context_menu.focus_handle(cx), cx);
+        let subscription = cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
+            this.context_menu.take();
+            cx.notify();
+        });
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn dispatch_context(&self, window: &Window, cx: &App) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("BookmarksPanel");
+        dispatch_context.add("menu");
+        let identifier = if self
+            .editing
+            .as_ref()
+            .is_some_and(|editing| editing.editor.focus_handle(cx).is_focused(window))
+        {
+            "editing"
+        } else {
+            "not_editing"
+        };
+        dispatch_context.add(identifier);
+        dispatch_context
+    }
+
+    fn render_entries(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        let selected_ix = self.selected_index();
+        range
+            .map(|ix| match &self.entries[ix] {
+                ListEntry::File { name, dir } => h_flex()
+                    .id(("bookmark-file", ix))
+                    .px_2()
+                    .pt_2()
+                    .pb_0p5()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::File)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(Label::new(name.clone()).size(LabelSize::Small))
+                    .children(dir.clone().map(|dir| {
+                        Label::new(dir)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate()
+                    }))
+                    .into_any_element(),
+                ListEntry::Bookmark(entry) => self
+                    .render_bookmark(ix, entry, selected_ix == Some(ix), cx)
+                    .into_any_element(),
+            })
+            .collect()
+    }
+
+    fn render_bookmark(
+        &self,
+        ix: usize,
+        entry: &BookmarkListEntry,
+        is_selected: bool,
+        cx: &mut Context<Self>,
+    ) -> ListItem {
+        let editing = self.editing.as_ref().filter(|editing| {
+            editing.bookmark.abs_path == entry.abs_path
+                && editing.bookmark.row == entry.bookmark.row
+        });
+        let editor_for = |field: EditedField| {
+            editing
+                .filter(|editing| editing.field == field)
+                .map(|editing| editing.editor.clone())
+        };
+
+        let title = match (editor_for(EditedField::Label), &entry.bookmark.label) {
+            (Some(editor), _) => editor.into_any_element(),
+            (None, Some(label)) => Label::new(label.clone()).truncate().into_any_element(),
+            (None, None) => Label::new(
+                entry
+                    .line_text
+                    .clone()
+                    .filter(|line_text| !line_text.is_empty())
+                    .unwrap_or_else(|| format!("Line {}", entry.bookmark.row + 1).into()),
+            )
+            .color(Color::Muted)
+            .truncate()
+            .into_any_element(),
+        };
+        let note = match (editor_for(EditedField::Note), &entry.bookmark.note) {
+            (Some(editor), _) => Some(editor.into_any_element()),
+            (None, Some(note)) => Some(
+                Label::new(note.clone())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .italic()
+                    .truncate()
+                    .into_any_element(),
+            ),
+            (None, None) => None,
+        };
+
+        ListItem::new(("bookmark", ix))
+            .inset(true)
+            .indent_level(1)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(is_selected)
+            .start_slot(
+                Icon::new(IconName::Bookmark)
+                    .size(IconSize::Small)
+                    .color(Color::Accent),
+            )
+            .end_slot(
+                Label::new((entry.bookmark.row + 1).to_string())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                this.select_index(ix, cx);
+                if let Some(entry) = this.selected_entry()
+                    && this.editing.is_none()
+                {
+                    let abs_path = entry.abs_path.clone();
+                    let row = entry.bookmark.row;
+                    this.open_bookmark(abs_path, row, window, cx);
+                }
+                if event.click_count() > 1 {
+                    this.start_editing(EditedField::Label, window, cx);
+                } else {
+                    window.focus(&this.focus_handle, cx);
+                }
+            }))
+            .on_secondary_mouse_down(cx.listener(
+                move |this, event: &MouseDownEvent, window, cx| {
+                    cx.stop_propagation();
+                    this.deploy_context_menu(event.position, ix, window, cx);
+                },
+            ))
+            .child(v_flex().w_full().min_w_0().child(title).children(note))
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .p_4()
+            .gap_1()
+            .items_center()
+            .justify_center()
+            .child(Label::new("No Bookmarks"))
+            .child(
+                Label::new("Bookmarks toggled in the editor are listed here.")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+    }
+}
+
+impl Render for BookmarksPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("bookmarks-panel")
+            .size_full()
+            .overflow_hidden()
+            .key_context(self.dispatch_context(window, cx))
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::rename_bookmark))
+            .on_action(cx.listener(Self::edit_bookmark_note))
+            .on_action(cx.listener(Self::move_bookmark_up))
+            .on_action(cx.listener(Self::move_bookmark_down))
+            .on_action(cx.listener(Self::delete_bookmark))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, window, cx| {
+                    window.focus(&this.focus_handle, cx);
+                }),
+            )
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(self.render_empty_state())
+                } else {
+                    this.child(
+                        uniform_list(
+                            "bookmarks",
+                            self.entries.len(),
+                            cx.processor(|this, range: Range<usize>, _, cx| {
+                                this.render_entries(range, cx)
+                            }),
+                        )
+                        .size_full()
+                        .track_scroll(&self.scroll_handle),
+                    )
+                }
+            })
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(gpui::Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
+    }
+}
+
+impl Focusable for BookmarksPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for BookmarksPanel {}
+
+impl Panel for BookmarksPanel {
+    fn persistent_name() -> &'static str {
+        "Bookmarks Panel"
+    }
+
+    fn panel_key() -> &'static str {
+        BOOKMARKS_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        BookmarksPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.bookmarks_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn default_size(&self, _: &Window, cx: &App) -> Pixels {
+        BookmarksPanelSettings::get_global(cx).default_width
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        // Opens the bookmarked files in the background, to show their lines
+        if active {
+            let bookmark_store = self.bookmark_store.clone();
+            cx.spawn(async move |_, cx| BookmarkStore::resolve_all(&bookmark_store, cx).await)
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        BookmarksPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::Bookmark)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Bookmarks Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::Buffer;
+    use pretty_assertions::assert_eq;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    use super::*;
+
+    const SELECTED_MARKER: &str = "  <==== selected";
+
+    #[gpui::test]
+    async fn test_bookmarks_grouped_by_file(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (project, panel, cx) = setup(cx).await;
+
+        let main = open_buffer(&project, path!("/root/src/main.rs"), cx).await;
+        let readme = open_buffer(&project, path!("/root/README.md"), cx).await;
+        toggle_bookmarks(&project, &readme, &[0], cx);
+        toggle_bookmarks(&project, &main, &[2, 0], cx);
+        cx.run_until_parked();
+
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "README.md",
+                "    1: # Readme",
+                "main.rs src",
+                "    3: let answer = 42;",
+                "    1: fn main() {",
+            ]
+        );
+
+        // Toggling a bookmark off removes its entry, and the file once it has no bookmarks left
+        toggle_bookmarks(&project, &readme, &[0], cx);
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "main.rs src",
+                "    3: let answer = 42;",
+                "    1: fn main() {",
+            ]
+        );
+
+        // Lines follow the edits of the bookmarked buffers
+        main.update(cx, |buffer, cx| {
+            buffer.edit([(0..0, "// entry\n")], None, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "main.rs src",
+                "    4: let answer = 42;",
+                "    2: fn main() {",
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_rename_and_edit_note(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (project, panel, cx) = setup(cx).await;
+
+        let main = open_buffer(&project, path!("/root/src/main.rs"), cx).await;
+        toggle_bookmarks(&project, &main, &[0, 2], cx);
+        cx.run_until_parked();
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.select_first(&SelectFirst, window, cx);
+            panel.rename_bookmark(&RenameBookmark, window, cx);
+        });
+        set_edited_text(&panel, "Entry point", cx);
+        panel.update_in(cx, |panel, window, cx| panel.confirm(&Confirm, window, cx));
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "main.rs src",
+                "    1: Entry point  <==== selected",
+                "    3: let answer = 42;",
+            ]
+        );
+
+        // Cancelling keeps the previous note
+        panel.update_in(cx, |panel, window, cx| {
+            panel.edit_bookmark_note(&EditBookmarkNote, window, cx);
+        });
+        set_edited_text(&panel, "Discarded", cx);
+        panel.update_in(cx, |panel, window, cx| panel.cancel(&Cancel, window, cx));
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "main.rs src",
+                "    1: Entry point  <==== selected",
+                "    3: let answer = 42;",
+            ]
+        );
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.edit_bookmark_note(&EditBookmarkNote, window, cx);
+        });
+        set_edited_text(&panel, "Parses the arguments first", cx);
+        panel.update_in(cx, |panel, window, cx| panel.confirm(&Confirm, window, cx));
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "main.rs src",
+                "    1: Entry point (Parses the arguments first)  <==== selected",
+                "    3: let answer = 42;",
+            ]
+        );
+
+        // An empty label falls back to the text of the line
+        panel.update_in(cx, |panel, window, cx| {
+            panel.rename_bookmark(&RenameBookmark, window, cx);
+        });
+        set_edited_text(&panel, "  ", cx);
+        panel.update_in(cx, |panel, window, cx| panel.confirm(&Confirm, window, cx));
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "main.rs src",
+                "    1: fn main() { (Parses the arguments first)  <==== selected",
+                "    3: let answer = 42;",
+            ]
+        );
+        assert_eq!(
+            serialized_bookmarks(&project, cx),
+            BTreeMap::from([(
+                project_path(path!("/root/src/main.rs")),
+                vec![
+                    SerializedBookmark {
+                        row: 0,
+                        label: None,
+                        note: Some("Parses the arguments first".into()),
+                        order: 0,
+                    },
+                    SerializedBookmark {
+                        order: 1,
+                        ..SerializedBookmark::new(2)
+                    },
+                ],
+            )])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_move_and_delete_bookmarks(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (project, panel, cx) = setup(cx).await;
+
+        let main = open_buffer(&project, path!("/root/src/main.rs"), cx).await;
+        let readme = open_buffer(&project, path!("/root/README.md"), cx).await;
+        toggle_bookmarks(&project, &main, &[0, 1, 2], cx);
+        toggle_bookmarks(&project, &readme, &[0], cx);
+        cx.run_until_parked();
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.select_last(&SelectLast, window, cx);
+            panel.move_bookmark_up(&MoveBookmarkUp, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "README.md",
+                "    1: # Readme",
+                "main.rs src",
+                "    1: fn main() {",
+                "    3: let answer = 42;  <==== selected",
+                "    2: let greeting = \"hello\";",
+            ]
+        );
+
+        // Bookmarks only move within their file
+        panel.update_in(cx, |panel, window, cx| {
+            panel.move_bookmark_up(&MoveBookmarkUp, window, cx);
+            panel.move_bookmark_up(&MoveBookmarkUp, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "README.md",
+                "    1: # Readme",
+                "main.rs src",
+                "    3: let answer = 42;  <==== selected",
+                "    1: fn main() {",
+                "    2: let greeting = \"hello\";",
+            ]
+        );
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.move_bookmark_down(&MoveBookmarkDown, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "README.md",
+                "    1: # Readme",
+                "main.rs src",
+                "    1: fn main() {",
+                "    3: let answer = 42;  <==== selected",
+                "    2: let greeting = \"hello\";",
+            ]
+        );
+
+        // Deleting selects the next bookmark, or the previous one for the last bookmark
+        panel.update_in(cx, |panel, window, cx| {
+            panel.delete_bookmark(&DeleteBookmark, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "README.md",
+                "    1: # Readme",
+                "main.rs src",
+                "    1: fn main() {",
+                "    2: let greeting = \"hello\";  <==== selected",
+            ]
+        );
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.delete_bookmark(&DeleteBookmark, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "README.md",
+                "    1: # Readme",
+                "main.rs src",
+                "    1: fn main() {  <==== selected",
+            ]
+        );
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.delete_bookmark(&DeleteBookmark, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            ["README.md", "    1: # Readme  <==== selected"]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_restore_serialized_bookmarks(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (project, panel, cx) = setup(cx).await;
+
+        let serialized = BTreeMap::from([
+            (
+                project_path(path!("/root/src/main.rs")),
+                vec![
+                    SerializedBookmark {
+                        row: 0,
+                        label: None,
+                        note: Some("Start here".into()),
+                        order: 1,
+                    },
+                    SerializedBookmark {
+                        row: 2,
+                        label: Some("Answer".into()),
+                        note: None,
+                        order: 0,
+                    },
+                ],
+            ),
+            (
+                project_path(path!("/root/README.md")),
+                vec![SerializedBookmark::new(0)],
+            ),
+        ]);
+        project
+            .update(cx, |project, cx| {
+                project.bookmark_store().update(cx, |bookmark_store, cx| {
+                    bookmark_store.load_serialized_bookmarks(serialized.clone(), cx)
+                })
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        // Until their files are opened, bookmarks are listed in their saved order by line number
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "README.md",
+                "    1: Line 1",
+                "main.rs src",
+                "    3: Answer",
+                "    1: Line 1 (Start here)",
+            ]
+        );
+
+        // Activating the panel opens the files to show the bookmarked lines
+        panel.update_in(cx, |panel, window, cx| panel.set_active(true, window, cx));
+        cx.run_until_parked();
+        assert_eq!(
+            display_entries(&panel, cx),
+            [
+                "README.md",
+                "    1: # Readme",
+                "main.rs src",
+                "    3: Answer",
+                "    1: fn main() { (Start here)",
+            ]
+        );
+
+        let mut expected = serialized;
+        for bookmarks in expected.values_mut() {
+            bookmarks.sort_by_key(|bookmark| bookmark.order);
+        }
+        assert_eq!(serialized_bookmarks(&project, cx), expected);
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    async fn setup(
+        cx: &mut TestAppContext,
+    ) -> (
+        Entity<Project>,
+        Entity<BookmarksPanel>,
+        &mut VisualTestContext,
+    ) {
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "README.md": "# Readme\n",
+                "src": {
+                    "main.rs": "fn main() {\n    let greeting = \"hello\";\n    let answer = 42;\n}\n",
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+        let window =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window
+            .read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone())
+            .unwrap();
+        let cx = VisualTestContext::from_window(window.into(), cx).into_mut();
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            cx.new(|cx| BookmarksPanel::new(workspace, window, cx))
+        });
+        (project, panel, cx)
+    }
+
+    fn project_path(path: &str) -> Arc<Path> {
+        Arc::from(Path::new(path))
+    }
+
+    async fn open_buffer(
+        project: &Entity<Project>,
+        path: &str,
+        cx: &mut VisualTestContext,
+    ) -> Entity<Buffer> {
+        project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(Path::new(path), cx)
+            })
+            .await
+            .unwrap()
+    }
+
+    fn toggle_bookmarks(
+        project: &Entity<Project>,
+        buffer: &Entity<Buffer>,
+        rows: &[u32],
+        cx: &mut VisualTestContext,
+    ) {
+        project.update(cx, |project, cx| {
+            let bookmark_store = project.bookmark_store();
+            for &row in rows {
+                let anchor = buffer
+                    .read(cx)
+                    .snapshot()
+                    .anchor_after(language::Point::new(row, 0));
+                bookmark_store.update(cx, |bookmark_store, cx| {
+                    bookmark_store.toggle_bookmark(buffer.clone(), anchor, cx);
+                });
+            }
+        });
+    }
+
+    fn set_edited_text(panel: &Entity<BookmarksPanel>, text: &str, cx: &mut VisualTestContext) {
+        panel.update_in(cx, |panel, window, cx| {
+            let editing = panel.editing.as_ref().expect("no bookmark is being edited");
+            editing
+                .editor
+                .update(cx, |editor, cx| editor.set_text(text, window, cx));
+        });
+    }
+
+    fn serialized_bookmarks(
+        project: &Entity<Project>,
+        cx: &mut VisualTestContext,
+    ) -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
+        project.read_with(cx, |project, cx| {
+            project
+                .bookmark_store()
+                .read(cx)
+                .all_serialized_bookmarks(cx)
+        })
+    }
+
+    fn display_entries(panel: &Entity<BookmarksPanel>, cx: &mut VisualTestContext) -> Vec<String> {
+        panel.read_with(cx, |panel, _| {
+            let selected_ix = panel.selected_index();
+            panel
+                .entries
+                .iter()
+                .enumerate()
+                .map(|(ix, entry)| match entry {
+                    ListEntry::File { name, dir } => match dir {
+                        Some(dir) => format!("{name} {dir}"),
+                        None => name.to_string(),
+                    },
+                    ListEntry::Bookmark(entry) => {
+                        let title = entry
+                            .bookmark
+                            .label
+                            .as_ref()
+                            .map(|label| label.to_string())
+                            .or_else(|| entry.line_text.as_ref().map(|text| text.to_string()))
+                            .unwrap_or_else(|| format!("Line {}", entry.bookmark.row + 1));
+                        let note = entry
+                            .bookmark
+                            .note
+                            .as_ref()
+                            .map(|note| format!(" ({note})"))
+                            .unwrap_or_default();
+                        let selected = if selected_ix == Some(ix) {
+                            SELECTED_MARKER
+                        } else {
+                            ""
+                        };
+                        format!("    {}: {title}{note}{selected}", entry.bookmark.row + 1)
+                    }
+                })
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,455 @@
+mod call_hierarchy_panel_settings;
+
+use std::{ops::Range, sync::Arc};
+
+use call_hierarchy_panel_settings::CallHierarchyPanelSettings;
+use editor::Editor;
+use gpui::{
+    Action, App, AsyncWindowContext, ClickEvent, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, KeyContext, MouseButton, Pixels, Task, WeakEntity, Window, actions, uniform_list,
+};
+use hierarchy_tree::{HierarchyNode, HierarchyTree, HierarchyTreeView};
+use language::ToPoint as _;
+use project::{CallHierarchyCall, Fs, Project};
+use settings::Settings;
+use ui::{ListItem, Tooltip, prelude::*};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+use zed_actions::call_hierarchy::ShowCallHierarchy;
+
+actions!(
+    call_hierarchy_panel,
+    [
+        /// Toggles focus on the call hierarchy panel.
+        ToggleFocus,
+        /// Switches the call hierarchy between incoming and outgoing calls.
+        ToggleCallDirection,
+        /// Expands the selected entry, fetching its calls if needed.
+        ExpandSelectedEntry,
+        /// Collapses the selected entry, or selects its parent if it is already collapsed.
+        CollapseSelectedEntry,
+    ]
+);
+
+const CALL_HIERARCHY_PANEL_KEY: &str = "CallHierarchyPanel";
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<CallHierarchyPanel>(window, cx);
+        });
+        workspace.register_action(show_call_hierarchy);
+    })
+    .detach();
+}
+
+fn show_call_hierarchy(
+    workspace: &mut Workspace,
+    _: &ShowCallHierarchy,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = ({
+        let editor = editor.read(cx);
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+    if let Some(panel) = workspace.focus_panel::<CallHierarchyPanel>(window, cx) {
+        panel.update(cx, |panel, cx| {
+            panel.show_call_hierarchy(buffer, position, cx);
+        });
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallDirection {
+    /// Expanding an entry lists its callers.
+    Incoming,
+    /// Expanding an entry lists its callees.
+    Outgoing,
+}
+
+/// Shows the functions calling, or called by, the symbol the hierarchy was
+/// requested for, as a tree whose levels are fetched from the language server
+/// when they are first expanded.
+///
+/// The roots are shown as calls without call sites.
+pub struct CallHierarchyPanel {
+    project: Entity<Project>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    direction: CallDirection,
+    tree: HierarchyTree<CallHierarchyCall>,
+}
+
+impl CallHierarchyPanel {
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<anyhow::Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            workspace.update_in(cx, |workspace, _, cx| cx.new(|cx| Self::new(workspace, cx)))
+        })
+    }
+
+    fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        Self {
+            project: workspace.project().clone(),
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            direction: CallDirection::Incoming,
+            tree: HierarchyTree::new(workspace.weak_handle()),
+        }
+    }
+
+    /// Rebuilds the hierarchy around the symbol at the given position.
+    pub fn show_call_hierarchy(
+        &mut self,
+        buffer: Entity<language::Buffer>,
+        position: language::Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let prepare = self.project.update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, position, cx)
+        });
+        let roots = cx.background_spawn(async move {
+            let items = prepare.await?;
+            anyhow::Ok(
+                items
+                    .into_iter()
+                    .map(|item| CallHierarchyCall {
+                        item,
+                        call_sites: Vec::new(),
+                    })
+                    .collect(),
+            )
+        });
+        self.load_roots(roots, cx);
+    }
+
+    pub fn direction(&self) -> CallDirection {
+        self.direction
+    }
+
+    pub fn set_direction(&mut self, direction: CallDirection, cx: &mut Context<Self>) {
+        if self.direction != direction {
+            self.direction = direction;
+            self.rebuild(cx);
+        }
+    }
+
+    fn toggle_call_direction(
+        &mut self,
+        _: &ToggleCallDirection,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let direction = match self.direction {
+            CallDirection::Incoming => CallDirection::Outgoing,
+            CallDirection::Outgoing => CallDirection::Incoming,
+        };
+        self.set_direction(direction, cx);
+    }
+
+    fn expand_selected(&mut self, _: &ExpandSelectedEntry, _: &mut Window, cx: &mut Context<Self>) {
+        self.expand_selected_entry(cx);
+    }
+
+    fn collapse_selected(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.collapse_selected_entry(cx);
+    }
+
+    fn render_entries(&self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range
+            .filter_map(|entry_ix| {
+                let (node_ix, node) = self.tree.entry(entry_ix)?;
+                Some(
+                    self.render_node(entry_ix, node_ix, node, cx)
+                        .into_any_element(),
+                )
+            })
+            .collect()
+    }
+
+    fn render_node(
+        &self,
+        entry_ix: usize,
+        node_ix: usize,
+        node: &HierarchyNode<CallHierarchyCall>,
+        cx: &mut Context<Self>,
+    ) -> ListItem {
+        let toggle = node.toggle_state();
+        let is_loading = node.is_loading();
+        let call = &node.item;
+
+        let buffer = call.item.location.buffer.read(cx);
+        let file_name = buffer
+            .file()
+            .map(|file| file.file_name(cx).to_string())
+            .unwrap_or_default();
+        let row = call.item.selection_range.start.to_point(buffer).row + 1;
+        let call_count = call.call_sites.len();
+
+        ListItem::new(("call-hierarchy-entry", entry_ix))
+            .indent_level(node.depth)
+            .indent_step_size(px(12.))
+            .toggle_state(self.tree.selected_node() == Some(node_ix))
+            .toggle(toggle)
+            .on_toggle(cx.listener(move |this, _, _, cx| {
+                this.toggle_expanded(node_ix, cx);
+            }))
+            .child(
+                h_flex()
+                    .min_w_0()
+                    .gap_1()
+                    .child(Label::new(call.item.name.clone()).single_line())
+                    .children(call.item.detail.clone().map(|detail| {
+                        Label::new(detail)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate()
+                    })),
+            )
+            .end_slot(
+                h_flex()
+                    .gap_1()
+                    .when(is_loading, |this| {
+                        this.child(
+                            Label::new("Loading…")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .when(call_count > 1, |this| {
+                        this.child(
+                            Label::new(format!("×{call_count}"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .child(
+                        Label::new(format!("{file_name}:{row}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                this.click_node(node_ix, event.click_count(), window, cx);
+            }))
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let direction_button = |id: &'static str, label: &'static str, direction: CallDirection| {
+            Button::new(id, label)
+                .label_size(LabelSize::Small)
+                .toggle_state(self.direction == direction)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.set_direction(direction, cx);
+                }))
+        };
+
+        h_flex()
+            .h(Tab::container_height(cx))
+            .px_2()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Label::new(
+                    self.tree
+                        .root_items()
+                        .first()
+                        .map(|call| SharedString::from(call.item.name.clone()))
+                        .unwrap_or_else(|| "Call Hierarchy".into()),
+                )
+                .size(LabelSize::Small)
+                .truncate(),
+            )
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        direction_button("incoming-calls", "Incoming", CallDirection::Incoming)
+                            .tooltip(Tooltip::text("Show the callers")),
+                    )
+                    .child(
+                        direction_button("outgoing-calls", "Outgoing", CallDirection::Outgoing)
+                            .tooltip(Tooltip::text("Show the callees")),
+                    ),
+            )
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("CallHierarchyPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        let (title, description) = if self.tree.is_loading_roots() {
+            ("Loading…".into(), None)
+        } else if let Some(error) = self.tree.error() {
+            ("No Call Hierarchy".into(), Some(error.clone()))
+        } else {
+            (
+                SharedString::from("No Call Hierarchy"),
+                Some("Use \"Show Call Hierarchy\" on a function to list its calls here.".into()),
+            )
+        };
+        v_flex()
+            .size_full()
+            .p_4()
+            .gap_1()
+            .items_center()
+            .justify_center()
+            .child(Label::new(title))
+            .children(description.map(|description| {
+                Label::new(description)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+            }))
+    }
+}
+
+impl Render for CallHierarchyPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("call-hierarchy-panel")
+            .size_full()
+            .overflow_hidden()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::expand_selected))
+            .on_action(cx.listener(Self::collapse_selected))
+            .on_action(cx.listener(Self::toggle_call_direction))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, window, cx| {
+                    window.focus(&this.focus_handle, cx);
+                }),
+            )
+            .child(self.render_header(cx))
+            .map(|this| {
+                if self.tree.entry_count() == 0 {
+                    this.child(self.render_empty_state())
+                } else {
+                    this.child(
+                        uniform_list(
+                            "call-hierarchy",
+                            self.tree.entry_count(),
+                            cx.processor(|this, range: Range<usize>, _, cx| {
+                                this.render_entries(range, cx)
+                            }),
+                        )
+                        .size_full()
+                        .track_scroll(self.tree.scroll_handle()),
+                    )
+                }
+            })
+    }
+}
+
+impl HierarchyTreeView for CallHierarchyPanel {
+    type Item = CallHierarchyCall;
+
+    fn tree(&self) -> &HierarchyTree<CallHierarchyCall> {
+        &self.tree
+    }
+
+    fn tree_mut(&mut self) -> &mut HierarchyTree<CallHierarchyCall> {
+        &mut self.tree
+    }
+
+    fn fetch_children(
+        &mut self,
+        call: &CallHierarchyCall,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<Vec<CallHierarchyCall>>> {
+        self.project.update(cx, |project, cx| match self.direction {
+            CallDirection::Incoming => project.incoming_calls(&call.item, cx),
+            CallDirection::Outgoing => project.outgoing_calls(&call.item, cx),
+        })
+    }
+
+    fn item_location(
+        call: &CallHierarchyCall,
+    ) -> (Entity<language::Buffer>, Range<language::Anchor>) {
+        (
+            call.item.location.buffer.clone(),
+            call.item.selection_range.clone(),
+        )
+    }
+}
+
+impl Focusable for CallHierarchyPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for CallHierarchyPanel {}
+
+impl Panel for CallHierarchyPanel {
+    fn persistent_name() -> &'static str {
+        "Call Hierarchy Panel"
+    }
+
+    fn panel_key() -> &'static str {
+        CALL_HIERARCHY_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        CallHierarchyPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.call_hierarchy_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn default_size(&self, _: &Window, cx: &App) -> Pixels {
+        CallHierarchyPanelSettings::get_global(cx).default_width
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        CallHierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListTree)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Call Hierarchy Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        9
+    }
+}
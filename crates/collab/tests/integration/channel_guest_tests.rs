@@ -3,9 +3,13 @@ use call::ActiveCall;
 use chrono::Utc;
 use collab::db::ChannelId;
 use editor::Editor;
+use fs::{FakeCommit, Fs as _};
+use git::repository::ResetMode;
 use gpui::{BackgroundExecutor, TestAppContext};
 use rpc::proto;
-use util::rel_path::rel_path;
+use serde_json::json;
+use std::{path::Path, sync::Arc};
+use util::{path, rel_path::rel_path};
 #[gpui::test]
 async fn test_channel_guests(
     executor: BackgroundExecutor,
@@ -66,6 +70,95 @@ async fn test_channel_guests(
     assert!(room_b.read_with(cx_b, |room, _| room.is_muted()));
 }
 
+#[gpui::test]
+async fn test_channel_guest_hard_reset(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    let channel_id = server
+        .make_public_channel("the-channel", &client_a, cx_a)
+        .await;
+
+    // Client A has a commit on top of the initial one and uncommitted changes
+    let fs = client_a.fs();
+    let dot_git = Path::new(path!("/project/.git"));
+    fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "one\n" }))
+        .await;
+    fs.set_head_and_index_for_repo(dot_git, &[("a.txt", "one\n".into())]);
+    fs.set_commits_for_repo(
+        dot_git,
+        &[FakeCommit::new("feature", "Shout").change("a.txt", Some("one\n"), Some("ONE\n"))],
+    );
+    fs.open_repo(dot_git, None)
+        .unwrap()
+        .cherry_pick("feature".into(), Arc::default())
+        .await
+        .unwrap();
+    fs.write(Path::new(path!("/project/a.txt")), b"uncommitted\n")
+        .await
+        .unwrap();
+    let head_sha = || {
+        fs.with_git_state(dot_git, false, |state| state.refs["HEAD"].clone())
+            .unwrap()
+    };
+    let committed_sha = head_sha();
+
+    let (project_a, _) = client_a.build_local_project(path!("/project"), cx_a).await;
+    active_call_a
+        .update(cx_a, |call, cx| call.join_channel(channel_id, cx))
+        .await
+        .unwrap();
+    active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    cx_a.executor().run_until_parked();
+
+    // Client B joins channel A as a read-only guest
+    cx_b.update(|cx| {
+        workspace::join_channel(channel_id, client_b.app_state.clone(), None, None, cx)
+    })
+    .await
+    .unwrap();
+    executor.run_until_parked();
+
+    let active_call_b = cx_b.read(ActiveCall::global);
+    let project_b =
+        active_call_b.read_with(cx_b, |call, _| call.location().unwrap().upgrade().unwrap());
+    assert!(project_b.read_with(cx_b, |project, cx| project.is_read_only(cx)));
+    let repo_b = cx_b.update(|cx| project_b.read(cx).active_repository(cx).unwrap());
+
+    // A hard reset would discard the host's uncommitted changes, so it is refused
+    let reset = repo_b.update(cx_b, |repo, cx| {
+        repo.reset("HEAD~".into(), ResetMode::Hard, cx)
+    });
+    assert!(reset.await.unwrap().is_err());
+    executor.run_until_parked();
+    assert_eq!(head_sha(), committed_sha);
+    assert_eq!(
+        fs.load(Path::new(path!("/project/a.txt"))).await.unwrap(),
+        "uncommitted\n"
+    );
+
+    // Mixed resets leave the working copy alone and remain allowed
+    let reset = repo_b.update(cx_b, |repo, cx| {
+        repo.reset("HEAD~".into(), ResetMode::Mixed, cx)
+    });
+    reset.await.unwrap().unwrap();
+    executor.run_until_parked();
+    assert_ne!(head_sha(), committed_sha);
+    assert_eq!(
+        fs.load(Path::new(path!("/project/a.txt"))).await.unwrap(),
+        "uncommitted\n"
+    );
+}
+
 #[gpui::test]
 async fn test_channel_guest_promotion(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
     let mut server = TestServer::start(cx_a.executor()).await;
@@ -4285,6 +4285,143 @@ async fn test_add_breakpoints(cx_a: &mut TestAppContext, cx_b: &mut TestAppConte
     assert_eq!(breakpoints_a, breakpoints_b);
 }
 
+#[gpui::test]
+async fn test_shared_bookmarks(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let executor = cx_a.executor();
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let active_call_b = cx_b.read(ActiveCall::global);
+    cx_a.update(editor::init);
+    cx_b.update(editor::init);
+    client_a
+        .fs()
+        .insert_tree(
+            "/a",
+            json!({
+                "test.txt": "one\ntwo\nthree\nfour\nfive",
+            }),
+        )
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/a", cx_a).await;
+    let project_path = ProjectPath {
+        worktree_id,
+        path: rel_path(&"test.txt").into(),
+    };
+    let abs_path: Arc<Path> = project_a.read_with(cx_a, |project, cx| {
+        project
+            .absolute_path(&project_path, cx)
+            .map(Arc::from)
+            .unwrap()
+    });
+    let user_b = client_b.user_id().unwrap();
+
+    active_call_a
+        .update(cx_a, |call, cx| call.set_location(Some(&project_a), cx))
+        .await
+        .unwrap();
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    active_call_b
+        .update(cx_b, |call, cx| call.set_location(Some(&project_b), cx))
+        .await
+        .unwrap();
+    let (workspace_a, cx_a) = client_a.build_workspace(&project_a, cx_a);
+    let (workspace_b, cx_b) = client_b.build_workspace(&project_b, cx_b);
+
+    let editor_a = workspace_a
+        .update_in(cx_a, |workspace, window, cx| {
+            workspace.open_path(project_path.clone(), None, true, window, cx)
+        })
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let editor_b = workspace_b
+        .update_in(cx_b, |workspace, window, cx| {
+            workspace.open_path(project_path.clone(), None, true, window, cx)
+        })
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let bookmark_store_a = project_a.read_with(cx_a, |project, _| project.bookmark_store());
+    let bookmark_store_b = project_b.read_with(cx_b, |project, _| project.bookmark_store());
+    let bookmark_rows = |store: &Entity<project::bookmark_store::BookmarkStore>,
+                         cx: &mut VisualTestContext| {
+        store.read_with(cx, |store, cx| {
+            store
+                .all_serialized_bookmarks(cx)
+                .into_values()
+                .flatten()
+                .map(|bookmark| (bookmark.row, bookmark.label))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Client A adds a bookmark on the first line, and client B one on the third line
+    editor_a.update_in(cx_a, |editor, window, cx| {
+        editor.toggle_bookmark(&editor::actions::ToggleBookmark, window, cx);
+    });
+    editor_b.update_in(cx_b, |editor, window, cx| {
+        editor.move_down(&zed_actions::editor::MoveDown, window, cx);
+        editor.move_down(&zed_actions::editor::MoveDown, window, cx);
+        editor.toggle_bookmark(&editor::actions::ToggleBookmark, window, cx);
+    });
+
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    assert_eq!(
+        bookmark_rows(&bookmark_store_a, cx_a),
+        [(0, None), (2, None)]
+    );
+    assert_eq!(
+        bookmark_rows(&bookmark_store_b, cx_b),
+        [(0, None), (2, None)]
+    );
+    bookmark_store_a.read_with(cx_a, |store, cx| {
+        assert_eq!(store.bookmark_owner(&abs_path, 0, cx), None);
+        assert_eq!(store.bookmark_owner(&abs_path, 2, cx), Some(user_b));
+    });
+
+    // Client B can label its own bookmark, but can't remove the bookmark of client A
+    bookmark_store_b.update(cx_b, |store, cx| {
+        store.set_bookmark_label(&abs_path, 2, "todo", cx);
+        store.remove_bookmark(&abs_path, 0, cx);
+    });
+
+    cx_b.run_until_parked();
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let expected = [(0, None), (2, Some(Arc::<str>::from("todo")))];
+    assert_eq!(bookmark_rows(&bookmark_store_a, cx_a), expected);
+    assert_eq!(bookmark_rows(&bookmark_store_b, cx_b), expected);
+
+    // Client A, as the host, can remove the bookmark of client B
+    bookmark_store_a.update(cx_a, |store, cx| {
+        store.remove_bookmark(&abs_path, 2, cx);
+    });
+
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    assert_eq!(bookmark_rows(&bookmark_store_a, cx_a), [(0, None)]);
+    assert_eq!(bookmark_rows(&bookmark_store_b, cx_b), [(0, None)]);
+}
+
 #[gpui::test]
 async fn test_client_can_query_lsp_ext(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
     let mut server = TestServer::start(cx_a.executor()).await;
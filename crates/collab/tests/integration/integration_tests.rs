@@ -5442,6 +5442,358 @@ async fn test_document_highlights(
     });
 }
 
+#[gpui::test(iterations = 10)]
+async fn test_call_hierarchy(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree(
+            path!("/root-1"),
+            json!({
+                "main.rs": "fn main() { helper(); }",
+                "helper.rs": "pub fn helper() {}",
+            }),
+        )
+        .await;
+
+    client_a.language_registry().add(rust_lang());
+    let capabilities = lsp::ServerCapabilities {
+        call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+        ..lsp::ServerCapabilities::default()
+    };
+    let mut fake_language_servers = client_a.language_registry().register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: capabilities.clone(),
+            ..FakeLspAdapter::default()
+        },
+    );
+    client_b.language_registry().add(rust_lang());
+    client_b.language_registry().register_fake_lsp_adapter(
+        "Rust",
+        FakeLspAdapter {
+            capabilities,
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (project_a, worktree_id) = client_a.build_local_project(path!("/root-1"), cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    // Open the callee on client B, the caller's file stays closed.
+    let (buffer_b, _handle) = project_b
+        .update(cx_b, |p, cx| {
+            p.open_buffer_with_lsp((worktree_id, rel_path("helper.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    let helper_item = lsp::CallHierarchyItem {
+        name: "helper".into(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: Some("pub fn helper()".into()),
+        uri: lsp::Uri::from_file_path(path!("/root-1/helper.rs")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 18)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 13)),
+        data: Some(json!({ "id": 2 })),
+    };
+    let main_item = lsp::CallHierarchyItem {
+        name: "main".into(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: Some("fn main()".into()),
+        uri: lsp::Uri::from_file_path(path!("/root-1/main.rs")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 23)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 7)),
+        data: Some(json!({ "id": 1 })),
+    };
+    let call_range = lsp::Range::new(lsp::Position::new(0, 12), lsp::Position::new(0, 18));
+
+    let fake_language_server = fake_language_servers.next().await.unwrap();
+    fake_language_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+        let helper_item = helper_item.clone();
+        move |params, _| {
+            let helper_item = helper_item.clone();
+            async move {
+                assert_eq!(
+                    params
+                        .text_document_position_params
+                        .text_document
+                        .uri
+                        .as_str(),
+                    uri!("file:///root-1/helper.rs")
+                );
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 8)
+                );
+                Ok(Some(vec![helper_item]))
+            }
+        }
+    });
+    fake_language_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>({
+        let main_item = main_item.clone();
+        move |params, _| {
+            let main_item = main_item.clone();
+            async move {
+                // The item is passed back to the server as it was sent, data included.
+                assert_eq!(params.item.data, Some(json!({ "id": 2 })));
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: main_item,
+                    from_ranges: vec![call_range],
+                }]))
+            }
+        }
+    });
+    fake_language_server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+        move |params, _| {
+            let helper_item = helper_item.clone();
+            async move {
+                assert_eq!(params.item.data, Some(json!({ "id": 1 })));
+                Ok(Some(vec![lsp::CallHierarchyOutgoingCall {
+                    to: helper_item,
+                    from_ranges: vec![call_range],
+                }]))
+            }
+        },
+    );
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let items = project_b
+        .update(cx_b, |p, cx| p.prepare_call_hierarchy(&buffer_b, 8, cx))
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let helper = items.into_iter().next().unwrap();
+    assert_eq!(helper.name, "helper");
+    assert_eq!(helper.detail.as_deref(), Some("pub fn helper()"));
+    assert_eq!(helper.location.buffer, buffer_b);
+    assert_eq!(helper.server_id, fake_language_server.server.server_id());
+    buffer_b.read_with(cx_b, |buffer, _| {
+        let snapshot = buffer.snapshot();
+        assert_eq!(helper.selection_range.to_offset(&snapshot), 7..13);
+    });
+
+    let incoming_calls = project_b
+        .update(cx_b, |p, cx| p.incoming_calls(&helper, cx))
+        .await
+        .unwrap();
+    assert_eq!(incoming_calls.len(), 1);
+    let caller = &incoming_calls[0];
+    assert_eq!(caller.item.name, "main");
+    assert_eq!(
+        caller.item.server_id,
+        fake_language_server.server.server_id()
+    );
+    caller.item.location.buffer.read_with(cx_b, |buffer, cx| {
+        let snapshot = buffer.snapshot();
+        assert_eq!(buffer.file().unwrap().path().as_ref(), rel_path("main.rs"));
+        assert_eq!(caller.item.selection_range.to_offset(&snapshot), 3..7);
+        let call_sites = caller
+            .call_sites
+            .iter()
+            .map(|call_site| {
+                assert_eq!(call_site.buffer.read(cx).remote_id(), buffer.remote_id());
+                call_site.range.to_offset(&snapshot)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(call_sites, vec![12..18]);
+    });
+
+    let outgoing_calls = project_b
+        .update(cx_b, |p, cx| p.outgoing_calls(&caller.item, cx))
+        .await
+        .unwrap();
+    assert_eq!(outgoing_calls.len(), 1);
+    let callee = &outgoing_calls[0];
+    assert_eq!(callee.item.name, "helper");
+    assert_eq!(callee.item.location.buffer, buffer_b);
+    assert_eq!(callee.call_sites.len(), 1);
+    assert_eq!(callee.call_sites[0].buffer, caller.item.location.buffer);
+}
+
+#[gpui::test(iterations = 10)]
+async fn test_type_hierarchy(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree(
+            path!("/root-1"),
+            json!({
+                "shape.rs": "pub trait Shape {}",
+                "circle.rs": "struct Circle; impl Shape for Circle {}",
+            }),
+        )
+        .await;
+
+    client_a.language_registry().add(rust_lang());
+    let capabilities: lsp::ServerCapabilities =
+        serde_json::from_value(json!({ "typeHierarchyProvider": true })).unwrap();
+    let mut fake_language_servers = client_a.language_registry().register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: capabilities.clone(),
+            ..FakeLspAdapter::default()
+        },
+    );
+    client_b.language_registry().add(rust_lang());
+    client_b.language_registry().register_fake_lsp_adapter(
+        "Rust",
+        FakeLspAdapter {
+            capabilities,
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (project_a, worktree_id) = client_a.build_local_project(path!("/root-1"), cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    // Open the trait on client B, the file of its implementor stays closed.
+    let (buffer_b, _handle) = project_b
+        .update(cx_b, |p, cx| {
+            p.open_buffer_with_lsp((worktree_id, rel_path("shape.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    let shape_item = lsp::TypeHierarchyItem {
+        name: "Shape".into(),
+        kind: lsp::SymbolKind::INTERFACE,
+        tags: None,
+        detail: Some("pub trait Shape".into()),
+        uri: lsp::Uri::from_file_path(path!("/root-1/shape.rs")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 18)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 10), lsp::Position::new(0, 15)),
+        data: Some(json!({ "id": 1 })),
+    };
+    let circle_item = lsp::TypeHierarchyItem {
+        name: "Circle".into(),
+        kind: lsp::SymbolKind::STRUCT,
+        tags: None,
+        detail: Some("struct Circle".into()),
+        uri: lsp::Uri::from_file_path(path!("/root-1/circle.rs")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 14)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 13)),
+        data: Some(json!({ "id": 2 })),
+    };
+
+    let fake_language_server = fake_language_servers.next().await.unwrap();
+    fake_language_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>({
+        let shape_item = shape_item.clone();
+        move |params, _| {
+            let shape_item = shape_item.clone();
+            async move {
+                assert_eq!(
+                    params
+                        .text_document_position_params
+                        .text_document
+                        .uri
+                        .as_str(),
+                    uri!("file:///root-1/shape.rs")
+                );
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 12)
+                );
+                Ok(Some(vec![shape_item]))
+            }
+        }
+    });
+    fake_language_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>(
+        move |params, _| {
+            let circle_item = circle_item.clone();
+            async move {
+                // The item is passed back to the server as it was sent, data included.
+                assert_eq!(params.item.data, Some(json!({ "id": 1 })));
+                Ok(Some(vec![circle_item]))
+            }
+        },
+    );
+    fake_language_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+        move |params, _| {
+            let shape_item = shape_item.clone();
+            async move {
+                assert_eq!(params.item.data, Some(json!({ "id": 2 })));
+                Ok(Some(vec![shape_item]))
+            }
+        },
+    );
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let items = project_b
+        .update(cx_b, |p, cx| p.prepare_type_hierarchy(&buffer_b, 12, cx))
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let shape = items.into_iter().next().unwrap();
+    assert_eq!(shape.name, "Shape");
+    assert_eq!(shape.detail.as_deref(), Some("pub trait Shape"));
+    assert_eq!(shape.location.buffer, buffer_b);
+    assert_eq!(shape.server_id, fake_language_server.server.server_id());
+    buffer_b.read_with(cx_b, |buffer, _| {
+        let snapshot = buffer.snapshot();
+        assert_eq!(shape.selection_range.to_offset(&snapshot), 10..15);
+    });
+
+    let subtypes = project_b
+        .update(cx_b, |p, cx| p.subtypes(&shape, cx))
+        .await
+        .unwrap();
+    assert_eq!(subtypes.len(), 1);
+    let circle = &subtypes[0];
+    assert_eq!(circle.name, "Circle");
+    assert_eq!(circle.server_id, fake_language_server.server.server_id());
+    circle.location.buffer.read_with(cx_b, |buffer, _| {
+        let snapshot = buffer.snapshot();
+        assert_eq!(
+            buffer.file().unwrap().path().as_ref(),
+            rel_path("circle.rs")
+        );
+        assert_eq!(circle.selection_range.to_offset(&snapshot), 7..13);
+    });
+
+    let supertypes = project_b
+        .update(cx_b, |p, cx| p.supertypes(circle, cx))
+        .await
+        .unwrap();
+    assert_eq!(supertypes.len(), 1);
+    assert_eq!(supertypes[0].name, "Shape");
+    assert_eq!(supertypes[0].location.buffer, buffer_b);
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_lsp_hover(
     executor: BackgroundExecutor,
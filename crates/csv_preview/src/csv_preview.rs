@@ -1,22 +1,36 @@
 use editor::{Editor, EditorEvent};
 use feature_flags::{FeatureFlag, FeatureFlagAppExt as _, PresenceFlag, register_feature_flag};
 use gpui::{
-    AppContext, Entity, EventEmitter, FocusHandle, Focusable, ListAlignment, Task, actions,
+    AppContext, Entity, EventEmitter, FocusHandle, Focusable, ListAlignment, Pixels, Point,
+    Subscription, Task, actions,
 };
 use std::{
     collections::HashMap,
+    path::PathBuf,
     time::{Duration, Instant},
 };
 
-use crate::table_data_engine::TableDataEngine;
+use crate::table_data_engine::{
+    TableDataEngine,
+    filtering_by_column::{ColumnFilter, FilterKind, FilterPredicate, TextMatcher},
+};
 use ui::{
-    AbsoluteLength, ResizableColumnsState, SharedString, TableInteractionState,
+    AbsoluteLength, ContextMenu, ResizableColumnsState, SharedString, TableInteractionState,
     TableResizeBehavior, prelude::*,
 };
 use workspace::{Item, SplitDirection, Workspace};
 
-use crate::{parser::EditorState, settings::CsvPreviewSettings, types::TableLikeContent};
+use crate::{
+    editing::{CellEditor, DataCellId},
+    parser::{CsvDialect, EditorState, TabularFormat},
+    settings::{
+        CsvDialectSetting, CsvPreviewSettings, dialect_char_from_input, dialect_char_to_input,
+        read_dialect_override, write_dialect_override,
+    },
+    types::{AnyColumn, TableLikeContent},
+};
 
+mod editing;
 mod parser;
 mod renderer;
 mod settings;
@@ -25,6 +39,28 @@ mod types;
 
 actions!(csv, [OpenPreview, OpenPreviewToTheSide]);
 
+actions!(
+    csv,
+    [
+        /// Edits the value of the selected cell.
+        EditCell,
+        /// Inserts an empty row above the selected cell.
+        InsertRowAbove,
+        /// Inserts an empty row below the selected cell.
+        InsertRowBelow,
+        /// Deletes the row of the selected cell.
+        DeleteRow,
+        /// Inserts an empty column to the left of the selected cell.
+        InsertColumnLeft,
+        /// Inserts an empty column to the right of the selected cell.
+        InsertColumnRight,
+        /// Deletes the column of the selected cell.
+        DeleteColumn,
+    ]
+);
+
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
 pub struct TabularDataPreviewFeatureFlag;
 
 impl FeatureFlag for TabularDataPreviewFeatureFlag {
@@ -42,11 +78,30 @@ pub struct CsvPreviewView {
     pub(crate) column_widths: ColumnWidths,
     pub(crate) parsing_task: Option<Task<anyhow::Result<()>>>,
     pub(crate) settings: CsvPreviewSettings,
+    /// Dialect the current contents were parsed with, either detected or overridden
+    pub(crate) active_dialect: CsvDialect,
+    /// Format of the previewed file, determined by its extension
+    pub(crate) active_format: TabularFormat,
     /// Performance metrics for debugging and monitoring CSV operations.
     pub(crate) performance_metrics: PerformanceMetrics,
     pub(crate) list_state: gpui::ListState,
     /// Time when the last parsing operation ended, used for smart debouncing
     pub(crate) last_parse_end_time: Option<std::time::Instant>,
+    /// Query of the global search, narrowing rows to the ones with matching cells
+    pub(crate) search_editor: Entity<Editor>,
+    /// Debounced filtering of the rows after the search query changed.
+    /// Dropped when rows are filtered synchronously, as that already accounts for the latest query
+    search_task: Option<Task<()>>,
+    /// Filter being composed in the toolbar, before it's added to the applied filters
+    pub(crate) filter_input: FilterInputState,
+    /// Characters of the dialect override, editable when one is active
+    pub(crate) dialect_input: DialectInputState,
+    _search_subscription: Subscription,
+    /// Cell targeted by row and column operations
+    pub(crate) selected_cell: Option<DataCellId>,
+    /// Inline editor replacing the content of the cell being edited
+    pub(crate) editing_cell: Option<CellEditor>,
+    pub(crate) context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
 }
 
 pub fn init(cx: &mut App) {
@@ -90,7 +145,7 @@ impl CsvPreviewView {
                         .and_then(|item| item.act_as::<Editor>(cx))
                         .filter(|editor| Self::is_csv_file(editor, cx))
                     {
-                        let csv_preview = Self::new(&editor, cx);
+                        let csv_preview = Self::new(&editor, window, cx);
                         workspace.active_pane().update(cx, |pane, cx| {
                             let existing = pane
                                 .items_of_type::<CsvPreviewView>()
@@ -111,7 +166,7 @@ impl CsvPreviewView {
                             .and_then(|item| item.act_as::<Editor>(cx))
                             .filter(|editor| Self::is_csv_file(editor, cx))
                         {
-                            let csv_preview = Self::new(&editor, cx);
+                            let csv_preview = Self::new(&editor, window, cx);
                             let pane = workspace
                                 .find_pane_in_direction(SplitDirection::Right, cx)
                                 .unwrap_or_else(|| {
@@ -148,7 +203,11 @@ impl CsvPreviewView {
         });
     }
 
-    fn new(editor: &Entity<Editor>, cx: &mut Context<Workspace>) -> Entity<Self> {
+    fn new(
+        editor: &Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
         let contents = TableLikeContent::default();
         let table_interaction_state = cx.new(|cx| {
             TableInteractionState::new(cx).with_custom_scrollbar(ui::Scrollbars::for_settings::<
@@ -161,7 +220,8 @@ impl CsvPreviewView {
                 editor,
                 |this: &mut CsvPreviewView, _editor, event: &EditorEvent, cx| {
                     match event {
-                        EditorEvent::Edited { .. } | EditorEvent::DirtyChanged => {
+                        // Also emitted for edits made from the table and by collaborators
+                        EditorEvent::BufferEdited | EditorEvent::DirtyChanged => {
                             this.parse_csv_from_active_editor(true, cx);
                         }
                         _ => {}
@@ -169,6 +229,22 @@ impl CsvPreviewView {
                 },
             );
 
+            let search_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Search all cells…", window, cx);
+                editor
+            });
+            let search_subscription = cx.subscribe(
+                &search_editor,
+                |this: &mut CsvPreviewView, _editor, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.update_search(cx);
+                    }
+                },
+            );
+            let filter_input = FilterInputState::new(window, cx);
+            let dialect_input = DialectInputState::new(window, cx);
+
             let mut view = CsvPreviewView {
                 focus_handle: cx.focus_handle(),
                 active_editor_state: EditorState {
@@ -181,10 +257,26 @@ impl CsvPreviewView {
                 performance_metrics: PerformanceMetrics::default(),
                 list_state: gpui::ListState::new(contents.rows.len(), ListAlignment::Top, px(1.)),
                 settings: CsvPreviewSettings::default(),
+                active_dialect: CsvDialect::default(),
+                active_format: TabularFormat::default(),
                 last_parse_end_time: None,
                 engine: TableDataEngine::default(),
+                search_editor,
+                search_task: None,
+                filter_input,
+                dialect_input,
+                _search_subscription: search_subscription,
+                selected_cell: None,
+                editing_cell: None,
+                context_menu: None,
             };
 
+            if let Some(abs_path) = view.file_abs_path(cx) {
+                view.settings.dialect = read_dialect_override(&abs_path, cx);
+                if let CsvDialectSetting::Override(dialect) = view.settings.dialect {
+                    view.dialect_input.set_dialect(dialect, window, cx);
+                }
+            }
             view.parse_csv_from_active_editor(false, cx);
             view
         })
@@ -193,19 +285,179 @@ impl CsvPreviewView {
     pub(crate) fn editor_state(&self) -> &EditorState {
         &self.active_editor_state
     }
+
+    /// Re-apply the global search after its query changed.
+    /// Rows are filtered on the background executor once typing settles, keeping their current order.
+    fn update_search(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx);
+        self.engine.search = TextMatcher::new(&query);
+        cx.notify();
+
+        self.search_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+            let Ok(filter) = this.update(cx, |this, _| this.engine.filter_in_background()) else {
+                return;
+            };
+            let instant = Instant::now();
+            let filtered_out_rows = cx.background_spawn(filter).await;
+            let duration = instant.elapsed();
+            this.update(cx, |this, cx| {
+                this.performance_metrics
+                    .timings
+                    .insert("Search", (duration, Instant::now()));
+                this.engine.set_filtered_out_rows(filtered_out_rows);
+                this.reset_list_state();
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Select the column in the filter input and focus its value editor
+    pub(crate) fn start_filtering_column(
+        &mut self,
+        col_idx: AnyColumn,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.filter_input.col_idx = col_idx;
+        self.filter_input.error = None;
+        window.focus(&self.filter_input.value_editor.focus_handle(cx), cx);
+        cx.notify();
+    }
+
+    pub(crate) fn set_filter_input_kind(
+        &mut self,
+        kind: FilterKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.filter_input.kind = kind;
+        self.filter_input.error = None;
+        self.filter_input.value_editor.update(cx, |editor, cx| {
+            editor.set_placeholder_text(kind.placeholder(), window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Parse the filter input and apply it, or show why it's invalid
+    pub(crate) fn add_filter_from_input(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let value = self.filter_input.value_editor.read(cx).text(cx);
+        match FilterPredicate::parse(self.filter_input.kind, &value) {
+            Ok(predicate) => {
+                self.engine.applied_filters.push(ColumnFilter {
+                    col_idx: self.filter_input.col_idx,
+                    predicate,
+                });
+                self.filter_input.error = None;
+                self.filter_input.value_editor.update(cx, |editor, cx| {
+                    editor.clear(window, cx);
+                });
+                self.apply_filter();
+            }
+            Err(error) => {
+                self.filter_input.error = Some(error.to_string().into());
+            }
+        }
+        cx.notify();
+    }
+
+    pub(crate) fn remove_filter(&mut self, filter_ix: usize, cx: &mut Context<Self>) {
+        if filter_ix < self.engine.applied_filters.len() {
+            self.engine.applied_filters.remove(filter_ix);
+            self.apply_filter();
+            cx.notify();
+        }
+    }
+
+    pub(crate) fn clear_filters(&mut self, cx: &mut Context<Self>) {
+        self.engine.applied_filters.clear();
+        self.apply_filter();
+        cx.notify();
+    }
+
+    /// Pick how the dialect of the file is determined, filling the dialect inputs with the
+    /// characters of an override
+    pub(crate) fn select_dialect(
+        &mut self,
+        dialect: CsvDialectSetting,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let CsvDialectSetting::Override(dialect) = dialect {
+            self.dialect_input.set_dialect(dialect, window, cx);
+        }
+        self.dialect_input.error = None;
+        self.set_dialect(dialect, cx);
+    }
+
+    /// Change how the dialect of the file is determined, remember it for the file and
+    /// re-parse its contents
+    pub(crate) fn set_dialect(&mut self, dialect: CsvDialectSetting, cx: &mut Context<Self>) {
+        if self.settings.dialect == dialect {
+            return;
+        }
+        self.settings.dialect = dialect;
+        if let Some(abs_path) = self.file_abs_path(cx) {
+            write_dialect_override(&abs_path, dialect, cx).detach_and_log_err(cx);
+        }
+        self.parse_csv_from_active_editor(false, cx);
+        cx.notify();
+    }
+
+    /// Apply the characters typed in the dialect inputs as the override
+    fn update_dialect_from_input(&mut self, cx: &mut Context<Self>) {
+        if !matches!(self.settings.dialect, CsvDialectSetting::Override(_)) {
+            return;
+        }
+        match self.dialect_input.dialect(cx) {
+            Ok(dialect) => {
+                self.dialect_input.error = None;
+                self.set_dialect(CsvDialectSetting::Override(dialect), cx);
+            }
+            Err(error) => self.dialect_input.error = Some(error),
+        }
+        cx.notify();
+    }
+
+    fn file_abs_path(&self, cx: &App) -> Option<PathBuf> {
+        let buffer = self
+            .active_editor_state
+            .editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()?;
+        Some(buffer.read(cx).file()?.as_local()?.abs_path(cx))
+    }
+
     pub(crate) fn apply_sort(&mut self) {
         self.performance_metrics.record("Sort", || {
             self.engine.apply_sort();
         });
     }
 
+    pub(crate) fn apply_filter(&mut self) {
+        self.search_task = None;
+        self.performance_metrics.record("Filter", || {
+            self.engine.apply_filter();
+        });
+
+        self.reset_list_state();
+    }
+
     /// Update ordered indices when ordering or content changes
     pub(crate) fn apply_filter_sort(&mut self) {
+        self.search_task = None;
         self.performance_metrics.record("Filter&sort", || {
             self.engine.calculate_d2d_mapping();
         });
 
-        // Update list state with filtered row count
+        self.reset_list_state();
+    }
+
+    /// Update list state with filtered row count
+    fn reset_list_state(&mut self) {
         let visible_rows = self.engine.d2d_mapping().visible_row_count();
         self.list_state = gpui::ListState::new(visible_rows, ListAlignment::Top, px(100.));
     }
@@ -231,9 +483,9 @@ impl CsvPreviewView {
                     .read(cx)
                     .file()
                     .and_then(|file| file.path().extension())
-                    .map(|ext| ext.eq_ignore_ascii_case("csv"))
+                    .and_then(TabularFormat::for_extension)
             })
-            .unwrap_or(false)
+            .is_some()
     }
 }
 
@@ -311,6 +563,106 @@ impl PerformanceMetrics {
     }
 }
 
+/// Holds state of the filter being composed in the toolbar.
+pub(crate) struct FilterInputState {
+    pub col_idx: AnyColumn,
+    pub kind: FilterKind,
+    pub value_editor: Entity<Editor>,
+    /// Reason the last attempt to add the filter failed
+    pub error: Option<SharedString>,
+}
+
+impl FilterInputState {
+    fn new(window: &mut Window, cx: &mut Context<CsvPreviewView>) -> Self {
+        let kind = FilterKind::Contains;
+        let value_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(kind.placeholder(), window, cx);
+            editor
+        });
+        Self {
+            col_idx: AnyColumn(0),
+            kind,
+            value_editor,
+            error: None,
+        }
+    }
+}
+
+/// Holds the inputs for the delimiter, quote and escape characters of a dialect override.
+pub(crate) struct DialectInputState {
+    pub delimiter_editor: Entity<Editor>,
+    pub quote_editor: Entity<Editor>,
+    pub escape_editor: Entity<Editor>,
+    /// Reason the typed characters can't be used as a dialect
+    pub error: Option<SharedString>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DialectInputState {
+    fn new(window: &mut Window, cx: &mut Context<CsvPreviewView>) -> Self {
+        let dialect = CsvDialect::default();
+        let mut subscriptions = Vec::new();
+        let mut new_input = |ch: char, cx: &mut Context<CsvPreviewView>| {
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_text(dialect_char_to_input(ch), window, cx);
+                editor
+            });
+            subscriptions.push(cx.subscribe(
+                &editor,
+                |this: &mut CsvPreviewView, _editor, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.update_dialect_from_input(cx);
+                    }
+                },
+            ));
+            editor
+        };
+        let delimiter_editor = new_input(dialect.delimiter, cx);
+        let quote_editor = new_input(dialect.quote, cx);
+        let escape_editor = new_input(dialect.escape, cx);
+        Self {
+            delimiter_editor,
+            quote_editor,
+            escape_editor,
+            error: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn set_dialect(&self, dialect: CsvDialect, window: &mut Window, cx: &mut App) {
+        for (editor, ch) in [
+            (&self.delimiter_editor, dialect.delimiter),
+            (&self.quote_editor, dialect.quote),
+            (&self.escape_editor, dialect.escape),
+        ] {
+            editor.update(cx, |editor, cx| {
+                editor.set_text(dialect_char_to_input(ch), window, cx);
+            });
+        }
+    }
+
+    fn dialect(&self, cx: &App) -> Result<CsvDialect, SharedString> {
+        let read = |editor: &Entity<Editor>| dialect_char_from_input(&editor.read(cx).text(cx));
+        let (Some(delimiter), Some(quote), Some(escape)) = (
+            read(&self.delimiter_editor),
+            read(&self.quote_editor),
+            read(&self.escape_editor),
+        ) else {
+            return Err("Enter a single character, or \\t for a tab".into());
+        };
+        if delimiter == quote || delimiter == escape {
+            return Err("The delimiter must differ from the quote and escape".into());
+        }
+        Ok(CsvDialect {
+            delimiter,
+            quote,
+            escape,
+        })
+    }
+}
+
 /// Holds state of column widths for a table component in CSV preview.
 pub(crate) struct ColumnWidths {
     pub widths: Entity<ResizableColumnsState>,
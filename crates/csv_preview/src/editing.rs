@@ -0,0 +1,543 @@
+//! Writing changes made in the table back to the source buffer.
+//!
+//! Edits are computed as byte ranges of the current buffer snapshot, using the anchors tracked
+//! by the parser, and applied to the buffer in a single transaction. This way they participate in
+//! undo/redo and collaboration like any other buffer edit, and the table is refreshed by re-parsing.
+
+use std::ops::Range;
+
+use editor::{
+    Editor, EditorEvent,
+    actions::{Redo, SelectAll, Undo},
+};
+use gpui::{AppContext as _, DismissEvent, Entity, Focusable as _, Pixels, Point, Subscription};
+use text::{BufferSnapshot, ToOffset as _};
+use ui::{ContextMenu, prelude::*, table_row::TableRow};
+
+use crate::{
+    CsvPreviewView, DeleteColumn, DeleteRow, EditCell, InsertColumnLeft, InsertColumnRight,
+    InsertRowAbove, InsertRowBelow,
+    parser::CsvDialect,
+    types::{AnyColumn, DataRow, TableCell, TableLikeContent},
+};
+
+/// Replacement of a byte range of the source buffer
+pub(crate) type SourceEdit = (Range<usize>, String);
+
+/// Where a row or column is inserted relatively to the selected one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertPosition {
+    /// Above the row, or to the left of the column
+    Before,
+    /// Below the row, or to the right of the column
+    After,
+}
+
+/// Cell which is selected or being edited, in data coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataCellId {
+    pub row: DataRow,
+    pub col: AnyColumn,
+}
+
+/// Inline editor of a single cell value
+pub(crate) struct CellEditor {
+    pub cell: DataCellId,
+    pub editor: Entity<Editor>,
+    _subscription: Subscription,
+}
+
+fn cell_range(cell: &TableCell, snapshot: &BufferSnapshot) -> Option<Range<usize>> {
+    match cell {
+        TableCell::Real { position, .. } => {
+            Some(position.start.to_offset(snapshot)..position.end.to_offset(snapshot))
+        }
+        TableCell::Virtual => None,
+    }
+}
+
+/// Range from the start of the first cell to the end of the last cell of the row,
+/// excluding the line break
+fn row_range(row: &TableRow<TableCell>, snapshot: &BufferSnapshot) -> Option<Range<usize>> {
+    let mut real_cells = row
+        .as_slice()
+        .iter()
+        .filter_map(|cell| cell_range(cell, snapshot));
+    let first = real_cells.next()?;
+    let last = real_cells.last().unwrap_or_else(|| first.clone());
+    Some(first.start..last.end)
+}
+
+fn empty_row(dialect: CsvDialect, number_of_cols: usize) -> String {
+    dialect
+        .delimiter
+        .to_string()
+        .repeat(number_of_cols.saturating_sub(1))
+}
+
+/// Replace the value of a cell. Cells padding a short row are created by appending delimiters.
+pub(crate) fn set_cell_value_edits(
+    contents: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    dialect: CsvDialect,
+    cell: DataCellId,
+    value: &str,
+) -> Option<Vec<SourceEdit>> {
+    let row = contents.get_row(cell.row)?;
+    let quoted_value = dialect.quote_field(value).into_owned();
+
+    if let Some(range) = cell_range(row.get(cell.col)?, snapshot) {
+        return Some(vec![(range, quoted_value)]);
+    }
+
+    if value.is_empty() {
+        return Some(Vec::new());
+    }
+    let (last_real_col, last_real_range) = row
+        .as_slice()
+        .iter()
+        .enumerate()
+        .filter_map(|(col, cell)| Some((col, cell_range(cell, snapshot)?)))
+        .last()?;
+    let padding = dialect
+        .delimiter
+        .to_string()
+        .repeat(*cell.col - last_real_col);
+    Some(vec![(
+        last_real_range.end..last_real_range.end,
+        format!("{padding}{quoted_value}"),
+    )])
+}
+
+pub(crate) fn insert_row_edits(
+    contents: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    dialect: CsvDialect,
+    data_row: DataRow,
+    position: InsertPosition,
+) -> Option<Vec<SourceEdit>> {
+    let range = row_range(contents.get_row(data_row)?, snapshot)?;
+    let new_row = empty_row(dialect, contents.number_of_cols);
+    Some(vec![match position {
+        InsertPosition::Before => (range.start..range.start, format!("{new_row}\n")),
+        InsertPosition::After => (range.end..range.end, format!("\n{new_row}")),
+    }])
+}
+
+/// Remove the row along with one of its adjacent line breaks
+pub(crate) fn delete_row_edits(
+    contents: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    data_row: DataRow,
+) -> Option<Vec<SourceEdit>> {
+    let range = row_range(contents.get_row(data_row)?, snapshot)?;
+
+    let next_row_start = contents
+        .get_row(DataRow(*data_row + 1))
+        .and_then(|row| row_range(row, snapshot))
+        .map(|next| next.start);
+    let deleted_range = if let Some(next_row_start) = next_row_start {
+        range.start..next_row_start
+    } else {
+        let previous_row = match data_row.checked_sub(1) {
+            Some(previous) => contents.get_row(DataRow(previous)),
+            None => Some(&contents.headers),
+        };
+        let previous_row_end = previous_row
+            .and_then(|row| row_range(row, snapshot))
+            .map(|previous| previous.end)
+            .unwrap_or(range.start);
+        previous_row_end..range.end
+    };
+    Some(vec![(deleted_range, String::new())])
+}
+
+/// Insert an empty column into the header and every row long enough to contain the column
+pub(crate) fn insert_column_edits(
+    contents: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    dialect: CsvDialect,
+    col: AnyColumn,
+    position: InsertPosition,
+) -> Vec<SourceEdit> {
+    std::iter::once(&contents.headers)
+        .chain(contents.rows.iter())
+        .filter_map(|row| {
+            let range = cell_range(row.get(col)?, snapshot)?;
+            let offset = match position {
+                InsertPosition::Before => range.start,
+                InsertPosition::After => range.end,
+            };
+            Some((offset..offset, dialect.delimiter.to_string()))
+        })
+        .collect()
+}
+
+/// Remove the column, along with its delimiter, from the header and every row containing it
+pub(crate) fn delete_column_edits(
+    contents: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    col: AnyColumn,
+) -> Vec<SourceEdit> {
+    std::iter::once(&contents.headers)
+        .chain(contents.rows.iter())
+        .filter_map(|row| {
+            let range = cell_range(row.get(col)?, snapshot)?;
+            let deleted_range = match col.checked_sub(1) {
+                // Remove the delimiter preceding the cell
+                Some(previous_col) => cell_range(row.get(previous_col)?, snapshot)?.end..range.end,
+                // Remove the delimiter following the first cell, if there is one
+                None => match row
+                    .get(AnyColumn(1))
+                    .and_then(|next| cell_range(next, snapshot))
+                {
+                    Some(next_range) => range.start..next_range.start,
+                    None => range,
+                },
+            };
+            Some((deleted_range, String::new()))
+        })
+        .collect()
+}
+
+impl CsvPreviewView {
+    /// Compute edits against the current buffer snapshot and apply them as a single transaction
+    fn edit_source(
+        &mut self,
+        cx: &mut Context<Self>,
+        compute_edits: impl FnOnce(&TableLikeContent, &BufferSnapshot, CsvDialect) -> Vec<SourceEdit>,
+    ) {
+        if !self.active_format.is_editable() {
+            return;
+        }
+        let Some(buffer) = self
+            .editor_state()
+            .editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()
+        else {
+            return;
+        };
+        let snapshot = buffer.read(cx).text_snapshot();
+        let edits = compute_edits(&self.engine.contents, &snapshot, self.active_dialect);
+        if edits.is_empty() {
+            return;
+        }
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit(edits, None, cx);
+        });
+    }
+
+    pub(crate) fn select_cell(&mut self, cell: DataCellId, cx: &mut Context<Self>) {
+        if self.selected_cell != Some(cell) {
+            self.selected_cell = Some(cell);
+            cx.notify();
+        }
+    }
+
+    /// Replace the cell content with an inline editor holding its value
+    pub(crate) fn start_editing_cell(
+        &mut self,
+        cell: DataCellId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.active_format.is_editable() {
+            return;
+        }
+        let Some(table_cell) = self
+            .engine
+            .contents
+            .get_row(cell.row)
+            .and_then(|row| row.get(cell.col))
+        else {
+            return;
+        };
+        let value = table_cell.display_value().cloned().unwrap_or_default();
+        self.commit_cell_edit(cx);
+
+        let editor = cx.new(|cx| {
+            let mut editor = if value.contains('\n') {
+                Editor::auto_height(1, 8, window, cx)
+            } else {
+                Editor::single_line(window, cx)
+            };
+            editor.set_text(value.as_str(), window, cx);
+            editor.select_all(&SelectAll, window, cx);
+            editor
+        });
+        let subscription = cx.subscribe_in(
+            &editor,
+            window,
+            |this, _, event: &EditorEvent, _window, cx| {
+                // Clicking away from the cell keeps the edit, like in spreadsheets
+                if let EditorEvent::Blurred = event {
+                    this.commit_cell_edit(cx);
+                }
+            },
+        );
+        window.focus(&editor.focus_handle(cx), cx);
+
+        self.selected_cell = Some(cell);
+        self.editing_cell = Some(CellEditor {
+            cell,
+            editor,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    pub(crate) fn edit_selected_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(cell) = self.selected_cell {
+            self.start_editing_cell(cell, window, cx);
+        }
+    }
+
+    /// Write the value of the inline editor into the source buffer and focus the table back
+    pub(crate) fn confirm_cell_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.commit_cell_edit(cx);
+        window.focus(&self.focus_handle, cx);
+    }
+
+    /// Write the value of the inline editor, if any, into the source buffer
+    fn commit_cell_edit(&mut self, cx: &mut Context<Self>) {
+        let Some(cell_editor) = self.editing_cell.take() else {
+            return;
+        };
+        let value = cell_editor.editor.read(cx).text(cx);
+        let original_value = self
+            .engine
+            .contents
+            .get_row(cell_editor.cell.row)
+            .and_then(|row| row.get(cell_editor.cell.col))
+            .and_then(|cell| cell.display_value())
+            .map(|value| value.as_str())
+            .unwrap_or("");
+
+        if value != original_value {
+            self.edit_source(cx, |contents, snapshot, dialect| {
+                set_cell_value_edits(contents, snapshot, dialect, cell_editor.cell, &value)
+                    .unwrap_or_default()
+            });
+        }
+        cx.notify();
+    }
+
+    pub(crate) fn cancel_cell_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_cell.take().is_some() {
+            window.focus(&self.focus_handle, cx);
+            cx.notify();
+        }
+    }
+
+    pub(crate) fn insert_row(&mut self, position: InsertPosition, cx: &mut Context<Self>) {
+        let Some(cell) = self.selected_cell else {
+            return;
+        };
+        self.edit_source(cx, |contents, snapshot, dialect| {
+            insert_row_edits(contents, snapshot, dialect, cell.row, position).unwrap_or_default()
+        });
+    }
+
+    pub(crate) fn delete_row(&mut self, cx: &mut Context<Self>) {
+        let Some(cell) = self.selected_cell else {
+            return;
+        };
+        self.edit_source(cx, |contents, snapshot, _| {
+            delete_row_edits(contents, snapshot, cell.row).unwrap_or_default()
+        });
+        self.selected_cell = None;
+    }
+
+    pub(crate) fn insert_column(&mut self, position: InsertPosition, cx: &mut Context<Self>) {
+        let Some(cell) = self.selected_cell else {
+            return;
+        };
+        self.edit_source(cx, |contents, snapshot, dialect| {
+            insert_column_edits(contents, snapshot, dialect, cell.col, position)
+        });
+    }
+
+    pub(crate) fn delete_column(&mut self, cx: &mut Context<Self>) {
+        let Some(cell) = self.selected_cell else {
+            return;
+        };
+        self.edit_source(cx, |contents, snapshot, _| {
+            delete_column_edits(contents, snapshot, cell.col)
+        });
+        self.selected_cell = None;
+    }
+
+    /// Undo the last edit of the source buffer, whether it was made from the table or the editor
+    pub(crate) fn undo(&mut self, _: &Undo, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor_state()
+            .editor
+            .update(cx, |editor, cx| editor.undo(&Undo, window, cx));
+    }
+
+    pub(crate) fn redo(&mut self, _: &Redo, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor_state()
+            .editor
+            .update(cx, |editor, cx| editor.redo(&Redo, window, cx));
+    }
+
+    pub(crate) fn deploy_cell_context_menu(
+        &mut self,
+        cell: DataCellId,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.commit_cell_edit(cx);
+        self.selected_cell = Some(cell);
+        if !self.active_format.is_editable() {
+            cx.notify();
+            return;
+        }
+
+        let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
+            menu.context(self.focus_handle.clone())
+                .action("Edit Cell", Box::new(EditCell))
+                .separator()
+                .action("Insert Row Above", Box::new(InsertRowAbove))
+                .action("Insert Row Below", Box::new(InsertRowBelow))
+                .action("Delete Row", Box::new(DeleteRow))
+                .separator()
+                .action("Insert Column Left", Box::new(InsertColumnLeft))
+                .action("Insert Column Right", Box::new(InsertColumnRight))
+                .action("Delete Column", Box::new(DeleteColumn))
+        });
+        window.focus(&context_menu.focus_handle(cx), cx);
+        let subscription = cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
+            this.context_menu.take();
+            cx.notify();
+        });
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser::from_buffer, settings::CsvDialectSetting};
+    use text::{Buffer, BufferId, ReplicaId};
+
+    /// Parse the text, compute edits on the parsed table and return the edited text
+    fn edit_text(
+        text: &str,
+        compute_edits: impl FnOnce(&TableLikeContent, &BufferSnapshot, CsvDialect) -> Vec<SourceEdit>,
+    ) -> String {
+        let mut buffer = Buffer::new(ReplicaId::LOCAL, BufferId::new(1).unwrap(), text);
+        let (contents, dialect) = from_buffer(buffer.snapshot(), CsvDialectSetting::Auto, None);
+        let edits = compute_edits(&contents, buffer.snapshot(), dialect);
+        buffer.edit(edits);
+        buffer.text()
+    }
+
+    fn cell(row: usize, col: usize) -> DataCellId {
+        DataCellId {
+            row: DataRow(row),
+            col: AnyColumn(col),
+        }
+    }
+
+    #[test]
+    fn test_set_cell_value() {
+        let text = "name,note\nJohn,\"a, b\"\nJane,c\n";
+        let edited = edit_text(text, |contents, snapshot, dialect| {
+            set_cell_value_edits(contents, snapshot, dialect, cell(0, 1), "said \"hi\"").unwrap()
+        });
+        assert_eq!(edited, "name,note\nJohn,\"said \"\"hi\"\"\"\nJane,c\n");
+
+        let edited = edit_text(text, |contents, snapshot, dialect| {
+            set_cell_value_edits(contents, snapshot, dialect, cell(1, 0), "Jane Doe").unwrap()
+        });
+        assert_eq!(edited, "name,note\nJohn,\"a, b\"\nJane Doe,c\n");
+    }
+
+    #[test]
+    fn test_set_virtual_cell_value() {
+        let text = "a,b,c\n1\n2,3,4\n";
+        let edited = edit_text(text, |contents, snapshot, dialect| {
+            set_cell_value_edits(contents, snapshot, dialect, cell(0, 2), "x;y").unwrap()
+        });
+        assert_eq!(edited, "a,b,c\n1,,x;y\n2,3,4\n");
+    }
+
+    #[test]
+    fn test_insert_and_delete_rows() {
+        let text = "a,b\n1,2\n3,4";
+        let edited = edit_text(text, |contents, snapshot, dialect| {
+            insert_row_edits(
+                contents,
+                snapshot,
+                dialect,
+                DataRow(0),
+                InsertPosition::Before,
+            )
+            .unwrap()
+        });
+        assert_eq!(edited, "a,b\n,\n1,2\n3,4");
+
+        let edited = edit_text(text, |contents, snapshot, dialect| {
+            insert_row_edits(
+                contents,
+                snapshot,
+                dialect,
+                DataRow(1),
+                InsertPosition::After,
+            )
+            .unwrap()
+        });
+        assert_eq!(edited, "a,b\n1,2\n3,4\n,");
+
+        let edited = edit_text(text, |contents, snapshot, _| {
+            delete_row_edits(contents, snapshot, DataRow(0)).unwrap()
+        });
+        assert_eq!(edited, "a,b\n3,4");
+
+        let edited = edit_text(text, |contents, snapshot, _| {
+            delete_row_edits(contents, snapshot, DataRow(1)).unwrap()
+        });
+        assert_eq!(edited, "a,b\n1,2");
+    }
+
+    #[test]
+    fn test_insert_and_delete_columns() {
+        let text = "a;b;c\n1;2;3\n4\n";
+        let edited = edit_text(text, |contents, snapshot, dialect| {
+            insert_column_edits(
+                contents,
+                snapshot,
+                dialect,
+                AnyColumn(1),
+                InsertPosition::Before,
+            )
+        });
+        assert_eq!(edited, "a;;b;c\n1;;2;3\n4\n");
+
+        let edited = edit_text(text, |contents, snapshot, dialect| {
+            insert_column_edits(
+                contents,
+                snapshot,
+                dialect,
+                AnyColumn(0),
+                InsertPosition::After,
+            )
+        });
+        assert_eq!(edited, "a;;b;c\n1;;2;3\n4;\n");
+
+        let edited = edit_text(text, |contents, snapshot, _| {
+            delete_column_edits(contents, snapshot, AnyColumn(0))
+        });
+        assert_eq!(edited, "b;c\n2;3\n\n");
+
+        let edited = edit_text(text, |contents, snapshot, _| {
+            delete_column_edits(contents, snapshot, AnyColumn(2))
+        });
+        assert_eq!(edited, "a;b\n1;2\n4\n");
+    }
+}
@@ -1,16 +1,43 @@
 use crate::{
     CsvPreviewView,
+    settings::CsvDialectSetting,
+    table_data_engine::column_types::infer_column_types,
     types::TableLikeContent,
     types::{LineNumber, TableCell},
 };
 use editor::Editor;
 use gpui::{AppContext, Context, Entity, Subscription, Task};
-use std::time::{Duration, Instant};
+use std::{
+    ops::Range,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use text::BufferSnapshot;
 use ui::{SharedString, table_row::TableRow};
 
+pub use dialect::CsvDialect;
+pub use format::TabularFormat;
+
+mod dialect;
+mod fixed_width;
+mod format;
+mod json_lines;
+
 pub(crate) const REPARSE_DEBOUNCE: Duration = Duration::from_millis(200);
 
+/// Cell value along with its byte range in the source text
+pub(crate) type ParsedCell = (SharedString, Range<usize>);
+
+/// Rows and columns split from the source text, before cells are anchored to the buffer.
+/// Missing cells are `None`.
+#[derive(Default)]
+pub(crate) struct ParsedTable {
+    pub headers: Vec<Option<ParsedCell>>,
+    pub rows: Vec<Vec<Option<ParsedCell>>>,
+    /// Follows the same indices as `rows`
+    pub line_numbers: Vec<LineNumber>,
+}
+
 pub(crate) struct EditorState {
     pub editor: Entity<Editor>,
     pub _subscription: Subscription,
@@ -54,22 +81,43 @@ impl CsvPreviewView {
                 }
             }
 
-            let buffer_snapshot = view.update(cx, |_, cx| {
-                editor
-                    .read(cx)
-                    .buffer()
-                    .read(cx)
-                    .as_singleton()
-                    .map(|b| b.read(cx).text_snapshot())
-            })?;
+            let (buffer_snapshot, file_extension, dialect_setting) =
+                view.update(cx, |view, cx| {
+                    let buffer = editor.read(cx).buffer().read(cx).as_singleton();
+                    let file_extension = buffer.as_ref().and_then(|b| {
+                        b.read(cx)
+                            .file()?
+                            .path()
+                            .extension()
+                            .map(|ext| ext.to_string())
+                    });
+                    (
+                        buffer.map(|b| b.read(cx).text_snapshot()),
+                        file_extension,
+                        view.settings.dialect,
+                    )
+                })?;
 
             let Some(buffer_snapshot) = buffer_snapshot else {
                 return Ok(());
             };
 
             let instant = Instant::now();
-            let parsed_csv = cx
-                .background_spawn(async move { from_buffer(&buffer_snapshot) })
+            let format = file_extension
+                .as_deref()
+                .and_then(TabularFormat::for_extension)
+                .unwrap_or_default();
+            let (parsed_csv, dialect, column_types) = cx
+                .background_spawn(async move {
+                    let (parsed_csv, dialect) = parse_buffer(
+                        &buffer_snapshot,
+                        format,
+                        dialect_setting,
+                        file_extension.as_deref(),
+                    );
+                    let column_types = infer_column_types(&parsed_csv);
+                    (parsed_csv, dialect, column_types)
+                })
                 .await;
             let parse_duration = instant.elapsed();
             let parse_end_time: Instant = Instant::now();
@@ -81,6 +129,9 @@ impl CsvPreviewView {
 
                 log::debug!("Parsed {} rows", parsed_csv.rows.len());
                 view.engine.contents = parsed_csv;
+                view.engine.column_types = column_types;
+                view.active_dialect = dialect;
+                view.active_format = format;
                 view.sync_column_widths(cx);
                 view.last_parse_end_time = Some(parse_end_time);
 
@@ -91,28 +142,100 @@ impl CsvPreviewView {
     }
 }
 
-pub fn from_buffer(buffer_snapshot: &BufferSnapshot) -> TableLikeContent {
+/// Parse buffer contents of the given format into a table.
+/// The returned dialect is only meaningful for delimited files.
+pub fn parse_buffer(
+    buffer_snapshot: &BufferSnapshot,
+    format: TabularFormat,
+    dialect_setting: CsvDialectSetting,
+    file_extension: Option<&str>,
+) -> (TableLikeContent, CsvDialect) {
+    let parsed_table = match format {
+        TabularFormat::Delimited => {
+            return from_buffer(buffer_snapshot, dialect_setting, file_extension);
+        }
+        TabularFormat::JsonLines => {
+            json_lines::parse_json_lines_with_positions(&buffer_snapshot.text())
+        }
+        TabularFormat::FixedWidth => {
+            fixed_width::parse_fixed_width_with_positions(&buffer_snapshot.text())
+        }
+    };
+    (
+        table_from_parsed(buffer_snapshot, parsed_table),
+        CsvDialect::default(),
+    )
+}
+
+/// Anchor parsed cells to the buffer, padding rows with virtual cells to the widest row
+fn table_from_parsed(
+    buffer_snapshot: &BufferSnapshot,
+    parsed_table: ParsedTable,
+) -> TableLikeContent {
+    let number_of_cols = parsed_table
+        .rows
+        .iter()
+        .map(|row| row.len())
+        .chain([parsed_table.headers.len()])
+        .max()
+        .unwrap_or(0);
+    if number_of_cols == 0 {
+        return TableLikeContent::default();
+    }
+
+    let create_row = |cells: Vec<Option<ParsedCell>>| {
+        let mut cells = cells
+            .into_iter()
+            .map(|cell| match cell {
+                Some((content, range)) => TableCell::from_buffer_position(
+                    content,
+                    range.start,
+                    range.end,
+                    buffer_snapshot,
+                ),
+                None => TableCell::Virtual,
+            })
+            .collect::<Vec<_>>();
+        cells.resize(number_of_cols, TableCell::Virtual);
+        TableRow::from_vec(cells, number_of_cols)
+    };
+
+    TableLikeContent {
+        number_of_cols,
+        headers: create_row(parsed_table.headers),
+        rows: Arc::new(parsed_table.rows.into_iter().map(create_row).collect()),
+        line_numbers: parsed_table.line_numbers,
+    }
+}
+
+/// Parse delimited buffer contents into a table, returning it along with the dialect it was parsed with
+pub fn from_buffer(
+    buffer_snapshot: &BufferSnapshot,
+    dialect_setting: CsvDialectSetting,
+    file_extension: Option<&str>,
+) -> (TableLikeContent, CsvDialect) {
     let text = buffer_snapshot.text();
+    let dialect = dialect_setting.resolve(&text, file_extension);
 
     if text.trim().is_empty() {
-        return TableLikeContent::default();
+        return (TableLikeContent::default(), dialect);
     }
 
-    let (parsed_cells_with_positions, line_numbers) = parse_csv_with_positions(&text);
+    let (parsed_cells_with_positions, line_numbers) = parse_csv_with_positions(&text, dialect);
     if parsed_cells_with_positions.is_empty() {
-        return TableLikeContent::default();
+        return (TableLikeContent::default(), dialect);
     }
     let raw_headers = parsed_cells_with_positions[0].clone();
 
     // Calculating the longest row, as CSV might have less headers than max row width
     let Some(max_number_of_cols) = parsed_cells_with_positions.iter().map(|r| r.len()).max() else {
-        return TableLikeContent::default();
+        return (TableLikeContent::default(), dialect);
     };
 
     // Convert to TableCell objects with buffer positions
     let headers = create_table_row(&buffer_snapshot, max_number_of_cols, raw_headers);
 
-    let rows = parsed_cells_with_positions
+    let rows: Vec<_> = parsed_cells_with_positions
         .into_iter()
         .skip(1)
         .map(|row| create_table_row(&buffer_snapshot, max_number_of_cols, row))
@@ -120,17 +243,19 @@ pub fn from_buffer(buffer_snapshot: &BufferSnapshot) -> TableLikeContent {
 
     let row_line_numbers = line_numbers.into_iter().skip(1).collect();
 
-    TableLikeContent {
+    let table = TableLikeContent {
         headers,
-        rows,
+        rows: Arc::new(rows),
         line_numbers: row_line_numbers,
         number_of_cols: max_number_of_cols,
-    }
+    };
+    (table, dialect)
 }
 
-/// Parse CSV and track byte positions for each cell
+/// Parse CSV with the given dialect and track byte positions for each cell
 fn parse_csv_with_positions(
     text: &str,
+    dialect: CsvDialect,
 ) -> (
     Vec<Vec<(SharedString, std::ops::Range<usize>)>>,
     Vec<LineNumber>,
@@ -150,13 +275,28 @@ fn parse_csv_with_positions(
         let char_byte_len = ch.len_utf8();
 
         match ch {
-            '"' => {
+            c if c == dialect.escape && !dialect.escapes_by_doubling() => {
+                // Escape character: take the next character literally
+                if current_field.is_empty() && !in_quotes {
+                    field_start_offset = current_offset;
+                }
+                if let Some(escaped) = chars.next_if(|&next| next != '\n' || in_quotes) {
+                    if escaped == '\n' {
+                        current_line += 1;
+                    }
+                    current_field.push(escaped);
+                    current_offset += escaped.len_utf8();
+                } else {
+                    current_field.push(c);
+                }
+            }
+            c if c == dialect.quote => {
                 if in_quotes {
-                    if chars.peek() == Some(&'"') {
+                    if dialect.escapes_by_doubling() && chars.peek() == Some(&dialect.quote) {
                         // Escaped quote
                         chars.next();
-                        current_field.push('"');
-                        current_offset += 1; // Skip the second quote
+                        current_field.push(dialect.quote);
+                        current_offset += char_byte_len; // Skip the second quote
                     } else {
                         // End of quoted field
                         in_quotes = false;
@@ -170,7 +310,7 @@ fn parse_csv_with_positions(
                     }
                 }
             }
-            ',' if !in_quotes => {
+            c if c == dialect.delimiter && !in_quotes => {
                 // Field separator
                 let field_end_offset = current_offset;
                 if current_field.is_empty() && !in_quotes {
@@ -198,9 +338,7 @@ fn parse_csv_with_positions(
                     current_field.clear();
 
                     // Only add non-empty rows
-                    if !current_row.is_empty()
-                        && !current_row.iter().all(|(field, _)| field.trim().is_empty())
-                    {
+                    if !is_blank_row(&current_row) {
                         rows.push(current_row);
                         // Add line number info for this row
                         let line_info = if row_start_line == current_line - 1 {
@@ -236,9 +374,7 @@ fn parse_csv_with_positions(
                         current_field.clear();
 
                         // Only add non-empty rows
-                        if !current_row.is_empty()
-                            && !current_row.iter().all(|(field, _)| field.trim().is_empty())
-                        {
+                        if !is_blank_row(&current_row) {
                             rows.push(current_row);
                             // Add line number info for this row
                             let line_info = if row_start_line == current_line - 1 {
@@ -276,7 +412,7 @@ fn parse_csv_with_positions(
             field_start_offset..field_end_offset,
         ));
     }
-    if !current_row.is_empty() && !current_row.iter().all(|(field, _)| field.trim().is_empty()) {
+    if !is_blank_row(&current_row) {
         rows.push(current_row);
         // Add line number info for the last row
         let line_info = if row_start_line == current_line {
@@ -290,6 +426,16 @@ fn parse_csv_with_positions(
     (rows, line_numbers)
 }
 
+/// Blank lines are skipped, while rows made of empty fields (e.g. `,,`) are kept,
+/// so that freshly inserted rows show up in the table
+fn is_blank_row(row: &[(SharedString, std::ops::Range<usize>)]) -> bool {
+    match row {
+        [] => true,
+        [(field, _)] => field.trim().is_empty(),
+        _ => false,
+    }
+}
+
 fn create_table_row(
     buffer_snapshot: &BufferSnapshot,
     max_number_of_cols: usize,
@@ -421,7 +567,7 @@ Jane,"Simple name""#;
     #[test]
     fn test_csv_parsing_quote_offset_handling() {
         let csv_data = r#"first,"se,cond",third"#;
-        let (parsed_cells, _) = parse_csv_with_positions(csv_data);
+        let (parsed_cells, _) = parse_csv_with_positions(csv_data, CsvDialect::default());
 
         assert_eq!(parsed_cells.len(), 1); // One row
         assert_eq!(parsed_cells[0].len(), 3); // Three cells
@@ -447,7 +593,7 @@ Jane,"Simple name""#;
         let csv_data = r#"id,"name with spaces","description, with commas",status
 1,"John Doe","A person with ""quotes"" and, commas",active
 2,"Jane Smith","Simple description",inactive"#;
-        let (parsed_cells, _) = parse_csv_with_positions(csv_data);
+        let (parsed_cells, _) = parse_csv_with_positions(csv_data, CsvDialect::default());
 
         assert_eq!(parsed_cells.len(), 3); // header + 2 rows
 
@@ -495,16 +641,99 @@ Jane,"Simple name""#;
         assert_eq!(first_row[3].0.as_ref(), "active");
         assert_eq!(first_row[3].1, 108..114);
     }
+
+    #[test]
+    fn test_tsv_parsing_positions() {
+        let tsv_data = "name\tnote\nJohn\t\"a\tb\"\n";
+        let (parsed_cells, _) = parse_csv_with_positions(tsv_data, CsvDialect::TAB);
+
+        assert_eq!(parsed_cells.len(), 2);
+        assert_eq!(parsed_cells[1][0].0.as_ref(), "John");
+        assert_eq!(parsed_cells[1][0].1, 10..14);
+        assert_eq!(parsed_cells[1][1].0.as_ref(), "a\tb");
+        assert_eq!(parsed_cells[1][1].1, 15..20);
+    }
+
+    #[test]
+    fn test_semicolon_parsing_detected() {
+        let csv_data = "product;price\napple;1,50\npear;2,10";
+        let parsed = TableLikeContent::from_str(csv_data.to_string());
+
+        assert_eq!(parsed.headers.cols(), 2);
+        assert_eq!(parsed.rows.len(), 2);
+        assert_eq!(parsed.rows[0][1].display_value().unwrap().as_ref(), "1,50");
+        assert_eq!(parsed.rows[1][0].display_value().unwrap().as_ref(), "pear");
+    }
+
+    #[test]
+    fn test_dialect_override() {
+        let csv_data = "a;b,c\n1;2,3";
+        let parsed = TableLikeContent::from_str_with_dialect(
+            csv_data.to_string(),
+            CsvDialectSetting::Override(CsvDialect::COMMA),
+        );
+
+        assert_eq!(parsed.headers.cols(), 2);
+        assert_eq!(parsed.headers[0].display_value().unwrap().as_ref(), "a;b");
+        assert_eq!(parsed.rows[0][1].display_value().unwrap().as_ref(), "3");
+    }
+
+    #[test]
+    fn test_custom_quote_and_escape_parsing() {
+        let dialect = CsvDialect {
+            delimiter: ',',
+            quote: '\'',
+            escape: '\\',
+        };
+        let csv_data = r"'id','text'
+1,'it\'s, here'";
+        let (parsed_cells, _) = parse_csv_with_positions(csv_data, dialect);
+
+        assert_eq!(parsed_cells.len(), 2);
+        assert_eq!(parsed_cells[0][1].0.as_ref(), "text");
+        assert_eq!(parsed_cells[1][1].0.as_ref(), "it's, here");
+        // Range includes quotes and the escape character
+        assert_eq!(parsed_cells[1][1].1, 14..27);
+    }
+
+    #[test]
+    fn test_json_lines_table() {
+        let jsonl_data = "{\"id\": 1, \"msg\": \"a\"}\n{\"id\": 2, \"extra\": true}\n";
+        let parsed = TableLikeContent::from_str_with_format(
+            jsonl_data.to_string(),
+            TabularFormat::JsonLines,
+        );
+
+        assert_eq!(parsed.number_of_cols, 3);
+        assert_eq!(parsed.headers[2].display_value().unwrap().as_ref(), "extra");
+        assert_eq!(parsed.rows.len(), 2);
+        assert!(parsed.rows[1][1].display_value().is_none());
+        assert_eq!(parsed.rows[1][2].display_value().unwrap().as_ref(), "true");
+    }
 }
 
 impl TableLikeContent {
     #[cfg(test)]
     pub fn from_str(text: String) -> Self {
+        Self::from_str_with_dialect(text, CsvDialectSetting::Auto)
+    }
+
+    #[cfg(test)]
+    pub fn from_str_with_format(text: String, format: TabularFormat) -> Self {
+        use text::{Buffer, BufferId, ReplicaId};
+
+        let buffer_id = BufferId::new(1).unwrap();
+        let buffer = Buffer::new(ReplicaId::LOCAL, buffer_id, text);
+        parse_buffer(buffer.snapshot(), format, CsvDialectSetting::Auto, None).0
+    }
+
+    #[cfg(test)]
+    pub fn from_str_with_dialect(text: String, dialect_setting: CsvDialectSetting) -> Self {
         use text::{Buffer, BufferId, ReplicaId};
 
         let buffer_id = BufferId::new(1).unwrap();
         let buffer = Buffer::new(ReplicaId::LOCAL, buffer_id, text);
         let snapshot = buffer.snapshot();
-        from_buffer(snapshot)
+        from_buffer(snapshot, dialect_setting, None).0
     }
 }
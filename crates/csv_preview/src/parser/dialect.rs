@@ -0,0 +1,334 @@
+//! CSV dialect definition and detection.
+//!
+//! A dialect describes how a delimiter-separated file is structured: which character separates
+//! fields, which one quotes them and how quotes are escaped inside quoted fields.
+
+use std::borrow::Cow;
+
+/// Delimiters considered when sniffing the dialect, in order of preference on ties.
+const CANDIDATE_DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+
+/// Number of leading rows inspected when sniffing the dialect.
+const SNIFF_SAMPLE_ROWS: usize = 20;
+
+/// Describes how fields of a delimiter-separated file are separated, quoted and escaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvDialect {
+    /// Separates fields within a row
+    pub delimiter: char,
+    /// Wraps fields containing delimiters, quotes or newlines
+    pub quote: char,
+    /// Escapes the next character inside a quoted field.
+    /// When equal to `quote`, quotes are escaped by doubling them (RFC 4180).
+    pub escape: char,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self::COMMA
+    }
+}
+
+impl CsvDialect {
+    pub const COMMA: Self = Self::with_delimiter(',');
+    pub const TAB: Self = Self::with_delimiter('\t');
+    pub const SEMICOLON: Self = Self::with_delimiter(';');
+    pub const PIPE: Self = Self::with_delimiter('|');
+
+    /// Dialects which can be picked explicitly as a per-file override
+    pub const PRESETS: [Self; 4] = [Self::COMMA, Self::TAB, Self::SEMICOLON, Self::PIPE];
+
+    /// Create RFC 4180 dialect (double quotes, escaped by doubling) with a custom delimiter
+    pub const fn with_delimiter(delimiter: char) -> Self {
+        Self {
+            delimiter,
+            quote: '"',
+            escape: '"',
+        }
+    }
+
+    /// Default dialect implied by the file extension, if it's specific to one delimiter
+    pub fn for_extension(extension: &str) -> Option<Self> {
+        if extension.eq_ignore_ascii_case("tsv") || extension.eq_ignore_ascii_case("tab") {
+            Some(Self::TAB)
+        } else if extension.eq_ignore_ascii_case("psv") {
+            Some(Self::PIPE)
+        } else {
+            None
+        }
+    }
+
+    /// Whether quotes inside quoted fields are escaped by doubling them
+    pub fn escapes_by_doubling(&self) -> bool {
+        self.escape == self.quote
+    }
+
+    /// Serialize a cell value, quoting and escaping it when it can't be written verbatim
+    pub fn quote_field<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        let needs_quoting = value.chars().any(|ch| {
+            ch == self.delimiter
+                || ch == self.quote
+                || ch == self.escape
+                || ch == '\n'
+                || ch == '\r'
+        });
+        if !needs_quoting {
+            return Cow::Borrowed(value);
+        }
+
+        let mut quoted = String::with_capacity(value.len() + 2);
+        quoted.push(self.quote);
+        for ch in value.chars() {
+            if ch == self.quote || ch == self.escape {
+                quoted.push(self.escape);
+            }
+            quoted.push(ch);
+        }
+        quoted.push(self.quote);
+        Cow::Owned(quoted)
+    }
+
+    /// Encode the dialect as its delimiter, quote and escape characters, for persisting it
+    pub fn encode(&self) -> String {
+        [self.delimiter, self.quote, self.escape].iter().collect()
+    }
+
+    /// Decode a dialect previously encoded with [`CsvDialect::encode`]
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let mut chars = encoded.chars();
+        let dialect = Self {
+            delimiter: chars.next()?,
+            quote: chars.next()?,
+            escape: chars.next()?,
+        };
+        chars.next().is_none().then_some(dialect)
+    }
+
+    /// Human readable name of the delimiter
+    pub fn delimiter_name(&self) -> String {
+        match self.delimiter {
+            ',' => "Comma".to_string(),
+            '\t' => "Tab".to_string(),
+            ';' => "Semicolon".to_string(),
+            '|' => "Pipe".to_string(),
+            other => format!("'{other}'"),
+        }
+    }
+
+    /// Detect the dialect from the first rows of the text.
+    ///
+    /// Picks the candidate delimiter which splits the most sampled rows into the same
+    /// number of fields, preferring the one producing more fields on ties.
+    /// Returns `None` when no candidate delimiter occurs in the sample.
+    pub fn sniff(text: &str) -> Option<Self> {
+        let quote = sniff_quote(text);
+        let escape = sniff_escape(text, quote);
+
+        let mut best: Option<(char, (usize, usize))> = None;
+        for delimiter in CANDIDATE_DELIMITERS {
+            let counts = delimiter_counts_per_row(text, delimiter, quote, escape);
+            let Some(score) = consistency_score(&counts) else {
+                continue;
+            };
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((delimiter, score));
+            }
+        }
+
+        best.map(|(delimiter, _)| Self {
+            delimiter,
+            quote,
+            escape,
+        })
+    }
+}
+
+/// Quote character is the one which most often opens a field in the sample
+fn sniff_quote(text: &str) -> char {
+    let mut double_quotes = 0;
+    let mut single_quotes = 0;
+    for line in text.lines().take(SNIFF_SAMPLE_ROWS) {
+        let mut at_field_start = true;
+        for ch in line.chars() {
+            if at_field_start {
+                match ch {
+                    '"' => double_quotes += 1,
+                    '\'' => single_quotes += 1,
+                    _ => {}
+                }
+            }
+            at_field_start = CANDIDATE_DELIMITERS.contains(&ch);
+        }
+    }
+
+    if single_quotes > double_quotes {
+        '\''
+    } else {
+        '"'
+    }
+}
+
+/// Backslash escaping is assumed when the sample contains a backslash-escaped quote
+fn sniff_escape(text: &str, quote: char) -> char {
+    let backslash_escaped_quote = format!("\\{quote}");
+    let sample_contains_backslash_escape = text
+        .lines()
+        .take(SNIFF_SAMPLE_ROWS)
+        .any(|line| line.contains(&backslash_escaped_quote));
+
+    if sample_contains_backslash_escape {
+        '\\'
+    } else {
+        quote
+    }
+}
+
+/// Count unquoted delimiter occurrences for each of the first non-empty rows
+fn delimiter_counts_per_row(text: &str, delimiter: char, quote: char, escape: char) -> Vec<usize> {
+    let mut counts = Vec::with_capacity(SNIFF_SAMPLE_ROWS);
+    let mut current_count = 0;
+    let mut row_is_empty = true;
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if in_quotes && ch == escape && (escape != quote || chars.peek() == Some(&quote)) {
+            chars.next();
+            continue;
+        }
+
+        if ch == quote {
+            in_quotes = !in_quotes;
+        } else if ch == delimiter && !in_quotes {
+            current_count += 1;
+        } else if (ch == '\n' || ch == '\r') && !in_quotes {
+            if !row_is_empty {
+                counts.push(current_count);
+                if counts.len() == SNIFF_SAMPLE_ROWS {
+                    return counts;
+                }
+            }
+            current_count = 0;
+            row_is_empty = true;
+            continue;
+        }
+
+        if !ch.is_whitespace() || ch == delimiter {
+            row_is_empty = false;
+        }
+    }
+
+    if !row_is_empty {
+        counts.push(current_count);
+    }
+    counts
+}
+
+/// Returns (rows sharing the most common delimiter count, that count),
+/// or `None` if the delimiter is absent from the rows
+fn consistency_score(counts: &[usize]) -> Option<(usize, usize)> {
+    let mut frequencies = std::collections::HashMap::<usize, usize>::new();
+    for &count in counts {
+        *frequencies.entry(count).or_default() += 1;
+    }
+
+    frequencies
+        .into_iter()
+        .filter(|&(count, _)| count > 0)
+        .map(|(count, rows)| (rows, count))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_comma() {
+        let text = "name,age,city\nJohn,30,\"New York; NY\"\nJane,25,Boston";
+        assert_eq!(CsvDialect::sniff(text), Some(CsvDialect::COMMA));
+    }
+
+    #[test]
+    fn test_sniff_tab() {
+        let text = "name\tage\tcity\nJohn, Jr.\t30\tNew York\nJane\t25\tBoston\n";
+        assert_eq!(CsvDialect::sniff(text), Some(CsvDialect::TAB));
+    }
+
+    #[test]
+    fn test_sniff_semicolon_with_decimal_commas() {
+        let text = "product;price;weight\napple;1,50;0,2\npear;2,10;0,3\n";
+        assert_eq!(CsvDialect::sniff(text), Some(CsvDialect::SEMICOLON));
+    }
+
+    #[test]
+    fn test_sniff_pipe() {
+        let text = "id|message\r\n1|hello, world\r\n2|bye\r\n";
+        assert_eq!(CsvDialect::sniff(text), Some(CsvDialect::PIPE));
+    }
+
+    #[test]
+    fn test_sniff_ignores_quoted_delimiters() {
+        let text = "a;b\n\"x,y,z\";1\n\"p,q\";2\n";
+        assert_eq!(CsvDialect::sniff(text), Some(CsvDialect::SEMICOLON));
+    }
+
+    #[test]
+    fn test_sniff_quote_and_escape() {
+        let text = "'id','text'\n'1','it\\'s'\n";
+        assert_eq!(
+            CsvDialect::sniff(text),
+            Some(CsvDialect {
+                delimiter: ',',
+                quote: '\'',
+                escape: '\\',
+            })
+        );
+    }
+
+    #[test]
+    fn test_quote_field() {
+        assert_eq!(CsvDialect::COMMA.quote_field("plain"), "plain");
+        assert_eq!(CsvDialect::COMMA.quote_field("a,b"), "\"a,b\"");
+        assert_eq!(
+            CsvDialect::COMMA.quote_field("say \"hi\""),
+            "\"say \"\"hi\"\"\""
+        );
+        assert_eq!(
+            CsvDialect::COMMA.quote_field("two\nlines"),
+            "\"two\nlines\""
+        );
+        assert_eq!(CsvDialect::TAB.quote_field("a,b"), "a,b");
+
+        let backslash_escaped = CsvDialect {
+            delimiter: ';',
+            quote: '"',
+            escape: '\\',
+        };
+        assert_eq!(
+            backslash_escaped.quote_field(r#"C:\ "x""#),
+            r#""C:\\ \"x\"""#
+        );
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let dialect = CsvDialect {
+            delimiter: '\t',
+            quote: '\'',
+            escape: '\\',
+        };
+        assert_eq!(CsvDialect::decode(&dialect.encode()), Some(dialect));
+        assert_eq!(
+            CsvDialect::decode(&CsvDialect::COMMA.encode()),
+            Some(CsvDialect::COMMA)
+        );
+        assert_eq!(CsvDialect::decode(",\""), None);
+        assert_eq!(CsvDialect::decode(",\"\"\""), None);
+    }
+
+    #[test]
+    fn test_sniff_single_column() {
+        assert_eq!(CsvDialect::sniff("header\nvalue\nother value\n"), None);
+    }
+}
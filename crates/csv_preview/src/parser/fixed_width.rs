@@ -0,0 +1,179 @@
+//! Fixed-width text tables, where columns are aligned with spaces and the first line is the header.
+//!
+//! Columns start at header labels preceded by a position which is blank in every line,
+//! e.g. in the output of `ps` or `docker ps`.
+
+use std::ops::Range;
+
+use crate::{
+    parser::{ParsedCell, ParsedTable},
+    types::LineNumber,
+};
+
+pub(super) fn parse_fixed_width_with_positions(text: &str) -> ParsedTable {
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    for (line_ix, line_with_break) in text.split_inclusive('\n').enumerate() {
+        let line = line_with_break.trim_end_matches(['\n', '\r']);
+        if !line.trim().is_empty() {
+            lines.push((line_ix + 1, line_start, line));
+        }
+        line_start += line_with_break.len();
+    }
+
+    let column_starts = detect_column_starts(lines.iter().map(|(_, _, line)| *line));
+    if column_starts.is_empty() {
+        return ParsedTable::default();
+    }
+
+    let mut rows = lines.into_iter().map(|(line_number, offset, line)| {
+        let cells = split_line(line, &column_starts)
+            .into_iter()
+            .map(|cell| {
+                cell.map(|(value, range)| (value, offset + range.start..offset + range.end))
+            })
+            .collect::<Vec<_>>();
+        (cells, LineNumber::Line(line_number))
+    });
+
+    let Some((headers, _)) = rows.next() else {
+        return ParsedTable::default();
+    };
+    let (rows, line_numbers) = rows.unzip();
+    ParsedTable {
+        headers,
+        rows,
+        line_numbers,
+    }
+}
+
+/// Character positions where columns start: non-blank positions of the header which follow
+/// a position blank in all lines. Lines shorter than a position are blank there.
+fn detect_column_starts<'a>(mut lines: impl Iterator<Item = &'a str>) -> Vec<usize> {
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let header_chars = header.chars().collect::<Vec<_>>();
+    let mut is_blank_column = header_chars
+        .iter()
+        .map(|ch| ch.is_whitespace())
+        .collect::<Vec<_>>();
+    for line in lines {
+        for (char_ix, ch) in line.chars().take(header_chars.len()).enumerate() {
+            if !ch.is_whitespace() {
+                is_blank_column[char_ix] = false;
+            }
+        }
+    }
+
+    let mut column_starts = header_chars
+        .iter()
+        .enumerate()
+        .filter(|&(char_ix, ch)| {
+            !ch.is_whitespace() && (char_ix == 0 || is_blank_column[char_ix - 1])
+        })
+        .map(|(char_ix, _)| char_ix)
+        .collect::<Vec<_>>();
+    // Values of the first column may start before its header
+    if let Some(first_start) = column_starts.first_mut() {
+        *first_start = 0;
+    }
+    column_starts
+}
+
+/// Cells of the line, trimmed, with byte ranges relative to the line start.
+/// Cells beyond the end of the line are `None`, the last cell extends to the end of the line.
+fn split_line(line: &str, column_starts: &[usize]) -> Vec<Option<ParsedCell>> {
+    let char_offsets = line
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain([line.len()])
+        .collect::<Vec<_>>();
+    let char_count = char_offsets.len() - 1;
+
+    column_starts
+        .iter()
+        .enumerate()
+        .map(|(col, &start)| {
+            if start >= char_count {
+                return None;
+            }
+            let end = column_starts
+                .get(col + 1)
+                .map_or(char_count, |&next_start| next_start.min(char_count));
+            let range: Range<usize> = char_offsets[start]..char_offsets[end];
+
+            let raw_value = &line[range.clone()];
+            let value = raw_value.trim();
+            let value_start = range.start + (raw_value.len() - raw_value.trim_start().len());
+            Some((
+                value.to_string().into(),
+                value_start..value_start + value.len(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell_values(cells: &[Option<ParsedCell>]) -> Vec<Option<&str>> {
+        cells
+            .iter()
+            .map(|cell| cell.as_ref().map(|(value, _)| value.as_ref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_fixed_width_columns() {
+        let text = "\
+CONTAINER ID   IMAGE     STATUS
+4f1a2b3c4d5e   postgres  Up 2 hours
+
+9e8d7c6b5a4f   redis     Exited (0) 3 days ago
+0a1b2c3d4e5f   nginx
+";
+        let table = parse_fixed_width_with_positions(text);
+
+        assert_eq!(
+            cell_values(&table.headers),
+            vec![Some("CONTAINER ID"), Some("IMAGE"), Some("STATUS")]
+        );
+        assert_eq!(table.rows.len(), 3);
+        assert_eq!(
+            cell_values(&table.rows[0]),
+            vec![Some("4f1a2b3c4d5e"), Some("postgres"), Some("Up 2 hours")]
+        );
+        assert_eq!(
+            cell_values(&table.rows[1]),
+            vec![
+                Some("9e8d7c6b5a4f"),
+                Some("redis"),
+                Some("Exited (0) 3 days ago")
+            ]
+        );
+        assert_eq!(
+            cell_values(&table.rows[2]),
+            vec![Some("0a1b2c3d4e5f"), Some("nginx"), None]
+        );
+        assert!(matches!(table.line_numbers[1], LineNumber::Line(4)));
+
+        let (_, range) = table.rows[0][1].clone().unwrap();
+        assert_eq!(&text[range], "postgres");
+    }
+
+    #[test]
+    fn test_fixed_width_unicode() {
+        let text = "név   város\nÁrpád Győr\n";
+        let table = parse_fixed_width_with_positions(text);
+
+        assert_eq!(
+            cell_values(&table.headers),
+            vec![Some("név"), Some("város")]
+        );
+        let (value, range) = table.rows[0][1].clone().unwrap();
+        assert_eq!(value.as_ref(), "Győr");
+        assert_eq!(&text[range], "Győr");
+    }
+}
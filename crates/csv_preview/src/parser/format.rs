@@ -0,0 +1,46 @@
+/// Layout of the previewed file, determining how it's split into rows and columns
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TabularFormat {
+    /// Delimiter-separated values, e.g. CSV or TSV
+    #[default]
+    Delimited,
+    /// One JSON object per line, with top-level keys as columns
+    JsonLines,
+    /// Columns aligned with spaces, with the first line as header
+    FixedWidth,
+}
+
+impl TabularFormat {
+    /// Format of files with the given extension, if they can be previewed
+    pub fn for_extension(extension: &str) -> Option<Self> {
+        const FORMATS: [(&str, TabularFormat); 8] = [
+            ("csv", TabularFormat::Delimited),
+            ("tsv", TabularFormat::Delimited),
+            ("tab", TabularFormat::Delimited),
+            ("psv", TabularFormat::Delimited),
+            ("jsonl", TabularFormat::JsonLines),
+            ("ndjson", TabularFormat::JsonLines),
+            ("fwf", TabularFormat::FixedWidth),
+            ("prn", TabularFormat::FixedWidth),
+        ];
+
+        FORMATS
+            .iter()
+            .find(|(format_extension, _)| extension.eq_ignore_ascii_case(format_extension))
+            .map(|(_, format)| *format)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TabularFormat::Delimited => "CSV",
+            TabularFormat::JsonLines => "JSON Lines",
+            TabularFormat::FixedWidth => "Fixed Width",
+        }
+    }
+
+    /// Whether cells, rows and columns can be edited from the table.
+    /// Other formats are read-only, as edits can't be serialized back unambiguously.
+    pub fn is_editable(&self) -> bool {
+        matches!(self, TabularFormat::Delimited)
+    }
+}
@@ -0,0 +1,261 @@
+//! JSON Lines (NDJSON) parsing: every line holds a JSON object, whose top-level keys become columns.
+//!
+//! Keys and values are located by a small scanner over lines already validated by `serde_json`,
+//! so that cells keep their position in the source, like the cells of delimited files.
+
+use std::{collections::HashMap, ops::Range};
+
+use ui::SharedString;
+
+use crate::{
+    parser::{ParsedCell, ParsedTable},
+    types::LineNumber,
+};
+
+/// Split lines into columns named after the top-level keys, in order of their first appearance.
+/// Lines which aren't JSON objects are skipped.
+pub(super) fn parse_json_lines_with_positions(text: &str) -> ParsedTable {
+    let mut table = ParsedTable::default();
+    let mut column_indices = HashMap::<SharedString, usize>::default();
+    let mut line_start = 0;
+
+    for (line_ix, line_with_break) in text.split_inclusive('\n').enumerate() {
+        let offset = line_start;
+        line_start += line_with_break.len();
+
+        let line = line_with_break.trim_end_matches(['\n', '\r']);
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(members) = object_members(line) else {
+            log::debug!("Skipping line {} which is not a JSON object", line_ix + 1);
+            continue;
+        };
+
+        let mut row = vec![None; table.headers.len()];
+        for (key_range, value_range) in members {
+            let key: SharedString = serde_json::from_str::<String>(&line[key_range.clone()])
+                .unwrap_or_default()
+                .into();
+            let col = *column_indices.entry(key.clone()).or_insert_with(|| {
+                let header_range = offset + key_range.start..offset + key_range.end;
+                table.headers.push(Some((key, header_range)));
+                table.headers.len() - 1
+            });
+            if row.len() <= col {
+                row.resize(col + 1, None);
+            }
+            row[col] = Some((
+                display_value(&line[value_range.clone()]),
+                offset + value_range.start..offset + value_range.end,
+            ));
+        }
+
+        table.rows.push(row);
+        table.line_numbers.push(LineNumber::Line(line_ix + 1));
+    }
+
+    table
+}
+
+/// Strings are shown unquoted and nulls as empty cells, other values as written
+fn display_value(raw_value: &str) -> SharedString {
+    if raw_value.starts_with('"') {
+        serde_json::from_str::<String>(raw_value)
+            .unwrap_or_default()
+            .into()
+    } else if raw_value == "null" {
+        SharedString::default()
+    } else {
+        raw_value.to_string().into()
+    }
+}
+
+/// Ranges of keys and values of the top-level members, with strings including their quotes.
+/// Returns `None` if the line isn't a valid JSON object.
+fn object_members(line: &str) -> Option<Vec<(Range<usize>, Range<usize>)>> {
+    if !serde_json::from_str::<serde_json::Value>(line)
+        .ok()?
+        .is_object()
+    {
+        return None;
+    }
+
+    let mut scanner = Scanner {
+        bytes: line.as_bytes(),
+        position: 0,
+    };
+    let mut members = Vec::new();
+    scanner.skip_whitespace();
+    scanner.expect(b'{')?;
+    loop {
+        scanner.skip_whitespace();
+        match scanner.peek()? {
+            b'}' => break,
+            b',' => {
+                scanner.position += 1;
+                continue;
+            }
+            _ => {}
+        }
+        let key = scanner.scan_string()?;
+        scanner.skip_whitespace();
+        scanner.expect(b':')?;
+        scanner.skip_whitespace();
+        let value = scanner.scan_value()?;
+        members.push((key, value));
+    }
+    Some(members)
+}
+
+/// Locates tokens of valid JSON. Structural characters are ASCII, so scanning bytes is UTF-8 safe.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        (self.peek()? == byte).then(|| self.position += 1)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn scan_string(&mut self) -> Option<Range<usize>> {
+        let start = self.position;
+        self.expect(b'"')?;
+        loop {
+            match self.peek()? {
+                b'\\' => self.position += 2,
+                b'"' => {
+                    self.position += 1;
+                    return Some(start..self.position);
+                }
+                _ => self.position += 1,
+            }
+        }
+    }
+
+    fn scan_value(&mut self) -> Option<Range<usize>> {
+        let start = self.position;
+        match self.peek()? {
+            b'"' => return self.scan_string(),
+            b'{' | b'[' => {
+                let mut depth = 0;
+                loop {
+                    match self.peek()? {
+                        b'"' => {
+                            self.scan_string()?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                self.position += 1;
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.position += 1;
+                }
+            }
+            _ => {
+                while self.peek().is_some_and(|byte| {
+                    !matches!(byte, b',' | b'}' | b']') && !byte.is_ascii_whitespace()
+                }) {
+                    self.position += 1;
+                }
+            }
+        }
+        Some(start..self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell_values(cells: &[Option<ParsedCell>]) -> Vec<Option<&str>> {
+        cells
+            .iter()
+            .map(|cell| cell.as_ref().map(|(value, _)| value.as_ref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_json_lines_columns() {
+        let text = concat!(
+            r#"{"level": "info", "msg": "started", "ms": 12}"#,
+            "\n\n",
+            r#"{"msg":"say \"hi\"","tags":["a","b"],"ctx":{"id":1},"level":null}"#,
+            "\r\nnot json\n",
+            r#"{"ms": 7.5}"#,
+        );
+        let table = parse_json_lines_with_positions(text);
+
+        assert_eq!(
+            cell_values(&table.headers),
+            vec![
+                Some("level"),
+                Some("msg"),
+                Some("ms"),
+                Some("tags"),
+                Some("ctx")
+            ]
+        );
+        assert_eq!(table.rows.len(), 3);
+        assert_eq!(
+            cell_values(&table.rows[0]),
+            vec![Some("info"), Some("started"), Some("12")]
+        );
+        assert_eq!(
+            cell_values(&table.rows[1]),
+            vec![
+                Some(""),
+                Some("say \"hi\""),
+                None,
+                Some(r#"["a","b"]"#),
+                Some(r#"{"id":1}"#)
+            ]
+        );
+        assert_eq!(
+            cell_values(&table.rows[2]),
+            vec![None, None, Some("7.5"), None, None]
+        );
+
+        let line_numbers = table
+            .line_numbers
+            .iter()
+            .map(|line_number| match line_number {
+                LineNumber::Line(line) => *line,
+                LineNumber::LineRange(start, _) => *start,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(line_numbers, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn test_json_lines_positions() {
+        let text = "{\"a\": \"x,y\"}\n{\"b\": [1, 2], \"a\": true}";
+        let table = parse_json_lines_with_positions(text);
+
+        let (_, header_range) = table.headers[0].clone().unwrap();
+        assert_eq!(&text[header_range], "\"a\"");
+        let (_, value_range) = table.rows[0][0].clone().unwrap();
+        assert_eq!(&text[value_range], "\"x,y\"");
+        let (_, value_range) = table.rows[1][1].clone().unwrap();
+        assert_eq!(&text[value_range], "[1, 2]");
+        let (_, value_range) = table.rows[1][0].clone().unwrap();
+        assert_eq!(&text[value_range], "true");
+    }
+}
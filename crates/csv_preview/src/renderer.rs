@@ -1,5 +1,7 @@
+mod column_statistics;
 mod preview_view;
 mod render_table;
 mod row_identifiers;
 mod table_cell;
 mod table_header;
+mod toolbar;
@@ -0,0 +1,143 @@
+//! Popover with statistics of a single column, opened from the column header.
+
+use gpui::{DismissEvent, EventEmitter, FocusHandle, Focusable, Task};
+use ui::prelude::*;
+
+use crate::{
+    CsvPreviewView,
+    table_data_engine::{
+        column_statistics::{ColumnStatistics, compute_column_statistics},
+        column_types::ColumnType,
+    },
+    types::AnyColumn,
+};
+
+impl CsvPreviewView {
+    /// Values of the column in the displayed rows, `None` standing for missing cells
+    pub(crate) fn visible_column_values(&self, col_idx: AnyColumn) -> Vec<Option<SharedString>> {
+        self.engine
+            .d2d_mapping()
+            .mapping
+            .values()
+            .filter_map(|data_row| self.engine.contents.get_row(*data_row))
+            .map(|row| {
+                row.get(col_idx)
+                    .and_then(|cell| cell.display_value())
+                    .cloned()
+            })
+            .collect()
+    }
+}
+
+pub(crate) struct ColumnStatisticsPopover {
+    focus_handle: FocusHandle,
+    column_name: SharedString,
+    /// `None` while the statistics are being computed
+    statistics: Option<ColumnStatistics>,
+    _computation: Task<()>,
+}
+
+impl ColumnStatisticsPopover {
+    /// Start computing statistics of the values in the background
+    pub(crate) fn new(
+        column_name: SharedString,
+        column_type: ColumnType,
+        values: Vec<Option<SharedString>>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let computation = cx.spawn(async move |this, cx| {
+            let statistics = cx
+                .background_spawn(async move { compute_column_statistics(&values, column_type) })
+                .await;
+            this.update(cx, |this, cx| {
+                this.statistics = Some(statistics);
+                cx.notify();
+            })
+            .ok();
+        });
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            column_name,
+            statistics: None,
+            _computation: computation,
+        }
+    }
+
+    fn render_statistics(statistics: &ColumnStatistics) -> Div {
+        let row = |label: &'static str, value: String| {
+            h_flex()
+                .w_full()
+                .justify_between()
+                .gap_4()
+                .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+                .child(Label::new(value).size(LabelSize::Small))
+        };
+        let value_or_dash = |value: &Option<SharedString>| {
+            value.as_ref().map_or("—".to_string(), |v| v.to_string())
+        };
+
+        v_flex()
+            .gap_0p5()
+            .child(row("Type", statistics.column_type.label().to_string()))
+            .child(row("Count", statistics.count.to_string()))
+            .child(row("Distinct", statistics.distinct_count.to_string()))
+            .child(row("Empty", statistics.null_count.to_string()))
+            .child(row("Min", value_or_dash(&statistics.min)))
+            .child(row("Max", value_or_dash(&statistics.max)))
+            .when_some(statistics.mean, |this, mean| {
+                this.child(row("Mean", format!("{mean:.4}")))
+            })
+            .when(!statistics.top_values.is_empty(), |this| {
+                this.child(
+                    div().pt_1().child(
+                        Label::new("Top Values")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+                )
+                .children(statistics.top_values.iter().map(|(value, count)| {
+                    h_flex()
+                        .w_full()
+                        .justify_between()
+                        .gap_4()
+                        .child(Label::new(value.clone()).size(LabelSize::Small).truncate())
+                        .child(
+                            Label::new(count.to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                }))
+            })
+    }
+}
+
+impl EventEmitter<DismissEvent> for ColumnStatisticsPopover {}
+
+impl Focusable for ColumnStatisticsPopover {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ColumnStatisticsPopover {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|_, _: &menu::Cancel, _, cx| cx.emit(DismissEvent)))
+            .on_mouse_down_out(cx.listener(|_, _, _, cx| cx.emit(DismissEvent)))
+            .elevation_2(cx)
+            .w_64()
+            .p_2()
+            .gap_1()
+            .child(Label::new(self.column_name.clone()).truncate())
+            .child(match &self.statistics {
+                Some(statistics) => Self::render_statistics(statistics),
+                None => v_flex().child(
+                    Label::new("Computing…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            })
+    }
+}
@@ -1,11 +1,15 @@
 use std::time::Instant;
 
+use gpui::{anchored, deferred};
 use ui::{div, prelude::*};
 
-use crate::{CsvPreviewView, settings::FontType};
+use crate::{
+    CsvPreviewView, DeleteColumn, DeleteRow, EditCell, InsertColumnLeft, InsertColumnRight,
+    InsertRowAbove, InsertRowBelow, editing::InsertPosition, settings::FontType,
+};
 
 impl Render for CsvPreviewView {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
 
         self.performance_metrics.rendered_indices.clear();
@@ -15,6 +19,33 @@ impl Render for CsvPreviewView {
             .p_4()
             .bg(theme.colors().editor_background)
             .track_focus(&self.focus_handle)
+            .key_context("CsvPreview")
+            .on_action(
+                cx.listener(|this, _: &EditCell, window, cx| this.edit_selected_cell(window, cx)),
+            )
+            .on_action(cx.listener(|this, _: &menu::Confirm, window, cx| {
+                // Toolbar inputs confirm on enter too
+                if this.focus_handle.is_focused(window) {
+                    this.edit_selected_cell(window, cx)
+                }
+            }))
+            .on_action(cx.listener(|this, _: &InsertRowAbove, _, cx| {
+                this.insert_row(InsertPosition::Before, cx)
+            }))
+            .on_action(cx.listener(|this, _: &InsertRowBelow, _, cx| {
+                this.insert_row(InsertPosition::After, cx)
+            }))
+            .on_action(cx.listener(|this, _: &DeleteRow, _, cx| this.delete_row(cx)))
+            .on_action(cx.listener(|this, _: &InsertColumnLeft, _, cx| {
+                this.insert_column(InsertPosition::Before, cx)
+            }))
+            .on_action(cx.listener(|this, _: &InsertColumnRight, _, cx| {
+                this.insert_column(InsertPosition::After, cx)
+            }))
+            .on_action(cx.listener(|this, _: &DeleteColumn, _, cx| this.delete_column(cx)))
+            .on_action(cx.listener(Self::undo))
+            .on_action(cx.listener(Self::redo))
+            .child(self.render_toolbar(window, cx))
             .child({
                 if self.engine.contents.number_of_cols == 0 {
                     div()
@@ -46,5 +77,14 @@ impl Render for CsvPreviewView {
             .w_full()
             .h_full()
             .child(table_with_settings)
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(gpui::Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
     }
 }
@@ -5,6 +5,7 @@ use ui::{ColumnWidthConfig, ResizableColumnsState, Table, UncheckedTableRow, div
 
 use crate::{
     CsvPreviewView,
+    editing::DataCellId,
     settings::RowRenderMechanism,
     types::{AnyColumn, DisplayCellId, DisplayRow},
 };
@@ -17,7 +18,21 @@ impl CsvPreviewView {
         current_widths: &Entity<ResizableColumnsState>,
         cx: &mut Context<Self>,
     ) -> AnyElement {
-        self.create_table_inner(self.engine.contents.rows.len(), current_widths, cx)
+        self.create_table_inner(
+            self.engine.d2d_mapping().visible_row_count(),
+            current_widths,
+            cx,
+        )
+    }
+
+    /// Header text of the column, or its position for columns without header
+    pub(crate) fn column_name(&self, col: AnyColumn) -> SharedString {
+        self.engine
+            .contents
+            .headers
+            .get(col)
+            .and_then(|h| h.display_value().cloned())
+            .unwrap_or_else(|| format!("Col {}", *col + 1).into())
     }
 
     fn create_table_inner(
@@ -34,13 +49,7 @@ impl CsvPreviewView {
 
         // Add the actual CSV headers with sort buttons
         for i in 0..(cols - 1) {
-            let header_text = self
-                .engine
-                .contents
-                .headers
-                .get(AnyColumn(i))
-                .and_then(|h| h.display_value().cloned())
-                .unwrap_or_else(|| format!("Col {}", i + 1).into());
+            let header_text = self.column_name(AnyColumn(i));
 
             headers.push(self.create_header_element_with_sort_button(
                 header_text,
@@ -127,16 +136,33 @@ impl CsvPreviewView {
             let cell_content = table_cell.display_value().cloned().unwrap_or_default();
 
             let display_cell_id = DisplayCellId::new(display_row, col);
-
-            let cell = div().size_full().whitespace_nowrap().text_ellipsis().child(
-                CsvPreviewView::create_selectable_cell(
-                    display_cell_id,
-                    cell_content,
-                    this.settings.vertical_alignment,
-                    this.settings.font_type,
-                    cx,
-                ),
-            );
+            let data_cell_id = DataCellId { row: data_row, col };
+            let is_search_match = this.engine.is_search_match(&cell_content);
+            let is_editing = this
+                .editing_cell
+                .as_ref()
+                .is_some_and(|cell_editor| cell_editor.cell == data_cell_id);
+
+            let cell = if is_editing && let Some(editing_cell) = this.create_editing_cell(cx) {
+                editing_cell
+            } else {
+                div()
+                    .size_full()
+                    .whitespace_nowrap()
+                    .text_ellipsis()
+                    .child(CsvPreviewView::create_selectable_cell(
+                        display_cell_id,
+                        data_cell_id,
+                        cell_content,
+                        is_search_match,
+                        this.selected_cell == Some(data_cell_id),
+                        this.engine.column_type(col).is_numeric(),
+                        this.settings.vertical_alignment,
+                        this.settings.font_type,
+                        cx,
+                    ))
+                    .into_any_element()
+            };
 
             elements.push(
                 div()
@@ -1,10 +1,11 @@
 //! Table Cell Rendering
 
-use gpui::{AnyElement, ElementId};
+use gpui::{AnyElement, ClickEvent, ElementId, MouseButton, MouseDownEvent};
 use ui::{SharedString, Tooltip, div, prelude::*};
 
 use crate::{
     CsvPreviewView,
+    editing::DataCellId,
     settings::{FontType, VerticalAlignment},
     types::DisplayCellId,
 };
@@ -13,7 +14,11 @@ impl CsvPreviewView {
     /// Create selectable table cell with mouse event handlers.
     pub fn create_selectable_cell(
         display_cell_id: DisplayCellId,
+        data_cell_id: DataCellId,
         cell_content: SharedString,
+        is_search_match: bool,
+        is_selected: bool,
+        is_numeric: bool,
         vertical_alignment: VerticalAlignment,
         font_type: FontType,
         cx: &Context<CsvPreviewView>,
@@ -21,23 +26,66 @@ impl CsvPreviewView {
         create_table_cell(
             display_cell_id,
             cell_content,
+            is_search_match,
+            is_numeric,
             vertical_alignment,
             font_type,
             cx,
         )
-        // Mouse events handlers will be here
+        .when(is_selected, |cell| {
+            cell.border_1()
+                .border_color(cx.theme().colors().border_focused)
+        })
+        .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+            if event.click_count() > 1 {
+                this.start_editing_cell(data_cell_id, window, cx);
+            } else {
+                this.select_cell(data_cell_id, cx);
+                window.focus(&this.focus_handle, cx);
+            }
+        }))
+        .on_mouse_down(
+            MouseButton::Right,
+            cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                this.deploy_cell_context_menu(data_cell_id, event.position, window, cx);
+            }),
+        )
         .into_any_element()
     }
+
+    /// Inline editor replacing the cell content while it's being edited
+    pub fn create_editing_cell(&self, cx: &Context<CsvPreviewView>) -> Option<AnyElement> {
+        let cell_editor = self.editing_cell.as_ref()?;
+        Some(
+            div()
+                .size_full()
+                .px_1()
+                .border_1()
+                .border_color(cx.theme().colors().border_focused)
+                .bg(cx.theme().colors().editor_background)
+                .on_action(cx.listener(|this, _: &menu::Confirm, window, cx| {
+                    this.confirm_cell_edit(window, cx)
+                }))
+                .on_action(cx.listener(|this, _: &menu::Cancel, window, cx| {
+                    this.cancel_cell_edit(window, cx)
+                }))
+                .child(cell_editor.editor.clone())
+                .into_any_element(),
+        )
+    }
 }
 
 /// Create styled table cell div element.
 fn create_table_cell(
     display_cell_id: DisplayCellId,
     cell_content: SharedString,
+    is_search_match: bool,
+    is_numeric: bool,
     vertical_alignment: VerticalAlignment,
     font_type: FontType,
     cx: &Context<'_, CsvPreviewView>,
 ) -> gpui::Stateful<Div> {
+    let colors = cx.theme().colors();
     div()
         .id(ElementId::NamedInteger(
             format!(
@@ -51,9 +99,13 @@ fn create_table_cell(
         .flex()
         .h_full()
         .px_1()
-        .bg(cx.theme().colors().editor_background)
+        .bg(if is_search_match {
+            colors.search_match_background
+        } else {
+            colors.editor_background
+        })
         .border_b_1()
-        .border_color(cx.theme().colors().border_variant)
+        .border_color(colors.border_variant)
         .map(|div| match vertical_alignment {
             VerticalAlignment::Top => div.items_start(),
             VerticalAlignment::Center => div.items_center(),
@@ -62,6 +114,8 @@ fn create_table_cell(
             VerticalAlignment::Top => div.content_start(),
             VerticalAlignment::Center => div.content_center(),
         })
+        // Numbers are right-aligned, so that their digits line up
+        .when(is_numeric, |div| div.justify_end())
         .map(|div| match font_type {
             FontType::Ui => div.font_ui(cx),
             FontType::Monospace => div.font_buffer(cx),
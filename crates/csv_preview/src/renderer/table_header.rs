@@ -1,8 +1,9 @@
-use gpui::ElementId;
-use ui::{Tooltip, prelude::*};
+use gpui::{Corner, ElementId};
+use ui::{PopoverMenu, Tooltip, prelude::*};
 
 use crate::{
     CsvPreviewView,
+    renderer::column_statistics::ColumnStatisticsPopover,
     settings::FontType,
     table_data_engine::sorting_by_column::{AppliedSorting, SortDirection},
     types::AnyColumn,
@@ -26,10 +27,80 @@ impl CsvPreviewView {
                 FontType::Monospace => div.font_buffer(cx),
             })
             .child(div().child(header_text))
-            .child(h_flex().gap_1().child(self.create_sort_button(cx, col_idx)))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(self.create_statistics_button(header_text.clone(), cx, col_idx))
+                    .child(self.create_filter_button(cx, col_idx))
+                    .child(self.create_sort_button(cx, col_idx)),
+            )
             .into_any_element()
     }
 
+    /// Button opening the statistics of the column, computed when opened
+    fn create_statistics_button(
+        &self,
+        column_name: SharedString,
+        cx: &mut Context<'_, CsvPreviewView>,
+        col_idx: AnyColumn,
+    ) -> PopoverMenu<ColumnStatisticsPopover> {
+        let view = cx.weak_entity();
+        PopoverMenu::new(ElementId::NamedInteger(
+            "statistics-popover".into(),
+            col_idx.get() as u64,
+        ))
+        .trigger_with_tooltip(
+            IconButton::new(
+                ElementId::NamedInteger("statistics-button".into(), col_idx.get() as u64),
+                IconName::Info,
+            )
+            .icon_size(IconSize::Small),
+            Tooltip::text(format!(
+                "{} column statistics",
+                self.engine.column_type(col_idx).label()
+            )),
+        )
+        .anchor(Corner::TopRight)
+        .menu(move |_window, cx| {
+            let (column_type, values) = view
+                .read_with(cx, |view, _| {
+                    (
+                        view.engine.column_type(col_idx),
+                        view.visible_column_values(col_idx),
+                    )
+                })
+                .ok()?;
+            let column_name = column_name.clone();
+            Some(cx.new(|cx| ColumnStatisticsPopover::new(column_name, column_type, values, cx)))
+        })
+    }
+
+    fn create_filter_button(
+        &self,
+        cx: &mut Context<'_, CsvPreviewView>,
+        col_idx: AnyColumn,
+    ) -> IconButton {
+        let is_filtered = self.engine.has_filters_for_column(col_idx);
+        IconButton::new(
+            ElementId::NamedInteger("filter-button".into(), col_idx.get() as u64),
+            IconName::Filter,
+        )
+        .icon_size(IconSize::Small)
+        .style(if is_filtered {
+            ButtonStyle::Filled
+        } else {
+            ButtonStyle::Subtle
+        })
+        .tooltip(Tooltip::text(if is_filtered {
+            "Column is filtered. Click to add another filter"
+        } else {
+            "Filter this column"
+        }))
+        .on_click(cx.listener(move |this, _event, window, cx| {
+            this.start_filtering_column(col_idx, window, cx);
+        }))
+    }
+
     fn create_sort_button(
         &self,
         cx: &mut Context<'_, CsvPreviewView>,
@@ -0,0 +1,346 @@
+//! Toolbar rendered above the table, holding per-file preview options.
+
+use editor::Editor;
+use gpui::{AnyElement, Entity};
+use ui::{ContextMenu, DropdownMenu, DropdownStyle, Tooltip, prelude::*};
+
+use crate::{
+    CsvPreviewView,
+    parser::{CsvDialect, TabularFormat},
+    settings::CsvDialectSetting,
+    table_data_engine::filtering_by_column::FilterKind,
+    types::AnyColumn,
+};
+
+impl CsvPreviewView {
+    pub(crate) fn render_toolbar(&self, window: &mut Window, cx: &mut Context<Self>) -> AnyElement {
+        v_flex()
+            .w_full()
+            .pb_2()
+            .gap_1p5()
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(self.render_search_input(cx))
+                    .map(|this| {
+                        if self.active_format == TabularFormat::Delimited {
+                            this.child(self.render_dialect_picker(window, cx))
+                        } else {
+                            this.child(
+                                Label::new(self.active_format.name())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        }
+                    }),
+            )
+            .when(
+                self.active_format == TabularFormat::Delimited
+                    && matches!(self.settings.dialect, CsvDialectSetting::Override(_)),
+                |toolbar| toolbar.child(self.render_dialect_inputs(cx)),
+            )
+            .child(self.render_filter_input(window, cx))
+            .when(!self.engine.applied_filters.is_empty(), |toolbar| {
+                toolbar.child(self.render_applied_filters(cx))
+            })
+            .into_any_element()
+    }
+
+    fn render_search_input(&self, cx: &mut Context<Self>) -> Div {
+        let colors = cx.theme().colors();
+        let visible_rows = self.engine.d2d_mapping().visible_row_count();
+        let total_rows = self.engine.contents.rows.len();
+
+        h_flex()
+            .h_8()
+            .flex_1()
+            .min_w_0()
+            .px_1p5()
+            .gap_1()
+            .border_1()
+            .border_color(colors.border_variant)
+            .rounded_md()
+            .bg(colors.toolbar_background)
+            .child(
+                Icon::new(IconName::MagnifyingGlass)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(self.search_editor.clone())
+            .when(visible_rows != total_rows, |this| {
+                this.child(
+                    Label::new(format!("{visible_rows} of {total_rows} rows"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+    }
+
+    /// Inputs to compose a column filter: column, predicate kind and value
+    fn render_filter_input(&self, window: &mut Window, cx: &mut Context<Self>) -> Div {
+        let column_picker = self.render_filter_column_picker(window, cx);
+        let kind_picker = self.render_filter_kind_picker(window, cx);
+        let colors = cx.theme().colors();
+        let kind = self.filter_input.kind;
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .child(column_picker)
+            .child(kind_picker)
+            .when(kind.takes_value(), |this| {
+                this.child(
+                    h_flex()
+                        .h_7()
+                        .flex_1()
+                        .min_w_0()
+                        .px_1p5()
+                        .border_1()
+                        .border_color(if self.filter_input.error.is_some() {
+                            colors.border_focused
+                        } else {
+                            colors.border_variant
+                        })
+                        .rounded_md()
+                        .bg(colors.toolbar_background)
+                        .on_action(cx.listener(|this, _: &menu::Confirm, window, cx| {
+                            this.add_filter_from_input(window, cx);
+                        }))
+                        .child(self.filter_input.value_editor.clone()),
+                )
+            })
+            .child(
+                Button::new("csv-add-filter", "Add Filter")
+                    .style(ButtonStyle::Outlined)
+                    .size(ButtonSize::Compact)
+                    .disabled(self.engine.contents.number_of_cols == 0)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.add_filter_from_input(window, cx);
+                    })),
+            )
+            .when_some(self.filter_input.error.clone(), |this, error| {
+                this.child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+            })
+    }
+
+    fn render_filter_column_picker(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> DropdownMenu {
+        let selected_col = self.filter_input.col_idx;
+        let column_names = (0..self.engine.contents.number_of_cols)
+            .map(|col| self.column_name(AnyColumn(col)))
+            .collect::<Vec<_>>();
+        let label = column_names
+            .get(*selected_col)
+            .cloned()
+            .unwrap_or_else(|| "Column".into());
+
+        let view = cx.weak_entity();
+        let menu = ContextMenu::build(window, cx, move |mut menu, _, _| {
+            for (col, name) in column_names.into_iter().enumerate() {
+                let view = view.clone();
+                menu = menu.toggleable_entry(
+                    name,
+                    *selected_col == col,
+                    IconPosition::Start,
+                    None,
+                    move |window, cx| {
+                        view.update(cx, |view, cx| {
+                            view.start_filtering_column(AnyColumn(col), window, cx)
+                        })
+                        .ok();
+                    },
+                );
+            }
+            menu
+        });
+
+        DropdownMenu::new("csv-filter-column-picker", label, menu)
+            .style(DropdownStyle::Outlined)
+            .trigger_size(ButtonSize::Compact)
+    }
+
+    fn render_filter_kind_picker(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> DropdownMenu {
+        let selected_kind = self.filter_input.kind;
+
+        let view = cx.weak_entity();
+        let menu = ContextMenu::build(window, cx, move |mut menu, _, _| {
+            for kind in FilterKind::ALL {
+                let view = view.clone();
+                menu = menu.toggleable_entry(
+                    kind.label(),
+                    kind == selected_kind,
+                    IconPosition::Start,
+                    None,
+                    move |window, cx| {
+                        view.update(cx, |view, cx| view.set_filter_input_kind(kind, window, cx))
+                            .ok();
+                    },
+                );
+            }
+            menu
+        });
+
+        DropdownMenu::new("csv-filter-kind-picker", selected_kind.label(), menu)
+            .style(DropdownStyle::Outlined)
+            .trigger_size(ButtonSize::Compact)
+    }
+
+    /// Removable chips describing each applied column filter
+    fn render_applied_filters(&self, cx: &mut Context<Self>) -> Div {
+        let colors = cx.theme().colors();
+
+        h_flex()
+            .w_full()
+            .flex_wrap()
+            .gap_1()
+            .children(
+                self.engine
+                    .applied_filters
+                    .iter()
+                    .enumerate()
+                    .map(|(filter_ix, filter)| {
+                        h_flex()
+                            .pl_1p5()
+                            .gap_0p5()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(colors.border_variant)
+                            .bg(colors.element_background)
+                            .child(
+                                Label::new(format!(
+                                    "{} {}",
+                                    self.column_name(filter.col_idx),
+                                    filter.predicate.description()
+                                ))
+                                .size(LabelSize::Small),
+                            )
+                            .child(
+                                IconButton::new(("csv-remove-filter", filter_ix), IconName::Close)
+                                    .icon_size(IconSize::XSmall)
+                                    .tooltip(Tooltip::text("Remove Filter"))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.remove_filter(filter_ix, cx);
+                                    })),
+                            )
+                    }),
+            )
+            .child(
+                Button::new("csv-clear-filters", "Clear All")
+                    .style(ButtonStyle::Subtle)
+                    .size(ButtonSize::Compact)
+                    .on_click(cx.listener(|this, _, _, cx| this.clear_filters(cx))),
+            )
+    }
+
+    /// Dropdown to pick the delimiter used for parsing, or let it be detected automatically
+    fn render_dialect_picker(&self, window: &mut Window, cx: &mut Context<Self>) -> DropdownMenu {
+        let current_setting = self.settings.dialect;
+        let label = match current_setting {
+            CsvDialectSetting::Auto => {
+                format!("Delimiter: Auto ({})", self.active_dialect.delimiter_name())
+            }
+            CsvDialectSetting::Override(dialect) => {
+                format!("Delimiter: {}", dialect.delimiter_name())
+            }
+        };
+
+        let is_custom = match current_setting {
+            CsvDialectSetting::Override(dialect) => !CsvDialect::PRESETS.contains(&dialect),
+            CsvDialectSetting::Auto => false,
+        };
+        let active_dialect = self.active_dialect;
+
+        let view = cx.weak_entity();
+        let menu = ContextMenu::build(window, cx, move |mut menu, _, _| {
+            let options = std::iter::once(CsvDialectSetting::Auto).chain(
+                CsvDialect::PRESETS
+                    .into_iter()
+                    .map(CsvDialectSetting::Override),
+            );
+            for setting in options {
+                let entry_label = match setting {
+                    CsvDialectSetting::Auto => "Detect Automatically".to_string(),
+                    CsvDialectSetting::Override(dialect) => dialect.delimiter_name(),
+                };
+                let view = view.clone();
+                menu = menu.toggleable_entry(
+                    entry_label,
+                    setting == current_setting,
+                    IconPosition::Start,
+                    None,
+                    move |window, cx| {
+                        view.update(cx, |view, cx| view.select_dialect(setting, window, cx))
+                            .ok();
+                    },
+                );
+            }
+            // Starts from the dialect in use, for its characters to be edited in the inputs
+            menu.toggleable_entry(
+                "Custom…",
+                is_custom,
+                IconPosition::Start,
+                None,
+                move |window, cx| {
+                    view.update(cx, |view, cx| {
+                        if !is_custom {
+                            view.select_dialect(
+                                CsvDialectSetting::Override(active_dialect),
+                                window,
+                                cx,
+                            );
+                        }
+                    })
+                    .ok();
+                },
+            )
+        });
+
+        DropdownMenu::new("csv-dialect-picker", label, menu)
+            .style(DropdownStyle::Outlined)
+            .trigger_size(ButtonSize::Compact)
+            .trigger_tooltip(Tooltip::text("Delimiter used to split rows into columns"))
+    }
+
+    /// Inputs for the delimiter, quote and escape characters of the dialect override
+    fn render_dialect_inputs(&self, cx: &mut Context<Self>) -> Div {
+        let colors = cx.theme().colors();
+        let input = |label: &'static str, editor: &Entity<Editor>| {
+            h_flex()
+                .gap_1()
+                .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+                .child(
+                    h_flex()
+                        .h_7()
+                        .w_10()
+                        .px_1p5()
+                        .border_1()
+                        .border_color(if self.dialect_input.error.is_some() {
+                            colors.border_focused
+                        } else {
+                            colors.border_variant
+                        })
+                        .rounded_md()
+                        .bg(colors.toolbar_background)
+                        .child(editor.clone()),
+                )
+        };
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .child(input("Delimiter", &self.dialect_input.delimiter_editor))
+            .child(input("Quote", &self.dialect_input.quote_editor))
+            .child(input("Escape", &self.dialect_input.escape_editor))
+            .when_some(self.dialect_input.error.clone(), |this, error| {
+                this.child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+            })
+    }
+}
@@ -1,3 +1,13 @@
+use std::path::Path;
+
+use db::kvp::KeyValueStore;
+use gpui::{App, Task};
+
+use crate::parser::CsvDialect;
+
+/// Namespace of the per-file dialect overrides, keyed by absolute file path
+const DIALECT_OVERRIDES_NAMESPACE: &str = "csv_preview_dialect_overrides";
+
 #[derive(Default, Clone, Copy)]
 pub enum RowRenderMechanism {
     /// More correct for multiline content, but slower.
@@ -35,6 +45,75 @@ pub enum RowIdentifiers {
     RowNum,
 }
 
+/// How the dialect (delimiter, quote and escape characters) of the previewed file is determined
+#[derive(Default, Clone, Copy, PartialEq)]
+pub enum CsvDialectSetting {
+    /// Use the dialect implied by the file extension, detecting it from the first rows
+    /// for extensions which don't imply one
+    #[default]
+    Auto,
+    /// Per-file dialect picked by the user, persisted across restarts
+    Override(CsvDialect),
+}
+
+impl CsvDialectSetting {
+    pub fn resolve(&self, text: &str, file_extension: Option<&str>) -> CsvDialect {
+        match self {
+            CsvDialectSetting::Auto => file_extension
+                .and_then(CsvDialect::for_extension)
+                .or_else(|| CsvDialect::sniff(text))
+                .unwrap_or_default(),
+            CsvDialectSetting::Override(dialect) => *dialect,
+        }
+    }
+}
+
+/// Dialect override previously picked for the file, if any
+pub(crate) fn read_dialect_override(abs_path: &Path, cx: &App) -> CsvDialectSetting {
+    KeyValueStore::global(cx)
+        .scoped(DIALECT_OVERRIDES_NAMESPACE)
+        .read(&abs_path.to_string_lossy())
+        .ok()
+        .flatten()
+        .and_then(|encoded| CsvDialect::decode(&encoded))
+        .map_or(CsvDialectSetting::Auto, CsvDialectSetting::Override)
+}
+
+/// Remember the dialect override of the file, or forget it when switching back to `Auto`
+pub(crate) fn write_dialect_override(
+    abs_path: &Path,
+    setting: CsvDialectSetting,
+    cx: &App,
+) -> Task<anyhow::Result<()>> {
+    let kvp = KeyValueStore::global(cx);
+    let key = abs_path.to_string_lossy().into_owned();
+    cx.background_spawn(async move {
+        let store = kvp.scoped(DIALECT_OVERRIDES_NAMESPACE);
+        match setting {
+            CsvDialectSetting::Override(dialect) => store.write(key, dialect.encode()).await,
+            CsvDialectSetting::Auto => store.delete(key).await,
+        }
+    })
+}
+
+/// Text shown in the dialect inputs for a character, spelling out the invisible tab
+pub(crate) fn dialect_char_to_input(ch: char) -> String {
+    match ch {
+        '\t' => "\\t".to_string(),
+        ch => ch.to_string(),
+    }
+}
+
+/// Parse a dialect input holding a single character, or `\t` for a tab
+pub(crate) fn dialect_char_from_input(input: &str) -> Option<char> {
+    if input == "\\t" {
+        return Some('\t');
+    }
+    let mut chars = input.chars();
+    let ch = chars.next()?;
+    (chars.next().is_none() && ch != '\n' && ch != '\r').then_some(ch)
+}
+
 #[derive(Clone, Default)]
 pub(crate) struct CsvPreviewSettings {
     pub(crate) rendering_with: RowRenderMechanism,
@@ -43,4 +122,45 @@ pub(crate) struct CsvPreviewSettings {
     pub(crate) numbering_type: RowIdentifiers,
     pub(crate) show_debug_info: bool,
     pub(crate) multiline_cells_enabled: bool,
+    pub(crate) dialect: CsvDialectSetting,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_extension() {
+        let text = "name,first\tcity\nDoe,John\tParis\nRoe,Jane\tRome\n";
+        assert_eq!(CsvDialect::sniff(text), Some(CsvDialect::COMMA));
+        let auto = CsvDialectSetting::Auto;
+        assert_eq!(auto.resolve(text, Some("tsv")), CsvDialect::TAB);
+        assert_eq!(auto.resolve("a,b\n1,2\n", Some("tsv")), CsvDialect::TAB);
+        assert_eq!(
+            auto.resolve("a;b\n1;2\n", Some("csv")),
+            CsvDialect::SEMICOLON
+        );
+        assert_eq!(auto.resolve("a;b\n1;2\n", None), CsvDialect::SEMICOLON);
+        assert_eq!(
+            auto.resolve("single\ncolumn\n", Some("csv")),
+            CsvDialect::COMMA
+        );
+
+        let pipe = CsvDialectSetting::Override(CsvDialect::PIPE);
+        assert_eq!(pipe.resolve(text, Some("tsv")), CsvDialect::PIPE);
+    }
+
+    #[test]
+    fn test_dialect_char_input() {
+        assert_eq!(dialect_char_from_input("\\t"), Some('\t'));
+        assert_eq!(dialect_char_from_input("#"), Some('#'));
+        assert_eq!(dialect_char_from_input("ß"), Some('ß'));
+        assert_eq!(dialect_char_from_input(""), None);
+        assert_eq!(dialect_char_from_input(";;"), None);
+        assert_eq!(dialect_char_from_input("\n"), None);
+        assert_eq!(
+            dialect_char_from_input(&dialect_char_to_input('\t')),
+            Some('\t')
+        );
+    }
 }
@@ -5,20 +5,36 @@
 //!
 //! It's designed to contain core logic of operations without relying on `CsvPreviewView`, context or window handles.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use ui::table_row::TableRow;
 
 use crate::{
-    table_data_engine::sorting_by_column::{AppliedSorting, sort_data_rows},
-    types::{DataRow, DisplayRow, TableCell, TableLikeContent},
+    table_data_engine::{
+        column_types::ColumnType,
+        filtering_by_column::{ColumnFilter, TextMatcher, filter_data_rows},
+        sorting_by_column::{AppliedSorting, sort_data_rows},
+    },
+    types::{AnyColumn, DataRow, DisplayRow, TableCell, TableLikeContent},
 };
 
+pub mod column_statistics;
+pub mod column_types;
+pub mod filtering_by_column;
 pub mod sorting_by_column;
 
 #[derive(Default)]
 pub(crate) struct TableDataEngine {
     pub applied_sorting: Option<AppliedSorting>,
+    /// Column filters, all of which a row must satisfy to be displayed
+    pub applied_filters: Vec<ColumnFilter>,
+    /// Global search. Rows without a matching cell are hidden, matching cells are highlighted
+    pub search: Option<TextMatcher>,
+    /// Types inferred for each column of `contents`, used for sorting and alignment
+    pub column_types: Vec<ColumnType>,
     d2d_mapping: DisplayToDataMapping,
     pub contents: TableLikeContent,
 }
@@ -28,27 +44,82 @@ impl TableDataEngine {
         &self.d2d_mapping
     }
 
+    /// Type inferred for the column, strings for unknown columns
+    pub(crate) fn column_type(&self, col_idx: AnyColumn) -> ColumnType {
+        self.column_types.get(*col_idx).copied().unwrap_or_default()
+    }
+
     pub(crate) fn apply_sort(&mut self) {
-        self.d2d_mapping
-            .apply_sorting(self.applied_sorting, &self.contents.rows);
+        let sorting = self.typed_sorting();
+        self.d2d_mapping.apply_sorting(sorting, &self.contents.rows);
+        self.d2d_mapping.merge_mappings();
+    }
+
+    pub(crate) fn apply_filter(&mut self) {
+        self.d2d_mapping.apply_filtering(
+            &self.applied_filters,
+            self.search.as_ref(),
+            &self.contents.rows,
+        );
+        self.d2d_mapping.merge_mappings();
+    }
+
+    /// Computes rows hidden by the current filters and search without borrowing the engine,
+    /// so that the work can be moved to the background executor
+    pub(crate) fn filter_in_background(&self) -> impl Future<Output = HashSet<DataRow>> + use<> {
+        let rows = self.contents.rows.clone();
+        let filters = self.applied_filters.clone();
+        let search = self.search.clone();
+        async move { filter_data_rows(&rows, &filters, search.as_ref()) }
+    }
+
+    /// Applies rows computed by [`Self::filter_in_background`], keeping the current sort order
+    pub(crate) fn set_filtered_out_rows(&mut self, filtered_out_rows: HashSet<DataRow>) {
+        self.d2d_mapping.filtered_out_rows = filtered_out_rows;
         self.d2d_mapping.merge_mappings();
     }
 
     /// Applies sorting and filtering to the data and produces display to data mapping
     pub(crate) fn calculate_d2d_mapping(&mut self) {
-        self.d2d_mapping
-            .apply_sorting(self.applied_sorting, &self.contents.rows);
+        let sorting = self.typed_sorting();
+        self.d2d_mapping.apply_sorting(sorting, &self.contents.rows);
+        self.d2d_mapping.apply_filtering(
+            &self.applied_filters,
+            self.search.as_ref(),
+            &self.contents.rows,
+        );
         self.d2d_mapping.merge_mappings();
     }
+
+    fn typed_sorting(&self) -> Option<(AppliedSorting, ColumnType)> {
+        self.applied_sorting
+            .map(|sorting| (sorting, self.column_type(sorting.col_idx)))
+    }
+
+    /// Whether the cell value matches the global search and should be highlighted
+    pub(crate) fn is_search_match(&self, value: &str) -> bool {
+        self.search
+            .as_ref()
+            .is_some_and(|search| search.is_match(value))
+    }
+
+    pub(crate) fn has_filters_for_column(&self, col_idx: AnyColumn) -> bool {
+        self.applied_filters
+            .iter()
+            .any(|filter| filter.col_idx == col_idx)
+    }
 }
 
 /// Relation of Display (rendered) rows to Data (src) rows with applied transformations
 /// Transformations applied:
 /// - sorting by column
+/// - filtering by column predicates and global search
 #[derive(Debug, Default)]
 pub struct DisplayToDataMapping {
     /// All rows sorted, regardless of applied filtering. Applied every time sorting changes
     pub sorted_rows: Vec<DataRow>,
+    /// Rows hidden by filters or search, regardless of sorting. Applied every time filters change
+    pub filtered_out_rows: HashSet<DataRow>,
     /// Filtered and sorted rows. Computed cheaply from `sorted_mapping` and `filtered_out_rows`
     pub mapping: Arc<HashMap<DisplayRow, DataRow>>,
 }
@@ -65,11 +136,15 @@ impl DisplayToDataMapping {
     }
 
     /// Computes sorting
-    fn apply_sorting(&mut self, sorting: Option<AppliedSorting>, rows: &[TableRow<TableCell>]) {
+    fn apply_sorting(
+        &mut self,
+        sorting: Option<(AppliedSorting, ColumnType)>,
+        rows: &[TableRow<TableCell>],
+    ) {
         let data_rows: Vec<DataRow> = (0..rows.len()).map(DataRow).collect();
 
-        let sorted_rows = if let Some(sorting) = sorting {
-            sort_data_rows(&rows, data_rows, sorting)
+        let sorted_rows = if let Some((sorting, column_type)) = sorting {
+            sort_data_rows(&rows, data_rows, sorting, column_type)
         } else {
             data_rows
         };
@@ -77,11 +152,22 @@ impl DisplayToDataMapping {
         self.sorted_rows = sorted_rows;
     }
 
+    /// Computes filtering
+    fn apply_filtering(
+        &mut self,
+        filters: &[ColumnFilter],
+        search: Option<&TextMatcher>,
+        rows: &[TableRow<TableCell>],
+    ) {
+        self.filtered_out_rows = filter_data_rows(rows, filters, search);
+    }
+
     /// Take pre-computed sorting and filtering results, and apply them to the mapping
     fn merge_mappings(&mut self) {
         self.mapping = Arc::new(
             self.sorted_rows
                 .iter()
+                .filter(|data| !self.filtered_out_rows.contains(data))
                 .enumerate()
                 .map(|(display, data)| (DisplayRow(display), *data))
                 .collect(),
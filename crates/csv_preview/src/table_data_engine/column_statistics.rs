@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use ui::SharedString;
+
+use crate::table_data_engine::column_types::{ColumnType, TypedValue};
+
+/// Number of most frequent values listed in the statistics
+const TOP_VALUES_COUNT: usize = 5;
+
+/// Summary of the values of a single column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStatistics {
+    pub column_type: ColumnType,
+    /// Number of non-empty values
+    pub count: usize,
+    /// Number of empty or missing values
+    pub null_count: usize,
+    /// Number of distinct non-empty values
+    pub distinct_count: usize,
+    /// Smallest value, compared according to the column type
+    pub min: Option<SharedString>,
+    /// Largest value, compared according to the column type
+    pub max: Option<SharedString>,
+    /// Average of numeric values
+    pub mean: Option<f64>,
+    /// Most frequent values with their number of occurrences, most frequent first
+    pub top_values: Vec<(SharedString, usize)>,
+}
+
+/// Compute statistics of column values, where `None` stands for a missing (virtual) cell
+pub fn compute_column_statistics(
+    values: &[Option<SharedString>],
+    column_type: ColumnType,
+) -> ColumnStatistics {
+    let mut null_count = 0;
+    let mut occurrences = HashMap::<&str, usize>::default();
+    let mut min: Option<(TypedValue, &SharedString)> = None;
+    let mut max: Option<(TypedValue, &SharedString)> = None;
+    let mut sum = 0.;
+    let mut numeric_count = 0;
+
+    for value in values {
+        let Some(value) = value.as_ref().filter(|value| !value.trim().is_empty()) else {
+            null_count += 1;
+            continue;
+        };
+        *occurrences.entry(value.as_str()).or_default() += 1;
+
+        let typed_value = TypedValue::parse_or_string(value.as_str(), column_type);
+        if let Some(number) = typed_value.as_f64() {
+            sum += number;
+            numeric_count += 1;
+        }
+        if min
+            .as_ref()
+            .is_none_or(|(min, _)| typed_value.compare(min).is_lt())
+        {
+            min = Some((typed_value.clone(), value));
+        }
+        if max
+            .as_ref()
+            .is_none_or(|(max, _)| typed_value.compare(max).is_gt())
+        {
+            max = Some((typed_value, value));
+        }
+    }
+
+    let mut top_values = occurrences
+        .iter()
+        .map(|(value, count)| (*value, *count))
+        .collect::<Vec<_>>();
+    top_values.sort_by(|(value_a, count_a), (value_b, count_b)| {
+        count_b.cmp(count_a).then_with(|| value_a.cmp(value_b))
+    });
+    top_values.truncate(TOP_VALUES_COUNT);
+
+    ColumnStatistics {
+        column_type,
+        count: values.len() - null_count,
+        null_count,
+        distinct_count: occurrences.len(),
+        min: min.map(|(_, value)| value.clone()),
+        max: max.map(|(_, value)| value.clone()),
+        mean: (column_type.is_numeric() && numeric_count > 0).then(|| sum / numeric_count as f64),
+        top_values: top_values
+            .into_iter()
+            .map(|(value, count)| (SharedString::from(value.to_string()), count))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(values: &[Option<&str>]) -> Vec<Option<SharedString>> {
+        values
+            .iter()
+            .map(|value| value.map(|value| SharedString::from(value.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn test_numeric_column_statistics() {
+        let values = values(&[
+            Some("10"),
+            Some("9"),
+            Some(""),
+            Some("10"),
+            None,
+            Some("-1"),
+        ]);
+        let stats = compute_column_statistics(&values, ColumnType::Integer);
+
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.null_count, 2);
+        assert_eq!(stats.distinct_count, 3);
+        assert_eq!(stats.min, Some("-1".into()));
+        assert_eq!(stats.max, Some("10".into()));
+        assert_eq!(stats.mean, Some(7.));
+        assert_eq!(
+            stats.top_values,
+            vec![("10".into(), 2), ("-1".into(), 1), ("9".into(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_text_column_statistics() {
+        let values = values(&[Some("pear"), Some("apple"), Some("pear")]);
+        let stats = compute_column_statistics(&values, ColumnType::String);
+
+        assert_eq!(stats.min, Some("apple".into()));
+        assert_eq!(stats.max, Some("pear".into()));
+        assert_eq!(stats.mean, None);
+        assert_eq!(stats.top_values[0], ("pear".into(), 2));
+    }
+}
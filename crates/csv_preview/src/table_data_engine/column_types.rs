@@ -0,0 +1,223 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use ui::table_row::TableRow;
+
+use crate::types::{AnyColumn, TableCell, TableLikeContent};
+
+/// Formats of date-time values recognized by type inference, besides RFC 3339
+const DATE_TIME_FORMATS: [&str; 3] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"];
+
+/// Formats of date values recognized by type inference
+const DATE_FORMATS: [&str; 2] = ["%Y-%m-%d", "%Y/%m/%d"];
+
+/// Type of values in a column, inferred from its non-empty cells
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum ColumnType {
+    Integer,
+    Float,
+    Date,
+    Boolean,
+    #[default]
+    String,
+}
+
+impl ColumnType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColumnType::Integer => "Integer",
+            ColumnType::Float => "Float",
+            ColumnType::Date => "Date",
+            ColumnType::Boolean => "Boolean",
+            ColumnType::String => "Text",
+        }
+    }
+
+    /// Whether values are numbers, which are right-aligned and have a mean
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, ColumnType::Integer | ColumnType::Float)
+    }
+
+    /// Whether the value can be parsed as this type. Empty values are nulls of any type.
+    fn accepts(&self, value: &str) -> bool {
+        TypedValue::parse(value, *self).is_some()
+    }
+}
+
+/// Cell value interpreted according to the type of its column
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedValue<'a> {
+    /// Empty or missing value
+    Null,
+    Integer(i64),
+    Float(f64),
+    Date(NaiveDateTime),
+    Boolean(bool),
+    String(&'a str),
+}
+
+impl<'a> TypedValue<'a> {
+    /// Returns `None` if the value can't be represented as the given type
+    pub fn parse(value: &'a str, column_type: ColumnType) -> Option<Self> {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Some(TypedValue::Null);
+        }
+        match column_type {
+            ColumnType::Integer => trimmed.parse().ok().map(TypedValue::Integer),
+            ColumnType::Float => trimmed
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+                .map(TypedValue::Float),
+            ColumnType::Date => parse_date(trimmed).map(TypedValue::Date),
+            ColumnType::Boolean => parse_boolean(trimmed).map(TypedValue::Boolean),
+            ColumnType::String => Some(TypedValue::String(value)),
+        }
+    }
+
+    /// Parse the value, falling back to a string for values not matching the column type
+    pub fn parse_or_string(value: &'a str, column_type: ColumnType) -> Self {
+        Self::parse(value, column_type).unwrap_or(TypedValue::String(value))
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            TypedValue::Integer(number) => Some(*number as f64),
+            TypedValue::Float(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Compares values of the same column. Nulls go after all other values,
+    /// and values not matching the column type go after the typed ones.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        use TypedValue::*;
+        match (self, other) {
+            (Null, Null) => Ordering::Equal,
+            (Null, _) => Ordering::Greater,
+            (_, Null) => Ordering::Less,
+            (Integer(a), Integer(b)) => a.cmp(b),
+            (Float(a), Float(b)) => a.total_cmp(b),
+            (Date(a), Date(b)) => a.cmp(b),
+            (Boolean(a), Boolean(b)) => a.cmp(b),
+            (String(a), String(b)) => a.cmp(b),
+            (String(_), _) => Ordering::Greater,
+            (_, String(_)) => Ordering::Less,
+            (a, b) => match (a.as_f64(), b.as_f64()) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                _ => Ordering::Equal,
+            },
+        }
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Some(date_time.naive_utc());
+    }
+    DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+fn parse_boolean(value: &str) -> Option<bool> {
+    if value.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Infer the most specific type all non-empty values of the column can be parsed as.
+/// Columns without values are strings.
+pub fn infer_column_type(rows: &[TableRow<TableCell>], col: AnyColumn) -> ColumnType {
+    const CANDIDATES: [ColumnType; 4] = [
+        ColumnType::Boolean,
+        ColumnType::Integer,
+        ColumnType::Float,
+        ColumnType::Date,
+    ];
+
+    let mut remaining_candidates = CANDIDATES.to_vec();
+    let mut has_values = false;
+    for value in rows
+        .iter()
+        .filter_map(|row| row.get(col)?.display_value())
+        .filter(|value| !value.trim().is_empty())
+    {
+        has_values = true;
+        remaining_candidates.retain(|candidate| candidate.accepts(value.as_str()));
+        if remaining_candidates.is_empty() {
+            return ColumnType::String;
+        }
+    }
+
+    if has_values {
+        remaining_candidates
+            .first()
+            .copied()
+            .unwrap_or(ColumnType::String)
+    } else {
+        ColumnType::String
+    }
+}
+
+pub fn infer_column_types(contents: &TableLikeContent) -> Vec<ColumnType> {
+    (0..contents.number_of_cols)
+        .map(|col| infer_column_type(&contents.rows, AnyColumn(col)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column_types(csv: &str) -> Vec<ColumnType> {
+        infer_column_types(&TableLikeContent::from_str(csv.to_string()))
+    }
+
+    #[test]
+    fn test_infer_column_types() {
+        let types = column_types(
+            "id,price,created,active,name,empty\n\
+             1,9.99,2024-01-31,true,apple,\n\
+             2,10,2024-02-01 10:30:00,FALSE,pear,\n\
+             ,,,,,\n\
+             -3,1e3,2024-03-01T08:00:00Z,true,42,\n",
+        );
+        assert_eq!(
+            types,
+            vec![
+                ColumnType::Integer,
+                ColumnType::Float,
+                ColumnType::Date,
+                ColumnType::Boolean,
+                ColumnType::String,
+                ColumnType::String,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_typed_values() {
+        let parse = |value| TypedValue::parse_or_string(value, ColumnType::Integer);
+        let mut values = vec!["10", "", "9", "n/a", "-1", "100"];
+        values.sort_by(|a, b| parse(a).compare(&parse(b)));
+        assert_eq!(values, vec!["-1", "9", "10", "100", "n/a", ""]);
+
+        let parse = |value| TypedValue::parse_or_string(value, ColumnType::Date);
+        assert_eq!(
+            parse("2024/01/02").compare(&parse("2023-12-31 23:59:59")),
+            Ordering::Greater
+        );
+    }
+}
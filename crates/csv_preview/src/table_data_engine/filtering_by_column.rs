@@ -0,0 +1,300 @@
+use std::collections::HashSet;
+
+use anyhow::{Context as _, bail};
+use regex::{Regex, RegexBuilder};
+use ui::table_row::TableRow;
+
+use crate::types::{AnyColumn, DataRow, TableCell};
+
+/// Kind of predicate which can be applied to a column
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FilterKind {
+    Contains,
+    Equals,
+    NumericRange,
+    Regex,
+    Empty,
+    NonEmpty,
+}
+
+impl FilterKind {
+    pub const ALL: [Self; 6] = [
+        Self::Contains,
+        Self::Equals,
+        Self::NumericRange,
+        Self::Regex,
+        Self::Empty,
+        Self::NonEmpty,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilterKind::Contains => "Contains",
+            FilterKind::Equals => "Equals",
+            FilterKind::NumericRange => "Number in range",
+            FilterKind::Regex => "Matches regex",
+            FilterKind::Empty => "Is empty",
+            FilterKind::NonEmpty => "Is not empty",
+        }
+    }
+
+    /// Whether the predicate needs a user provided value
+    pub fn takes_value(&self) -> bool {
+        !matches!(self, FilterKind::Empty | FilterKind::NonEmpty)
+    }
+
+    /// Hint displayed in the empty value input
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            FilterKind::Contains | FilterKind::Equals => "Text…",
+            FilterKind::NumericRange => "min..max",
+            FilterKind::Regex => "Regular expression…",
+            FilterKind::Empty | FilterKind::NonEmpty => "",
+        }
+    }
+}
+
+/// Case-insensitive literal text matcher, used by `Contains` filters and the global search
+#[derive(Debug, Clone)]
+pub struct TextMatcher {
+    query: String,
+    regex: Regex,
+}
+
+impl TextMatcher {
+    /// Returns `None` for an empty query, as it would match everything
+    pub fn new(query: &str) -> Option<Self> {
+        if query.is_empty() {
+            return None;
+        }
+        let regex = RegexBuilder::new(&regex::escape(query))
+            .case_insensitive(true)
+            .build()
+            .ok()?;
+        Some(Self {
+            query: query.to_string(),
+            regex,
+        })
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        self.regex.is_match(value)
+    }
+}
+
+/// Condition a cell value must satisfy for its row to stay visible
+#[derive(Debug, Clone)]
+pub enum FilterPredicate {
+    /// Cell contains the text, ignoring case
+    Contains(TextMatcher),
+    /// Cell is exactly equal to the text
+    Equals(String),
+    /// Cell is a number within the inclusive bounds
+    NumericRange { min: Option<f64>, max: Option<f64> },
+    /// Cell matches the regular expression
+    Regex(Regex),
+    /// Cell is empty or whitespace only
+    Empty,
+    /// Cell has non-whitespace content
+    NonEmpty,
+}
+
+impl FilterPredicate {
+    /// Create a predicate of the given kind from the user input
+    pub fn parse(kind: FilterKind, value: &str) -> anyhow::Result<Self> {
+        Ok(match kind {
+            FilterKind::Contains => FilterPredicate::Contains(
+                TextMatcher::new(value).context("Enter text to search for")?,
+            ),
+            FilterKind::Equals => FilterPredicate::Equals(value.to_string()),
+            FilterKind::NumericRange => parse_numeric_range(value)?,
+            FilterKind::Regex => {
+                FilterPredicate::Regex(Regex::new(value).context("Invalid regular expression")?)
+            }
+            FilterKind::Empty => FilterPredicate::Empty,
+            FilterKind::NonEmpty => FilterPredicate::NonEmpty,
+        })
+    }
+
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            FilterPredicate::Contains(matcher) => matcher.is_match(value),
+            FilterPredicate::Equals(expected) => value == expected,
+            FilterPredicate::NumericRange { min, max } => {
+                value.trim().parse::<f64>().is_ok_and(|number| {
+                    min.is_none_or(|min| number >= min) && max.is_none_or(|max| number <= max)
+                })
+            }
+            FilterPredicate::Regex(regex) => regex.is_match(value),
+            FilterPredicate::Empty => value.trim().is_empty(),
+            FilterPredicate::NonEmpty => !value.trim().is_empty(),
+        }
+    }
+
+    /// Short description of the predicate, e.g. `contains "foo"`
+    pub fn description(&self) -> String {
+        match self {
+            FilterPredicate::Contains(matcher) => format!("contains \"{}\"", matcher.query()),
+            FilterPredicate::Equals(expected) => format!("= \"{expected}\""),
+            FilterPredicate::NumericRange { min, max } => match (min, max) {
+                (Some(min), Some(max)) if min == max => format!("= {min}"),
+                (Some(min), Some(max)) => format!("{min} ≤ x ≤ {max}"),
+                (Some(min), None) => format!("≥ {min}"),
+                (None, Some(max)) => format!("≤ {max}"),
+                (None, None) => "is a number".to_string(),
+            },
+            FilterPredicate::Regex(regex) => format!("matches /{}/", regex.as_str()),
+            FilterPredicate::Empty => "is empty".to_string(),
+            FilterPredicate::NonEmpty => "is not empty".to_string(),
+        }
+    }
+}
+
+/// Parses `min..max` (either bound optional) or a single number
+fn parse_numeric_range(value: &str) -> anyhow::Result<FilterPredicate> {
+    let parse_bound = |bound: &str| -> anyhow::Result<Option<f64>> {
+        let bound = bound.trim();
+        if bound.is_empty() {
+            return Ok(None);
+        }
+        bound
+            .parse::<f64>()
+            .map(Some)
+            .with_context(|| format!("'{bound}' is not a number"))
+    };
+
+    let (min, max) = match value.split_once("..") {
+        Some((min, max)) => (parse_bound(min)?, parse_bound(max)?),
+        None => {
+            let Some(exact) = parse_bound(value)? else {
+                bail!("Enter a number or a range like 10..20");
+            };
+            (Some(exact), Some(exact))
+        }
+    };
+    if let (Some(min), Some(max)) = (min, max)
+        && min > max
+    {
+        bail!("Range start {min} is greater than its end {max}");
+    }
+
+    Ok(FilterPredicate::NumericRange { min, max })
+}
+
+/// Filter applied to a single column
+#[derive(Debug, Clone)]
+pub struct ColumnFilter {
+    /// 0-based column index
+    pub col_idx: AnyColumn,
+    pub predicate: FilterPredicate,
+}
+
+/// Returns rows which don't satisfy all of the column filters, or which have no cell matching the search.
+/// Virtual cells are treated as empty values.
+pub fn filter_data_rows(
+    content_rows: &[TableRow<TableCell>],
+    filters: &[ColumnFilter],
+    search: Option<&TextMatcher>,
+) -> HashSet<DataRow> {
+    if filters.is_empty() && search.is_none() {
+        return HashSet::default();
+    }
+
+    content_rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| {
+            let passes_filters = filters.iter().all(|filter| {
+                let value = row
+                    .get(filter.col_idx)
+                    .and_then(|cell| cell.display_value())
+                    .map(|value| value.as_str())
+                    .unwrap_or("");
+                filter.predicate.matches(value)
+            });
+            let passes_search = search.is_none_or(|search| {
+                row.as_slice()
+                    .iter()
+                    .filter_map(|cell| cell.display_value())
+                    .any(|value| search.is_match(value))
+            });
+            !(passes_filters && passes_search)
+        })
+        .map(|(ix, _)| DataRow(ix))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TableLikeContent;
+
+    fn rows(csv: &str) -> Vec<TableRow<TableCell>> {
+        TableLikeContent::from_str(csv.to_string()).rows
+    }
+
+    fn col_filter(col: usize, kind: FilterKind, value: &str) -> ColumnFilter {
+        ColumnFilter {
+            col_idx: AnyColumn(col),
+            predicate: FilterPredicate::parse(kind, value).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_column_filters() {
+        let rows = rows("name,level,ms\nalpha,INFO,12\nbeta,WARN,250\ngamma,,7.5\ndelta,info,x\n");
+
+        let contains =
+            filter_data_rows(&rows, &[col_filter(1, FilterKind::Contains, "info")], None);
+        assert_eq!(contains, HashSet::from([DataRow(1), DataRow(2)]));
+
+        let equals = filter_data_rows(&rows, &[col_filter(1, FilterKind::Equals, "INFO")], None);
+        assert_eq!(equals, HashSet::from([DataRow(1), DataRow(2), DataRow(3)]));
+
+        let range = filter_data_rows(
+            &rows,
+            &[col_filter(2, FilterKind::NumericRange, "..100")],
+            None,
+        );
+        assert_eq!(range, HashSet::from([DataRow(1), DataRow(3)]));
+
+        let regex = filter_data_rows(&rows, &[col_filter(0, FilterKind::Regex, "^(a|d)")], None);
+        assert_eq!(regex, HashSet::from([DataRow(1), DataRow(2)]));
+
+        let empty = filter_data_rows(&rows, &[col_filter(1, FilterKind::Empty, "")], None);
+        assert_eq!(empty, HashSet::from([DataRow(0), DataRow(1), DataRow(3)]));
+
+        let combined = filter_data_rows(
+            &rows,
+            &[
+                col_filter(1, FilterKind::NonEmpty, ""),
+                col_filter(2, FilterKind::NumericRange, "10.."),
+            ],
+            None,
+        );
+        assert_eq!(combined, HashSet::from([DataRow(2), DataRow(3)]));
+    }
+
+    #[test]
+    fn test_search() {
+        let rows = rows("name,message\nalpha,Connection reset\nbeta,ok\ngamma,RESET by peer\n");
+        let search = TextMatcher::new("reset").unwrap();
+
+        let filtered_out = filter_data_rows(&rows, &[], Some(&search));
+        assert_eq!(filtered_out, HashSet::from([DataRow(1)]));
+    }
+
+    #[test]
+    fn test_numeric_range_parsing() {
+        assert!(FilterPredicate::parse(FilterKind::NumericRange, "5").is_ok());
+        assert!(FilterPredicate::parse(FilterKind::NumericRange, "-1.5..2e3").is_ok());
+        assert!(FilterPredicate::parse(FilterKind::NumericRange, "abc").is_err());
+        assert!(FilterPredicate::parse(FilterKind::NumericRange, "10..1").is_err());
+        assert!(FilterPredicate::parse(FilterKind::NumericRange, "").is_err());
+        assert!(FilterPredicate::parse(FilterKind::Regex, "(").is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use ui::table_row::TableRow;
 
-use crate::types::{AnyColumn, DataRow, TableCell};
+use crate::{
+    table_data_engine::column_types::{ColumnType, TypedValue},
+    types::{AnyColumn, DataRow, TableCell},
+};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SortDirection {
@@ -17,33 +20,79 @@ pub struct AppliedSorting {
     pub direction: SortDirection,
 }
 
+/// Sort rows by the typed values of the column. Empty values stay at the bottom in both directions.
 pub fn sort_data_rows(
     content_rows: &[TableRow<TableCell>],
     mut data_row_ids: Vec<DataRow>,
     sorting: AppliedSorting,
+    column_type: ColumnType,
 ) -> Vec<DataRow> {
+    // Parse each value once rather than on every comparison
+    let values = content_rows
+        .iter()
+        .map(|row| {
+            let value = row
+                .get(sorting.col_idx)
+                .and_then(|tc| tc.display_value())
+                .map(|tc| tc.as_str())
+                .unwrap_or("");
+            TypedValue::parse_or_string(value, column_type)
+        })
+        .collect::<Vec<_>>();
+
     data_row_ids.sort_by(|&a, &b| {
-        let row_a = &content_rows[*a];
-        let row_b = &content_rows[*b];
-
-        // TODO: Decide how to handle nulls (on top or on bottom)
-        let val_a = row_a
-            .get(sorting.col_idx)
-            .and_then(|tc| tc.display_value())
-            .map(|tc| tc.as_str())
-            .unwrap_or("");
-        let val_b = row_b
-            .get(sorting.col_idx)
-            .and_then(|tc| tc.display_value())
-            .map(|tc| tc.as_str())
-            .unwrap_or("");
-
-        let cmp = val_a.cmp(val_b);
-        match sorting.direction {
-            SortDirection::Asc => cmp,
-            SortDirection::Desc => cmp.reverse(),
+        let val_a = &values[*a];
+        let val_b = &values[*b];
+
+        match (val_a, val_b) {
+            (TypedValue::Null, _) | (_, TypedValue::Null) => val_a.compare(val_b),
+            _ => {
+                let cmp = val_a.compare(val_b);
+                match sorting.direction {
+                    SortDirection::Asc => cmp,
+                    SortDirection::Desc => cmp.reverse(),
+                }
+            }
         }
     });
 
     data_row_ids
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TableLikeContent;
+
+    #[test]
+    fn test_sort_by_typed_values() {
+        let contents = TableLikeContent::from_str("n,id\n10,a\n,b\n9,c\n100,d\n".to_string());
+        let data_rows = (0..contents.rows.len()).map(DataRow).collect::<Vec<_>>();
+        let sorting = |direction| AppliedSorting {
+            col_idx: AnyColumn(0),
+            direction,
+        };
+
+        let ascending = sort_data_rows(
+            &contents.rows,
+            data_rows.clone(),
+            sorting(SortDirection::Asc),
+            ColumnType::Integer,
+        );
+        assert_eq!(
+            ascending,
+            vec![DataRow(2), DataRow(0), DataRow(3), DataRow(1)]
+        );
+
+        let descending = sort_data_rows(
+            &contents.rows,
+            data_rows,
+            sorting(SortDirection::Desc),
+            ColumnType::Integer,
+        );
+        assert_eq!(
+            descending,
+            vec![DataRow(3), DataRow(0), DataRow(2), DataRow(1)]
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use ui::table_row::TableRow;
 
 use crate::types::{DataRow, LineNumber, TableCell};
@@ -9,7 +11,8 @@ pub struct TableLikeContent {
     /// Defines table width used to validate `TableRow` on creation
     pub number_of_cols: usize,
     pub headers: TableRow<TableCell>,
-    pub rows: Vec<TableRow<TableCell>>,
+    /// Shared, so that rows can be filtered on the background executor
+    pub rows: Arc<Vec<TableRow<TableCell>>>,
     /// Follows the same indices as `rows`
     pub line_numbers: Vec<LineNumber>,
 }
@@ -19,7 +22,7 @@ impl Default for TableLikeContent {
         Self {
             number_of_cols: 0,
             headers: TableRow::<TableCell>::from_vec(vec![], 0),
-            rows: vec![],
+            rows: Arc::default(),
             line_numbers: vec![],
         }
     }
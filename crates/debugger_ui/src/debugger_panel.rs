@@ -5,14 +5,15 @@ use crate::session::running::breakpoint_list::BreakpointList;
 
 use crate::{
     ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, FocusBreakpointList,
-    FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto, StepOut, StepOver, Stop,
+    FocusConsole, FocusDisassembly, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal,
+    FocusVariables, GoToTarget, NewProcessModal, NewProcessMode, Pause, RerunSession,
+    ReverseContinue, ReverseStepInto, ReverseStepOut, StepBack, StepInto, StepOut, StepOver, Stop,
     ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence, spawn_task_or_modal,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::IndexMap;
 use dap::adapters::DebugAdapterName;
-use dap::{DapRegistry, StartDebuggingRequestArguments};
+use dap::{Capabilities, DapRegistry, StartDebuggingRequestArguments};
 use dap::{client::SessionId, debugger_settings::DebuggerSettings};
 use editor::{Editor, MultiBufferOffset, ToPoint};
 use feature_flags::{FeatureFlag, FeatureFlagAppExt as _, PresenceFlag, register_feature_flag};
@@ -816,6 +817,36 @@ impl DebugPanel {
                                                 }
                                             }),
                                     )
+                                    .when(
+                                        capabilities.supports_step_back.unwrap_or_default(),
+                                        |this| {
+                                            this.child(Divider::vertical())
+                                                .child(self.render_reverse_continue_button(
+                                                    running_state,
+                                                    thread_status,
+                                                    &focus_handle,
+                                                    window,
+                                                ))
+                                                .child(
+                                                    SplitButton::new(
+                                                        self.render_step_back_button(
+                                                            running_state,
+                                                            thread_status,
+                                                            &focus_handle,
+                                                            window,
+                                                        ),
+                                                        self.render_reverse_step_menu(
+                                                            running_state,
+                                                            thread_status,
+                                                            &capabilities,
+                                                            &focus_handle,
+                                                        )
+                                                        .into_any_element(),
+                                                    )
+                                                    .style(ui::SplitButtonStyle::Outlined),
+                                                )
+                                        },
+                                    )
                                     .child(Divider::vertical())
                                     .child(
                                         IconButton::new("debug-restart", IconName::RotateCcw)
@@ -1346,6 +1377,114 @@ impl DebugPanel {
         }
     }
 
+    fn render_reverse_continue_button(
+        &self,
+        running_state: &Entity<RunningState>,
+        thread_status: ThreadStatus,
+        focus_handle: &FocusHandle,
+        window: &mut Window,
+    ) -> IconButton {
+        IconButton::new("debug-reverse-continue", IconName::DebugReverseContinue)
+            .icon_size(IconSize::Small)
+            .on_click(window.listener_for(running_state, |this, _, _window, cx| {
+                this.reverse_continue_thread(cx);
+            }))
+            .disabled(thread_status != ThreadStatus::Stopped)
+            .tooltip({
+                let focus_handle = focus_handle.clone();
+                move |_window, cx| {
+                    Tooltip::for_action_in("Reverse Continue", &ReverseContinue, &focus_handle, cx)
+                }
+            })
+    }
+
+    fn render_step_back_button(
+        &self,
+        running_state: &Entity<RunningState>,
+        thread_status: ThreadStatus,
+        focus_handle: &FocusHandle,
+        window: &mut Window,
+    ) -> IconButton {
+        IconButton::new("debug-step-back", IconName::DebugStepBack)
+            .icon_size(IconSize::Small)
+            .on_click(window.listener_for(running_state, |this, _, _window, cx| {
+                this.step_back(cx);
+            }))
+            .disabled(thread_status != ThreadStatus::Stopped)
+            .tooltip({
+                let focus_handle = focus_handle.clone();
+                move |_window, cx| {
+                    Tooltip::for_action_in("Reverse Step Over", &StepBack, &focus_handle, cx)
+                }
+            })
+    }
+
+    fn render_reverse_step_menu(
+        &self,
+        running_state: &Entity<RunningState>,
+        thread_status: ThreadStatus,
+        capabilities: &Capabilities,
+        focus_handle: &FocusHandle,
+    ) -> impl IntoElement {
+        let supports_goto_targets = capabilities
+            .supports_goto_targets_request
+            .unwrap_or_default();
+        PopoverMenu::new("debug-reverse-step-menu")
+            .trigger(
+                ui::ButtonLike::new_rounded_right("debug-reverse-step-menu-trigger")
+                    .layer(ui::ElevationIndex::ModalSurface)
+                    .size(ui::ButtonSize::None)
+                    .child(
+                        div()
+                            .px_1()
+                            .child(Icon::new(IconName::ChevronDown).size(IconSize::XSmall)),
+                    )
+                    .disabled(thread_status != ThreadStatus::Stopped),
+            )
+            .menu({
+                let running_state = running_state.clone();
+                let focus_handle = focus_handle.clone();
+                move |window, cx| {
+                    let running_state = running_state.clone();
+                    Some(ContextMenu::build(window, cx, {
+                        let focus_handle = focus_handle.clone();
+                        move |context_menu, _window, _cx| {
+                            context_menu
+                                .context(focus_handle)
+                                .entry("Reverse Step In", Some(ReverseStepInto.boxed_clone()), {
+                                    let running_state = running_state.clone();
+                                    move |_window, cx| {
+                                        running_state.update(cx, |state, cx| {
+                                            state.reverse_step_in(cx);
+                                        });
+                                    }
+                                })
+                                .entry("Reverse Step Out", Some(ReverseStepOut.boxed_clone()), {
+                                    let running_state = running_state.clone();
+                                    move |_window, cx| {
+                                        running_state.update(cx, |state, cx| {
+                                            state.reverse_step_out(cx);
+                                        });
+                                    }
+                                })
+                                .when(supports_goto_targets, |this| {
+                                    this.separator().entry(
+                                        "Go to Cursor",
+                                        Some(GoToTarget.boxed_clone()),
+                                        move |_window, cx| {
+                                            running_state.update(cx, |state, cx| {
+                                                state.go_to_cursor(cx);
+                                            });
+                                        },
+                                    )
+                                })
+                        }
+                    }))
+                }
+            })
+            .anchor(Corner::TopRight)
+    }
+
     fn render_history_button(
         &self,
         running_state: &Entity<RunningState>,
@@ -1736,6 +1875,15 @@ impl Render for DebugPanel {
                     .ok();
                 }
             })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusDisassembly, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+                    })
+                    .ok();
+                }
+            })
             .on_action({
                 let this = this.clone();
                 move |_: &ToggleThreadPicker, window, cx| {
@@ -49,6 +49,18 @@ actions!(
         StepOut,
         /// Steps back to the previous statement.
         StepBack,
+        /// Runs backwards until the previous breakpoint.
+        ReverseContinue,
+        /// Steps backwards into the function called by the previous statement.
+        ReverseStepInto,
+        /// Runs backwards to the statement that called the current function.
+        ReverseStepOut,
+        /// Moves execution to the cursor without running the code in between.
+        GoToTarget,
+        /// Steps into the next machine instruction.
+        StepIntoInstruction,
+        /// Steps over the current machine instruction.
+        StepOverInstruction,
         /// Stops the debugging session.
         Stop,
         /// Toggles whether to ignore all breakpoints.
@@ -69,6 +81,8 @@ actions!(
         FocusLoadedSources,
         /// Focuses on the terminal panel.
         FocusTerminal,
+        /// Focuses on the disassembly panel.
+        FocusDisassembly,
         /// Toggles the thread picker dropdown.
         ToggleThreadPicker,
         /// Toggles the session picker dropdown.
@@ -159,6 +173,9 @@ pub fn init(cx: &mut App) {
 
                 let caps = running_state.capabilities(cx);
                 let supports_step_back = caps.supports_step_back.unwrap_or_default();
+                let supports_goto_targets = caps.supports_goto_targets_request.unwrap_or_default();
+                let supports_stepping_granularity =
+                    caps.supports_stepping_granularity.unwrap_or_default();
                 let supports_detach = running_state.session().read(cx).is_attached();
                 let status = running_state.thread_status(cx);
 
@@ -191,9 +208,61 @@ pub fn init(cx: &mut App) {
                         }
                     })
                     .when(supports_step_back, |div| {
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepBack, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &ReverseContinue, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.reverse_continue_thread(cx))
+                                    .ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &ReverseStepInto, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.reverse_step_in(cx))
+                                    .ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &ReverseStepOut, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.reverse_step_out(cx))
+                                    .ok();
+                            }
+                        })
+                    })
+                    .when(supports_goto_targets, |div| {
                         let active_item = active_item.clone();
-                        div.on_action(move |_: &StepBack, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                        div.on_action(move |_: &GoToTarget, _, cx| {
+                            active_item
+                                .update(cx, |item, cx| item.go_to_cursor(cx))
+                                .ok();
+                        })
+                    })
+                    .when(supports_stepping_granularity, |div| {
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepIntoInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_in_instruction(cx))
+                                    .ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepOverInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_over_instruction(cx))
+                                    .ok();
+                            }
                         })
                     })
                     .on_action({
@@ -1,5 +1,6 @@
 pub(crate) mod breakpoint_list;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
 pub(crate) mod loaded_source_list;
 pub(crate) mod memory_view;
 pub(crate) mod module_list;
@@ -25,11 +26,13 @@ use breakpoint_list::BreakpointList;
 use collections::{HashMap, IndexMap};
 use console::Console;
 use dap::{
-    Capabilities, DapRegistry, RunInTerminalRequestArguments, Thread,
+    Capabilities, DapRegistry, RunInTerminalRequestArguments, SteppingGranularity, Thread,
     adapters::{DebugAdapterName, DebugTaskDefinition},
     client::SessionId,
     debugger_settings::DebuggerSettings,
 };
+use disassembly_view::DisassemblyView;
+use editor::Editor;
 use futures::{SinkExt, channel::mpsc};
 use gpui::{
     Action as _, AnyView, AppContext, Axis, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
@@ -40,7 +43,10 @@ use loaded_source_list::LoadedSourceList;
 use module_list::ModuleList;
 use project::{
     DebugScenarioContext, Project, WorktreeId,
-    debugger::session::{self, Session, SessionEvent, SessionStateEvent, ThreadId, ThreadStatus},
+    debugger::{
+        breakpoint_store::BreakpointStore,
+        session::{self, Session, SessionEvent, SessionStateEvent, ThreadId, ThreadStatus},
+    },
 };
 use rpc::proto::ViewId;
 use serde_json::Value;
@@ -89,6 +95,7 @@ pub struct RunningState {
     pub(crate) scenario: Option<DebugScenario>,
     pub(crate) scenario_context: Option<DebugScenarioContext>,
     memory_view: Entity<MemoryView>,
+    disassembly_view: Entity<DisassemblyView>,
 }
 
 impl RunningState {
@@ -808,6 +815,15 @@ impl RunningState {
 
         let module_list = cx.new(|cx| ModuleList::new(session.clone(), workspace.clone(), cx));
 
+        let disassembly_view = cx.new(|cx| {
+            DisassemblyView::new(
+                session.clone(),
+                stack_frame_list.clone(),
+                workspace.clone(),
+                cx,
+            )
+        });
+
         let loaded_source_list = cx.new(|cx| LoadedSourceList::new(session.clone(), cx));
 
         let console = cx.new(|cx| {
@@ -879,6 +895,9 @@ impl RunningState {
                         {
                             this.remove_pane_item(DebuggerPaneItem::LoadedSources, window, cx);
                         }
+                        if !capabilities.supports_disassemble_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+                        }
                     }
                     SessionEvent::RunInTerminal { request, sender } => this
                         .handle_run_in_terminal(request, sender.clone(), window, cx)
@@ -917,6 +936,7 @@ impl RunningState {
                 &loaded_source_list,
                 &debug_terminal,
                 &memory_view,
+                &disassembly_view,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -946,6 +966,7 @@ impl RunningState {
 
         Self {
             memory_view,
+            disassembly_view,
             session,
             workspace,
             project: weak_project,
@@ -1314,6 +1335,7 @@ impl RunningState {
             show_command: false,
             show_rerun: false,
             save: task::SaveStrategy::default(),
+            problem_matchers: Vec::new(),
         };
 
         let workspace = self.workspace.clone();
@@ -1417,6 +1439,14 @@ impl RunningState {
                 host_pane,
                 cx,
             )),
+            DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                running_state,
+                host_pane,
+                cx,
+            )),
         }
     }
 
@@ -1613,6 +1643,11 @@ impl RunningState {
         &self.module_list
     }
 
+    #[cfg(test)]
+    pub(crate) fn disassembly_view(&self) -> &Entity<DisassemblyView> {
+        &self.disassembly_view
+    }
+
     pub(crate) fn activate_item(
         &mut self,
         item: DebuggerPaneItem,
@@ -1743,6 +1778,28 @@ impl RunningState {
         });
     }
 
+    /// Steps over the current instruction, regardless of the stepping granularity setting.
+    pub(crate) fn step_over_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_over(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    /// Steps into the current instruction, regardless of the stepping granularity setting.
+    pub(crate) fn step_in_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_in(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
     pub(crate) fn step_back(&mut self, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
@@ -1755,6 +1812,72 @@ impl RunningState {
         });
     }
 
+    pub(crate) fn reverse_continue_thread(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.reverse_continue_thread(thread_id, cx);
+        });
+    }
+
+    pub(crate) fn reverse_step_in(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.reverse_step_in(thread_id, cx);
+        });
+    }
+
+    pub(crate) fn reverse_step_out(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.reverse_step_out(thread_id, cx);
+        });
+    }
+
+    /// Moves the thread to the cursor of the active editor, without running
+    /// the code in between.
+    pub(crate) fn go_to_cursor(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+        let Some(editor) = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).active_item_as::<Editor>(cx))
+        else {
+            return;
+        };
+        let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+            let cursor_point: language::Point = editor
+                .selections
+                .newest(&editor.display_snapshot(cx))
+                .head();
+            editor
+                .buffer()
+                .read(cx)
+                .point_to_buffer_point(cursor_point, cx)
+        }) else {
+            return;
+        };
+        let Some(path) = BreakpointStore::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state
+                .go_to_position(path, position.row, thread_id, cx)
+                .detach();
+        });
+    }
+
     pub fn rerun_session(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some((scenario, context)) = self.scenario.take().zip(self.scenario_context.take())
             && scenario.build.is_some()
@@ -670,6 +670,7 @@ impl Render for BreakpointList {
         self.breakpoints.clear();
         let path_style = self.worktree_store.read(cx).path_style();
         let weak = cx.weak_entity();
+        let session = self.session.as_ref().map(|session| session.read(cx));
         let breakpoints = breakpoints.into_iter().flat_map(|(path, mut breakpoints)| {
             let relative_worktree_path = self
                 .worktree_store
@@ -699,11 +700,15 @@ impl Render for BreakpointList {
                     .map(SharedString::from)?;
                 let weak = weak.clone();
                 let line = breakpoint.row + 1;
+                let hit_count = session.map_or(0, |session| {
+                    session.breakpoint_hit_count(&breakpoint.path, breakpoint.row)
+                });
                 Some(BreakpointEntry {
                     kind: BreakpointEntryKind::LineBreakpoint(LineBreakpoint {
                         name,
                         dir,
                         line,
+                        hit_count,
                         breakpoint,
                     }),
                     weak,
@@ -816,6 +821,9 @@ struct LineBreakpoint {
     name: SharedString,
     dir: Option<SharedString>,
     line: u32,
+    /// How many times the breakpoint was hit, or its message logged, in the
+    /// current session.
+    hit_count: u64,
     breakpoint: SourceBreakpoint,
 }
 
@@ -948,6 +956,25 @@ impl LineBreakpoint {
                             )))
                         }),
                 )
+                .when(self.hit_count > 0, |this| {
+                    let hit_count = self.hit_count;
+                    this.child(
+                        div()
+                            .id("hit-count")
+                            .flex_none()
+                            .child(
+                                Label::new(format!("×{hit_count}"))
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small)
+                                    .line_height_style(ui::LineHeightStyle::UiLabel),
+                            )
+                            .tooltip(Tooltip::text(if hit_count == 1 {
+                                "Hit once".to_string()
+                            } else {
+                                format!("Hit {hit_count} times")
+                            })),
+                    )
+                })
                 .child(BreakpointOptionsStrip {
                     props,
                     breakpoint: BreakpointEntry {
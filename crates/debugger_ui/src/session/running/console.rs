@@ -39,8 +39,19 @@ actions!(
     ]
 );
 
+/// The stream of messages shown in the console.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ConsoleChannel {
+    /// The program's output and the results of evaluated expressions.
+    Output,
+    /// Messages printed by logpoints.
+    Logpoints,
+}
+
 pub struct Console {
     console: Entity<Editor>,
+    logpoints: Entity<Editor>,
+    channel: ConsoleChannel,
     query_bar: Entity<Editor>,
     session: Entity<Session>,
     _subscriptions: Vec<Subscription>,
@@ -48,6 +59,8 @@ pub struct Console {
     stack_frame_list: Entity<StackFrameList>,
     last_token: OutputToken,
     update_output_task: Option<Task<()>>,
+    last_logpoint_token: OutputToken,
+    update_logpoint_output_task: Option<Task<()>>,
     focus_handle: FocusHandle,
     history: SearchHistory,
     cursor: SearchHistoryCursor,
@@ -61,34 +74,8 @@ impl Console {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
-        let console = cx.new(|cx| {
-            let mut editor = Editor::multi_line(window, cx);
-            editor.set_mode(EditorMode::Full {
-                scale_ui_elements_with_buffer_font_size: true,
-                show_active_line_background: true,
-                sizing_behavior: SizingBehavior::ExcludeOverscrollMargin,
-            });
-            editor.move_to_end(&editor::actions::MoveToEnd, window, cx);
-            editor.set_read_only(true);
-            editor.disable_scrollbars_and_minimap(window, cx);
-            editor.set_show_gutter(false, cx);
-            editor.set_show_runnables(false, cx);
-            editor.set_show_bookmarks(false, cx);
-            editor.set_show_breakpoints(false, cx);
-            editor.set_show_code_actions(false, cx);
-            editor.set_show_line_numbers(false, cx);
-            editor.set_show_git_diff_gutter(false, cx);
-            editor.set_autoindent(false);
-            editor.set_input_enabled(false);
-            editor.set_use_autoclose(false);
-            editor.set_show_wrap_guides(false, cx);
-            editor.set_show_indent_guides(false, cx);
-            editor.set_show_edit_predictions(Some(false), window, cx);
-            editor.set_use_modal_editing(false);
-            editor.disable_mouse_wheel_zoom();
-            editor.set_soft_wrap_mode(language::language_settings::SoftWrap::EditorWidth, cx);
-            editor
-        });
+        let console = Self::output_editor(window, cx);
+        let logpoints = Self::output_editor(window, cx);
         let focus_handle = cx.focus_handle();
 
         let this = cx.weak_entity();
@@ -116,12 +103,16 @@ impl Console {
         Self {
             session,
             console,
+            logpoints,
+            channel: ConsoleChannel::Output,
             query_bar,
             variable_list,
             _subscriptions,
             stack_frame_list,
             update_output_task: None,
             last_token: OutputToken(0),
+            update_logpoint_output_task: None,
+            last_logpoint_token: OutputToken(0),
             focus_handle,
             history: SearchHistory::new(
                 None,
@@ -131,11 +122,48 @@ impl Console {
         }
     }
 
+    /// A read-only editor that output is appended to.
+    fn output_editor(window: &mut Window, cx: &mut Context<Self>) -> Entity<Editor> {
+        cx.new(|cx| {
+            let mut editor = Editor::multi_line(window, cx);
+            editor.set_mode(EditorMode::Full {
+                scale_ui_elements_with_buffer_font_size: true,
+                show_active_line_background: true,
+                sizing_behavior: SizingBehavior::ExcludeOverscrollMargin,
+            });
+            editor.move_to_end(&editor::actions::MoveToEnd, window, cx);
+            editor.set_read_only(true);
+            editor.disable_scrollbars_and_minimap(window, cx);
+            editor.set_show_gutter(false, cx);
+            editor.set_show_runnables(false, cx);
+            editor.set_show_bookmarks(false, cx);
+            editor.set_show_breakpoints(false, cx);
+            editor.set_show_code_actions(false, cx);
+            editor.set_show_line_numbers(false, cx);
+            editor.set_show_git_diff_gutter(false, cx);
+            editor.set_autoindent(false);
+            editor.set_input_enabled(false);
+            editor.set_use_autoclose(false);
+            editor.set_show_wrap_guides(false, cx);
+            editor.set_show_indent_guides(false, cx);
+            editor.set_show_edit_predictions(Some(false), window, cx);
+            editor.set_use_modal_editing(false);
+            editor.disable_mouse_wheel_zoom();
+            editor.set_soft_wrap_mode(language::language_settings::SoftWrap::EditorWidth, cx);
+            editor
+        })
+    }
+
     #[cfg(test)]
     pub(crate) fn editor(&self) -> &Entity<Editor> {
         &self.console
     }
 
+    #[cfg(test)]
+    pub(crate) fn logpoints_editor(&self) -> &Entity<Editor> {
+        &self.logpoints
+    }
+
     fn is_running(&self, cx: &Context<Self>) -> bool {
         self.session.read(cx).is_started()
     }
@@ -153,16 +181,23 @@ impl Console {
     }
 
     pub(crate) fn show_indicator(&self, cx: &App) -> bool {
-        self.session.read(cx).has_new_output(self.last_token)
+        let session = self.session.read(cx);
+        session.has_new_output(self.last_token)
+            || session.has_new_logpoint_output(self.last_logpoint_token)
+    }
+
+    pub(crate) fn set_channel(&mut self, channel: ConsoleChannel, cx: &mut Context<Self>) {
+        self.channel = channel;
+        cx.notify();
     }
 
     fn add_messages(
-        &mut self,
+        editor: &Entity<Editor>,
         events: Vec<OutputEvent>,
         window: &mut Window,
         cx: &mut App,
     ) -> Task<Result<()>> {
-        self.console.update(cx, |_, cx| {
+        editor.update(cx, |_, cx| {
             cx.spawn_in(window, async move |console, cx| {
                 let mut len = console
                     .update(cx, |this, cx| this.buffer().read(cx).len(cx))?
@@ -390,7 +425,11 @@ impl Console {
     }
 
     fn render_console(&self, cx: &Context<Self>) -> impl IntoElement {
-        EditorElement::new(&self.console, Self::editor_style(&self.console, cx))
+        let editor = match self.channel {
+            ConsoleChannel::Output => &self.console,
+            ConsoleChannel::Logpoints => &self.logpoints,
+        };
+        EditorElement::new(editor, Self::editor_style(editor, cx))
     }
 
     fn editor_style(editor: &Entity<Editor>, cx: &Context<Self>) -> EditorStyle {
@@ -439,7 +478,12 @@ impl Console {
                         }
                         Some((
                             last_processed_token,
-                            this.add_messages(output.cloned().collect(), window, cx),
+                            Self::add_messages(
+                                &this.console,
+                                output.cloned().collect(),
+                                window,
+                                cx,
+                            ),
                         ))
                     })
                     .ok()
@@ -460,12 +504,67 @@ impl Console {
             });
         }));
     }
+
+    fn update_logpoint_output(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.update_logpoint_output_task.is_some() {
+            return;
+        }
+        let (output, last_processed_token) = self
+            .session
+            .read(cx)
+            .logpoint_output(self.last_logpoint_token);
+        if last_processed_token == self.last_logpoint_token {
+            return;
+        }
+        let task = Self::add_messages(&self.logpoints, output.cloned().collect(), window, cx);
+        self.update_logpoint_output_task = Some(cx.spawn(async move |this, cx| {
+            _ = task.await.log_err();
+            _ = this.update(cx, |this, _| {
+                this.last_logpoint_token = last_processed_token;
+                this.update_logpoint_output_task.take();
+            });
+        }));
+    }
+
+    fn render_channel_tabs(&self, cx: &Context<Self>) -> impl IntoElement {
+        let logpoint_count = self.last_logpoint_token.0;
+        let channel_button = |id: &'static str, label: SharedString, channel: ConsoleChannel| {
+            Button::new(id, label)
+                .label_size(LabelSize::Small)
+                .toggle_state(self.channel == channel)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.set_channel(channel, cx);
+                }))
+        };
+
+        h_flex()
+            .px_1()
+            .gap_1()
+            .child(channel_button(
+                "console-channel-output",
+                "Output".into(),
+                ConsoleChannel::Output,
+            ))
+            .child(
+                channel_button(
+                    "console-channel-logpoints",
+                    if logpoint_count == 0 {
+                        "Logpoints".into()
+                    } else {
+                        format!("Logpoints ({logpoint_count})").into()
+                    },
+                    ConsoleChannel::Logpoints,
+                )
+                .tooltip(Tooltip::text("Messages printed by logpoints")),
+            )
+    }
 }
 
 impl Render for Console {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let query_focus_handle = self.query_bar.focus_handle(cx);
         self.update_output(window, cx);
+        self.update_logpoint_output(window, cx);
 
         v_flex()
             .track_focus(&self.focus_handle)
@@ -475,6 +574,7 @@ impl Render for Console {
             .size_full()
             .border_2()
             .bg(cx.theme().colors().editor_background)
+            .child(self.render_channel_tabs(cx))
             .child(self.render_console(cx))
             .when(self.is_running(cx), |this| {
                 this.child(Divider::horizontal()).child(
@@ -0,0 +1,444 @@
+use std::{ops::Range, path::Path, sync::Arc};
+
+use collections::HashMap;
+use dap::DisassembledInstruction;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, ScrollStrategy, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent};
+use ui::{Tooltip, VisibleOnHover, WithScrollbar, prelude::*};
+use util::ResultExt;
+use workspace::Workspace;
+
+use crate::session::running::stack_frame_list::{StackFrameList, StackFrameListEvent};
+
+/// How many instructions are disassembled before and after the instruction pointer.
+const INSTRUCTIONS_BEFORE: u64 = 32;
+const INSTRUCTIONS_AFTER: u64 = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum DisassemblyRow {
+    /// The source line the following instructions were compiled from.
+    Source {
+        location: SharedString,
+        text: Option<SharedString>,
+    },
+    Instruction(DisassembledInstruction),
+}
+
+/// Shows the machine instructions around the instruction pointer of the
+/// selected stack frame, interleaved with the source lines they belong to.
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    stack_frame_list: Entity<StackFrameList>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    rows: Vec<DisassemblyRow>,
+    /// The address of the instruction the selected frame is stopped at.
+    instruction_pointer: Option<String>,
+    selected_ix: Option<usize>,
+    error: Option<SharedString>,
+    _load_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_) => this.reload(cx),
+                StackFrameListEvent::BuiltEntries => {}
+            }),
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_) | SessionEvent::HistoricSnapshotSelected => {
+                    this.reload(cx)
+                }
+                _ => {}
+            }),
+            // Picks up instruction breakpoints toggled elsewhere.
+            cx.observe(&session, |_, _, cx| cx.notify()),
+        ];
+
+        let mut this = Self {
+            session,
+            stack_frame_list,
+            workspace,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            rows: Vec::new(),
+            instruction_pointer: None,
+            selected_ix: None,
+            error: None,
+            _load_task: Task::ready(()),
+            _subscriptions,
+        };
+        this.reload(cx);
+        this
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let instruction_pointer = self
+            .stack_frame_list
+            .read(cx)
+            .opened_stack_frame()
+            .and_then(|frame| frame.instruction_pointer_reference.clone());
+        let Some(instruction_pointer) = instruction_pointer else {
+            self.rows.clear();
+            self.instruction_pointer = None;
+            self.selected_ix = None;
+            self.error = Some("The selected frame has no instruction pointer".into());
+            self._load_task = Task::ready(());
+            cx.notify();
+            return;
+        };
+        if self.instruction_pointer.as_ref() == Some(&instruction_pointer) && !self.rows.is_empty()
+        {
+            return;
+        }
+
+        let disassemble = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                instruction_pointer.clone(),
+                -(INSTRUCTIONS_BEFORE as i64),
+                INSTRUCTIONS_BEFORE + INSTRUCTIONS_AFTER,
+                cx,
+            )
+        });
+        let fs = self
+            .workspace
+            .read_with(cx, |workspace, cx| {
+                workspace.project().read(cx).fs().clone()
+            })
+            .ok();
+
+        self._load_task = cx.spawn(async move |this, cx| {
+            let Some(instructions) = disassemble.await else {
+                this.update(cx, |this, cx| {
+                    this.rows.clear();
+                    this.error = Some("Failed to disassemble the selected frame".into());
+                    cx.notify();
+                })
+                .ok();
+                return;
+            };
+
+            let mut sources = HashMap::default();
+            if let Some(fs) = fs {
+                for path in source_paths(&instructions) {
+                    if let Some(text) = fs.load(Path::new(path.as_ref())).await.log_err() {
+                        sources.insert(path, text);
+                    }
+                }
+            }
+            let rows = interleave_source(instructions, &sources);
+
+            this.update(cx, |this, cx| {
+                let current_ix = rows.iter().position(|row| match row {
+                    DisassemblyRow::Instruction(instruction) => {
+                        same_address(&instruction.address, &instruction_pointer)
+                    }
+                    DisassemblyRow::Source { .. } => false,
+                });
+                this.rows = rows;
+                this.instruction_pointer = Some(instruction_pointer);
+                this.selected_ix = current_ix;
+                this.error = None;
+                if let Some(ix) = current_ix {
+                    this.scroll_handle
+                        .scroll_to_item(ix, ScrollStrategy::Center);
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    #[cfg(test)]
+    pub(crate) fn rows(&self) -> &[DisassemblyRow] {
+        &self.rows
+    }
+
+    fn toggle_breakpoint(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(DisassemblyRow::Instruction(instruction)) = self.rows.get(ix) else {
+            return;
+        };
+        let instruction_reference = instruction.address.clone();
+        self.session.update(cx, |session, cx| {
+            session.toggle_instruction_breakpoint(instruction_reference, cx);
+        });
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_ix {
+            self.toggle_breakpoint(ix, cx);
+        }
+    }
+
+    fn select_ix(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        self.selected_ix = ix;
+        if let Some(ix) = ix {
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _window: &mut Window, cx: &mut Context<Self>) {
+        let ix = match self.selected_ix {
+            _ if self.rows.is_empty() => None,
+            None => Some(0),
+            Some(ix) => Some((ix + 1) % self.rows.len()),
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ix = match self.selected_ix {
+            _ if self.rows.is_empty() => None,
+            None => Some(self.rows.len() - 1),
+            Some(ix) => Some((ix + self.rows.len() - 1) % self.rows.len()),
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn render_row(&mut self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        match &self.rows[ix] {
+            DisassemblyRow::Source { location, text } => h_flex()
+                .id(("disassembly-source", ix))
+                .w_full()
+                .pl_5()
+                .gap_2()
+                .child(
+                    Label::new(location.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Accent),
+                )
+                .when_some(text.clone(), |this, text| {
+                    this.child(
+                        Label::new(text)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx)
+                            .single_line(),
+                    )
+                })
+                .into_any_element(),
+            DisassemblyRow::Instruction(instruction) => {
+                let is_current = self
+                    .instruction_pointer
+                    .as_ref()
+                    .is_some_and(|pointer| same_address(&instruction.address, pointer));
+                let has_breakpoint = self
+                    .session
+                    .read(cx)
+                    .has_instruction_breakpoint(&instruction.address);
+                let colors = cx.theme().colors();
+
+                h_flex()
+                    .id(("disassembly-instruction", ix))
+                    .group("disassembly-instruction")
+                    .w_full()
+                    .gap_2()
+                    .font_buffer(cx)
+                    .text_buffer(cx)
+                    .when(is_current, |this| {
+                        this.bg(colors.editor_debugger_active_line_background)
+                    })
+                    .when(Some(ix) == self.selected_ix && !is_current, |this| {
+                        this.bg(colors.element_hover)
+                    })
+                    .hover(|style| style.bg(colors.element_hover))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.selected_ix = Some(ix);
+                        cx.notify();
+                    }))
+                    .child(
+                        div()
+                            .id(("disassembly-breakpoint", ix))
+                            .flex_none()
+                            .w_4()
+                            .cursor_pointer()
+                            .child(
+                                Icon::new(IconName::DebugBreakpoint)
+                                    .size(IconSize::XSmall)
+                                    .color(Color::Debugger),
+                            )
+                            .when(!has_breakpoint, |this| {
+                                this.visible_on_hover("disassembly-instruction")
+                            })
+                            .tooltip(Tooltip::text(if has_breakpoint {
+                                "Remove Instruction Breakpoint"
+                            } else {
+                                "Set Instruction Breakpoint"
+                            }))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                cx.stop_propagation();
+                                this.toggle_breakpoint(ix, cx);
+                            })),
+                    )
+                    .child(
+                        Label::new(instruction.address.clone())
+                            .color(if is_current {
+                                Color::Default
+                            } else {
+                                Color::Muted
+                            })
+                            .buffer_font(cx),
+                    )
+                    .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                        this.child(
+                            div()
+                                .flex_none()
+                                .w(rems(10.))
+                                .overflow_hidden()
+                                .child(Label::new(bytes).color(Color::Muted).buffer_font(cx)),
+                        )
+                    })
+                    .child(
+                        Label::new(instruction.instruction.clone())
+                            .buffer_font(cx)
+                            .single_line(),
+                    )
+                    .when_some(instruction.symbol.clone(), |this, symbol| {
+                        this.child(
+                            Label::new(format!("<{symbol}>"))
+                                .color(Color::Muted)
+                                .buffer_font(cx)
+                                .single_line(),
+                        )
+                    })
+                    .into_any_element()
+            }
+        }
+    }
+
+    fn render_list(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "disassembly-view",
+            self.rows.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range.map(|ix| this.render_row(ix, cx)).collect()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .size_full()
+    }
+}
+
+/// Whether two memory references point at the same address, regardless of how
+/// the adapter formats them.
+fn same_address(a: &str, b: &str) -> bool {
+    fn parse(address: &str) -> Option<u64> {
+        let address = address.trim();
+        match address
+            .strip_prefix("0x")
+            .or_else(|| address.strip_prefix("0X"))
+        {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => address.parse().ok(),
+        }
+    }
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn source_paths(instructions: &[DisassembledInstruction]) -> Vec<Arc<str>> {
+    let mut paths: Vec<Arc<str>> = Vec::new();
+    for path in instructions
+        .iter()
+        .filter_map(|instruction| instruction.location.as_ref()?.path.as_deref())
+    {
+        if !paths.iter().any(|known| known.as_ref() == path) {
+            paths.push(path.into());
+        }
+    }
+    paths
+}
+
+/// Inserts a source row before each run of instructions compiled from the same
+/// line. Adapters may omit the location of an instruction when it matches the
+/// previous one's, so the last seen location carries over.
+pub(crate) fn interleave_source(
+    instructions: Vec<DisassembledInstruction>,
+    sources: &HashMap<Arc<str>, String>,
+) -> Vec<DisassemblyRow> {
+    let mut rows = Vec::with_capacity(instructions.len());
+    let mut current_source: Option<dap::Source> = None;
+    let mut last_line = None;
+
+    for instruction in instructions {
+        if let Some(location) = &instruction.location {
+            current_source = Some(location.clone());
+        }
+        if let Some(line) = instruction.line {
+            let source = current_source.as_ref();
+            let path = source.and_then(|source| source.path.as_deref());
+            let line_key = (path.map(str::to_string), line);
+            if last_line.as_ref() != Some(&line_key) {
+                let name = source
+                    .and_then(|source| source.name.clone())
+                    .or_else(|| {
+                        path.and_then(|path| Path::new(path).file_name())
+                            .map(|name| name.to_string_lossy().into_owned())
+                    })
+                    .unwrap_or_default();
+                let text = path
+                    .and_then(|path| sources.get(path))
+                    .and_then(|text| text.lines().nth(line.saturating_sub(1) as usize))
+                    .map(|text| SharedString::from(text.trim().to_string()));
+                rows.push(DisassemblyRow::Source {
+                    location: format!("{name}:{line}").into(),
+                    text,
+                });
+                last_line = Some(line_key);
+            }
+        }
+        rows.push(DisassemblyRow::Instruction(instruction));
+    }
+    rows
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let error = self.rows.is_empty().then(|| self.error.clone()).flatten();
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .key_context("DisassemblyView")
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .p_1()
+            .map(|this| match error {
+                Some(error) => this.child(
+                    h_flex()
+                        .size_full()
+                        .justify_center()
+                        .child(Label::new(error).color(Color::Muted)),
+                ),
+                None => this
+                    .child(self.render_list(window, cx))
+                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+            })
+    }
+}
@@ -21,6 +21,8 @@ mod dap_logger;
 #[cfg(test)]
 mod debugger_panel;
 #[cfg(test)]
+mod disassembly_view;
+#[cfg(test)]
 mod inline_values;
 #[cfg(test)]
 mod module_list;
@@ -29,6 +31,8 @@ mod new_process_modal;
 #[cfg(test)]
 mod persistence;
 #[cfg(test)]
+mod reverse_debugging;
+#[cfg(test)]
 mod stack_frame_list;
 #[cfg(test)]
 mod variable_list;
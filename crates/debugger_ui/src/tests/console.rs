@@ -3,11 +3,16 @@ use crate::{
     tests::{active_debug_session_panel, start_debug_session},
     *,
 };
-use dap::requests::StackTrace;
+use dap::requests::{SetBreakpoints, StackTrace};
 use editor::{DisplayPoint, display_map::DisplayRow};
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
-use project::{FakeFs, Project};
+use language::Point;
+use project::{
+    FakeFs, Project,
+    debugger::breakpoint_store::{Breakpoint, BreakpointEditAction, BreakpointWithPosition},
+};
 use serde_json::json;
+use std::{path::Path, sync::Arc};
 use tests::{init_test, init_test_workspace};
 use util::path;
 
@@ -357,6 +362,108 @@ async fn test_escape_code_processing(executor: BackgroundExecutor, cx: &mut Test
         .unwrap();
 }
 
+#[gpui::test]
+async fn test_logpoint_output_channel(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "fn main() {\n    let x = 1;\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/project/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    breakpoint_store.update(cx, |store, cx| {
+        let position = buffer.read(cx).anchor_before(Point::new(1, 0));
+        store.toggle_breakpoint(
+            buffer.clone(),
+            BreakpointWithPosition {
+                position,
+                bp: Breakpoint::new_log("x = {x}"),
+            },
+            BreakpointEditAction::Toggle,
+            cx,
+        );
+    });
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<SetBreakpoints, _>(|_, _| {
+            Ok(dap::SetBreakpointsResponse {
+                breakpoints: Vec::default(),
+            })
+        });
+    })
+    .unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    let output = |output: &str, line: Option<u64>| {
+        dap::messages::Events::Output(dap::OutputEvent {
+            category: Some(dap::OutputEventCategory::Console),
+            output: output.to_string(),
+            data: None,
+            variables_reference: None,
+            source: Some(dap::Source {
+                name: Some("main.rs".into()),
+                path: Some(path!("/project/main.rs").into()),
+                source_reference: None,
+                presentation_hint: None,
+                origin: None,
+                sources: None,
+                adapter_data: None,
+                checksums: None,
+            }),
+            line,
+            column: None,
+            group: None,
+            location_reference: None,
+        })
+    };
+    client.fake_event(output("x = 1\n", Some(2))).await;
+    client.fake_event(output("program output\n", Some(3))).await;
+    client.fake_event(output("x = 2\n", Some(2))).await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+    running_state.update(cx, |_, cx| {
+        cx.refresh_windows();
+    });
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| {
+        let console = state.console().read(cx);
+        assert_eq!(console.editor().read(cx).text(cx), "program output\n");
+        assert_eq!(
+            console.logpoints_editor().read(cx).text(cx),
+            "x = 1\nx = 2\n"
+        );
+    });
+    session.read_with(cx, |session, _| {
+        assert_eq!(
+            session.breakpoint_hit_count(&Arc::from(Path::new(path!("/project/main.rs"))), 1),
+            2
+        );
+    });
+}
+
 // #[gpui::test]
 // async fn test_grouped_output(executor: BackgroundExecutor, cx: &mut TestAppContext) {
 //     init_test(cx);
@@ -0,0 +1,292 @@
+#![expect(clippy::result_large_err)]
+use crate::tests::{
+    active_debug_session_panel, init_test, init_test_workspace, start_debug_session,
+};
+use dap::{
+    StackFrame, SteppingGranularity,
+    requests::{
+        Goto, GotoTargets, Initialize, ReverseContinue, Scopes, SetBreakpoints, StackTrace,
+        StepBack, Threads,
+    },
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use parking_lot::Mutex;
+use project::{
+    FakeFs, Project,
+    debugger::session::{SessionEvent, ThreadId, ThreadStatus},
+};
+use serde_json::json;
+use std::{path::Path, sync::Arc};
+use unindent::Unindent as _;
+use util::path;
+
+fn frame(id: u64, name: &str, line: u64) -> StackFrame {
+    StackFrame {
+        id,
+        name: name.into(),
+        source: Some(dap::Source {
+            name: Some("main.rs".into()),
+            path: Some(path!("/project/main.rs").into()),
+            source_reference: None,
+            presentation_hint: None,
+            origin: None,
+            sources: None,
+            adapter_data: None,
+            checksums: None,
+        }),
+        line,
+        column: 1,
+        end_line: None,
+        end_column: None,
+        can_restart: None,
+        instruction_pointer_reference: None,
+        module_id: None,
+        presentation_hint: None,
+    }
+}
+
+fn stopped(reason: dap::StoppedEventReason) -> dap::messages::Events {
+    dap::messages::Events::Stopped(dap::StoppedEvent {
+        reason,
+        description: None,
+        thread_id: Some(1),
+        preserve_focus_hint: None,
+        text: None,
+        all_threads_stopped: None,
+        hit_breakpoint_ids: None,
+    })
+}
+
+#[gpui::test]
+async fn test_reverse_debugging(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": r#"
+                fn main() {
+                    helper();
+                }
+                fn helper() {
+                    let x = 1;
+                }
+            "#.unindent(),
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_step_back: Some(true),
+                supports_goto_targets_request: Some(true),
+                supports_stepping_granularity: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let stack_frames = Arc::new(Mutex::new(vec![frame(2, "helper", 5), frame(1, "main", 2)]));
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+    client.on_request::<StackTrace, _>({
+        let stack_frames = stack_frames.clone();
+        move |_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: stack_frames.lock().clone(),
+                total_frames: None,
+            })
+        }
+    });
+
+    let reverse_continues = Arc::new(Mutex::new(0));
+    client.on_request::<ReverseContinue, _>({
+        let reverse_continues = reverse_continues.clone();
+        move |_, args| {
+            assert_eq!(args.thread_id, 1);
+            *reverse_continues.lock() += 1;
+            Ok(())
+        }
+    });
+    let breakpoint_lines = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<SetBreakpoints, _>({
+        let breakpoint_lines = breakpoint_lines.clone();
+        move |_, args| {
+            *breakpoint_lines.lock() = args
+                .breakpoints
+                .unwrap_or_default()
+                .iter()
+                .map(|breakpoint| breakpoint.line)
+                .collect::<Vec<_>>();
+            Ok(dap::SetBreakpointsResponse {
+                breakpoints: Vec::default(),
+            })
+        }
+    });
+    let step_backs = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<StepBack, _>({
+        let step_backs = step_backs.clone();
+        move |_, args| {
+            step_backs.lock().push(args.granularity);
+            Ok(())
+        }
+    });
+    client.on_request::<GotoTargets, _>(move |_, args| {
+        assert_eq!(args.source.path.as_deref(), Some(path!("/project/main.rs")));
+        assert_eq!(args.line, 5);
+        Ok(dap::GotoTargetsResponse {
+            targets: vec![dap::GotoTarget {
+                id: 7,
+                label: "let x = 1;".into(),
+                line: 5,
+                column: None,
+                end_line: None,
+                end_column: None,
+                instruction_pointer_reference: None,
+            }],
+        })
+    });
+    let goto_targets = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<Goto, _>({
+        let goto_targets = goto_targets.clone();
+        move |_, args| {
+            goto_targets.lock().push(args.target_id);
+            Ok(())
+        }
+    });
+
+    client
+        .fake_event(stopped(dap::StoppedEventReason::Pause))
+        .await;
+    cx.run_until_parked();
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .read_with(cx, |item, _| item.running_state().clone());
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| state.reverse_continue_thread(cx));
+    running_state.update(cx, |state, cx| {
+        assert_eq!(state.thread_status(cx), Some(ThreadStatus::Running));
+    });
+    cx.run_until_parked();
+    assert_eq!(*reverse_continues.lock(), 1);
+
+    client
+        .fake_event(stopped(dap::StoppedEventReason::Pause))
+        .await;
+    cx.run_until_parked();
+
+    // Stepping back out of `helper` runs backwards to the line that called it.
+    running_state.update(cx, |state, cx| state.reverse_step_out(cx));
+    cx.run_until_parked();
+    assert_eq!(*breakpoint_lines.lock(), vec![2]);
+    assert_eq!(*reverse_continues.lock(), 2);
+
+    *stack_frames.lock() = vec![frame(1, "main", 2)];
+    client
+        .fake_event(stopped(dap::StoppedEventReason::Breakpoint))
+        .await;
+    cx.run_until_parked();
+    assert!(
+        breakpoint_lines.lock().is_empty(),
+        "The temporary breakpoint should be removed once the thread stops"
+    );
+
+    // Stepping back into the call from the line after it enters `helper`.
+    *stack_frames.lock() = vec![frame(1, "main", 3)];
+    client
+        .fake_event(stopped(dap::StoppedEventReason::Pause))
+        .await;
+    cx.run_until_parked();
+
+    let stops = Arc::new(Mutex::new(0));
+    let _subscription = cx.update(|_, cx| {
+        let stops = stops.clone();
+        cx.subscribe(&session, move |_, event, _| {
+            if let SessionEvent::Stopped(_) = event {
+                *stops.lock() += 1;
+            }
+        })
+    });
+    let history_len = session.read_with(cx, |session, _| session.historic_snapshots().len());
+
+    running_state.update(cx, |state, cx| state.reverse_step_in(cx));
+    cx.run_until_parked();
+    *stack_frames.lock() = vec![frame(1, "main", 2)];
+    client
+        .fake_event(stopped(dap::StoppedEventReason::Step))
+        .await;
+    cx.run_until_parked();
+    *stack_frames.lock() = vec![frame(2, "helper", 6), frame(1, "main", 2)];
+    client
+        .fake_event(stopped(dap::StoppedEventReason::Step))
+        .await;
+    cx.run_until_parked();
+    assert_eq!(
+        *step_backs.lock(),
+        vec![
+            Some(SteppingGranularity::Instruction),
+            Some(SteppingGranularity::Instruction)
+        ],
+        "Stepping back should stop once the thread is inside the callee"
+    );
+    assert_eq!(
+        *stops.lock(),
+        1,
+        "Only the stop inside the callee should be reported"
+    );
+    assert_eq!(
+        session.read_with(cx, |session, _| session.historic_snapshots().len()),
+        history_len + 1,
+        "Reverse stepping in should be recorded in the history as a single step"
+    );
+
+    // Continuing while stepping back in cancels the emulation.
+    step_backs.lock().clear();
+    *stack_frames.lock() = vec![frame(1, "main", 3)];
+    client
+        .fake_event(stopped(dap::StoppedEventReason::Pause))
+        .await;
+    cx.run_until_parked();
+    running_state.update(cx, |state, cx| state.reverse_step_in(cx));
+    cx.run_until_parked();
+    running_state.update(cx, |state, cx| state.reverse_continue_thread(cx));
+    cx.run_until_parked();
+    *stack_frames.lock() = vec![frame(1, "main", 2)];
+    client
+        .fake_event(stopped(dap::StoppedEventReason::Step))
+        .await;
+    cx.run_until_parked();
+    assert_eq!(
+        *step_backs.lock(),
+        vec![Some(SteppingGranularity::Instruction)],
+        "No further steps should be taken once the user continues"
+    );
+
+    session
+        .update(cx, |session, cx| {
+            session.go_to_position(
+                Arc::from(Path::new(path!("/project/main.rs"))),
+                4,
+                ThreadId(1),
+                cx,
+            )
+        })
+        .await;
+    assert_eq!(*goto_targets.lock(), vec![7]);
+}
@@ -63,6 +63,7 @@ pub mod cursor_excerpt;
 pub mod example_spec;
 pub mod fim;
 mod license_detection;
+mod lsp_edit_prediction_delegate;
 pub mod mercury;
 pub mod metrics;
 pub mod ollama;
@@ -91,6 +92,7 @@ pub use crate::prediction::EditPredictionId;
 use crate::prediction::EditPredictionResult;
 pub use capture_example::capture_example;
 pub use language_model::ApiKeyState;
+pub use lsp_edit_prediction_delegate::LspEditPredictionDelegate;
 pub use telemetry_events::EditPredictionRating;
 pub use zed_edit_prediction_delegate::ZedEditPredictionDelegate;
 
@@ -2113,7 +2115,8 @@ fn is_ep_store_provider(provider: EditPredictionProvider) -> bool {
         | EditPredictionProvider::Experimental(_) => true,
         EditPredictionProvider::None
         | EditPredictionProvider::Copilot
-        | EditPredictionProvider::Codestral => false,
+        | EditPredictionProvider::Codestral
+        | EditPredictionProvider::LanguageServer => false,
     }
 }
 
@@ -2152,7 +2155,8 @@ impl EditPredictionStore {
                 EditPredictionProvider::OpenAiCompatibleApi => (false, 2),
                 EditPredictionProvider::None
                 | EditPredictionProvider::Copilot
-                | EditPredictionProvider::Codestral => {
+                | EditPredictionProvider::Codestral
+                | EditPredictionProvider::LanguageServer => {
                     log::error!("queue_prediction_refresh called with non-store provider");
                     return;
                 }
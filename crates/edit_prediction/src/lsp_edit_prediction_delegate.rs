@@ -0,0 +1,498 @@
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use anyhow::{Context as _, Result};
+use edit_prediction_types::{
+    EditPrediction, EditPredictionDelegate, EditPredictionDiscardReason, EditPredictionIconSet,
+    interpolate_edits,
+};
+use gpui::{App, AsyncApp, Entity, Task, prelude::*};
+use language::{
+    Anchor, Buffer, BufferSnapshot, EditPreview, OffsetRangeExt as _, ToOffset as _,
+    ToPointUtf16 as _, point_to_lsp, range_from_lsp,
+};
+use lsp::{
+    LanguageServer,
+    inline_completion::{
+        InlineCompletionContext, InlineCompletionItem, InlineCompletionParams,
+        InlineCompletionRequest, InlineCompletionText, InlineCompletionTriggerKind,
+    },
+};
+use project::{Project, lsp_command::file_path_to_lsp_url, project_settings::ProjectSettings};
+use settings::Settings as _;
+use text::Bias;
+use ui::IconName;
+use util::ResultExt as _;
+
+pub const LSP_EDIT_PREDICTION_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(75);
+
+struct CurrentCompletion {
+    snapshot: BufferSnapshot,
+    edits: Arc<[(Range<Anchor>, Arc<str>)]>,
+    edit_preview: EditPreview,
+    server: Arc<LanguageServer>,
+    /// Sent back to the server once the completion is accepted.
+    command: Option<lsp::Command>,
+}
+
+impl CurrentCompletion {
+    /// Attempts to adjust the edits based on changes made to the buffer since the completion was generated.
+    /// Returns None if the user's edits conflict with the predicted edits.
+    fn interpolate(&self, new_snapshot: &BufferSnapshot) -> Option<Vec<(Range<Anchor>, Arc<str>)>> {
+        interpolate_edits(&self.snapshot, new_snapshot, &self.edits)
+    }
+}
+
+/// Sources edit predictions from the first language server of the buffer that provides
+/// `textDocument/inlineCompletion`.
+///
+/// Only language servers running locally are queried.
+pub struct LspEditPredictionDelegate {
+    project: Entity<Project>,
+    pending_request: Option<Task<Result<()>>>,
+    current_completion: Option<CurrentCompletion>,
+}
+
+impl LspEditPredictionDelegate {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self {
+            project,
+            pending_request: None,
+            current_completion: None,
+        }
+    }
+
+    async fn fetch_completion(
+        project: Entity<Project>,
+        buffer: Entity<Buffer>,
+        cursor_position: Anchor,
+        cx: &mut AsyncApp,
+    ) -> Result<Option<CurrentCompletion>> {
+        let Some((server, snapshot, params, request_timeout)) = cx.update(|cx| {
+            let server = project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+                buffer.update(cx, |buffer, cx| {
+                    lsp_store.inline_completion_server_for_local_buffer(buffer, cx)
+                })
+            })?;
+            let snapshot = buffer.read(cx).snapshot();
+            let abs_path = snapshot.file()?.as_local()?.abs_path(cx);
+            let params = InlineCompletionParams {
+                text_document_position: lsp::TextDocumentPositionParams {
+                    text_document: lsp::TextDocumentIdentifier {
+                        uri: file_path_to_lsp_url(&abs_path).log_err()?,
+                    },
+                    position: point_to_lsp(cursor_position.to_point_utf16(&snapshot)),
+                },
+                context: InlineCompletionContext {
+                    trigger_kind: InlineCompletionTriggerKind::Automatic,
+                    selected_completion_info: None,
+                },
+                work_done_progress_params: Default::default(),
+            };
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            Some((server, snapshot, params, request_timeout))
+        }) else {
+            return Ok(None);
+        };
+
+        let response = server
+            .request::<InlineCompletionRequest>(params, request_timeout)
+            .await
+            .into_response()
+            .context("inline completion LSP request")?;
+        let Some((edit, command)) = response
+            .map(|response| response.into_items())
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|item| {
+                let edit = completion_edit(&item, cursor_position, &snapshot)?;
+                Some((edit, item.command))
+            })
+        else {
+            return Ok(None);
+        };
+
+        let edits: Arc<[(Range<Anchor>, Arc<str>)]> = vec![edit].into();
+        let edit_preview = buffer
+            .read_with(cx, |buffer, cx| buffer.preview_edits(edits.clone(), cx))
+            .await;
+        Ok(Some(CurrentCompletion {
+            snapshot,
+            edits,
+            edit_preview,
+            server,
+            command,
+        }))
+    }
+}
+
+impl EditPredictionDelegate for LspEditPredictionDelegate {
+    fn name() -> &'static str {
+        "language-server"
+    }
+
+    fn display_name() -> &'static str {
+        "Language Server"
+    }
+
+    fn show_predictions_in_menu() -> bool {
+        true
+    }
+
+    fn icons(&self, _cx: &App) -> EditPredictionIconSet {
+        EditPredictionIconSet::new(IconName::ZedPredict)
+            .with_disabled(IconName::ZedPredictDisabled)
+            .with_error(IconName::ZedPredictError)
+    }
+
+    fn is_enabled(&self, _buffer: &Entity<Buffer>, _cursor_position: Anchor, cx: &App) -> bool {
+        self.project.read(cx).is_local()
+    }
+
+    fn is_refreshing(&self, _cx: &App) -> bool {
+        self.pending_request.is_some()
+    }
+
+    fn refresh(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cursor_position: Anchor,
+        debounce: bool,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(current_completion) = self.current_completion.as_ref()
+            && current_completion
+                .interpolate(&buffer.read(cx).snapshot())
+                .is_some()
+        {
+            return;
+        }
+
+        let project = self.project.clone();
+        self.pending_request = Some(cx.spawn(async move |this, cx| {
+            if debounce {
+                cx.background_executor()
+                    .timer(LSP_EDIT_PREDICTION_DEBOUNCE_TIMEOUT)
+                    .await;
+            }
+
+            let completion =
+                match Self::fetch_completion(project, buffer, cursor_position, cx).await {
+                    Ok(completion) => completion,
+                    Err(e) => {
+                        this.update(cx, |this, cx| {
+                            this.pending_request = None;
+                            cx.notify();
+                        })?;
+                        return Err(e);
+                    }
+                };
+            this.update(cx, |this, cx| {
+                this.pending_request = None;
+                this.current_completion = completion;
+                cx.notify();
+            })?;
+            Ok(())
+        }));
+    }
+
+    fn accept(&mut self, cx: &mut Context<Self>) {
+        self.pending_request = None;
+        let Some(completion) = self.current_completion.take() else {
+            return;
+        };
+        let Some(command) = completion.command else {
+            return;
+        };
+        let request_timeout = ProjectSettings::get_global(cx)
+            .global_lsp_settings
+            .get_request_timeout();
+        cx.background_spawn(async move {
+            completion
+                .server
+                .request::<lsp::request::ExecuteCommand>(
+                    lsp::ExecuteCommandParams {
+                        command: command.command,
+                        arguments: command.arguments.unwrap_or_default(),
+                        ..Default::default()
+                    },
+                    request_timeout,
+                )
+                .await
+                .into_response()
+                .context("inline completion command")?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn discard(&mut self, _reason: EditPredictionDiscardReason, _cx: &mut Context<Self>) {
+        self.pending_request = None;
+        self.current_completion = None;
+    }
+
+    fn suggest(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        _cursor_position: Anchor,
+        cx: &mut Context<Self>,
+    ) -> Option<EditPrediction> {
+        let current_completion = self.current_completion.as_ref()?;
+        let buffer = buffer.read(cx);
+        if current_completion.snapshot.remote_id() != buffer.remote_id() {
+            return None;
+        }
+        let edits = current_completion.interpolate(&buffer.snapshot())?;
+        if edits.is_empty() {
+            return None;
+        }
+        Some(EditPrediction::Local {
+            id: None,
+            edits,
+            cursor_position: None,
+            edit_preview: Some(current_completion.edit_preview.clone()),
+        })
+    }
+}
+
+/// Converts an inline completion item into a single edit, trimming the text the buffer already
+/// contains at either end of the replaced range so that only the new text is shown.
+///
+/// Snippet completions are skipped, as their placeholders cannot be displayed as ghost text.
+fn completion_edit(
+    item: &InlineCompletionItem,
+    cursor_position: Anchor,
+    snapshot: &BufferSnapshot,
+) -> Option<(Range<Anchor>, Arc<str>)> {
+    let InlineCompletionText::Plain(text) = &item.insert_text else {
+        return None;
+    };
+    let mut range = match item.range {
+        Some(range) => {
+            let range = range_from_lsp(range);
+            (snapshot.clip_point_utf16(range.start, Bias::Left)
+                ..snapshot.clip_point_utf16(range.end, Bias::Left))
+                .to_offset(snapshot)
+        }
+        None => {
+            let offset = cursor_position.to_offset(snapshot);
+            offset..offset
+        }
+    };
+
+    let prefix_len = common_prefix(snapshot.chars_for_range(range.clone()), text.chars());
+    range.start += prefix_len;
+    let suffix_len = common_prefix(
+        snapshot.reversed_chars_for_range(range.clone()),
+        text[prefix_len..].chars().rev(),
+    );
+    range.end -= suffix_len;
+    let text = &text[prefix_len..text.len() - suffix_len];
+    if text.trim().is_empty() {
+        return None;
+    }
+
+    Some((
+        snapshot.anchor_after(range.start)..snapshot.anchor_after(range.end),
+        Arc::from(text),
+    ))
+}
+
+fn common_prefix<T1: Iterator<Item = char>, T2: Iterator<Item = char>>(a: T1, b: T2) -> usize {
+    a.zip(b)
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::test::editor_lsp_test_context::EditorLspTestContext;
+    use futures::StreamExt;
+    use gpui::{BackgroundExecutor, TestAppContext};
+    use indoc::indoc;
+    use lsp::inline_completion::{InlineCompletionResponse, StringValue};
+    use serde_json::json;
+    use settings::SettingsStore;
+
+    #[gpui::test]
+    async fn test_lsp_edit_predictions(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let mut cx = EditorLspTestContext::new_rust(Default::default(), cx).await;
+        cx.lsp
+            .request::<lsp::request::RegisterCapability>(
+                lsp::RegistrationParams {
+                    registrations: vec![lsp::Registration {
+                        id: "inline-completion".to_string(),
+                        method: "textDocument/inlineCompletion".to_string(),
+                        register_options: None,
+                    }],
+                },
+                lsp::DEFAULT_LSP_REQUEST_TIMEOUT,
+            )
+            .await
+            .into_response()
+            .unwrap();
+        let project = cx.update_workspace(|workspace, _, _| workspace.project().clone());
+        let provider = cx.new(|_| LspEditPredictionDelegate::new(project));
+        cx.update_editor(|editor, window, cx| {
+            editor.set_edit_prediction_provider(Some(provider), window, cx)
+        });
+
+        cx.set_state(indoc! {"
+            fn main() {
+                let greeting = ˇ;
+            }
+        "});
+        executor.advance_clock(LSP_EDIT_PREDICTION_DEBOUNCE_TIMEOUT);
+        cx.run_until_parked();
+
+        // The server repeats the whole line. Only the text missing around the cursor is
+        // predicted, and the snippet listed first is skipped.
+        let mut inline_completion_requests = cx
+            .set_request_handler::<InlineCompletionRequest, _, _>(|url, params, _| async move {
+                assert_eq!(params.text_document_position.text_document.uri, url);
+                assert_eq!(
+                    params.text_document_position.position,
+                    lsp::Position::new(1, 20)
+                );
+                Ok(Some(InlineCompletionResponse::Array(vec![
+                    InlineCompletionItem {
+                        insert_text: InlineCompletionText::Snippet(StringValue {
+                            kind: "snippet".to_string(),
+                            value: "hello($0)".to_string(),
+                        }),
+                        filter_text: None,
+                        range: None,
+                        command: None,
+                    },
+                    InlineCompletionItem {
+                        insert_text: InlineCompletionText::Plain(
+                            "    let greeting = hello();".to_string(),
+                        ),
+                        filter_text: None,
+                        range: Some(lsp::Range::new(
+                            lsp::Position::new(1, 0),
+                            lsp::Position::new(1, 21),
+                        )),
+                        command: Some(lsp::Command {
+                            title: "Accept".to_string(),
+                            command: "inline-completion.accept".to_string(),
+                            arguments: Some(vec![json!(1)]),
+                        }),
+                    },
+                ])))
+            });
+
+        cx.simulate_keystroke("h");
+        executor.advance_clock(LSP_EDIT_PREDICTION_DEBOUNCE_TIMEOUT);
+        assert!(inline_completion_requests.next().await.is_some());
+        cx.run_until_parked();
+        cx.update_editor(|editor, _, cx| {
+            assert!(editor.has_active_edit_prediction());
+            assert_eq!(
+                editor.display_text(cx),
+                "fn main() {\n    let greeting = hello();\n}\n"
+            );
+            assert_eq!(editor.text(cx), "fn main() {\n    let greeting = h;\n}\n");
+        });
+
+        // Typing the predicted text interpolates the prediction instead of requesting a new one.
+        cx.simulate_keystroke("e");
+        executor.advance_clock(LSP_EDIT_PREDICTION_DEBOUNCE_TIMEOUT);
+        cx.run_until_parked();
+        assert!(inline_completion_requests.try_next().is_err());
+        cx.update_editor(|editor, _, cx| {
+            assert!(editor.has_active_edit_prediction());
+            assert_eq!(
+                editor.display_text(cx),
+                "fn main() {\n    let greeting = hello();\n}\n"
+            );
+            assert_eq!(editor.text(cx), "fn main() {\n    let greeting = he;\n}\n");
+        });
+
+        // Accepting the prediction runs the command of its item.
+        let mut execute_command_requests = cx
+            .set_request_handler::<lsp::request::ExecuteCommand, _, _>(|_, params, _| async move {
+                assert_eq!(params.command, "inline-completion.accept");
+                assert_eq!(params.arguments, vec![json!(1)]);
+                Ok(None)
+            });
+        cx.update_editor(|editor, window, cx| {
+            editor.accept_edit_prediction(&Default::default(), window, cx);
+            assert!(!editor.has_active_edit_prediction());
+            assert_eq!(
+                editor.text(cx),
+                "fn main() {\n    let greeting = hello();\n}\n"
+            );
+        });
+        assert!(execute_command_requests.next().await.is_some());
+    }
+
+    #[gpui::test]
+    async fn test_lsp_edit_predictions_document_selector(
+        executor: BackgroundExecutor,
+        cx: &mut TestAppContext,
+    ) {
+        init_test(cx);
+
+        let mut cx = EditorLspTestContext::new_rust(Default::default(), cx).await;
+        let project = cx.update_workspace(|workspace, _, _| workspace.project().clone());
+        let provider = cx.new(|_| LspEditPredictionDelegate::new(project));
+        cx.update_editor(|editor, window, cx| {
+            editor.set_edit_prediction_provider(Some(provider), window, cx)
+        });
+        let mut inline_completion_requests = cx
+            .set_request_handler::<InlineCompletionRequest, _, _>(|_, _, _| async move {
+                Ok(Some(InlineCompletionResponse::Array(Vec::new())))
+            });
+
+        let register = |id: &str, language: &str| lsp::RegistrationParams {
+            registrations: vec![lsp::Registration {
+                id: id.to_string(),
+                method: "textDocument/inlineCompletion".to_string(),
+                register_options: Some(json!({
+                    "documentSelector": [{ "language": language }],
+                })),
+            }],
+        };
+
+        // A registration for another language does not apply to Rust buffers.
+        cx.lsp
+            .request::<lsp::request::RegisterCapability>(
+                register("python-completions", "python"),
+                lsp::DEFAULT_LSP_REQUEST_TIMEOUT,
+            )
+            .await
+            .into_response()
+            .unwrap();
+        cx.set_state("fn main() { ˇ }");
+        cx.simulate_keystroke("h");
+        executor.advance_clock(LSP_EDIT_PREDICTION_DEBOUNCE_TIMEOUT);
+        cx.run_until_parked();
+        assert!(inline_completion_requests.try_next().is_err());
+
+        cx.lsp
+            .request::<lsp::request::RegisterCapability>(
+                register("rust-completions", "rust"),
+                lsp::DEFAULT_LSP_REQUEST_TIMEOUT,
+            )
+            .await
+            .into_response()
+            .unwrap();
+        cx.simulate_keystroke("i");
+        executor.advance_clock(LSP_EDIT_PREDICTION_DEBOUNCE_TIMEOUT);
+        assert!(inline_completion_requests.next().await.is_some());
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+        });
+    }
+}
@@ -274,6 +274,60 @@ impl Render for EditPredictionButton {
                         .with_handle(self.popover_menu_handle.clone()),
                 )
             }
+            EditPredictionProvider::LanguageServer => {
+                let enabled = self.editor_enabled.unwrap_or(true);
+                let is_local = self
+                    .project
+                    .upgrade()
+                    .is_none_or(|project| project.read(cx).is_local());
+                let this = cx.weak_entity();
+
+                let tooltip_meta = if is_local {
+                    "Powered by language servers"
+                } else {
+                    "Language server predictions are only available in local projects"
+                };
+
+                div().child(
+                    PopoverMenu::new("language-server")
+                        .menu(move |window, cx| {
+                            this.update(cx, |this, cx| {
+                                this.build_edit_prediction_context_menu(
+                                    EditPredictionProvider::LanguageServer,
+                                    window,
+                                    cx,
+                                )
+                            })
+                            .ok()
+                        })
+                        .anchor(Corner::BottomRight)
+                        .trigger_with_tooltip(
+                            IconButton::new("language-server-icon", IconName::ZedPredict)
+                                .shape(IconButtonShape::Square)
+                                .when(!is_local, |this| {
+                                    this.indicator(Indicator::dot().color(Color::Warning))
+                                        .indicator_border_color(Some(
+                                            cx.theme().colors().status_bar_background,
+                                        ))
+                                })
+                                .when(is_local && !enabled, |this| {
+                                    this.indicator(Indicator::dot().color(Color::Ignored))
+                                        .indicator_border_color(Some(
+                                            cx.theme().colors().status_bar_background,
+                                        ))
+                                }),
+                            move |_window, cx| {
+                                Tooltip::with_meta(
+                                    "Edit Prediction",
+                                    Some(&ToggleMenu),
+                                    tooltip_meta,
+                                    cx,
+                                )
+                            },
+                        )
+                        .with_handle(self.popover_menu_handle.clone()),
+                )
+            }
             EditPredictionProvider::Ollama => {
                 let enabled = self.editor_enabled.unwrap_or(true);
                 let this = cx.weak_entity();
@@ -1486,6 +1540,9 @@ pub fn get_available_providers(cx: &mut App) -> Vec<EditPredictionProvider> {
         providers.push(EditPredictionProvider::Mercury);
     }
 
+    // Needs no credentials, only a language server providing inline completions.
+    providers.push(EditPredictionProvider::LanguageServer);
+
     providers
 }
 
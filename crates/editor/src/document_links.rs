@@ -0,0 +1,102 @@
+use futures::future::join_all;
+use gpui::App;
+use itertools::Itertools;
+use project::lsp_store::LspDocumentLink;
+use text::{BufferId, ToOffset as _};
+use ui::{Context, Window};
+
+use crate::{Editor, LSP_REQUEST_DEBOUNCE_TIMEOUT};
+
+impl Editor {
+    /// Fetches `textDocument/documentLink` results for the visible buffers, so that
+    /// hovering them with the link modifier held can underline and open them.
+    pub(super) fn refresh_document_links(
+        &mut self,
+        for_buffer: Option<BufferId>,
+        _window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.lsp_data_enabled() {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let multi_buffer = self.buffer().clone();
+        self.document_links
+            .retain(|buffer_id, _| multi_buffer.read(cx).buffer(*buffer_id).is_some());
+
+        let buffers_to_query = self
+            .visible_buffers(cx)
+            .into_iter()
+            .filter(|buffer| self.is_lsp_relevant(buffer.read(cx).file(), cx))
+            .chain(for_buffer.and_then(|id| self.buffer.read(cx).buffer(id)))
+            .filter(|buffer| {
+                let id = buffer.read(cx).remote_id();
+                for_buffer.is_none_or(|target| target == id)
+                    && self.registered_buffers.contains_key(&id)
+            })
+            .unique_by(|buffer| buffer.read(cx).remote_id())
+            .collect::<Vec<_>>();
+        if buffers_to_query.is_empty() {
+            return;
+        }
+
+        self.refresh_document_links_task = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(LSP_REQUEST_DEBOUNCE_TIMEOUT)
+                .await;
+
+            let Some(tasks) = editor
+                .update(cx, |_, cx| {
+                    project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+                        buffers_to_query
+                            .into_iter()
+                            .map(|buffer| {
+                                let buffer_id = buffer.read(cx).remote_id();
+                                let task = lsp_store.fetch_document_links(&buffer, cx);
+                                async move { (buffer_id, task.await) }
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .ok()
+            else {
+                return;
+            };
+
+            let results = join_all(tasks).await;
+            if results.is_empty() {
+                return;
+            }
+
+            editor
+                .update(cx, |editor, _| {
+                    editor.document_links.extend(results);
+                })
+                .ok();
+        });
+    }
+
+    /// Returns the innermost document link reported for the given position, if any.
+    pub(crate) fn document_link_at(
+        &self,
+        position: text::Anchor,
+        cx: &App,
+    ) -> Option<LspDocumentLink> {
+        let buffer = self.buffer.read(cx).buffer(position.buffer_id)?;
+        let buffer = buffer.read(cx);
+        self.document_links
+            .get(&position.buffer_id)?
+            .iter()
+            .filter(|link| {
+                link.range.start.cmp(&position, buffer).is_le()
+                    && link.range.end.cmp(&position, buffer).is_ge()
+            })
+            .min_by_key(|link| {
+                link.range.end.to_offset(buffer) - link.range.start.to_offset(buffer)
+            })
+            .cloned()
+    }
+}
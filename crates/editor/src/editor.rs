@@ -18,6 +18,7 @@ mod clangd_ext;
 pub mod code_context_menus;
 pub mod display_map;
 mod document_colors;
+mod document_links;
 mod document_symbols;
 mod editor_settings;
 mod element;
@@ -38,6 +39,7 @@ mod persistence;
 mod runnables;
 mod rust_analyzer_ext;
 pub mod scroll;
+mod selection_ranges;
 mod selections_collection;
 pub mod semantic_tokens;
 mod split;
@@ -174,7 +176,7 @@ use project::{
     git_store::GitStoreEvent,
     lsp_store::{
         BufferSemanticTokens, CacheInlayHints, CompletionDocumentation, FormatTrigger,
-        LspFormatTarget, OpenLspBufferHandle, RefreshForServer,
+        LspDocumentLink, LspFormatTarget, OpenLspBufferHandle, RefreshForServer,
     },
     project_settings::{DiagnosticSeverity, GoToDiagnosticSeverityFilter, ProjectSettings},
 };
@@ -182,6 +184,7 @@ use rand::seq::SliceRandom;
 use regex::Regex;
 use rpc::{ErrorCode, ErrorExt, proto::PeerId};
 use scroll::{Autoscroll, OngoingScroll, ScrollAnchor, ScrollManager, SharedScrollAnchor};
+use selection_ranges::LspSelectionRanges;
 use selections_collection::{MutableSelectionsCollection, SelectionsCollection};
 use serde::{Deserialize, Serialize};
 use settings::{
@@ -258,6 +261,7 @@ pub const SELECTION_HIGHLIGHT_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis
 
 pub(crate) const CODE_ACTION_TIMEOUT: Duration = Duration::from_secs(5);
 pub(crate) const FORMAT_TIMEOUT: Duration = Duration::from_secs(5);
+pub(crate) const SELECTION_RANGES_TIMEOUT: Duration = Duration::from_millis(500);
 pub(crate) const SCROLL_CENTER_TOP_BOTTOM_DEBOUNCE_TIMEOUT: Duration = Duration::from_secs(1);
 pub const LSP_REQUEST_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(50);
 
@@ -1152,6 +1156,9 @@ pub struct Editor {
     autoclose_regions: Vec<AutocloseRegion>,
     snippet_stack: InvalidationStack<SnippetState>,
     select_syntax_node_history: SelectSyntaxNodeHistory,
+    /// How many times to expand the selections once the pending LSP selection ranges arrive.
+    pending_selection_expansions: usize,
+    lsp_selection_ranges_task: Task<()>,
     ime_transaction: Option<TransactionId>,
     pub diagnostics_max_severity: DiagnosticSeverity,
     active_diagnostics: ActiveDiagnostic,
@@ -1342,6 +1349,8 @@ pub struct Editor {
     refresh_colors_task: Task<()>,
     use_document_folding_ranges: bool,
     refresh_folding_ranges_task: Task<()>,
+    document_links: HashMap<BufferId, Vec<LspDocumentLink>>,
+    refresh_document_links_task: Task<()>,
     inlay_hints: Option<LspInlayHintData>,
     folding_newlines: Task<()>,
     select_next_is_case_sensitive: Option<bool>,
@@ -2413,6 +2422,8 @@ impl Editor {
             autoclose_regions: Vec::new(),
             snippet_stack: InvalidationStack::default(),
             select_syntax_node_history: SelectSyntaxNodeHistory::default(),
+            pending_selection_expansions: 0,
+            lsp_selection_ranges_task: Task::ready(()),
             ime_transaction: None,
             active_diagnostics: ActiveDiagnostic::None,
             show_inline_diagnostics: ProjectSettings::get_global(cx).diagnostics.inline.enabled,
@@ -2594,6 +2605,8 @@ impl Editor {
             refresh_colors_task: Task::ready(()),
             use_document_folding_ranges: false,
             refresh_folding_ranges_task: Task::ready(()),
+            document_links: HashMap::default(),
+            refresh_document_links_task: Task::ready(()),
             inlay_hints: None,
             next_color_inlay_id: 0,
             post_scroll_update: Task::ready(()),
@@ -3728,6 +3741,10 @@ impl Editor {
         self.select_next_state = None;
         self.select_prev_state = None;
         self.select_syntax_node_history.try_clear();
+        if !self.select_syntax_node_history.disable_clearing {
+            // The selections moved while waiting for language server selection ranges.
+            self.pending_selection_expansions = 0;
+        }
         self.invalidate_autoclose_regions(&selection_anchors, buffer);
         self.snippet_stack.invalidate(&selection_anchors, buffer);
         self.take_rename(false, window, cx);
@@ -9306,6 +9323,13 @@ impl Editor {
 
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
 
+        let log_selection_msg = self.selection_for_logpoint(cx).map(|expression| {
+            (
+                SharedString::from(format!("Log `{expression}` Here")),
+                format!("{expression} = {{{expression}}}"),
+            )
+        });
+
         let toggle_state_msg = breakpoint.as_ref().map_or(None, |bp| match bp.1.state {
             BreakpointState::Enabled => Some("Disable"),
             BreakpointState::Disabled => Some("Enable"),
@@ -9386,6 +9410,22 @@ impl Editor {
                             .log_err();
                     }
                 })
+                .when_some(log_selection_msg, |this, (label, message)| {
+                    let breakpoint = breakpoint.clone();
+                    let weak_editor = weak_editor.clone();
+                    this.entry(label, None, move |_window, cx| {
+                        weak_editor
+                            .update(cx, |this, cx| {
+                                this.edit_breakpoint_at_anchor(
+                                    anchor,
+                                    breakpoint.as_ref().clone(),
+                                    BreakpointEditAction::EditLogMessage(message.as_str().into()),
+                                    cx,
+                                );
+                            })
+                            .log_err();
+                    })
+                })
                 .entry(condition_breakpoint_msg, None, {
                     let breakpoint = breakpoint.clone();
                     let weak_editor = weak_editor.clone();
@@ -9431,6 +9471,22 @@ impl Editor {
         })
     }
 
+    /// The text of the newest selection, when it can be interpolated into a
+    /// logpoint message as a single expression.
+    fn selection_for_logpoint(&self, cx: &mut App) -> Option<String> {
+        let snapshot = self.display_snapshot(cx);
+        let selection = self.selections.newest::<Point>(&snapshot);
+        if selection.is_empty() || selection.start.row != selection.end.row {
+            return None;
+        }
+        let expression = snapshot
+            .buffer_snapshot()
+            .text_for_range(selection.range())
+            .collect::<String>();
+        let expression = expression.trim();
+        (!expression.is_empty()).then(|| expression.to_string())
+    }
+
     fn render_breakpoint(
         &self,
         position: Anchor,
@@ -17352,6 +17408,50 @@ impl Editor {
         _: &SelectLargerSyntaxNode,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        // Repeated invocations while the language server responds expand further once it does.
+        if self.pending_selection_expansions > 0 {
+            self.pending_selection_expansions += 1;
+            return;
+        }
+
+        match self.lsp_selection_ranges(cx) {
+            Some(selection_ranges_task) => {
+                self.pending_selection_expansions = 1;
+                let mut timeout = cx
+                    .background_executor()
+                    .timer(SELECTION_RANGES_TIMEOUT)
+                    .fuse();
+                self.lsp_selection_ranges_task = cx.spawn_in(window, async move |editor, cx| {
+                    // Fall back to tree-sitter rather than waiting on a slow language server.
+                    let selection_ranges = futures::select_biased! {
+                        selection_ranges = selection_ranges_task.fuse() => selection_ranges,
+                        () = timeout => {
+                            log::warn!("timed out waiting for selection ranges");
+                            LspSelectionRanges::default()
+                        }
+                    };
+                    editor
+                        .update_in(cx, |editor, window, cx| {
+                            let expansions = mem::take(&mut editor.pending_selection_expansions);
+                            for _ in 0..expansions {
+                                editor.expand_to_larger_syntax_node(&selection_ranges, window, cx);
+                            }
+                        })
+                        .ok();
+                });
+            }
+            None => self.expand_to_larger_syntax_node(&LspSelectionRanges::default(), window, cx),
+        }
+    }
+
+    /// Grows each selection to the smallest enclosing range reported by a language server,
+    /// falling back to the enclosing tree-sitter node for selections without one.
+    fn expand_to_larger_syntax_node(
+        &mut self,
+        lsp_selection_ranges: &LspSelectionRanges,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         let Some(visible_row_count) = self.visible_row_count() else {
             return;
@@ -17375,6 +17475,32 @@ impl Editor {
             .map(|selection| {
                 let old_range = selection.start..selection.end;
 
+                let lsp_range = lsp_selection_ranges
+                    .get(&selection.id)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|range| {
+                        let range = buffer.buffer_anchor_range_to_anchor_range(range.clone())?;
+                        Some(range.start.to_offset(&buffer)..range.end.to_offset(&buffer))
+                    })
+                    .find(|range| {
+                        range.start <= old_range.start
+                            && range.end >= old_range.end
+                            && *range != old_range
+                            && !display_map.intersects_fold(range.start)
+                            && !display_map.intersects_fold(range.end)
+                    });
+                if let Some(new_range) = lsp_range {
+                    selected_larger_node = true;
+                    return Selection {
+                        id: selection.id,
+                        start: new_range.start,
+                        end: new_range.end,
+                        goal: SelectionGoal::None,
+                        reversed: selection.reversed,
+                    };
+                }
+
                 if let Some((node, _)) = buffer.syntax_ancestor(old_range.clone()) {
                     // manually select word at selection
                     if ["string_content", "inline"].contains(&node.kind()) {
@@ -17483,6 +17609,11 @@ impl Editor {
     ) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
 
+        if self.pending_selection_expansions > 0 {
+            self.pending_selection_expansions -= 1;
+            return;
+        }
+
         if let Some((mut selections, scroll_behavior, is_selection_reversed)) =
             self.select_syntax_node_history.pop()
         {
@@ -23857,6 +23988,42 @@ impl Editor {
         snapshot.line_len(buffer_row) == 0
     }
 
+    /// Rows of the file at `HEAD` covered by the newest selection, mapped through the
+    /// uncommitted changes of the buffer. Returns `None` when the editor doesn't edit a
+    /// single buffer, or when all selected lines were added since `HEAD`.
+    pub fn selected_head_rows(&self, cx: &mut App) -> Option<Range<u32>> {
+        let selection = self.selections.newest::<Point>(&self.display_snapshot(cx));
+        // A selection ending at the start of a line doesn't include that line.
+        let end_row = if selection.end.column > 0 || selection.is_empty() {
+            selection.end.row + 1
+        } else {
+            selection.end.row
+        };
+        let rows = selection.start.row..end_row.max(selection.start.row + 1);
+
+        let multi_buffer = self.buffer().read(cx);
+        let buffer_snapshot = multi_buffer.as_singleton()?.read(cx).snapshot();
+        let Some(diff) = multi_buffer.diff_for(buffer_snapshot.remote_id()) else {
+            return Some(rows);
+        };
+        let diff_snapshot = diff.read(cx).snapshot(cx);
+        let max_point = buffer_snapshot.max_point();
+        let head_row = |row: u32| {
+            let point = Point::new(row, 0);
+            if point <= max_point {
+                return diff_snapshot
+                    .buffer_point_to_base_text_point(point, &buffer_snapshot)
+                    .row;
+            }
+            // Past the end of a last line without a trailing newline
+            let head_point =
+                diff_snapshot.buffer_point_to_base_text_point(max_point, &buffer_snapshot);
+            head_point.row + u32::from(head_point.column > 0)
+        };
+        let head_rows = head_row(rows.start)..head_row(rows.end);
+        (!head_rows.is_empty()).then_some(head_rows)
+    }
+
     fn get_permalink_to_line(&self, cx: &mut Context<Self>) -> Task<Result<url::Url>> {
         let buffer_and_selection = maybe!({
             let selection = self.selections.newest::<Point>(&self.display_snapshot(cx));
@@ -26292,6 +26459,7 @@ impl Editor {
         self.refresh_semantic_tokens(for_buffer, None, cx);
         self.refresh_document_colors(for_buffer, window, cx);
         self.refresh_folding_ranges(for_buffer, window, cx);
+        self.refresh_document_links(for_buffer, window, cx);
         self.refresh_document_symbols(for_buffer, cx);
     }
 
@@ -27597,6 +27765,14 @@ pub trait SemanticsProvider {
 
     fn supports_semantic_tokens(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
 
+    /// Returns `None` when no language server for the buffer reports selection ranges.
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>>;
+
     fn document_highlights(
         &self,
         buffer: &Entity<Buffer>,
@@ -28136,6 +28312,21 @@ impl SemanticsProvider for WeakEntity<Project> {
         .unwrap_or(false)
     }
 
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>> {
+        self.update(cx, |project, cx| {
+            project
+                .any_language_server_supports_selection_ranges(buffer.read(cx), cx)
+                .then(|| project.selection_ranges(buffer, positions, cx))
+        })
+        .ok()
+        .flatten()
+    }
+
     fn inline_values(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -5818,6 +5818,35 @@ async fn test_custom_newlines_cause_no_false_positive_diffs(
     });
 }
 
+#[gpui::test]
+async fn test_selected_head_rows(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc! {"
+        one
+        ˇadded
+        two
+        three
+        four"});
+    cx.set_head_text("zero\none\ntwo\nthree\nfour");
+    executor.run_until_parked();
+
+    let mut head_rows = |marked_text: &str| {
+        cx.set_selections_state(marked_text);
+        cx.update_editor(|editor, _, cx| editor.selected_head_rows(cx))
+    };
+
+    // Lines added since HEAD have no history.
+    assert_eq!(head_rows("one\nadˇded\ntwo\nthree\nfour"), None);
+    assert_eq!(head_rows("one\n«ˇadded\n»two\nthree\nfour"), None);
+
+    // Selections spanning added lines are shrunk to the committed ones.
+    assert_eq!(head_rows("«one\nadded\ntwoˇ»\nthree\nfour"), Some(1..3));
+    assert_eq!(head_rows("one\nadded\n«two\nthreeˇ»\nfour"), Some(2..4));
+    assert_eq!(head_rows("one\nadded\ntwo\n«three\nˇ»four"), Some(3..4));
+    assert_eq!(head_rows("one\nadded\ntwo\nthree\nfoˇur"), Some(4..5));
+}
+
 #[gpui::test]
 async fn test_manipulate_immutable_lines_with_single_selection(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -11230,6 +11259,144 @@ async fn test_select_larger_smaller_syntax_node_for_string(cx: &mut TestAppConte
     });
 }
 
+#[gpui::test]
+async fn test_select_larger_smaller_syntax_node_with_lsp_selection_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
+
+    // Every position expands to its identifier, then its statement, then the whole function.
+    let mut selection_range_requests = cx
+        .set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(
+            move |_, params, _| async move {
+                Ok(Some(
+                    params
+                        .positions
+                        .into_iter()
+                        .map(|position| {
+                            let range = |start_line, start, end_line, end| {
+                                lsp::Range::new(
+                                    lsp::Position::new(start_line, start),
+                                    lsp::Position::new(end_line, end),
+                                )
+                            };
+                            lsp::SelectionRange {
+                                range: range(position.line, 8, position.line, 9),
+                                parent: Some(Box::new(lsp::SelectionRange {
+                                    range: range(position.line, 4, position.line, 14),
+                                    parent: Some(Box::new(lsp::SelectionRange {
+                                        range: range(0, 0, 3, 1),
+                                        parent: None,
+                                    })),
+                                })),
+                            }
+                        })
+                        .collect(),
+                ))
+            },
+        );
+
+    cx.set_state(indoc! {"
+        fn main() {
+            let aˇ = 1;
+            let bˇ = 2;
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    assert!(selection_range_requests.next().await.is_some());
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let «aˇ» = 1;
+            let «bˇ» = 2;
+        }
+    "});
+
+    // Both selections grow to the whole function and are merged into one.
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    assert!(selection_range_requests.next().await.is_some());
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        «fn main() {
+            let a = 1;
+            let b = 2;
+        }ˇ»
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            «let a = 1;ˇ»
+            «let b = 2;ˇ»
+        }
+    "});
+}
+
+#[gpui::test]
+async fn test_select_larger_syntax_node_when_lsp_selection_ranges_time_out(
+    cx: &mut TestAppContext,
+) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
+
+    let mut selection_range_requests = cx
+        .set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(
+            move |_, _, _| async move {
+                futures::future::pending::<()>().await;
+                unreachable!()
+            },
+        );
+
+    cx.set_state(indoc! {"
+        fn main() {
+            let vaˇr = 1;
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    assert!(selection_range_requests.next().await.is_some());
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let vaˇr = 1;
+        }
+    "});
+
+    // The selection is expanded with tree-sitter once the request times out.
+    cx.executor().advance_clock(super::SELECTION_RANGES_TIMEOUT);
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let «varˇ» = 1;
+        }
+    "});
+}
+
 #[gpui::test]
 async fn test_unwrap_syntax_nodes(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -27737,7 +27904,7 @@ impl BookmarkTestContext {
                 .get(&abs_path)
                 .unwrap()
                 .iter()
-                .map(|b| b.0)
+                .map(|b| b.row)
                 .collect();
             rows.sort();
             assert_eq!(expected_rows, rows);
@@ -8,7 +8,7 @@ use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Pixels, Task, Wi
 use language::{Bias, ToOffset};
 use linkify::{LinkFinder, LinkKind};
 use lsp::LanguageServerId;
-use project::{InlayId, LocationLink, Project, ResolvedPath};
+use project::{InlayId, LocationLink, Project, ResolvedPath, lsp_store::LspDocumentLink};
 use regex::Regex;
 use settings::Settings;
 use std::{ops::Range, sync::LazyLock};
@@ -355,13 +355,30 @@ pub fn show_link_definition(
     }
     let project = editor.project.clone();
     let provider = editor.semantics_provider.clone();
+    let document_link = match &trigger_point {
+        TriggerPoint::Text(_) => editor.document_link_at(anchor, cx),
+        TriggerPoint::InlayHint(..) => None,
+    };
 
     let snapshot = snapshot.buffer_snapshot().clone();
     hovered_link_state.task = Some(cx.spawn_in(window, async move |this, cx| {
         async move {
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, anchor, cx.clone()) {
+                    let document_link = match document_link {
+                        Some(document_link) => {
+                            find_document_link_target(document_link, &buffer, project.clone(), cx)
+                                .await
+                        }
+                        None => None,
+                    };
+                    if let Some((link_range, link)) = document_link {
+                        let range = maybe!({
+                            let range = snapshot.buffer_anchor_range_to_anchor_range(link_range)?;
+                            Some(RangeInEditor::Text(range))
+                        });
+                        Some((range, vec![link]))
+                    } else if let Some((url_range, url)) = find_url(&buffer, anchor, cx.clone()) {
                         this.read_with(cx, |_, _| {
                             let range = maybe!({
                                 let range =
@@ -603,6 +620,38 @@ pub(crate) fn find_url_from_range(
     None
 }
 
+/// Turns a document link reported by a language server into a link that can be opened,
+/// resolving its target first if the server left it out.
+async fn find_document_link_target(
+    document_link: LspDocumentLink,
+    buffer: &Entity<language::Buffer>,
+    project: Option<Entity<Project>>,
+    cx: &mut AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, HoverLink)> {
+    let project = project?;
+    let document_link = project
+        .update(cx, |project, cx| {
+            project.lsp_store().update(cx, |lsp_store, cx| {
+                lsp_store.resolve_document_link(buffer, document_link, cx)
+            })
+        })
+        .await
+        .log_err()?;
+    let target = document_link.target()?;
+    let link = if target.scheme() == "file" {
+        let path = target.to_file_path().ok()?;
+        let resolved_path = project
+            .update(cx, |project, cx| {
+                project.resolve_path_in_buffer(&path.to_string_lossy(), buffer, cx)
+            })
+            .await?;
+        HoverLink::File(resolved_path)
+    } else {
+        HoverLink::Url(target.as_str().to_string())
+    };
+    Some((document_link.range, link))
+}
+
 pub(crate) async fn find_file(
     buffer: &Entity<language::Buffer>,
     project: Option<Entity<Project>>,
@@ -1362,6 +1411,65 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        let mut link_request = cx.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(
+            move |_, _, _| async move {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: lsp::Range::new(lsp::Position::new(0, 8), lsp::Position::new(0, 19)),
+                    target: None,
+                    tooltip: None,
+                    data: Some(serde_json::json!("schema")),
+                }]))
+            },
+        );
+        let mut resolve_request = cx
+            .set_request_handler::<lsp::request::DocumentLinkResolve, _, _>(
+                move |_, link, _| async move {
+                    assert_eq!(link.data, Some(serde_json::json!("schema")));
+                    Ok(lsp::DocumentLink {
+                        target: Some("https://zed.dev/schema.json".parse().unwrap()),
+                        ..link
+                    })
+                },
+            );
+
+        cx.set_state(indoc! {"
+            ˇschema: schema.json
+        "});
+        assert!(link_request.next().await.is_some());
+        cx.run_until_parked();
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            schema: schemˇa.json
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        assert!(resolve_request.next().await.is_some());
+        cx.run_until_parked();
+        cx.assert_editor_text_highlights(
+            HighlightKey::HoveredLinkState,
+            indoc! {"
+            schema: «schema.jsonˇ»
+        "},
+        );
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://zed.dev/schema.json".into()));
+    }
+
     #[gpui::test]
     async fn test_hover_preconditions(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
@@ -16,6 +16,7 @@ use zed_actions::agent::AddSelectionToThread;
 use zed_actions::preview::{
     markdown::OpenPreview as OpenMarkdownPreview, svg::OpenPreview as OpenSvgPreview,
 };
+use zed_actions::{call_hierarchy::ShowCallHierarchy, type_hierarchy::ShowTypeHierarchy};
 
 #[derive(Debug)]
 pub enum MenuPosition {
@@ -216,6 +217,7 @@ pub fn deploy_context_menu(
                         .repository_and_path_for_buffer_id(buffer_anchor.buffer_id, cx)
                         .is_some()
                 });
+        let has_committed_lines = has_git_repo && editor.selected_head_rows(cx).is_some();
 
         let evaluate_selection = window.is_action_available(&EvaluateSelectedText, cx);
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
@@ -264,6 +266,8 @@ pub fn deploy_context_menu(
                     "Find All References",
                     Box::new(FindAllReferences::default()),
                 )
+                .action("Show Call Hierarchy", Box::new(ShowCallHierarchy))
+                .action("Show Type Hierarchy", Box::new(ShowTypeHierarchy))
                 .separator()
                 .action("Rename Symbol", Box::new(Rename))
                 .action("Format Buffer", Box::new(Format))
@@ -311,6 +315,11 @@ pub fn deploy_context_menu(
                     !has_git_repo,
                     "View File History",
                     Box::new(git::FileHistory),
+                )
+                .action_disabled_when(
+                    !has_committed_lines,
+                    "View Line History",
+                    Box::new(git::LineHistory),
                 );
             match focus {
                 Some(focus) => builder.context(focus),
@@ -0,0 +1,73 @@
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::{Context, Entity, Task};
+use language::Buffer;
+use std::ops::Range;
+use text::{Anchor, BufferId};
+
+use crate::Editor;
+
+/// Selection ranges reported by language servers, keyed by the id of the selection they were
+/// requested for, from the innermost range to the outermost one.
+pub(super) type LspSelectionRanges = HashMap<usize, Vec<Range<Anchor>>>;
+
+impl Editor {
+    /// Requests `textDocument/selectionRange` for every selection in a buffer whose language
+    /// server supports it, querying each buffer once for all of its selections.
+    ///
+    /// Returns `None` when no selection can be expanded this way, so callers can fall back to
+    /// tree-sitter without waiting.
+    pub(super) fn lsp_selection_ranges(
+        &self,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<LspSelectionRanges>> {
+        let provider = self.semantics_provider.clone()?;
+        let display_snapshot = self.display_snapshot(cx);
+        let selections = self.selections.all_anchors(&display_snapshot);
+        let snapshot = display_snapshot.buffer_snapshot();
+
+        let mut positions_per_buffer =
+            HashMap::<BufferId, (Entity<Buffer>, Vec<usize>, Vec<Anchor>)>::default();
+        let multi_buffer = self.buffer.read(cx);
+        for selection in selections.iter() {
+            if let Some((_, range)) =
+                snapshot.anchor_range_to_buffer_anchor_range(selection.range())
+                && let Some(buffer) = multi_buffer.buffer(range.start.buffer_id)
+            {
+                let (_, selection_ids, positions) = positions_per_buffer
+                    .entry(range.start.buffer_id)
+                    .or_insert_with(|| (buffer, Vec::new(), Vec::new()));
+                selection_ids.push(selection.id);
+                positions.push(range.start);
+            }
+        }
+
+        let tasks = positions_per_buffer
+            .into_values()
+            .filter_map(|(buffer, selection_ids, positions)| {
+                let task = provider.selection_ranges(&buffer, positions, cx)?;
+                Some(async move {
+                    match task.await {
+                        Ok(selection_ranges) => selection_ids
+                            .into_iter()
+                            .zip(selection_ranges)
+                            .collect::<Vec<_>>(),
+                        Err(e) => {
+                            log::error!("Failed to fetch selection ranges: {e:#}");
+                            Vec::new()
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        if tasks.is_empty() {
+            return None;
+        }
+
+        Some(
+            cx.background_spawn(
+                async move { join_all(tasks).await.into_iter().flatten().collect() },
+            ),
+        )
+    }
+}
@@ -7,9 +7,11 @@ use futures::future::{self, BoxFuture, join_all};
 use git::repository::GitCommitTemplate;
 use git::{
     Oid, RunHook,
+    bisect::{BisectState, BisectVerdict},
     blame::Blame,
+    rebase::{RebaseAction, RebaseOperation, RebaseState, RebaseTodoEntry},
     repository::{
-        AskPassDelegate, Branch, CommitDataReader, CommitDetails, CommitOptions,
+        AskPassDelegate, Branch, CommitDataReader, CommitDetails, CommitOptions, ConflictStages,
         CreateWorktreeTarget, FetchOptions, GRAPH_CHUNK_SIZE, GitRepository,
         GitRepositoryCheckpoint, InitialGraphCommitData, LogOrder, LogSource, PushOptions, RefEdit,
         Remote, RepoPath, ResetMode, SearchCommitArgs, Worktree,
@@ -17,15 +19,16 @@ use git::{
     stash::GitStash,
     status::{
         DiffTreeType, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
-        UnmergedStatus,
+        UnmergedStatus, UnmergedStatusCode,
     },
+    submodule::{Submodule, SubmoduleDiff, SubmoduleOperation, SubmoduleState},
 };
 use gpui::{AsyncApp, BackgroundExecutor, SharedString, Task};
 use ignore::gitignore::GitignoreBuilder;
 use parking_lot::Mutex;
 use rope::Rope;
 use smol::{channel::Sender, future::FutureExt as _};
-use std::{path::PathBuf, sync::Arc, sync::atomic::AtomicBool};
+use std::{ops::Range, path::PathBuf, sync::Arc, sync::atomic::AtomicBool};
 use text::LineEnding;
 use util::{paths::PathStyle, rel_path::RelPath};
 
@@ -47,11 +50,80 @@ pub struct FakeCommitSnapshot {
     pub sha: String,
 }
 
+/// A commit that can be cherry-picked or reverted, described by the contents
+/// of the paths it changes. A `None` content means the path doesn't exist.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FakeCommit {
+    pub sha: String,
+    pub subject: String,
+    /// The contents of each changed path before and after the commit
+    pub changes: HashMap<RepoPath, (Option<String>, Option<String>)>,
+}
+
+impl FakeCommit {
+    pub fn new(sha: impl Into<String>, subject: impl Into<String>) -> Self {
+        Self {
+            sha: sha.into(),
+            subject: subject.into(),
+            changes: HashMap::default(),
+        }
+    }
+
+    pub fn change(mut self, path: &str, old: Option<&str>, new: Option<&str>) -> Self {
+        self.changes.insert(
+            git::repository::repo_path(path),
+            (old.map(Into::into), new.map(Into::into)),
+        );
+        self
+    }
+}
+
+/// An interactive rebase that stopped at an `edit` entry.
+#[derive(Debug, Clone)]
+pub struct FakeRebase {
+    pub state: RebaseState,
+    /// The history and HEAD to restore when the rebase is aborted
+    pub original_history: Vec<FakeCommit>,
+    pub original_head: String,
+}
+
+/// A bisect session over the commits of the fake repository's history.
+#[derive(Debug, Clone)]
+pub struct FakeBisect {
+    pub state: BisectState,
+    /// The HEAD to restore when the session is reset
+    pub original_head: String,
+}
+
+/// The unmerged status git reports for a conflicted path with the given stages.
+pub(crate) fn unmerged_status(stages: &ConflictStages) -> UnmergedStatus {
+    let code = |stage: &Option<String>| match (&stages.base, stage) {
+        (_, None) => UnmergedStatusCode::Deleted,
+        (None, Some(_)) => UnmergedStatusCode::Added,
+        (Some(_), Some(_)) => UnmergedStatusCode::Updated,
+    };
+    UnmergedStatus {
+        first_head: code(&stages.ours),
+        second_head: code(&stages.theirs),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FakeGitRepositoryState {
     pub commit_history: Vec<FakeCommitSnapshot>,
+    /// Commits that can be cherry-picked or reverted, keyed by sha
+    pub commits: HashMap<String, FakeCommit>,
+    /// The commits reachable from HEAD, oldest first
+    pub history: Vec<FakeCommit>,
+    pub rebase: Option<FakeRebase>,
+    pub bisect: Option<FakeBisect>,
+    pub submodules: Vec<Submodule>,
+    /// The changes of the submodule pointers, keyed by submodule path
+    pub submodule_diffs: HashMap<RepoPath, SubmoduleDiff>,
     pub event_emitter: smol::channel::Sender<PathBuf>,
     pub unmerged_paths: HashMap<RepoPath, UnmergedStatus>,
+    /// Index stages 1 to 3 of the conflicted paths
+    pub conflict_stages: HashMap<RepoPath, git::repository::ConflictStages>,
     pub head_contents: HashMap<RepoPath, String>,
     pub index_contents: HashMap<RepoPath, String>,
     // everything in commit contents is in oids
@@ -77,6 +149,7 @@ impl FakeGitRepositoryState {
             head_contents: Default::default(),
             index_contents: Default::default(),
             unmerged_paths: Default::default(),
+            conflict_stages: Default::default(),
             blames: Default::default(),
             current_branch_name: Default::default(),
             branches: Default::default(),
@@ -89,11 +162,120 @@ impl FakeGitRepositoryState {
             remotes: HashMap::default(),
             graph_commits: Vec::new(),
             commit_history: Vec::new(),
+            commits: HashMap::default(),
+            history: Vec::new(),
+            rebase: None,
+            bisect: None,
+            submodules: Vec::new(),
+            submodule_diffs: HashMap::default(),
             stash_entries: Default::default(),
         }
     }
 }
 
+impl FakeGitRepositoryState {
+    fn history_position(&self, rev: &str) -> Result<usize> {
+        let sha = self.refs.get(rev).map_or(rev, String::as_str);
+        self.history
+            .iter()
+            .position(|commit| commit.sha == sha)
+            .with_context(|| format!("invalid upstream '{rev}'"))
+    }
+
+    /// Narrows down the commits that may be the first bad one, like git does
+    /// after each verdict, and checks out the untested one in the middle.
+    fn update_bisect(&mut self) -> Result<()> {
+        let Some(bisect) = self.bisect.as_mut() else {
+            bail!("You need to start by \"git bisect start\"");
+        };
+        let state = &mut bisect.state;
+        state.remaining.clear();
+        let (Some(bad), false) = (&state.bad, state.good.is_empty()) else {
+            return Ok(());
+        };
+        let position = |sha: &str| {
+            self.history
+                .iter()
+                .position(|commit| commit.sha == sha)
+                .with_context(|| format!("bad revision '{sha}'"))
+        };
+        let bad_ix = position(bad.as_ref())?;
+        let mut good_ix = 0;
+        for good in &state.good {
+            good_ix = good_ix.max(position(good.as_ref())?);
+        }
+        anyhow::ensure!(
+            good_ix < bad_ix,
+            "some good revs are not ancestors of the bad rev"
+        );
+        state.remaining = self.history[good_ix + 1..=bad_ix]
+            .iter()
+            .rev()
+            .map(|commit| commit.sha.clone().into())
+            .collect();
+
+        let untested = state
+            .remaining
+            .iter()
+            .filter(|sha| Some(*sha) != state.bad.as_ref() && !state.skipped.contains(sha))
+            .collect::<Vec<_>>();
+        if let Some(current) = untested.get(untested.len() / 2) {
+            self.refs.insert("HEAD".into(), current.to_string());
+        }
+        Ok(())
+    }
+
+    /// Applies the remaining entries of the rebase in progress, stopping after
+    /// an `edit` entry. Only the history is rewritten, file contents are left
+    /// alone.
+    fn run_rebase_todo(&mut self) -> Result<()> {
+        let Some(rebase) = self.rebase.as_mut() else {
+            bail!("No rebase in progress?");
+        };
+        rebase.state.stopped_sha = None;
+        while !rebase.state.remaining.is_empty() {
+            let entry = rebase.state.remaining.remove(0);
+            rebase.state.done.push(entry.clone());
+            if entry.action == RebaseAction::Drop {
+                continue;
+            }
+            let mut commit = self
+                .commits
+                .get(entry.sha.as_ref())
+                .cloned()
+                .with_context(|| format!("invalid commit '{}'", entry.sha))?;
+            if entry.action.melds_into_previous() {
+                let previous = self
+                    .history
+                    .last_mut()
+                    .context("cannot squash without a previous commit")?;
+                for (path, (old, new)) in commit.changes {
+                    previous
+                        .changes
+                        .entry(path)
+                        .and_modify(|change| change.1 = new.clone())
+                        .or_insert((old, new));
+                }
+                self.commits.insert(previous.sha.clone(), previous.clone());
+                continue;
+            }
+            commit.sha = format!("{}-rebased", commit.sha);
+            if let Some(message) = &entry.message {
+                commit.subject = message.lines().next().unwrap_or_default().to_string();
+            }
+            self.commits.insert(commit.sha.clone(), commit.clone());
+            self.refs.insert("HEAD".into(), commit.sha.clone());
+            self.history.push(commit);
+            if entry.action == RebaseAction::Edit {
+                rebase.state.stopped_sha = Some(entry.sha);
+                return Ok(());
+            }
+        }
+        self.rebase = None;
+        Ok(())
+    }
+}
+
 impl FakeGitRepository {
     fn with_state_async<F, T>(&self, write: bool, f: F) -> BoxFuture<'static, Result<T>>
     where
@@ -124,6 +306,104 @@ impl FakeGitRepository {
         })
     }
 
+    /// Writes the given contents to the working copy, deleting the paths whose
+    /// contents are `None`.
+    async fn write_worktree(&self, changes: HashMap<RepoPath, Option<String>>) -> Result<()> {
+        let workdir_path = self.dot_git_path.parent().unwrap();
+        for (path, content) in changes {
+            let abs_path = workdir_path.join(path.as_std_path());
+            match content {
+                Some(content) => self.fs.write(&abs_path, content.as_bytes()).await?,
+                None => {
+                    self.fs
+                        .remove_file(
+                            &abs_path,
+                            RemoveOptions {
+                                recursive: false,
+                                ignore_if_not_exists: true,
+                            },
+                        )
+                        .await?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the changes of `commit`, or undoes them when `revert` is set, on
+    /// top of HEAD. Paths whose contents in HEAD differ from the ones the change
+    /// expects are left conflicted, and `CHERRY_PICK_HEAD` or `REVERT_HEAD` is set
+    /// until the result is committed.
+    fn apply_commit(&self, commit: String, revert: bool) -> BoxFuture<'_, Result<()>> {
+        let apply = self.with_state_async(true, move |state| {
+            if let Some(head) = ["CHERRY_PICK_HEAD", "REVERT_HEAD"]
+                .into_iter()
+                .find(|head| state.refs.contains_key(*head))
+            {
+                bail!("{head} exists, a cherry-pick or revert is already in progress");
+            }
+            let picked = state
+                .commits
+                .get(&commit)
+                .cloned()
+                .with_context(|| format!("bad revision '{commit}'"))?;
+            if state.index_contents != state.head_contents {
+                bail!("your local changes would be overwritten by the {commit} changes");
+            }
+
+            let mut worktree_changes = HashMap::default();
+            let mut conflicts = Vec::new();
+            for (path, (old, new)) in picked.changes {
+                let (base, theirs) = if revert { (new, old) } else { (old, new) };
+                let ours = state.head_contents.get(&path).cloned();
+                if ours == base || ours == theirs {
+                    match &theirs {
+                        Some(content) => state.index_contents.insert(path.clone(), content.clone()),
+                        None => state.index_contents.remove(&path),
+                    };
+                    worktree_changes.insert(path, theirs);
+                } else {
+                    let markers = format!(
+                        "<<<<<<< HEAD\n{}=======\n{}>>>>>>> {} ({})\n",
+                        ours.as_deref().unwrap_or_default(),
+                        theirs.as_deref().unwrap_or_default(),
+                        picked.sha,
+                        picked.subject,
+                    );
+                    worktree_changes.insert(path.clone(), Some(markers));
+                    conflicts.push((path, ConflictStages { base, ours, theirs }));
+                }
+            }
+
+            if conflicts.is_empty() {
+                let old_sha = state.refs.get("HEAD").cloned().unwrap_or_default();
+                state.commit_history.push(FakeCommitSnapshot {
+                    head_contents: state.head_contents.clone(),
+                    index_contents: state.index_contents.clone(),
+                    sha: old_sha,
+                });
+                state.head_contents = state.index_contents.clone();
+                let new_sha = format!("fake-commit-{}", state.commit_history.len());
+                state.refs.insert("HEAD".into(), new_sha);
+            } else {
+                let head = if revert {
+                    "REVERT_HEAD"
+                } else {
+                    "CHERRY_PICK_HEAD"
+                };
+                state.refs.insert(head.into(), picked.sha);
+                for (path, stages) in conflicts {
+                    state
+                        .unmerged_paths
+                        .insert(path.clone(), unmerged_status(&stages));
+                    state.conflict_stages.insert(path, stages);
+                }
+            }
+            Ok(worktree_changes)
+        });
+        async move { self.write_worktree(apply.await?).await }.boxed()
+    }
+
     /// Scans `.git/worktrees/*/gitdir` to find the admin entry directory for a
     /// worktree at the given checkout path. Used when the working tree directory
     /// has already been deleted and we can't read its `.git` pointer file.
@@ -276,7 +556,7 @@ impl GitRepository for FakeGitRepository {
         mode: ResetMode,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
-        self.with_state_async(true, move |state| {
+        let reset = self.with_state_async(true, move |state| {
             let pop_count = if commit == "HEAD~" || commit == "HEAD^" {
                 1
             } else if let Some(suffix) = commit.strip_prefix("HEAD~") {
@@ -305,19 +585,47 @@ impl GitRepository for FakeGitRepository {
             let snapshot = state.commit_history[target_index].clone();
             state.commit_history.truncate(target_index);
 
-            match mode {
+            let worktree_changes = match mode {
                 ResetMode::Soft => {
                     state.head_contents = snapshot.head_contents;
+                    None
                 }
                 ResetMode::Mixed => {
                     state.head_contents = snapshot.head_contents;
                     state.index_contents = state.head_contents.clone();
+                    None
                 }
-            }
+                ResetMode::Hard => {
+                    // Files tracked before the reset but not by the target commit are deleted.
+                    let mut changes = state
+                        .head_contents
+                        .keys()
+                        .chain(state.index_contents.keys())
+                        .filter(|path| !snapshot.head_contents.contains_key(*path))
+                        .map(|path| (path.clone(), None))
+                        .collect::<HashMap<_, _>>();
+                    changes.extend(
+                        snapshot
+                            .head_contents
+                            .iter()
+                            .map(|(path, content)| (path.clone(), Some(content.clone()))),
+                    );
+                    state.head_contents = snapshot.head_contents;
+                    state.index_contents = state.head_contents.clone();
+                    Some(changes)
+                }
+            };
 
             state.refs.insert("HEAD".into(), snapshot.sha);
+            Ok(worktree_changes)
+        });
+        async move {
+            if let Some(changes) = reset.await? {
+                self.write_worktree(changes).await?;
+            }
             Ok(())
-        })
+        }
+        .boxed()
     }
 
     fn checkout_files(
@@ -922,6 +1230,21 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn line_history(
+        &self,
+        path: RepoPath,
+        rows: Range<u32>,
+    ) -> BoxFuture<'_, Result<git::line_history::LineHistory>> {
+        async move {
+            Ok(git::line_history::LineHistory {
+                entries: Vec::new(),
+                path,
+                rows,
+            })
+        }
+        .boxed()
+    }
+
     fn stage_paths(
         &self,
         paths: Vec<RepoPath>,
@@ -942,6 +1265,8 @@ impl GitRepository for FakeGitRepository {
             let contents = join_all(contents).await;
             self.with_state_async(true, move |state| {
                 for (path, content) in contents {
+                    state.unmerged_paths.remove(&path);
+                    state.conflict_stages.remove(&path);
                     if let Some(content) = content {
                         state.index_contents.insert(path, content);
                     } else {
@@ -1002,6 +1327,269 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn rebase_todo(&self, base: String) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        self.with_state_async(false, move |state| {
+            let base_ix = state.history_position(&base)?;
+            Ok(state.history[base_ix + 1..]
+                .iter()
+                .map(|commit| RebaseTodoEntry::pick(commit.sha.clone(), commit.subject.clone()))
+                .collect())
+        })
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            anyhow::ensure!(!todo.is_empty(), "Nothing to rebase");
+            anyhow::ensure!(state.rebase.is_none(), "A rebase is already in progress");
+            let base_ix = state.history_position(&base)?;
+            let onto = state.history[base_ix].sha.clone();
+            state.rebase = Some(FakeRebase {
+                state: RebaseState {
+                    head_name: state.current_branch_name.clone().map(Into::into),
+                    onto: Some(onto.clone().into()),
+                    stopped_sha: None,
+                    done: Vec::new(),
+                    remaining: todo,
+                },
+                original_history: state.history.clone(),
+                original_head: state.refs.get("HEAD").cloned().unwrap_or_default(),
+            });
+            state.history.truncate(base_ix + 1);
+            state.refs.insert("HEAD".into(), onto);
+            state.run_rebase_todo()
+        })
+    }
+
+    fn rebase_operation(
+        &self,
+        operation: RebaseOperation,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let Some(rebase) = state.rebase.as_ref() else {
+                bail!("No rebase in progress?");
+            };
+            match operation {
+                RebaseOperation::Continue => {}
+                RebaseOperation::Skip => {
+                    if rebase.state.stopped_sha.is_some() {
+                        state.history.pop();
+                        let head = state.history.last().map(|commit| commit.sha.clone());
+                        state.refs.insert("HEAD".into(), head.unwrap_or_default());
+                    }
+                }
+                RebaseOperation::Abort => {
+                    let rebase = state.rebase.take().unwrap();
+                    state.history = rebase.original_history;
+                    state.refs.insert("HEAD".into(), rebase.original_head);
+                    return Ok(());
+                }
+            }
+            state.run_rebase_todo()
+        })
+    }
+
+    fn rebase_state(&self) -> BoxFuture<'_, Result<Option<RebaseState>>> {
+        self.with_state_async(false, |state| {
+            Ok(state.rebase.as_ref().map(|rebase| rebase.state.clone()))
+        })
+    }
+
+    fn cherry_pick(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.apply_commit(commit, false)
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.apply_commit(commit, true)
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        _message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let tag_ref = format!("refs/tags/{name}");
+            if state.refs.contains_key(&tag_ref) {
+                bail!("tag '{name}' already exists");
+            }
+            state.refs.insert(tag_ref, commit);
+            Ok(())
+        })
+    }
+
+    fn delete_tag(
+        &self,
+        name: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.refs.remove(&format!("refs/tags/{name}")).is_none() {
+                bail!("tag '{name}' not found");
+            }
+            Ok(())
+        })
+    }
+
+    fn bisect_start(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            if state.bisect.is_none() {
+                state.bisect = Some(FakeBisect {
+                    state: BisectState::default(),
+                    original_head: state.refs.get("HEAD").cloned().unwrap_or_default(),
+                });
+            }
+            Ok(())
+        })
+    }
+
+    fn bisect_mark(
+        &self,
+        verdict: BisectVerdict,
+        commit: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let commit = commit.unwrap_or_else(|| "HEAD".into());
+            let sha = state.refs.get(&commit).cloned().unwrap_or(commit);
+            let Some(bisect) = state.bisect.as_mut() else {
+                bail!("You need to start by \"git bisect start\"");
+            };
+            let sha = SharedString::from(sha);
+            match verdict {
+                BisectVerdict::Good => bisect.state.good.push(sha),
+                BisectVerdict::Bad => bisect.state.bad = Some(sha),
+                BisectVerdict::Skip => bisect.state.skipped.push(sha),
+            }
+            state.update_bisect()
+        })
+    }
+
+    fn bisect_reset(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            if let Some(bisect) = state.bisect.take() {
+                state.refs.insert("HEAD".into(), bisect.original_head);
+            }
+            Ok(())
+        })
+    }
+
+    fn bisect_state(&self) -> BoxFuture<'_, Result<Option<BisectState>>> {
+        self.with_state_async(false, |state| {
+            Ok(state.bisect.as_ref().map(|bisect| BisectState {
+                current: state.refs.get("HEAD").cloned().map(Into::into),
+                ..bisect.state.clone()
+            }))
+        })
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        self.with_state_async(false, |state| Ok(state.submodules.clone()))
+    }
+
+    fn submodule_operation(
+        &self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(true, move |state| {
+            if let Some(path) = paths
+                .iter()
+                .find(|path| !state.submodules.iter().any(|s| &s.path == *path))
+            {
+                bail!(
+                    "error: pathspec '{}' did not match any file(s) known to git",
+                    path.as_unix_str()
+                );
+            }
+            let mut stdout = String::new();
+            for submodule in &mut state.submodules {
+                if !paths.is_empty() && !paths.contains(&submodule.path) {
+                    continue;
+                }
+                let path = submodule.path.as_unix_str();
+                match operation {
+                    SubmoduleOperation::Init | SubmoduleOperation::Sync => {}
+                    SubmoduleOperation::Update => match submodule.state {
+                        SubmoduleState::UpToDate => {}
+                        SubmoduleState::Conflicted => {
+                            bail!("Skipping unmerged submodule {path}");
+                        }
+                        SubmoduleState::Uninitialized | SubmoduleState::Modified => {
+                            // Check out the commit recorded in the superproject.
+                            if let Some(recorded_sha) = state
+                                .submodule_diffs
+                                .remove(&submodule.path)
+                                .and_then(|diff| diff.old_sha)
+                            {
+                                submodule.sha = recorded_sha;
+                            }
+                            submodule.state = SubmoduleState::UpToDate;
+                            stdout.push_str(&format!(
+                                "Submodule path '{path}': checked out '{}'\n",
+                                submodule.sha
+                            ));
+                        }
+                    },
+                }
+            }
+            Ok(git::repository::RemoteCommandOutput {
+                stdout,
+                stderr: String::new(),
+            })
+        })
+    }
+
+    fn submodule_diff(&self, path: RepoPath) -> BoxFuture<'_, Result<SubmoduleDiff>> {
+        self.with_state_async(false, move |state| {
+            if let Some(diff) = state.submodule_diffs.get(&path) {
+                return Ok(diff.clone());
+            }
+            let submodule = state
+                .submodules
+                .iter()
+                .find(|submodule| submodule.path == path)
+                .with_context(|| format!("'{}' is not a submodule", path.as_unix_str()))?;
+            Ok(SubmoduleDiff {
+                old_sha: Some(submodule.sha.clone()),
+                new_sha: Some(submodule.sha.clone()),
+                ..SubmoduleDiff::default()
+            })
+        })
+    }
+
+    fn load_conflict_stages(
+        &self,
+        path: RepoPath,
+    ) -> BoxFuture<'_, Result<git::repository::ConflictStages>> {
+        self.with_state_async(false, move |state| {
+            Ok(state
+                .conflict_stages
+                .get(&path)
+                .cloned()
+                .unwrap_or_default())
+        })
+        .boxed()
+    }
+
     fn commit(
         &self,
         _message: gpui::SharedString,
@@ -1027,6 +1615,9 @@ impl GitRepository for FakeGitRepository {
 
             let new_sha = format!("fake-commit-{}", state.commit_history.len());
             state.refs.insert("HEAD".into(), new_sha);
+            // Committing the resolved conflicts concludes a cherry-pick or revert.
+            state.refs.remove("CHERRY_PICK_HEAD");
+            state.refs.remove("REVERT_HEAD");
 
             Ok(())
         })
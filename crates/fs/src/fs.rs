@@ -53,11 +53,14 @@ mod fake_git_repo;
 #[cfg(feature = "test-support")]
 use collections::{BTreeMap, btree_map};
 #[cfg(feature = "test-support")]
+pub use fake_git_repo::FakeCommit;
+#[cfg(feature = "test-support")]
 use fake_git_repo::FakeGitRepositoryState;
 #[cfg(feature = "test-support")]
 use git::{
-    repository::{InitialGraphCommitData, RepoPath, Worktree, repo_path},
+    repository::{ConflictStages, InitialGraphCommitData, RepoPath, Worktree, repo_path},
     status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus},
+    submodule::{Submodule, SubmoduleDiff, SubmoduleState},
 };
 #[cfg(feature = "test-support")]
 use util::normalize_path;
@@ -2127,6 +2130,84 @@ impl FakeFs {
         .unwrap();
     }
 
+    /// Sets the conflicted paths of the repository along with their index stages
+    pub fn set_conflict_stages_for_repo(
+        &self,
+        dot_git: &Path,
+        conflicts: &[(&str, ConflictStages)],
+    ) {
+        self.with_git_state(dot_git, true, |state| {
+            state.conflict_stages.clear();
+            state.unmerged_paths.clear();
+            for (path, stages) in conflicts {
+                state
+                    .unmerged_paths
+                    .insert(repo_path(path), fake_git_repo::unmerged_status(stages));
+                state
+                    .conflict_stages
+                    .insert(repo_path(path), stages.clone());
+            }
+        })
+        .unwrap();
+    }
+
+    /// Sets the commits that can be cherry-picked or reverted in the repository
+    pub fn set_commits_for_repo(&self, dot_git: &Path, commits: &[FakeCommit]) {
+        self.with_git_state(dot_git, true, |state| {
+            state.commits.clear();
+            state.commits.extend(
+                commits
+                    .iter()
+                    .map(|commit| (commit.sha.clone(), commit.clone())),
+            );
+        })
+        .unwrap();
+    }
+
+    /// Sets the commits reachable from HEAD, oldest first, and checks out the last one
+    pub fn set_history_for_repo(&self, dot_git: &Path, history: &[FakeCommit]) {
+        self.with_git_state(dot_git, true, |state| {
+            state.history = history.to_vec();
+            state.commits.extend(
+                history
+                    .iter()
+                    .map(|commit| (commit.sha.clone(), commit.clone())),
+            );
+            if let Some(head) = history.last() {
+                state.refs.insert("HEAD".into(), head.sha.clone());
+            }
+        })
+        .unwrap();
+    }
+
+    /// Sets the submodules of the repository, given as path, checked out sha and state
+    pub fn set_submodules_for_repo(
+        &self,
+        dot_git: &Path,
+        submodules: &[(&str, &str, SubmoduleState)],
+    ) {
+        self.with_git_state(dot_git, true, |state| {
+            state.submodules = submodules
+                .iter()
+                .map(|(path, sha, submodule_state)| Submodule {
+                    path: repo_path(path),
+                    sha: sha.to_string().into(),
+                    state: *submodule_state,
+                })
+                .collect();
+        })
+        .unwrap();
+    }
+
+    /// Sets the change of a submodule pointer. Updating the submodule checks out
+    /// the diff's old sha, the one recorded in the superproject.
+    pub fn set_submodule_diff_for_repo(&self, dot_git: &Path, path: &str, diff: SubmoduleDiff) {
+        self.with_git_state(dot_git, true, |state| {
+            state.submodule_diffs.insert(repo_path(path), diff);
+        })
+        .unwrap();
+    }
+
     pub fn set_index_for_repo(&self, dot_git: &Path, index_state: &[(&str, String)]) {
         self.with_git_state(dot_git, true, |state| {
             state.index_contents.clear();
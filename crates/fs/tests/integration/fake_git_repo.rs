@@ -1,7 +1,14 @@
-use fs::{FakeFs, Fs};
+use fs::{FakeCommit, FakeFs, Fs};
+use git::{
+    repository::{AskPassDelegate, repo_path},
+    submodule::{SubmoduleCommit, SubmoduleDiff, SubmoduleOperation, SubmoduleState},
+};
 use gpui::{BackgroundExecutor, TestAppContext};
 use serde_json::json;
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use util::path;
 
 #[gpui::test]
@@ -194,3 +201,155 @@ async fn test_checkpoints(executor: BackgroundExecutor) {
     assert!(diff.contains("b"), "diff should mention changed file 'b'");
     assert!(diff.contains("c"), "diff should mention added file 'c'");
 }
+
+#[gpui::test]
+async fn test_cherry_pick_and_revert(executor: BackgroundExecutor) {
+    let fs = FakeFs::new(executor);
+    fs.insert_tree(
+        path!("/project"),
+        json!({".git": {}, "a.txt": "one\n", "b.txt": "two\n"}),
+    )
+    .await;
+    let dot_git = Path::new(path!("/project/.git"));
+    fs.set_head_and_index_for_repo(
+        dot_git,
+        &[("a.txt", "one\n".into()), ("b.txt", "two\n".into())],
+    );
+    fs.set_commits_for_repo(
+        dot_git,
+        &[
+            FakeCommit::new("feature", "Shout").change("a.txt", Some("one\n"), Some("ONE\n")),
+            FakeCommit::new("other", "Translate").change("b.txt", Some("zwei\n"), Some("drei\n")),
+        ],
+    );
+    let repo = fs.open_repo(dot_git, None).unwrap();
+    let head_contents = |path: &str| {
+        fs.with_git_state(dot_git, false, |state| {
+            state
+                .head_contents
+                .get(&git::repository::repo_path(path))
+                .cloned()
+        })
+        .unwrap()
+    };
+
+    repo.cherry_pick("feature".into(), Arc::default())
+        .await
+        .unwrap();
+    assert_eq!(head_contents("a.txt").as_deref(), Some("ONE\n"));
+    assert_eq!(
+        fs.load(Path::new(path!("/project/a.txt"))).await.unwrap(),
+        "ONE\n"
+    );
+
+    repo.revert("feature".into(), Arc::default()).await.unwrap();
+    assert_eq!(head_contents("a.txt").as_deref(), Some("one\n"));
+    assert_eq!(
+        fs.load(Path::new(path!("/project/a.txt"))).await.unwrap(),
+        "one\n"
+    );
+
+    // The picked commit expects different contents, so it stops on a conflict
+    repo.cherry_pick("other".into(), Arc::default())
+        .await
+        .unwrap();
+    assert_eq!(head_contents("b.txt").as_deref(), Some("two\n"));
+    assert_eq!(
+        fs.load(Path::new(path!("/project/b.txt"))).await.unwrap(),
+        "<<<<<<< HEAD\ntwo\n=======\ndrei\n>>>>>>> other (Translate)\n"
+    );
+    fs.with_git_state(dot_git, false, |state| {
+        assert_eq!(
+            state.refs.get("CHERRY_PICK_HEAD").map(String::as_str),
+            Some("other")
+        );
+        assert!(
+            state
+                .unmerged_paths
+                .contains_key(&git::repository::repo_path("b.txt"))
+        );
+    })
+    .unwrap();
+
+    // Another cherry-pick is refused until the conflicted one is concluded
+    assert!(
+        repo.cherry_pick("feature".into(), Arc::default())
+            .await
+            .is_err()
+    );
+}
+
+#[gpui::test]
+async fn test_submodules(cx: &mut TestAppContext) {
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/project"),
+        json!({".git": {}, "lib": {}, "vendor": {}}),
+    )
+    .await;
+    let dot_git = Path::new(path!("/project/.git"));
+    fs.set_submodules_for_repo(
+        dot_git,
+        &[
+            ("lib", "new", SubmoduleState::Modified),
+            ("vendor", "abc", SubmoduleState::Uninitialized),
+        ],
+    );
+    let lib_diff = SubmoduleDiff {
+        old_sha: Some("old".into()),
+        new_sha: Some("new".into()),
+        added: vec![SubmoduleCommit {
+            sha: "new".into(),
+            subject: "Add a feature".into(),
+        }],
+        removed: Vec::new(),
+    };
+    fs.set_submodule_diff_for_repo(dot_git, "lib", lib_diff.clone());
+    let repo = fs.open_repo(dot_git, None).unwrap();
+    let update = |paths| {
+        repo.submodule_operation(
+            SubmoduleOperation::Update,
+            paths,
+            AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+            Arc::default(),
+            cx.to_async(),
+        )
+    };
+    let states = async || {
+        repo.submodules()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|submodule| (submodule.sha.to_string(), submodule.state))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        repo.submodule_diff(repo_path("lib")).await.unwrap(),
+        lib_diff
+    );
+    assert!(repo.submodule_diff(repo_path("src")).await.is_err());
+    assert!(update(vec![repo_path("src")]).await.is_err());
+
+    update(vec![repo_path("vendor")]).await.unwrap();
+    assert_eq!(
+        states().await,
+        [
+            ("new".to_string(), SubmoduleState::Modified),
+            ("abc".to_string(), SubmoduleState::UpToDate),
+        ]
+    );
+
+    // Updating checks out the commit recorded in the superproject
+    update(Vec::new()).await.unwrap();
+    assert_eq!(
+        states().await,
+        [
+            ("old".to_string(), SubmoduleState::UpToDate),
+            ("abc".to_string(), SubmoduleState::UpToDate),
+        ]
+    );
+    let lib_diff = repo.submodule_diff(repo_path("lib")).await.unwrap();
+    assert_eq!(lib_diff.old_sha, lib_diff.new_sha);
+    assert!(lib_diff.added.is_empty());
+}
@@ -0,0 +1,130 @@
+use gpui::SharedString;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BisectVerdict {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl BisectVerdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BisectVerdict::Good => "good",
+            BisectVerdict::Bad => "bad",
+            BisectVerdict::Skip => "skip",
+        }
+    }
+
+    /// Interprets the exit code of a test command the same way `git bisect run`
+    /// does, returning `None` for codes that should stop the bisection.
+    pub fn from_exit_code(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(BisectVerdict::Good),
+            125 => Some(BisectVerdict::Skip),
+            1..=127 => Some(BisectVerdict::Bad),
+            _ => None,
+        }
+    }
+}
+
+/// The progress of a `git bisect` session.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BisectState {
+    /// The commit that is currently checked out for testing.
+    pub current: Option<SharedString>,
+    pub bad: Option<SharedString>,
+    pub good: Vec<SharedString>,
+    pub skipped: Vec<SharedString>,
+    /// The commits that may still be the first bad one, including the bad
+    /// commit itself. Empty until both a good and a bad commit are known.
+    pub remaining: Vec<SharedString>,
+}
+
+impl BisectState {
+    /// Builds a state from the output of
+    /// `git for-each-ref --format=%(refname)%00%(objectname) refs/bisect`.
+    pub fn from_bisect_refs(refs: &str) -> Self {
+        let mut state = Self::default();
+        for line in refs.lines() {
+            let Some((name, sha)) = line.split_once('\0') else {
+                continue;
+            };
+            let Some(name) = name.strip_prefix("refs/bisect/") else {
+                continue;
+            };
+            let sha = SharedString::from(sha.trim().to_string());
+            if name == "bad" {
+                state.bad = Some(sha);
+            } else if name.starts_with("good-") {
+                state.good.push(sha);
+            } else if name.starts_with("skip-") {
+                state.skipped.push(sha);
+            }
+        }
+        state
+    }
+
+    /// The first bad commit, once it has been narrowed down to a single one.
+    pub fn first_bad(&self) -> Option<&SharedString> {
+        match self.remaining.as_slice() {
+            [only] if Some(only) == self.bad.as_ref() => Some(only),
+            _ => None,
+        }
+    }
+
+    /// The commits left to test, i.e. the remaining ones other than the bad
+    /// commit and the skipped ones.
+    pub fn untested_count(&self) -> usize {
+        self.remaining
+            .iter()
+            .filter(|sha| Some(*sha) != self.bad.as_ref() && !self.skipped.contains(sha))
+            .count()
+    }
+
+    /// Roughly how many more commits have to be tested to find the first bad one.
+    pub fn steps_left(&self) -> usize {
+        let untested = self.untested_count();
+        (usize::BITS - untested.leading_zeros()) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_bisect_state() {
+        let mut state = BisectState::from_bisect_refs(
+            "refs/bisect/bad\0aaaa\n\
+             refs/bisect/good-bbbb\0bbbb\n\
+             refs/bisect/skip-cccc\0cccc\n\
+             refs/heads/main\0dddd\n",
+        );
+        assert_eq!(state.bad.as_deref(), Some("aaaa"));
+        assert_eq!(state.good, vec![SharedString::from("bbbb")]);
+        assert_eq!(state.skipped, vec![SharedString::from("cccc")]);
+
+        state.remaining = vec!["aaaa".into(), "cccc".into(), "eeee".into(), "ffff".into()];
+        assert_eq!(state.untested_count(), 2);
+        assert_eq!(state.steps_left(), 2);
+        assert_eq!(state.first_bad(), None);
+
+        state.remaining = vec!["aaaa".into()];
+        assert_eq!(state.steps_left(), 0);
+        assert_eq!(state.first_bad().map(|sha| sha.as_ref()), Some("aaaa"));
+    }
+
+    #[test]
+    fn test_verdict_from_exit_code() {
+        assert_eq!(BisectVerdict::from_exit_code(0), Some(BisectVerdict::Good));
+        assert_eq!(BisectVerdict::from_exit_code(1), Some(BisectVerdict::Bad));
+        assert_eq!(
+            BisectVerdict::from_exit_code(125),
+            Some(BisectVerdict::Skip)
+        );
+        assert_eq!(BisectVerdict::from_exit_code(128), None);
+        assert_eq!(BisectVerdict::from_exit_code(-1), None);
+    }
+}
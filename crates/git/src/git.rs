@@ -1,10 +1,15 @@
+pub mod bisect;
 pub mod blame;
 pub mod commit;
 mod hosting_provider;
+pub mod line_history;
+pub mod log_filter;
+pub mod rebase;
 mod remote;
 pub mod repository;
 pub mod stash;
 pub mod status;
+pub mod submodule;
 
 pub use crate::hosting_provider::*;
 pub use crate::remote::*;
@@ -49,6 +54,10 @@ actions!(
         Blame,
         /// Shows the git history for the current file.
         FileHistory,
+        /// Shows the git history of the selected lines, following them across renames.
+        LineHistory,
+        /// Opens the three-way merge editor for the current conflicted file.
+        OpenMergeEditor,
         /// Stages the current file.
         StageFile,
         /// Unstages the current file.
@@ -80,10 +89,25 @@ actions!(
         Pull,
         /// Pulls changes from the remote repository with rebase.
         PullRebase,
+        /// Interactively rebases the current branch onto its upstream.
+        InteractiveRebase,
+        /// Continues the rebase in progress.
+        RebaseContinue,
+        /// Skips the commit the rebase in progress stopped at.
+        RebaseSkip,
+        /// Aborts the rebase in progress.
+        RebaseAbort,
         /// Fetches changes from the remote repository.
         Fetch,
         /// Fetches changes from a specific remote.
         FetchFrom,
+        /// Initializes the submodules of the repository.
+        SubmoduleInit,
+        /// Updates the submodules to the commits recorded in the repository,
+        /// cloning them first if needed.
+        SubmoduleUpdate,
+        /// Syncs the submodule remote URLs with `.gitmodules`.
+        SubmoduleSync,
         /// Creates a new commit with staged changes.
         Commit,
         /// Amends the last commit with staged changes.
@@ -0,0 +1,155 @@
+use crate::repository::{FileHistoryEntry, RepoPath};
+use gpui::SharedString;
+use std::ops::Range;
+
+/// Separates the commits in the output of `git log -L`. The patch follows the
+/// formatted metadata, so the delimiter has to come first.
+pub(crate) const COMMIT_START: &str = "<<COMMIT_START-9b1e4f2a-6c3d-4a8e-b7f5-2d9c0e1a3b6f>>";
+
+/// The `--format` argument matching [`parse_line_history`].
+pub(crate) fn line_history_format() -> String {
+    format!("--format={COMMIT_START}%H%x00%s%x00%B%x00%at%x00%an%x00%ae%x00")
+}
+
+/// The `-L` argument for the zero-based, end-exclusive `rows` of `path`.
+pub(crate) fn line_range_arg(path: &RepoPath, rows: &Range<u32>) -> String {
+    let start = rows.start + 1;
+    let end = rows.end.max(start);
+    format!("-L{start},{end}:{}", path.as_unix_str())
+}
+
+/// A commit that changed the tracked lines, along with how it changed them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineHistoryEntry {
+    pub commit: FileHistoryEntry,
+    /// The path of the file in this commit, which differs from the requested
+    /// one before a rename.
+    pub path: RepoPath,
+    /// The part of the commit's diff touching the lines, starting at its first
+    /// `@@` header.
+    pub hunk: SharedString,
+}
+
+#[derive(Clone, Debug)]
+pub struct LineHistory {
+    pub entries: Vec<LineHistoryEntry>,
+    pub path: RepoPath,
+    /// The zero-based, end-exclusive rows whose history was requested.
+    pub rows: Range<u32>,
+}
+
+/// Parses the output of `git log -L` run with [`line_history_format`].
+pub(crate) fn parse_line_history(output: &str, path: &RepoPath) -> Vec<LineHistoryEntry> {
+    output
+        .split(COMMIT_START)
+        .filter_map(|block| {
+            let mut fields = block.splitn(7, '\0');
+            let sha = fields.next()?.trim();
+            if sha.is_empty() {
+                return None;
+            }
+            let subject = fields.next()?.trim();
+            let message = fields.next()?.trim();
+            let commit_timestamp = fields.next()?.trim().parse().unwrap_or(0);
+            let author_name = fields.next()?.trim();
+            let author_email = fields.next()?.trim();
+            let patch = fields.next().unwrap_or_default();
+
+            let path = patch
+                .lines()
+                .find_map(|line| line.strip_prefix("+++ b/"))
+                .and_then(|path| RepoPath::new(unquote(path)).ok())
+                .unwrap_or_else(|| path.clone());
+            let hunk = patch
+                .find("@@")
+                .map_or("", |ix| patch[ix..].trim_end_matches('\n'));
+
+            Some(LineHistoryEntry {
+                commit: FileHistoryEntry {
+                    sha: sha.to_string().into(),
+                    subject: subject.to_string().into(),
+                    message: message.to_string().into(),
+                    commit_timestamp,
+                    author_name: author_name.to_string().into(),
+                    author_email: author_email.to_string().into(),
+                },
+                path,
+                hunk: hunk.to_string().into(),
+            })
+        })
+        .collect()
+}
+
+/// Git quotes paths containing unusual characters in diff headers.
+fn unquote(path: &str) -> &str {
+    path.strip_prefix('"')
+        .and_then(|path| path.strip_suffix('"'))
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::repo_path;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_line_range_arg() {
+        assert_eq!(
+            line_range_arg(&repo_path("src/lib.rs"), &(9..20)),
+            "-L10,20:src/lib.rs"
+        );
+        assert_eq!(line_range_arg(&repo_path("a.rs"), &(4..4)), "-L5,5:a.rs");
+    }
+
+    #[test]
+    fn test_parse_line_history() {
+        let output = format!(
+            "{COMMIT_START}aaaa\0Tweak parse\0Tweak parse\n\nMore details.\n\0200\0Jane\0jane@example.com\0\n\
+             diff --git a/src/parse.rs b/src/parse.rs\n\
+             --- a/src/parse.rs\n\
+             +++ b/src/parse.rs\n\
+             @@ -3,2 +3,2 @@\n\
+             \x20fn parse() {{\n\
+             -    old();\n\
+             +    new();\n\
+             \n\
+             {COMMIT_START}bbbb\0Add parse\0Add parse\n\0100\0John\0john@example.com\0\n\
+             diff --git a/src/old.rs b/src/old.rs\n\
+             --- /dev/null\n\
+             +++ b/src/old.rs\n\
+             @@ -0,0 +1,2 @@\n\
+             +fn parse() {{\n\
+             +    old();\n"
+        );
+        assert_eq!(
+            parse_line_history(&output, &repo_path("src/parse.rs")),
+            vec![
+                LineHistoryEntry {
+                    commit: FileHistoryEntry {
+                        sha: "aaaa".into(),
+                        subject: "Tweak parse".into(),
+                        message: "Tweak parse\n\nMore details.".into(),
+                        commit_timestamp: 200,
+                        author_name: "Jane".into(),
+                        author_email: "jane@example.com".into(),
+                    },
+                    path: repo_path("src/parse.rs"),
+                    hunk: "@@ -3,2 +3,2 @@\n fn parse() {\n-    old();\n+    new();".into(),
+                },
+                LineHistoryEntry {
+                    commit: FileHistoryEntry {
+                        sha: "bbbb".into(),
+                        subject: "Add parse".into(),
+                        message: "Add parse".into(),
+                        commit_timestamp: 100,
+                        author_name: "John".into(),
+                        author_email: "john@example.com".into(),
+                    },
+                    path: repo_path("src/old.rs"),
+                    hunk: "@@ -0,0 +1,2 @@\n+fn parse() {\n+    old();".into(),
+                },
+            ]
+        );
+    }
+}
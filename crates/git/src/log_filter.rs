@@ -0,0 +1,199 @@
+use crate::Oid;
+use crate::repository::InitialGraphCommitData;
+use collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::sync::Arc;
+
+/// Restricts which commits are shown in the git graph.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LogFilter {
+    /// Only keep commits whose author matches this pattern, case-insensitively.
+    pub author: Option<String>,
+    /// Only keep commits touching a file matching one of these globs.
+    pub paths: Vec<String>,
+    /// Only keep commits newer than this date, in any format `git log --since` accepts.
+    pub since: Option<String>,
+    /// Only keep commits older than this date, in any format `git log --until` accepts.
+    pub until: Option<String>,
+    /// Only walk the history of these branches, or of all refs when empty.
+    pub branches: Vec<String>,
+}
+
+impl LogFilter {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether the filter keeps commits based on their metadata rather than
+    /// only on the files they touch. Git doesn't rewrite parents in that case,
+    /// so [`rewrite_parents`] has to reconnect the remaining commits.
+    pub fn filters_commits(&self) -> bool {
+        self.author.is_some() || self.since.is_some() || self.until.is_some()
+    }
+
+    /// The revisions to walk, followed by the pathspecs if there are any.
+    pub fn revision_args(&self) -> Vec<String> {
+        let mut args = if self.branches.is_empty() {
+            vec!["--all".to_string()]
+        } else {
+            self.branches.clone()
+        };
+        if !self.paths.is_empty() {
+            args.push("--".to_string());
+            args.extend(self.paths.iter().map(|path| format!(":(glob){path}")));
+        }
+        args
+    }
+
+    pub fn commit_filter_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(author) = &self.author {
+            args.push(format!("--author={author}"));
+            args.push("--regexp-ignore-case".to_string());
+        }
+        if let Some(since) = &self.since {
+            args.push(format!("--since={since}"));
+        }
+        if let Some(until) = &self.until {
+            args.push(format!("--until={until}"));
+        }
+        args
+    }
+}
+
+/// Drops the commits that aren't in `kept` and points the parents of the
+/// remaining ones at their nearest kept ancestors, so the graph stays connected.
+pub fn rewrite_parents(
+    commits: Vec<Arc<InitialGraphCommitData>>,
+    kept: &HashSet<Oid>,
+) -> Vec<Arc<InitialGraphCommitData>> {
+    let parents_by_sha: HashMap<Oid, &[Oid]> = commits
+        .iter()
+        .map(|commit| (commit.sha, commit.parents.as_slice()))
+        .collect();
+    let mut rewritten: HashMap<Oid, SmallVec<[Oid; 1]>> = HashMap::default();
+
+    for commit in &commits {
+        if !kept.contains(&commit.sha) {
+            continue;
+        }
+        let mut stack = vec![commit.sha];
+        while let Some(&sha) = stack.last() {
+            if rewritten.contains_key(&sha) {
+                stack.pop();
+                continue;
+            }
+            let parents = parents_by_sha.get(&sha).copied().unwrap_or_default();
+            let pending = parents
+                .iter()
+                .filter(|parent| !kept.contains(*parent) && !rewritten.contains_key(*parent))
+                .copied()
+                .collect::<Vec<_>>();
+            if !pending.is_empty() {
+                stack.extend(pending);
+                continue;
+            }
+
+            let mut nearest_kept = SmallVec::<[Oid; 1]>::new();
+            for parent in parents {
+                if kept.contains(parent) {
+                    if !nearest_kept.contains(parent) {
+                        nearest_kept.push(*parent);
+                    }
+                } else {
+                    for ancestor in &rewritten[parent] {
+                        if !nearest_kept.contains(ancestor) {
+                            nearest_kept.push(*ancestor);
+                        }
+                    }
+                }
+            }
+            rewritten.insert(sha, nearest_kept);
+            stack.pop();
+        }
+    }
+
+    commits
+        .into_iter()
+        .filter(|commit| kept.contains(&commit.sha))
+        .map(|commit| {
+            Arc::new(InitialGraphCommitData {
+                sha: commit.sha,
+                parents: rewritten.remove(&commit.sha).unwrap_or_default(),
+                ref_names: commit.ref_names.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn oid(n: u8) -> Oid {
+        Oid::from_bytes(&[n; 20]).unwrap()
+    }
+
+    fn commit(sha: u8, parents: &[u8]) -> Arc<InitialGraphCommitData> {
+        Arc::new(InitialGraphCommitData {
+            sha: oid(sha),
+            parents: parents.iter().map(|parent| oid(*parent)).collect(),
+            ref_names: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_log_filter_args() {
+        let filter = LogFilter {
+            author: Some("jane".into()),
+            paths: vec!["src/**/*.rs".into()],
+            since: Some("2 weeks ago".into()),
+            until: None,
+            branches: vec!["main".into(), "feature".into()],
+        };
+        assert_eq!(
+            filter.revision_args(),
+            vec!["main", "feature", "--", ":(glob)src/**/*.rs"]
+        );
+        assert_eq!(
+            filter.commit_filter_args(),
+            vec![
+                "--author=jane",
+                "--regexp-ignore-case",
+                "--since=2 weeks ago"
+            ]
+        );
+        assert!(filter.filters_commits());
+        assert_eq!(LogFilter::default().revision_args(), vec!["--all"]);
+        assert!(LogFilter::default().is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_parents() {
+        // 6 merges 4 (hidden) and 5, which both come from 3 (hidden) on top
+        // of 2 (hidden) on top of 1.
+        let commits = vec![
+            commit(6, &[4, 5]),
+            commit(5, &[3]),
+            commit(4, &[3]),
+            commit(3, &[2]),
+            commit(2, &[1]),
+            commit(1, &[]),
+        ];
+        let kept = [oid(6), oid(5), oid(1)].into_iter().collect::<HashSet<_>>();
+        let rewritten = rewrite_parents(commits, &kept);
+        assert_eq!(
+            rewritten
+                .iter()
+                .map(|commit| (commit.sha, commit.parents.to_vec()))
+                .collect::<Vec<_>>(),
+            vec![
+                (oid(6), vec![oid(1), oid(5)]),
+                (oid(5), vec![oid(1)]),
+                (oid(1), vec![]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use gpui::SharedString;
+use std::path::Path;
+
+/// Name of the directory inside the git directory where reworded commit
+/// messages are kept while an interactive rebase is running.
+pub const REBASE_MESSAGES_DIR: &str = "zed-rebase-messages";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RebaseAction {
+    #[default]
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [RebaseAction; 6] = [
+        RebaseAction::Pick,
+        RebaseAction::Reword,
+        RebaseAction::Edit,
+        RebaseAction::Squash,
+        RebaseAction::Fixup,
+        RebaseAction::Drop,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Edit => "edit",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+
+    pub fn from_todo_command(command: &str) -> Option<Self> {
+        Some(match command {
+            "p" | "pick" => RebaseAction::Pick,
+            "r" | "reword" => RebaseAction::Reword,
+            "e" | "edit" => RebaseAction::Edit,
+            "s" | "squash" => RebaseAction::Squash,
+            "f" | "fixup" => RebaseAction::Fixup,
+            "d" | "drop" => RebaseAction::Drop,
+            _ => return None,
+        })
+    }
+
+    /// Whether this action folds the commit into the one before it.
+    pub fn melds_into_previous(&self) -> bool {
+        matches!(self, RebaseAction::Squash | RebaseAction::Fixup)
+    }
+}
+
+/// A single commit line of an interactive rebase todo list.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RebaseTodoEntry {
+    pub action: RebaseAction,
+    pub sha: SharedString,
+    pub subject: SharedString,
+    /// The new message for a [`RebaseAction::Reword`] entry.
+    ///
+    /// A reworded entry without a message keeps the original one.
+    pub message: Option<SharedString>,
+}
+
+impl RebaseTodoEntry {
+    pub fn pick(sha: impl Into<SharedString>, subject: impl Into<SharedString>) -> Self {
+        Self {
+            action: RebaseAction::Pick,
+            sha: sha.into(),
+            subject: subject.into(),
+            message: None,
+        }
+    }
+}
+
+/// The progress of an interactive rebase that is currently stopped, either
+/// because of conflicts or because a commit was marked for editing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RebaseState {
+    pub head_name: Option<SharedString>,
+    pub onto: Option<SharedString>,
+    pub stopped_sha: Option<SharedString>,
+    pub done: Vec<RebaseTodoEntry>,
+    pub remaining: Vec<RebaseTodoEntry>,
+}
+
+impl RebaseState {
+    /// Reads the state of an in-progress interactive rebase from the given git
+    /// directory, returning `None` if no rebase is in progress.
+    pub fn load(git_dir: &Path) -> Option<Self> {
+        let rebase_dir = git_dir.join("rebase-merge");
+        if !rebase_dir.is_dir() {
+            return None;
+        }
+        let read = |name: &str| {
+            std::fs::read_to_string(rebase_dir.join(name))
+                .ok()
+                .map(|contents| contents.trim().to_string())
+                .filter(|contents| !contents.is_empty())
+        };
+        Some(Self {
+            head_name: read("head-name").map(|name| {
+                name.strip_prefix("refs/heads/")
+                    .unwrap_or(&name)
+                    .to_string()
+                    .into()
+            }),
+            onto: read("onto").map(Into::into),
+            stopped_sha: read("stopped-sha").map(Into::into),
+            done: parse_rebase_todo(&read("done").unwrap_or_default()),
+            remaining: parse_rebase_todo(&read("git-rebase-todo").unwrap_or_default()),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RebaseOperation {
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl RebaseOperation {
+    pub fn as_flag(&self) -> &'static str {
+        match self {
+            RebaseOperation::Continue => "--continue",
+            RebaseOperation::Skip => "--skip",
+            RebaseOperation::Abort => "--abort",
+        }
+    }
+}
+
+/// Parses the commit lines of a git-rebase-todo (or done) file.
+///
+/// Comments and commands that don't refer to a commit are ignored, except for
+/// the `exec` lines that [`render_rebase_todo`] emits for reworded commits,
+/// which mark the preceding entry as a reword.
+pub fn parse_rebase_todo(text: &str) -> Vec<RebaseTodoEntry> {
+    let mut entries: Vec<RebaseTodoEntry> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        if matches!(command, "x" | "exec") {
+            if rest.contains(REBASE_MESSAGES_DIR)
+                && let Some(previous) = entries.last_mut()
+            {
+                previous.action = RebaseAction::Reword;
+            }
+            continue;
+        }
+        let Some(action) = RebaseAction::from_todo_command(command) else {
+            continue;
+        };
+        // `fixup -C` and `fixup -c` carry an extra flag before the sha.
+        let rest = rest
+            .strip_prefix("-C ")
+            .or_else(|| rest.strip_prefix("-c "))
+            .unwrap_or(rest)
+            .trim_start();
+        let (sha, subject) = rest.split_once(' ').unwrap_or((rest, ""));
+        if sha.is_empty() {
+            continue;
+        }
+        entries.push(RebaseTodoEntry {
+            action,
+            sha: sha.to_string().into(),
+            subject: subject.trim().to_string().into(),
+            message: None,
+        });
+    }
+    entries
+}
+
+/// Renders the given entries as a git-rebase-todo file.
+///
+/// Git would open an editor for reworded commits, so those are instead picked
+/// and then amended with the message stored at the path returned by
+/// `message_path` for the entry's index.
+pub fn render_rebase_todo(
+    entries: &[RebaseTodoEntry],
+    message_path: impl Fn(usize) -> String,
+) -> String {
+    let mut todo = String::new();
+    for (ix, entry) in entries.iter().enumerate() {
+        let subject = entry.subject.lines().next().unwrap_or_default();
+        match (&entry.action, &entry.message) {
+            (RebaseAction::Reword, Some(_)) => {
+                todo.push_str(&format!("pick {} {}\n", entry.sha, subject));
+                todo.push_str(&format!(
+                    "exec git commit --amend --only --no-verify --allow-empty -F {}\n",
+                    shell_quote(&message_path(ix))
+                ));
+            }
+            (action, _) => {
+                todo.push_str(&format!("{} {} {}\n", action.as_str(), entry.sha, subject));
+            }
+        }
+    }
+    todo
+}
+
+pub(crate) fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_rebase_todo() {
+        let todo = "\
+pick 1111111 First commit
+r 2222222 Second commit
+exec git commit --amend -F '/repo/.git/zed-rebase-messages/1'
+fixup -C 3333333 Third commit
+
+# Rebase aaaaaaa..3333333 onto aaaaaaa (3 commands)
+break
+d 4444444 Fourth commit
+";
+        let entries = parse_rebase_todo(todo);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.action, entry.sha.as_ref(), entry.subject.as_ref()))
+                .collect::<Vec<_>>(),
+            vec![
+                (RebaseAction::Pick, "1111111", "First commit"),
+                (RebaseAction::Reword, "2222222", "Second commit"),
+                (RebaseAction::Fixup, "3333333", "Third commit"),
+                (RebaseAction::Drop, "4444444", "Fourth commit"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_rebase_todo() {
+        let entries = vec![
+            RebaseTodoEntry::pick("1111111", "First commit"),
+            RebaseTodoEntry {
+                action: RebaseAction::Reword,
+                sha: "2222222".into(),
+                subject: "Second commit".into(),
+                message: Some("Better message".into()),
+            },
+            RebaseTodoEntry {
+                action: RebaseAction::Squash,
+                ..RebaseTodoEntry::pick("3333333", "Third commit")
+            },
+        ];
+        let todo = render_rebase_todo(&entries, |ix| format!("/tmp/it's/{ix}"));
+        assert_eq!(
+            todo,
+            "\
+pick 1111111 First commit
+pick 2222222 Second commit
+exec git commit --amend --only --no-verify --allow-empty -F '/tmp/it'\\''s/1'
+squash 3333333 Third commit
+"
+        );
+        assert_eq!(
+            parse_rebase_todo(&todo)
+                .into_iter()
+                .map(|entry| entry.action)
+                .collect::<Vec<_>>(),
+            vec![
+                RebaseAction::Pick,
+                RebaseAction::Reword,
+                RebaseAction::Squash
+            ]
+        );
+    }
+}
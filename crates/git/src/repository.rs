@@ -1,6 +1,17 @@
+use crate::bisect::{BisectState, BisectVerdict};
 use crate::commit::parse_git_diff_name_status;
+use crate::line_history::{LineHistory, line_history_format, line_range_arg, parse_line_history};
+use crate::log_filter::{LogFilter, rewrite_parents};
+use crate::rebase::{
+    REBASE_MESSAGES_DIR, RebaseOperation, RebaseState, RebaseTodoEntry, render_rebase_todo,
+    shell_quote,
+};
 use crate::stash::GitStash;
 use crate::status::{DiffTreeType, GitStatus, StatusCode, TreeDiff};
+use crate::submodule::{
+    Submodule, SubmoduleCommit, SubmoduleDiff, SubmoduleOperation, parse_submodule_commits,
+    parse_submodule_status,
+};
 use crate::{Oid, RunHook, SHORT_SHA_LENGTH};
 use anyhow::{Context as _, Result, anyhow, bail};
 use collections::HashMap;
@@ -28,6 +39,7 @@ use std::str::FromStr;
 use std::{
     cmp::Ordering,
     future,
+    ops::Range,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -475,6 +487,18 @@ pub struct FileHistoryEntry {
     pub author_email: SharedString,
 }
 
+/// The three versions of a conflicted file. Each of them is missing when the
+/// file doesn't exist on that side, e.g. when it was added on both branches.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConflictStages {
+    /// The merge base, from index stage 1.
+    pub base: Option<String>,
+    /// The current branch's version, from index stage 2.
+    pub ours: Option<String>,
+    /// The incoming version, from index stage 3.
+    pub theirs: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct FileHistory {
     pub entries: Vec<FileHistoryEntry>,
@@ -536,6 +560,8 @@ pub enum ResetMode {
     /// Reset the branch pointer and index, leave worktree unchanged (this makes it look as though things that were
     /// committed are now unstaged).
     Mixed,
+    /// Reset the branch pointer, index and worktree, discarding all uncommitted changes.
+    Hard,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -706,16 +732,29 @@ pub enum LogSource {
     All,
     Branch(SharedString),
     Sha(Oid),
+    Filtered(LogFilter),
 }
 
 impl LogSource {
-    fn get_arg(&self) -> Result<&str> {
+    fn args(&self) -> Result<Vec<String>> {
         match self {
-            LogSource::All => Ok("--all"),
-            LogSource::Branch(branch) => Ok(branch.as_str()),
-            LogSource::Sha(oid) => {
-                str::from_utf8(oid.as_bytes()).context("Failed to build str from sha")
-            }
+            LogSource::All => Ok(vec!["--all".to_string()]),
+            LogSource::Branch(branch) => Ok(vec![branch.to_string()]),
+            LogSource::Sha(oid) => Ok(vec![
+                str::from_utf8(oid.as_bytes())
+                    .context("Failed to build str from sha")?
+                    .to_string(),
+            ]),
+            LogSource::Filtered(filter) => Ok(filter.revision_args()),
+        }
+    }
+
+    /// Whether the source walks every ref, and so changes whenever the stash does.
+    pub fn includes_all_refs(&self) -> bool {
+        match self {
+            LogSource::All => true,
+            LogSource::Filtered(filter) => filter.branches.is_empty(),
+            LogSource::Branch(_) | LogSource::Sha(_) => false,
         }
     }
 }
@@ -739,6 +778,9 @@ pub trait GitRepository: Send + Sync {
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>>;
     fn load_blob_content(&self, oid: Oid) -> BoxFuture<'_, Result<String>>;
 
+    /// Returns the versions of a conflicted file recorded in the index stages.
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>>;
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -830,6 +872,9 @@ pub trait GitRepository: Send + Sync {
         skip: usize,
         limit: Option<usize>,
     ) -> BoxFuture<'_, Result<FileHistory>>;
+    /// Lists the commits that changed the given zero-based, end-exclusive
+    /// `rows` of `path`, following them across renames.
+    fn line_history(&self, path: RepoPath, rows: Range<u32>) -> BoxFuture<'_, Result<LineHistory>>;
 
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
     /// worktree's gitdir within the main repository (typically `.git/worktrees/<name>`).
@@ -893,6 +938,105 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Returns the commits that an interactive rebase onto `base` would replay,
+    /// oldest first, all set to be picked.
+    fn rebase_todo(&self, base: String) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>>;
+
+    /// Starts an interactive rebase onto `base` using the given todo list.
+    ///
+    /// Stopping because of conflicts or an `edit` entry is not an error; the
+    /// progress can be inspected with [`GitRepository::rebase_state`].
+    fn rebase_interactive(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Continues, skips or aborts the rebase in progress.
+    fn rebase_operation(
+        &self,
+        operation: RebaseOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the progress of the interactive rebase in progress, if any.
+    fn rebase_state(&self) -> BoxFuture<'_, Result<Option<RebaseState>>>;
+
+    /// Applies the changes introduced by `commit` on top of HEAD.
+    ///
+    /// Stopping because of conflicts is not an error; they are reported through
+    /// the repository's merge details like any other conflicted operation.
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates a new commit that undoes the changes introduced by `commit`.
+    ///
+    /// Like [`GitRepository::cherry_pick`], stopping because of conflicts is not
+    /// an error.
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates a tag pointing at `commit`. The tag is annotated when a message is
+    /// given, and lightweight otherwise.
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn delete_tag(
+        &self,
+        name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Starts a bisect session. Commits are then marked with
+    /// [`GitRepository::bisect_mark`], which checks out the next one to test.
+    fn bisect_start(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Marks `commit`, or the checked out commit if `None`, as good, bad or skipped.
+    fn bisect_mark(
+        &self,
+        verdict: BisectVerdict,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Ends the bisect session and checks out the branch it was started from.
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the progress of the bisect session in progress, if any.
+    fn bisect_state(&self) -> BoxFuture<'_, Result<Option<BisectState>>>;
+
+    /// Lists the submodules registered in the repository.
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>>;
+
+    /// Runs a submodule command for the given submodules, or for all of them
+    /// if `paths` is empty.
+    fn submodule_operation(
+        &self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Lists the commits between the submodule commit recorded in `HEAD` and
+    /// the one currently checked out in the submodule.
+    fn submodule_diff(&self, path: RepoPath) -> BoxFuture<'_, Result<SubmoduleDiff>>;
+
     fn push(
         &self,
         branch_name: String,
@@ -1387,6 +1531,7 @@ impl GitRepository for RealGitRepository {
             let mode_flag = match mode {
                 ResetMode::Mixed => "--mixed",
                 ResetMode::Soft => "--soft",
+                ResetMode::Hard => "--hard",
             };
 
             let git = git_binary?;
@@ -1497,6 +1642,39 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                const STAGE_BASE: i32 = 1;
+                const STAGE_OURS: i32 = 2;
+                const STAGE_THEIRS: i32 = 3;
+
+                let repo = repo.lock();
+                let mut index = repo.index()?;
+                index.read(false)?;
+                // git2 unwraps internally on empty paths or `.`
+                if path.is_empty() {
+                    bail!("empty path has no conflict stages");
+                }
+                let load_stage = |stage| -> Result<Option<String>> {
+                    let Some(entry) = index.get_path(path.as_std_path(), stage) else {
+                        return Ok(None);
+                    };
+                    let content = repo.find_blob(entry.id)?.content().to_owned();
+                    Ok(Some(
+                        String::from_utf8(content).context("conflicted file is not UTF-8")?,
+                    ))
+                };
+                Ok(ConflictStages {
+                    base: load_stage(STAGE_BASE)?,
+                    ours: load_stage(STAGE_OURS)?,
+                    theirs: load_stage(STAGE_THEIRS)?,
+                })
+            })
+            .boxed()
+    }
+
     fn load_blob_content(&self, oid: Oid) -> BoxFuture<'_, Result<String>> {
         let repo = self.repository.clone();
         self.executor
@@ -2145,6 +2323,39 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn line_history(&self, path: RepoPath, rows: Range<u32>) -> BoxFuture<'_, Result<LineHistory>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let format = line_history_format();
+                let line_range = line_range_arg(&path, &rows);
+                let output = git
+                    .build_command(&[
+                        "log",
+                        "--no-color",
+                        "--no-ext-diff",
+                        format.as_str(),
+                        line_range.as_str(),
+                    ])
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    bail!("git log failed: {stderr}");
+                }
+
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                Ok(LineHistory {
+                    entries: parse_line_history(&stdout, &path),
+                    path,
+                    rows,
+                })
+            })
+            .boxed()
+    }
+
     fn diff(&self, diff: DiffType) -> BoxFuture<'_, Result<String>> {
         let git_binary = self.git_binary();
         self.executor
@@ -2359,81 +2570,494 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
-    fn commit(
+    fn rebase_todo(&self, base: String) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .run(&[
+                        "log",
+                        "--reverse",
+                        "--topo-order",
+                        "--no-merges",
+                        "--format=%H%x00%s",
+                        &format!("{base}..HEAD"),
+                        "--",
+                    ])
+                    .await?;
+                Ok(output
+                    .lines()
+                    .filter_map(|line| {
+                        let (sha, subject) = line.split_once('\0')?;
+                        Some(RebaseTodoEntry::pick(sha.to_string(), subject.to_string()))
+                    })
+                    .collect())
+            })
+            .boxed()
+    }
+
+    fn rebase_interactive(
         &self,
-        message: SharedString,
-        name_and_email: Option<(SharedString, SharedString)>,
-        options: CommitOptions,
-        ask_pass: AskPassDelegate,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
         let git_binary = self.git_binary();
-        let executor = self.executor.clone();
-        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
-        // which we want to block on.
-        async move {
-            let git = git_binary?;
-            let mut cmd = git.build_command(&["commit", "--quiet", "-m"]);
-            cmd.envs(env.iter())
-                .arg(&message.to_string())
-                .arg("--cleanup=strip")
-                .arg("--no-verify")
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                anyhow::ensure!(!todo.is_empty(), "Nothing to rebase");
+                anyhow::ensure!(
+                    RebaseState::load(&git_dir).is_none(),
+                    "A rebase is already in progress"
+                );
 
-            if options.amend {
-                cmd.arg("--amend");
-            }
+                let messages_dir = git_dir.join(REBASE_MESSAGES_DIR);
+                smol::fs::remove_dir_all(&messages_dir).await.ok();
+                smol::fs::create_dir_all(&messages_dir).await?;
+                for (ix, entry) in todo.iter().enumerate() {
+                    if let Some(message) = &entry.message {
+                        smol::fs::write(messages_dir.join(ix.to_string()), message.as_bytes())
+                            .await?;
+                    }
+                }
+                let todo_path = messages_dir.join("git-rebase-todo");
+                let todo_text = render_rebase_todo(&todo, |ix| {
+                    messages_dir
+                        .join(ix.to_string())
+                        .to_string_lossy()
+                        .into_owned()
+                });
+                smol::fs::write(&todo_path, todo_text).await?;
 
-            if options.signoff {
-                cmd.arg("--signoff");
-            }
+                let mut command =
+                    git.build_command(&["rebase", "--interactive", "--no-autosquash", &base]);
+                command.envs(env.iter()).env(
+                    "GIT_SEQUENCE_EDITOR",
+                    format!("cp {}", shell_quote(&todo_path.to_string_lossy())),
+                );
+                run_rebase_command(command, &git_dir).await
+            })
+            .boxed()
+    }
 
-            if options.allow_empty {
-                cmd.arg("--allow-empty");
-            }
+    fn rebase_operation(
+        &self,
+        operation: RebaseOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let mut command = git.build_command(&["rebase", operation.as_flag()]);
+                command.envs(env.iter());
+                run_rebase_command(command, &git_dir).await
+            })
+            .boxed()
+    }
 
-            if let Some((name, email)) = name_and_email {
-                cmd.arg("--author").arg(&format!("{name} <{email}>"));
-            }
+    fn rebase_state(&self) -> BoxFuture<'_, Result<Option<RebaseState>>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { Ok(RebaseState::load(&git_dir)) })
+            .boxed()
+    }
 
-            run_git_command(env, ask_pass, cmd, executor).await?;
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let mut command = git.build_command(&["cherry-pick", &commit]);
+                command.envs(env.iter());
+                run_sequencer_command(command, &git_dir.join("CHERRY_PICK_HEAD"), "cherry-pick")
+                    .await
+            })
+            .boxed()
+    }
 
-            Ok(())
-        }
-        .boxed()
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let mut command = git.build_command(&["revert", "--no-edit", &commit]);
+                command.envs(env.iter());
+                run_sequencer_command(command, &git_dir.join("REVERT_HEAD"), "revert").await
+            })
+            .boxed()
     }
 
-    fn update_ref(&self, ref_name: String, commit: String) -> BoxFuture<'_, Result<()>> {
-        self.edit_ref(RefEdit::Update { ref_name, commit })
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let mut args = vec!["tag"];
+                if let Some(message) = message.as_deref() {
+                    args.extend(["-a", "-m", message]);
+                }
+                args.extend(["--", &name, &commit]);
+                let output = git.build_command(&args).envs(env.iter()).output().await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to create tag:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
     }
 
-    fn delete_ref(&self, ref_name: String) -> BoxFuture<'_, Result<()>> {
-        self.edit_ref(RefEdit::Delete { ref_name })
+    fn delete_tag(
+        &self,
+        name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&["tag", "-d", "--", &name])
+                    .envs(env.iter())
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to delete tag:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
     }
 
-    fn repair_worktrees(&self) -> BoxFuture<'_, Result<()>> {
+    fn bisect_start(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
         let git_binary = self.git_binary();
         self.executor
             .spawn(async move {
-                let args: Vec<OsString> = vec!["worktree".into(), "repair".into()];
-                git_binary?.run(&args).await?;
+                let git = git_binary?;
+                let output = git
+                    .build_command(&["bisect", "start"])
+                    .envs(env.iter())
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to start bisect:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
                 Ok(())
             })
             .boxed()
     }
 
-    fn push(
+    fn bisect_mark(
         &self,
-        branch_name: String,
-        remote_branch_name: String,
-        remote_name: String,
-        options: Option<PushOptions>,
-        ask_pass: AskPassDelegate,
+        verdict: BisectVerdict,
+        commit: Option<String>,
         env: Arc<HashMap<String, String>>,
-        cx: AsyncApp,
-    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
-        let working_directory = self.working_directory();
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let mut args = vec!["bisect", verdict.as_str()];
+                args.extend(commit.as_deref());
+                let output = git.build_command(&args).envs(env.iter()).output().await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to mark commit as {}:\n{}",
+                    verdict.as_str(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&["bisect", "reset"])
+                    .envs(env.iter())
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to reset bisect:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn bisect_state(&self) -> BoxFuture<'_, Result<Option<BisectState>>> {
+        let git_binary = self.git_binary();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                if smol::fs::metadata(git_dir.join("BISECT_START"))
+                    .await
+                    .is_err()
+                {
+                    return Ok(None);
+                }
+                let git = git_binary?;
+                let refs = git
+                    .run(&[
+                        "for-each-ref",
+                        "--format=%(refname)%00%(objectname)",
+                        "refs/bisect",
+                    ])
+                    .await?;
+                let mut state = BisectState::from_bisect_refs(&refs);
+                state.current = git.run(&["rev-parse", "HEAD"]).await.ok().map(Into::into);
+                if let Some(bad) = state.bad.as_ref()
+                    && !state.good.is_empty()
+                {
+                    let mut args = vec!["rev-list", bad.as_ref(), "--not"];
+                    args.extend(state.good.iter().map(|sha| sha.as_ref()));
+                    state.remaining = git
+                        .run(&args)
+                        .await?
+                        .lines()
+                        .map(|sha| sha.to_string().into())
+                        .collect();
+                }
+                Ok(Some(state))
+            })
+            .boxed()
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git.run(&["submodule", "status"]).await?;
+                Ok(parse_submodule_status(&output))
+            })
+            .boxed()
+    }
+
+    fn submodule_operation(
+        &self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let git_directory = self.path();
+        let git_binary_path = self.system_git_binary_path.clone();
+        let executor = cx.background_executor().clone();
+        let is_trusted = self.is_trusted();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path = git_binary_path.with_context(|| {
+                format!("git not found on $PATH, can't run {}", operation.name())
+            })?;
+            let working_directory = working_directory?;
+            let git = GitBinary::new(
+                git_binary_path,
+                working_directory,
+                git_directory,
+                executor.clone(),
+                is_trusted,
+            );
+            let mut command = git.build_command(operation.args());
+            command
+                .args(paths.iter().map(|path| path.as_unix_str()))
+                .envs(env.iter())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            run_git_command(env, ask_pass, command, executor).await
+        }
+        .boxed()
+    }
+
+    fn submodule_diff(&self, path: RepoPath) -> BoxFuture<'_, Result<SubmoduleDiff>> {
+        let git_binary = self.git_binary();
+        let working_directory = self.working_directory();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let submodule_path = working_directory?.join(path.as_std_path());
+                let old_sha = git
+                    .run(&["rev-parse", &format!("HEAD:{}", path.as_unix_str())])
+                    .await
+                    .ok();
+                // Git walks up to the superproject when the submodule isn't checked out,
+                // so the submodule's HEAD is only read when it is a work tree of its own.
+                let is_checked_out = git
+                    .run(&[
+                        OsStr::new("-C"),
+                        submodule_path.as_os_str(),
+                        OsStr::new("rev-parse"),
+                        OsStr::new("--show-toplevel"),
+                    ])
+                    .await
+                    .is_ok_and(|toplevel| {
+                        let toplevel = Path::new(&toplevel);
+                        toplevel == submodule_path
+                            || std::fs::canonicalize(toplevel).is_ok_and(|toplevel| {
+                                std::fs::canonicalize(&submodule_path)
+                                    .is_ok_and(|submodule_path| submodule_path == toplevel)
+                            })
+                    });
+                let checked_out_sha = if is_checked_out {
+                    git.run(&[
+                        OsStr::new("-C"),
+                        submodule_path.as_os_str(),
+                        OsStr::new("rev-parse"),
+                        OsStr::new("HEAD"),
+                    ])
+                    .await
+                    .ok()
+                } else {
+                    None
+                };
+                let new_sha = match checked_out_sha {
+                    Some(sha) => Some(sha),
+                    // Fall back to the commit recorded in the index when the
+                    // submodule isn't checked out.
+                    None => git
+                        .run(&["rev-parse", &format!(":{}", path.as_unix_str())])
+                        .await
+                        .ok(),
+                };
+
+                let mut diff = SubmoduleDiff::default();
+                if let (Some(old_sha), Some(new_sha)) = (&old_sha, &new_sha)
+                    && old_sha != new_sha
+                {
+                    let log = async |range: String| -> Result<Vec<SubmoduleCommit>> {
+                        let output = git
+                            .run(&[
+                                OsStr::new("-C"),
+                                submodule_path.as_os_str(),
+                                OsStr::new("log"),
+                                OsStr::new("--format=%H%x00%s"),
+                                OsStr::new(&range),
+                                OsStr::new("--"),
+                            ])
+                            .await?;
+                        Ok(parse_submodule_commits(&output))
+                    };
+                    diff.added = log(format!("{old_sha}..{new_sha}")).await?;
+                    diff.removed = log(format!("{new_sha}..{old_sha}")).await?;
+                }
+                diff.old_sha = old_sha.map(Into::into);
+                diff.new_sha = new_sha.map(Into::into);
+                Ok(diff)
+            })
+            .boxed()
+    }
+
+    fn commit(
+        &self,
+        message: SharedString,
+        name_and_email: Option<(SharedString, SharedString)>,
+        options: CommitOptions,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        let executor = self.executor.clone();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git = git_binary?;
+            let mut cmd = git.build_command(&["commit", "--quiet", "-m"]);
+            cmd.envs(env.iter())
+                .arg(&message.to_string())
+                .arg("--cleanup=strip")
+                .arg("--no-verify")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            if options.amend {
+                cmd.arg("--amend");
+            }
+
+            if options.signoff {
+                cmd.arg("--signoff");
+            }
+
+            if options.allow_empty {
+                cmd.arg("--allow-empty");
+            }
+
+            if let Some((name, email)) = name_and_email {
+                cmd.arg("--author").arg(&format!("{name} <{email}>"));
+            }
+
+            run_git_command(env, ask_pass, cmd, executor).await?;
+
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn update_ref(&self, ref_name: String, commit: String) -> BoxFuture<'_, Result<()>> {
+        self.edit_ref(RefEdit::Update { ref_name, commit })
+    }
+
+    fn delete_ref(&self, ref_name: String) -> BoxFuture<'_, Result<()>> {
+        self.edit_ref(RefEdit::Delete { ref_name })
+    }
+
+    fn repair_worktrees(&self) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let args: Vec<OsString> = vec!["worktree".into(), "repair".into()];
+                git_binary?.run(&args).await?;
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn push(
+        &self,
+        branch_name: String,
+        remote_branch_name: String,
+        remote_name: String,
+        options: Option<PushOptions>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
         let git_directory = self.path();
         let executor = cx.background_executor().clone();
         let git_binary_path = self.system_git_binary_path.clone();
@@ -3015,12 +3639,44 @@ impl GitRepository for RealGitRepository {
         async move {
             let git = git_binary?;
 
-            let mut command = git.build_command(&[
-                "log",
-                GRAPH_COMMIT_FORMAT,
-                log_order.as_arg(),
-                log_source.get_arg()?,
-            ]);
+            let mut args = vec![
+                "log".to_string(),
+                GRAPH_COMMIT_FORMAT.to_string(),
+                log_order.as_arg().to_string(),
+            ];
+            let commit_filter = match &log_source {
+                LogSource::Filtered(filter) => {
+                    // Rewrites parents to skip commits that don't touch the filtered paths.
+                    args.push("--parents".to_string());
+                    filter.filters_commits().then(|| filter.clone())
+                }
+                _ => None,
+            };
+            args.extend(log_source.args()?);
+
+            if let Some(filter) = commit_filter {
+                let mut rev_list_args = vec!["rev-list".to_string()];
+                rev_list_args.extend(filter.commit_filter_args());
+                rev_list_args.extend(filter.revision_args());
+                let kept = git
+                    .run(&rev_list_args)
+                    .await?
+                    .lines()
+                    .filter_map(|line| Oid::from_str(line).ok())
+                    .collect::<collections::HashSet<_>>();
+
+                let output = git.run(&args).await?;
+                let commits = rewrite_parents(parse_initial_graph_output(output.lines()), &kept);
+                for chunk in commits.chunks(GRAPH_CHUNK_SIZE) {
+                    if request_tx.send(chunk.to_vec()).await.is_err() {
+                        log::warn!("initial_graph_data: receiver dropped while sending commits");
+                        break;
+                    }
+                }
+                return Ok(());
+            }
+
+            let mut command = git.build_command(&args);
             command.stdout(Stdio::piped());
             command.stderr(Stdio::piped());
 
@@ -3091,9 +3747,7 @@ impl GitRepository for RealGitRepository {
         async move {
             let git = git_binary?;
 
-            let mut args = vec!["log", SEARCH_COMMIT_FORMAT, log_source.get_arg()?];
-
-            args.push("--fixed-strings");
+            let mut args = vec!["log", SEARCH_COMMIT_FORMAT, "--fixed-strings"];
 
             if !search_args.case_sensitive {
                 args.push("--regexp-ignore-case");
@@ -3102,6 +3756,10 @@ impl GitRepository for RealGitRepository {
             args.push("--grep");
             args.push(search_args.query.as_str());
 
+            // Pathspecs have to come last.
+            let source_args = log_source.args()?;
+            args.extend(source_args.iter().map(String::as_str));
+
             let mut command = git.build_command(&args);
             command.stdout(Stdio::piped());
             command.stderr(Stdio::null());
@@ -3500,6 +4158,56 @@ struct GitBinaryCommandError {
     status: ExitStatus,
 }
 
+/// Runs a rebase command without ever prompting for a commit message.
+///
+/// Git exits with a failure status when the rebase stops at a conflict. That is
+/// only treated as an error if the rebase didn't make any progress, e.g. when
+/// continuing with conflicts that are still unresolved.
+async fn run_rebase_command(mut command: util::command::Command, git_dir: &Path) -> Result<()> {
+    let done_before = RebaseState::load(git_dir).map(|state| state.done.len());
+    let output = command.env("GIT_EDITOR", "true").output().await?;
+    let done_after = RebaseState::load(git_dir).map(|state| state.done.len());
+    if done_after.is_none() {
+        smol::fs::remove_dir_all(git_dir.join(REBASE_MESSAGES_DIR))
+            .await
+            .ok();
+    }
+
+    let stopped_after_progress = match (done_before, done_after) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(before), Some(after)) => after > before,
+    };
+    anyhow::ensure!(
+        output.status.success() || stopped_after_progress,
+        "Failed to rebase:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(())
+}
+
+/// Runs a cherry-pick or revert without ever prompting for a commit message.
+///
+/// A failure that creates `sequencer_head` means the operation stopped at a
+/// conflict, which is left for the user to resolve rather than reported as an
+/// error. When the file already existed, another operation is in progress and
+/// git refused to start this one.
+async fn run_sequencer_command(
+    mut command: util::command::Command,
+    sequencer_head: &Path,
+    operation: &str,
+) -> Result<()> {
+    let was_in_progress = smol::fs::metadata(sequencer_head).await.is_ok();
+    let output = command.env("GIT_EDITOR", "true").output().await?;
+    let stopped_on_conflicts = !was_in_progress && smol::fs::metadata(sequencer_head).await.is_ok();
+    anyhow::ensure!(
+        output.status.success() || stopped_on_conflicts,
+        "Failed to {operation}:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(())
+}
+
 async fn run_git_command(
     env: Arc<HashMap<String, String>>,
     ask_pass: AskPassDelegate,
@@ -4554,6 +5262,587 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_interactive_rebase(cx: &mut TestAppContext) {
+        use crate::rebase::RebaseAction;
+
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        for (file, contents, message) in [
+            ("a", "base", "Base"),
+            ("a", "one", "One"),
+            ("b", "two", "Two"),
+            ("c", "three", "Three"),
+            ("a", "four", "Four"),
+        ] {
+            smol::fs::write(repo_dir.path().join(file), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+        }
+        let log = async || {
+            let output = repo
+                .git_binary()
+                .unwrap()
+                .run(&["log", "--format=%B%x00"])
+                .await
+                .unwrap();
+            output
+                .split('\0')
+                .map(|message| message.trim().to_string())
+                .filter(|message| !message.is_empty())
+                .collect::<Vec<_>>()
+        };
+
+        let todo = repo.rebase_todo("HEAD~4".into()).await.unwrap();
+        assert_eq!(
+            todo.iter()
+                .map(|entry| entry.subject.as_ref())
+                .collect::<Vec<_>>(),
+            ["One", "Two", "Three", "Four"]
+        );
+
+        // Reorder, reword, squash and drop commits.
+        let [one, two, three, four] = todo.try_into().unwrap();
+        let todo = vec![
+            RebaseTodoEntry {
+                action: RebaseAction::Reword,
+                message: Some("Two, reworded\n\nWith a body".into()),
+                ..two
+            },
+            RebaseTodoEntry {
+                action: RebaseAction::Fixup,
+                ..three
+            },
+            one,
+            RebaseTodoEntry {
+                action: RebaseAction::Drop,
+                ..four
+            },
+        ];
+        repo.rebase_interactive("HEAD~4".into(), todo, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_state().await.unwrap(), None);
+        assert_eq!(log().await, ["One", "Two, reworded\n\nWith a body", "Base"]);
+        assert!(
+            !repo_dir
+                .path()
+                .join(".git")
+                .join(REBASE_MESSAGES_DIR)
+                .exists()
+        );
+
+        // Stop at a conflict, then abort.
+        smol::fs::write(repo_dir.path().join("a"), "five")
+            .await
+            .unwrap();
+        repo.stage_paths(vec![repo_path("a")], Arc::new(HashMap::default()))
+            .await
+            .unwrap();
+        repo.commit(
+            "Five".into(),
+            None,
+            CommitOptions::default(),
+            AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        let mut todo = repo.rebase_todo("HEAD~2".into()).await.unwrap();
+        todo.swap(0, 1);
+        repo.rebase_interactive("HEAD~2".into(), todo, env.clone())
+            .await
+            .unwrap();
+        let state = repo.rebase_state().await.unwrap().unwrap();
+        assert_eq!(
+            state
+                .done
+                .iter()
+                .map(|entry| entry.subject.as_ref())
+                .collect::<Vec<_>>(),
+            ["Five"]
+        );
+        assert_eq!(
+            state
+                .remaining
+                .iter()
+                .map(|entry| entry.subject.as_ref())
+                .collect::<Vec<_>>(),
+            ["One"]
+        );
+
+        // Continuing without resolving the conflict fails.
+        assert!(
+            repo.rebase_operation(RebaseOperation::Continue, env.clone())
+                .await
+                .is_err()
+        );
+
+        repo.rebase_operation(RebaseOperation::Abort, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_state().await.unwrap(), None);
+        assert_eq!(
+            log().await,
+            ["Five", "One", "Two, reworded\n\nWith a body", "Base"]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_revert_and_tags(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        let git = repo.git_binary().unwrap();
+
+        let commit = async |file: &str, contents: &str, message: &str| {
+            smol::fs::write(repo_dir.path().join(file), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.to_string().into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            repo.head_sha().await.unwrap()
+        };
+        let log = async || {
+            git.run(&["log", "--format=%s"])
+                .await
+                .unwrap()
+                .lines()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let base = commit("a", "base", "Base").await;
+        git.run(&["switch", "-c", "feature"]).await.unwrap();
+        let feature = commit("b", "feature", "Feature").await;
+        let conflicting = commit("a", "feature", "Conflicting").await;
+        git.run(&["switch", "-"]).await.unwrap();
+        commit("a", "main", "Main").await;
+
+        repo.cherry_pick(feature.clone(), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(log().await, ["Feature", "Main", "Base"]);
+
+        // A conflicting cherry-pick stops and leaves CHERRY_PICK_HEAD behind.
+        repo.cherry_pick(conflicting, env.clone()).await.unwrap();
+        assert!(repo_dir.path().join(".git/CHERRY_PICK_HEAD").exists());
+        // Git refuses to start another one while it is in progress.
+        assert!(
+            repo.cherry_pick(feature.clone(), env.clone())
+                .await
+                .is_err()
+        );
+        git.run(&["cherry-pick", "--abort"]).await.unwrap();
+
+        let head = repo.head_sha().await.unwrap();
+        repo.revert(head.clone(), env.clone()).await.unwrap();
+        assert_eq!(
+            log().await,
+            ["Revert \"Feature\"", "Feature", "Main", "Base"]
+        );
+        assert!(
+            repo.cherry_pick("nonexistent".into(), env.clone())
+                .await
+                .is_err()
+        );
+
+        repo.create_tag("v1".into(), base.clone(), None, env.clone())
+            .await
+            .unwrap();
+        repo.create_tag(
+            "v2".into(),
+            head.clone(),
+            Some("Second release".into()),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(git.run(&["cat-file", "-t", "v1"]).await.unwrap(), "commit");
+        assert_eq!(git.run(&["cat-file", "-t", "v2"]).await.unwrap(), "tag");
+        assert_eq!(git.run(&["rev-parse", "v2^{commit}"]).await.unwrap(), head);
+        assert!(
+            repo.create_tag("v1".into(), head, None, env.clone())
+                .await
+                .is_err()
+        );
+
+        repo.delete_tag("v1".into(), env.clone()).await.unwrap();
+        assert_eq!(git.run(&["tag", "--list"]).await.unwrap(), "v2");
+
+        smol::fs::write(repo_dir.path().join("a"), "dirty")
+            .await
+            .unwrap();
+        repo.reset(base, ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(log().await, ["Base"]);
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("a"))
+                .await
+                .unwrap(),
+            "base"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_bisect(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let mut shas = Vec::new();
+        for ix in 0..8 {
+            let contents = if ix < 5 { "fine" } else { "broken" };
+            smol::fs::write(repo_dir.path().join("a"), format!("{contents} {ix}"))
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path("a")], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Commit {ix}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            shas.push(repo.head_sha().await.unwrap());
+        }
+
+        assert_eq!(repo.bisect_state().await.unwrap(), None);
+        repo.bisect_start(env.clone()).await.unwrap();
+        repo.bisect_mark(BisectVerdict::Bad, None, env.clone())
+            .await
+            .unwrap();
+        repo.bisect_mark(BisectVerdict::Good, Some(shas[0].clone()), env.clone())
+            .await
+            .unwrap();
+
+        let state = repo.bisect_state().await.unwrap().unwrap();
+        assert_eq!(state.bad.as_deref(), Some(shas[7].as_str()));
+        assert_eq!(state.remaining.len(), 7);
+
+        let mut steps = 0;
+        let first_bad = loop {
+            let state = repo.bisect_state().await.unwrap().unwrap();
+            if let Some(first_bad) = state.first_bad() {
+                break first_bad.to_string();
+            }
+            let contents = smol::fs::read_to_string(repo_dir.path().join("a"))
+                .await
+                .unwrap();
+            let verdict = if contents.starts_with("broken") {
+                BisectVerdict::Bad
+            } else {
+                BisectVerdict::Good
+            };
+            repo.bisect_mark(verdict, None, env.clone()).await.unwrap();
+            steps += 1;
+            assert!(steps <= 3);
+        };
+        assert_eq!(first_bad, shas[5]);
+
+        repo.bisect_reset(env.clone()).await.unwrap();
+        assert_eq!(repo.bisect_state().await.unwrap(), None);
+        assert_eq!(repo.head_sha().await.unwrap(), shas[7]);
+    }
+
+    #[gpui::test]
+    async fn test_submodule_diff(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let env = Arc::new(checkpoint_author_envs());
+        let commit = async |repo: &RealGitRepository, file: &str, message: &str| {
+            let work_dir = repo.working_directory().unwrap();
+            smol::fs::write(work_dir.join(file), message).await.unwrap();
+            repo.stage_paths(vec![repo_path(file)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.to_string().into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            repo.head_sha().await.unwrap()
+        };
+
+        let library_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(library_dir.path()).unwrap();
+        let library = RealGitRepository::new(
+            &library_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let first = commit(&library, "a", "First").await;
+        let second = commit(&library, "a", "Second").await;
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let git = repo.git_binary().unwrap();
+        commit(&repo, "b", "Base").await;
+        git.run(&[
+            OsStr::new("-c"),
+            OsStr::new("protocol.file.allow=always"),
+            OsStr::new("submodule"),
+            OsStr::new("add"),
+            library_dir.path().as_os_str(),
+            OsStr::new("library"),
+        ])
+        .await
+        .unwrap();
+        commit(&repo, "b", "Add library").await;
+
+        let diff = repo.submodule_diff(repo_path("library")).await.unwrap();
+        assert_eq!(diff.old_sha.as_deref(), Some(second.as_str()));
+        assert_eq!(diff.new_sha.as_deref(), Some(second.as_str()));
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+
+        let submodule_dir = repo_dir.path().join("library");
+        git.run(&[
+            OsStr::new("-C"),
+            submodule_dir.as_os_str(),
+            OsStr::new("checkout"),
+            OsStr::new("--quiet"),
+            OsStr::new(&first),
+        ])
+        .await
+        .unwrap();
+        let diff = repo.submodule_diff(repo_path("library")).await.unwrap();
+        assert_eq!(diff.old_sha.as_deref(), Some(second.as_str()));
+        assert_eq!(diff.new_sha.as_deref(), Some(first.as_str()));
+        assert!(diff.added.is_empty());
+        assert_eq!(
+            diff.removed,
+            [SubmoduleCommit {
+                sha: second.clone().into(),
+                subject: "Second".into(),
+            }]
+        );
+
+        // An uninitialized submodule is an empty directory inside the superproject,
+        // whose recorded commit is reported instead of the superproject's HEAD.
+        git.run(&["submodule", "deinit", "--force", "--", "library"])
+            .await
+            .unwrap();
+        assert!(!submodule_dir.join(".git").exists());
+        let diff = repo.submodule_diff(repo_path("library")).await.unwrap();
+        assert_eq!(diff.old_sha.as_deref(), Some(second.as_str()));
+        assert_eq!(diff.new_sha.as_deref(), Some(second.as_str()));
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_line_history(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        let git = repo.git_binary().unwrap();
+
+        let commit = async |file: &str, contents: &str, message: &str| {
+            smol::fs::write(repo_dir.path().join(file), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.to_string().into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+        };
+        let history = async |path: &str, rows: Range<u32>| {
+            let history = repo.line_history(repo_path(path), rows).await.unwrap();
+            history
+                .entries
+                .into_iter()
+                .map(|entry| (entry.commit.subject.to_string(), entry.path))
+                .collect::<Vec<_>>()
+        };
+
+        commit("a.txt", "one\ntwo\nthree\n", "Add a").await;
+        commit("a.txt", "one\nTWO\nthree\n", "Change two").await;
+        commit("a.txt", "one\nTWO\nthree\nfour\n", "Append four").await;
+        git.run(&["mv", "a.txt", "b.txt"]).await.unwrap();
+        commit("b.txt", "ONE\nTWO\nthree\nfour\n", "Rename and change one").await;
+
+        assert_eq!(
+            history("b.txt", 1..2).await,
+            [
+                ("Change two".to_string(), repo_path("a.txt")),
+                ("Add a".to_string(), repo_path("a.txt")),
+            ]
+        );
+        assert_eq!(
+            history("b.txt", 0..1).await,
+            [
+                ("Rename and change one".to_string(), repo_path("b.txt")),
+                ("Add a".to_string(), repo_path("a.txt")),
+            ]
+        );
+        assert_eq!(
+            history("b.txt", 3..4).await,
+            [("Append four".to_string(), repo_path("a.txt"))]
+        );
+        // Rows past the end of the file at `HEAD` are rejected by git.
+        assert!(repo.line_history(repo_path("b.txt"), 4..6).await.is_err());
+    }
+
+    #[gpui::test]
+    async fn test_load_conflict_stages(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        let git = repo.git_binary().unwrap();
+
+        let commit = async |file: &str, contents: &str, message: &str| {
+            smol::fs::write(repo_dir.path().join(file), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.to_string().into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+        };
+
+        commit("a", "base\n", "Base").await;
+        commit("b", "clean\n", "Clean").await;
+        git.run(&["switch", "-c", "feature"]).await.unwrap();
+        commit("a", "feature\n", "Feature").await;
+        commit("c", "added on feature\n", "Add c on feature").await;
+        git.run(&["switch", "-"]).await.unwrap();
+        commit("a", "main\n", "Main").await;
+        commit("c", "added on main\n", "Add c on main").await;
+
+        // The merge stops with conflicts in `a` and `c`.
+        assert!(git.run(&["merge", "feature"]).await.is_err());
+
+        assert_eq!(
+            repo.load_conflict_stages(repo_path("a")).await.unwrap(),
+            ConflictStages {
+                base: Some("base\n".into()),
+                ours: Some("main\n".into()),
+                theirs: Some("feature\n".into()),
+            }
+        );
+        // Added on both sides, so there is no merge base.
+        assert_eq!(
+            repo.load_conflict_stages(repo_path("c")).await.unwrap(),
+            ConflictStages {
+                base: None,
+                ours: Some("added on main\n".into()),
+                theirs: Some("added on feature\n".into()),
+            }
+        );
+        assert_eq!(
+            repo.load_conflict_stages(repo_path("b")).await.unwrap(),
+            ConflictStages::default()
+        );
+    }
+
     #[test]
     fn test_original_repo_path_from_common_dir() {
         // Normal repo: common_dir is <work_dir>/.git
@@ -0,0 +1,182 @@
+use crate::repository::RepoPath;
+use gpui::SharedString;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SubmoduleState {
+    /// The submodule is registered but hasn't been cloned yet.
+    Uninitialized,
+    /// The checked out commit matches the one recorded in the superproject.
+    UpToDate,
+    /// The checked out commit differs from the one recorded in the superproject.
+    Modified,
+    /// The submodule pointer has merge conflicts.
+    Conflicted,
+}
+
+impl SubmoduleState {
+    fn from_status_prefix(prefix: char) -> Option<Self> {
+        Some(match prefix {
+            ' ' => SubmoduleState::UpToDate,
+            '-' => SubmoduleState::Uninitialized,
+            '+' => SubmoduleState::Modified,
+            'U' => SubmoduleState::Conflicted,
+            _ => return None,
+        })
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SubmoduleState::Uninitialized => "Not initialized",
+            SubmoduleState::UpToDate => "Up to date",
+            SubmoduleState::Modified => "New commits",
+            SubmoduleState::Conflicted => "Conflicted",
+        }
+    }
+}
+
+/// A submodule of a repository, as reported by `git submodule status`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Submodule {
+    pub path: RepoPath,
+    /// The commit that is checked out in the submodule, or the recorded one if
+    /// it isn't initialized.
+    pub sha: SharedString,
+    pub state: SubmoduleState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmoduleOperation {
+    Init,
+    Update,
+    Sync,
+}
+
+impl SubmoduleOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SubmoduleOperation::Init => "submodule init",
+            SubmoduleOperation::Update => "submodule update",
+            SubmoduleOperation::Sync => "submodule sync",
+        }
+    }
+
+    pub fn args(&self) -> &'static [&'static str] {
+        match self {
+            SubmoduleOperation::Init => &["submodule", "init", "--"],
+            SubmoduleOperation::Update => &["submodule", "update", "--init", "--recursive", "--"],
+            SubmoduleOperation::Sync => &["submodule", "sync", "--recursive", "--"],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SubmoduleCommit {
+    pub sha: SharedString,
+    pub subject: SharedString,
+}
+
+/// The change of a submodule pointer between the superproject's `HEAD` and
+/// the commit currently checked out in the submodule.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmoduleDiff {
+    pub old_sha: Option<SharedString>,
+    pub new_sha: Option<SharedString>,
+    /// Commits reachable from the new pointer but not the old one.
+    pub added: Vec<SubmoduleCommit>,
+    /// Commits reachable from the old pointer but not the new one.
+    pub removed: Vec<SubmoduleCommit>,
+}
+
+/// Parses the output of `git submodule status`.
+pub fn parse_submodule_status(output: &str) -> Vec<Submodule> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut chars = line.chars();
+            let state = SubmoduleState::from_status_prefix(chars.next()?)?;
+            let rest = chars.as_str();
+            let (sha, rest) = rest.split_once(' ')?;
+            // The path is followed by ` (<describe output>)` for checked out submodules.
+            let path = match rest.rfind(" (") {
+                Some(ix) if rest.ends_with(')') => &rest[..ix],
+                _ => rest,
+            };
+            Some(Submodule {
+                path: RepoPath::new(path).ok()?,
+                sha: sha.to_string().into(),
+                state,
+            })
+        })
+        .collect()
+}
+
+/// Parses `git log --format=%H%x00%s` output into commits.
+pub(crate) fn parse_submodule_commits(output: &str) -> Vec<SubmoduleCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (sha, subject) = line.split_once('\0')?;
+            Some(SubmoduleCommit {
+                sha: sha.to_string().into(),
+                subject: subject.to_string().into(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::repo_path;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_submodule_status() {
+        let output = " 1111111111111111111111111111111111111111 libs/core (v1.0.0)\n\
+                      -2222222222222222222222222222222222222222 vendor/uninit\n\
+                      +3333333333333333333333333333333333333333 third party (heads/main)\n\
+                      U4444444444444444444444444444444444444444 conflicted\n";
+        assert_eq!(
+            parse_submodule_status(output),
+            vec![
+                Submodule {
+                    path: repo_path("libs/core"),
+                    sha: "1111111111111111111111111111111111111111".into(),
+                    state: SubmoduleState::UpToDate,
+                },
+                Submodule {
+                    path: repo_path("vendor/uninit"),
+                    sha: "2222222222222222222222222222222222222222".into(),
+                    state: SubmoduleState::Uninitialized,
+                },
+                Submodule {
+                    path: repo_path("third party"),
+                    sha: "3333333333333333333333333333333333333333".into(),
+                    state: SubmoduleState::Modified,
+                },
+                Submodule {
+                    path: repo_path("conflicted"),
+                    sha: "4444444444444444444444444444444444444444".into(),
+                    state: SubmoduleState::Conflicted,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_submodule_commits() {
+        assert_eq!(
+            parse_submodule_commits("aaaa\0First\nbbbb\0Second: with colon\n"),
+            vec![
+                SubmoduleCommit {
+                    sha: "aaaa".into(),
+                    subject: "First".into(),
+                },
+                SubmoduleCommit {
+                    sha: "bbbb".into(),
+                    subject: "Second: with colon".into(),
+                },
+            ]
+        );
+    }
+}
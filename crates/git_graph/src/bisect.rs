@@ -0,0 +1,729 @@
+use std::str::FromStr as _;
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use collections::HashSet;
+use git::{
+    Oid,
+    bisect::{BisectState, BisectVerdict},
+};
+use git_ui::{commit_view::CommitView, git_panel::show_error_toast};
+use gpui::{Action as _, App, Context, Corner, SharedString, Task, Window};
+use project::{TaskSourceKind, WorktreeId};
+use task::{TaskId, TaskTemplate};
+use ui::{ContextMenu, PopoverMenu, Tooltip, prelude::*};
+
+use crate::{BisectBad, BisectGood, BisectReset, BisectSkip, GitGraph};
+
+/// The state of the `git bisect` session of the graph's repository, if any.
+#[derive(Default)]
+pub(crate) struct BisectSession {
+    state: Option<BisectState>,
+    remaining: HashSet<Oid>,
+    /// The tasks that can be run to test each commit automatically.
+    tasks: Vec<(TaskSourceKind, TaskTemplate)>,
+    /// The task currently driving the bisection.
+    running: Option<RunningBisectTask>,
+    _refresh_task: Option<Task<()>>,
+    _load_tasks_task: Option<Task<()>>,
+}
+
+/// A task that tests each commit of the bisection, with the loop running it.
+struct RunningBisectTask {
+    label: SharedString,
+    /// The id of the terminal task, once it has been resolved.
+    task_id: Option<TaskId>,
+    _task: Task<()>,
+}
+
+impl BisectSession {
+    pub(crate) fn is_active(&self) -> bool {
+        self.state.is_some()
+    }
+
+    pub(crate) fn remaining(&self) -> impl Iterator<Item = &Oid> {
+        self.remaining.iter()
+    }
+
+    fn set_state(&mut self, state: Option<BisectState>) {
+        self.remaining = state
+            .iter()
+            .flat_map(|state| &state.remaining)
+            .filter_map(|sha| Oid::from_str(sha).ok())
+            .collect();
+        self.state = state;
+    }
+}
+
+impl GitGraph {
+    pub(crate) fn refresh_bisect_state(&mut self, cx: &mut Context<Self>) {
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        let receiver = repository.update(cx, |repository, _| repository.bisect_state());
+        self.bisect._refresh_task = Some(cx.spawn(async move |this, cx| {
+            let Ok(Ok(state)) = receiver.await else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                let was_active = this.bisect.is_active();
+                this.bisect.set_state(state);
+                if this.bisect.is_active() && !was_active {
+                    this.load_bisect_tasks(cx);
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// The worktree containing the graph's repository, whose tasks and task
+    /// context are used to test the bisected commits.
+    fn bisect_worktree_id(&self, cx: &App) -> Option<WorktreeId> {
+        let workspace = self.workspace.upgrade()?;
+        let work_directory = self
+            .get_repository(cx)?
+            .read(cx)
+            .work_directory_abs_path
+            .clone();
+        workspace
+            .read(cx)
+            .project()
+            .read(cx)
+            .visible_worktrees(cx)
+            .find(|worktree| work_directory.starts_with(worktree.read(cx).abs_path()))
+            .map(|worktree| worktree.read(cx).id())
+    }
+
+    fn load_bisect_tasks(&mut self, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let worktree_id = self.bisect_worktree_id(cx);
+        let project = workspace.read(cx).project().read(cx);
+        let Some(inventory) = project.task_store().read(cx).task_inventory().cloned() else {
+            return;
+        };
+        let tasks = inventory.read(cx).list_tasks(None, None, worktree_id, cx);
+        self.bisect._load_tasks_task = Some(cx.spawn(async move |this, cx| {
+            let tasks = tasks.await;
+            this.update(cx, |this, cx| {
+                this.bisect.tasks = tasks;
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Marks `commit`, or the commit checked out by the bisect session if
+    /// `None`, starting a session first if there is none.
+    pub(crate) fn mark_bisect_commit(
+        &mut self,
+        verdict: BisectVerdict,
+        commit: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        let start = (!self.bisect.is_active())
+            .then(|| repository.update(cx, |repository, _| repository.bisect_start()));
+        cx.spawn(async move |this, cx| {
+            let result = async {
+                if let Some(start) = start {
+                    start.await??;
+                }
+                repository
+                    .update(cx, |repository, _| repository.bisect_mark(verdict, commit))
+                    .await?
+            }
+            .await;
+            this.update(cx, |this, cx| {
+                if let Err(error) = result
+                    && let Some(workspace) = this.workspace.upgrade()
+                {
+                    show_error_toast(workspace, "bisect", error, cx);
+                }
+                this.refresh_bisect_state(cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    pub(crate) fn reset_bisect(&mut self, cx: &mut Context<Self>) {
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        self.stop_bisect_task(cx);
+        let receiver = repository.update(cx, |repository, _| repository.bisect_reset());
+        cx.spawn(async move |this, cx| {
+            let result = receiver.await.map_err(anyhow::Error::from).flatten();
+            this.update(cx, |this, cx| {
+                if let Err(error) = result
+                    && let Some(workspace) = this.workspace.upgrade()
+                {
+                    show_error_toast(workspace, "bisect reset", error, cx);
+                }
+                this.refresh_bisect_state(cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Repeatedly runs the given task on the checked out commit and marks the
+    /// commit according to its exit code, like `git bisect run` does, until the
+    /// first bad commit is found.
+    fn run_bisect_task(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        template: TaskTemplate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(workspace), Some(repository)) =
+            (self.workspace.upgrade(), self.get_repository(cx))
+        else {
+            return;
+        };
+        let work_directory = repository.read(cx).work_directory_abs_path.to_path_buf();
+        let worktree_id = self.bisect_worktree_id(cx);
+        let task_contexts = workspace.update(cx, |workspace, cx| {
+            tasks_ui::task_contexts(workspace, window, cx)
+        });
+        let label = SharedString::from(template.label.clone());
+        let workspace = self.workspace.clone();
+
+        let task = cx.spawn_in(window, async move |this, cx| {
+            let result: Result<()> = async {
+                let task_contexts = task_contexts.await;
+                let mut task_context = worktree_id
+                    .and_then(|worktree_id| task_contexts.task_context_for_worktree_id(worktree_id))
+                    .or_else(|| task_contexts.active_context())
+                    .cloned()
+                    .unwrap_or_default();
+                task_context.cwd = Some(work_directory);
+                let resolved_task = template
+                    .resolve_task(&task_source_kind.to_id_base(), &task_context)
+                    .with_context(|| format!("could not resolve task \"{}\"", template.label))?;
+                let spawn_in_terminal = resolved_task.resolved;
+                this.update(cx, |this, cx| {
+                    if let Some(running) = this.bisect.running.as_mut() {
+                        running.label = resolved_task.resolved_label.into();
+                        running.task_id = Some(spawn_in_terminal.id.clone());
+                        cx.notify();
+                    }
+                })?;
+
+                loop {
+                    let state = repository
+                        .update(cx, |repository, _| repository.bisect_state())
+                        .await??
+                        .ok_or_else(|| anyhow!("no bisect in progress"))?;
+                    if state.first_bad().is_some() {
+                        return Ok(());
+                    }
+                    if state.remaining.is_empty() {
+                        bail!("mark a good and a bad commit before running a task");
+                    }
+                    if state.untested_count() == 0 {
+                        bail!("only skipped commits are left to test");
+                    }
+
+                    let spawn_in_terminal = spawn_in_terminal.clone();
+                    let status = workspace
+                        .update_in(cx, |workspace, window, cx| {
+                            workspace.spawn_in_terminal(spawn_in_terminal, window, cx)
+                        })?
+                        .await
+                        .ok_or_else(|| anyhow!("task was cancelled"))??;
+                    let Some(verdict) = status.code().and_then(BisectVerdict::from_exit_code)
+                    else {
+                        bail!("task exited with {status}, stopping bisect");
+                    };
+                    repository
+                        .update(cx, |repository, _| repository.bisect_mark(verdict, None))
+                        .await??;
+                }
+            }
+            .await;
+
+            this.update(cx, |this, cx| {
+                this.bisect.running.take();
+                if let Err(error) = result
+                    && let Some(workspace) = this.workspace.upgrade()
+                {
+                    show_error_toast(workspace, "bisect run", error, cx);
+                }
+                this.refresh_bisect_state(cx);
+            })
+            .ok();
+        });
+        self.bisect.running = Some(RunningBisectTask {
+            label,
+            task_id: None,
+            _task: task,
+        });
+        cx.notify();
+    }
+
+    /// Stops the bisection driven by a task, killing the task if it's running.
+    fn stop_bisect_task(&mut self, cx: &mut Context<Self>) {
+        let Some(running) = self.bisect.running.take() else {
+            return;
+        };
+        cx.notify();
+        let (Some(task_id), Some(workspace)) = (running.task_id, self.workspace.upgrade()) else {
+            return;
+        };
+        let terminals = workspace
+            .read(cx)
+            .project()
+            .read(cx)
+            .local_terminal_handles()
+            .clone();
+        for terminal in terminals {
+            terminal
+                .update(cx, |terminal, _| {
+                    if terminal
+                        .task()
+                        .is_some_and(|task| task.spawned_task.id == task_id)
+                    {
+                        terminal.kill_active_task();
+                    }
+                })
+                .ok();
+        }
+    }
+
+    fn open_first_bad_commit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(sha), Some(repository)) = (
+            self.bisect
+                .state
+                .as_ref()
+                .and_then(|state| state.first_bad())
+                .cloned(),
+            self.get_repository(cx),
+        ) else {
+            return;
+        };
+        self.select_commit_by_sha(sha.as_ref(), cx);
+        CommitView::open(
+            sha.to_string(),
+            repository.downgrade(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+    }
+
+    pub(crate) fn render_bisect_bar(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let state = self.bisect.state.as_ref()?;
+        let first_bad = state.first_bad();
+        let can_mark = first_bad.is_none() && self.bisect.running.is_none();
+
+        let status = if let Some(first_bad) = first_bad {
+            format!(
+                "{} is the first bad commit",
+                first_bad.get(..7).unwrap_or(first_bad)
+            )
+        } else if state.remaining.is_empty() {
+            "Bisecting: mark a good and a bad commit to start narrowing down".to_string()
+        } else {
+            let untested = state.untested_count();
+            format!(
+                "Bisecting: {untested} {} left to test (roughly {} {})",
+                if untested == 1 { "commit" } else { "commits" },
+                state.steps_left(),
+                if state.steps_left() == 1 {
+                    "step"
+                } else {
+                    "steps"
+                },
+            )
+        };
+
+        let verdict_button = |id: &'static str, label: &'static str, verdict: BisectVerdict| {
+            let tooltip = match verdict {
+                BisectVerdict::Good => "Mark the checked out commit as good",
+                BisectVerdict::Bad => "Mark the checked out commit as bad",
+                BisectVerdict::Skip => "Skip the checked out commit",
+            };
+            Button::new(id, label)
+                .label_size(LabelSize::Small)
+                .disabled(!can_mark || state.current.is_none())
+                .tooltip(Tooltip::text(tooltip))
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.mark_bisect_commit(verdict, None, cx);
+                }))
+        };
+        let focus_handle = self.focus_handle.clone();
+
+        Some(
+            h_flex()
+                .w_full()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .border_b_1()
+                .border_color(cx.theme().colors().border_variant)
+                .bg(cx.theme().status().info_background.opacity(0.1))
+                .child(
+                    Icon::new(IconName::GitBranch)
+                        .size(IconSize::Small)
+                        .color(Color::Info),
+                )
+                .child(
+                    div()
+                        .flex_1()
+                        .min_w_0()
+                        .child(Label::new(status).size(LabelSize::Small).truncate()),
+                )
+                .when_some(first_bad, |this, _| {
+                    this.child(
+                        Button::new("bisect-open-first-bad", "Open Commit")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.open_first_bad_commit(window, cx);
+                            })),
+                    )
+                })
+                .map(|this| match &self.bisect.running {
+                    Some(RunningBisectTask { label, .. }) => this
+                        .child(
+                            Label::new(format!("Running {label}…"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(
+                            Button::new("bisect-stop", "Stop")
+                                .label_size(LabelSize::Small)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.stop_bisect_task(cx);
+                                })),
+                        ),
+                    None => {
+                        this.child(verdict_button("bisect-good", "Good", BisectVerdict::Good))
+                            .child(verdict_button("bisect-bad", "Bad", BisectVerdict::Bad))
+                            .child(verdict_button("bisect-skip", "Skip", BisectVerdict::Skip))
+                            .child(self.render_bisect_task_menu(
+                                can_mark && !state.remaining.is_empty(),
+                                cx,
+                            ))
+                    }
+                })
+                .child(
+                    Button::new("bisect-reset", "End Bisect")
+                        .label_size(LabelSize::Small)
+                        .tooltip(move |_, cx| {
+                            Tooltip::for_action_in("End Bisect", &BisectReset, &focus_handle, cx)
+                        })
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.reset_bisect(cx);
+                        })),
+                ),
+        )
+    }
+
+    fn render_bisect_task_menu(&self, enabled: bool, cx: &Context<Self>) -> impl IntoElement {
+        let this = cx.weak_entity();
+        let tasks = self.bisect.tasks.clone();
+        PopoverMenu::new("bisect-run-task")
+            .trigger(
+                Button::new("bisect-run-task-trigger", "Run Task…")
+                    .label_size(LabelSize::Small)
+                    .disabled(!enabled || tasks.is_empty())
+                    .tooltip(Tooltip::text(
+                        "Test each commit with a task: exit code 0 marks it good, \
+                         125 skips it and any other code up to 127 marks it bad",
+                    )),
+            )
+            .anchor(Corner::TopRight)
+            .menu(move |window, cx| {
+                let this = this.clone();
+                let tasks = tasks.clone();
+                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for (task_source_kind, template) in tasks {
+                        let this = this.clone();
+                        menu = menu.entry(template.label.clone(), None, move |window, cx| {
+                            this.update(cx, |this, cx| {
+                                this.run_bisect_task(
+                                    task_source_kind.clone(),
+                                    template.clone(),
+                                    window,
+                                    cx,
+                                );
+                            })
+                            .ok();
+                        });
+                    }
+                    menu
+                }))
+            })
+    }
+
+    pub(crate) fn bisect_menu_entries(&self, menu: ContextMenu) -> ContextMenu {
+        menu.separator()
+            .action("Bisect: Mark as Good", BisectGood.boxed_clone())
+            .action("Bisect: Mark as Bad", BisectBad.boxed_clone())
+            .action("Bisect: Skip", BisectSkip.boxed_clone())
+            .when(self.bisect.is_active(), |menu| {
+                menu.action("End Bisect", BisectReset.boxed_clone())
+            })
+    }
+
+    /// Marks the commit selected in the graph, as opposed to the checked out one.
+    pub(crate) fn mark_selected_bisect_commit(
+        &mut self,
+        verdict: BisectVerdict,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(sha) = self.selected_commit_sha() else {
+            return;
+        };
+        self.mark_bisect_commit(verdict, Some(sha), cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::{FakeCommit, FakeFs};
+    use gpui::{Entity, TestAppContext, VisualTestContext};
+    use project::Project;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::{
+        path::Path,
+        process::ExitStatus,
+        sync::{Arc, Mutex},
+    };
+    use task::SpawnInTerminal;
+    use workspace::{MultiWorkspace, TerminalProvider, Workspace};
+
+    const FIRST_BAD: usize = 5;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+        });
+    }
+
+    fn sha(ix: usize) -> String {
+        Oid::from_bytes(&[ix as u8 + 1; 20]).unwrap().to_string()
+    }
+
+    fn head(fs: &FakeFs) -> String {
+        fs.with_git_state(Path::new("/project/.git"), false, |state| {
+            state.refs["HEAD"].clone()
+        })
+        .unwrap()
+    }
+
+    fn is_bad(commit: &str) -> bool {
+        (0..8).position(|ix| sha(ix) == commit).unwrap() >= FIRST_BAD
+    }
+
+    fn exit_status(code: i32) -> ExitStatus {
+        #[cfg(unix)]
+        {
+            std::os::unix::process::ExitStatusExt::from_raw(code << 8)
+        }
+        #[cfg(windows)]
+        {
+            std::os::windows::process::ExitStatusExt::from_raw(code as u32)
+        }
+    }
+
+    /// Tests the checked out commit, recording the arguments of the spawned tasks.
+    struct BisectTerminalProvider {
+        fs: Arc<FakeFs>,
+        spawned_args: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    impl TerminalProvider for BisectTerminalProvider {
+        fn spawn(
+            &self,
+            task: SpawnInTerminal,
+            _window: &mut Window,
+            _cx: &mut App,
+        ) -> Task<Option<Result<ExitStatus>>> {
+            self.spawned_args.lock().unwrap().push(task.args);
+            let code = if is_bad(&head(&self.fs)) { 1 } else { 0 };
+            Task::ready(Some(Ok(exit_status(code))))
+        }
+    }
+
+    /// Opens the graph of a repository whose history has eight commits, the
+    /// last one checked out.
+    async fn build_graph(
+        cx: &mut TestAppContext,
+    ) -> (
+        Arc<FakeFs>,
+        Entity<Workspace>,
+        Entity<GitGraph>,
+        &mut VisualTestContext,
+    ) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/project", json!({ ".git": {}, "file.txt": "content" }))
+            .await;
+        fs.set_history_for_repo(
+            Path::new("/project/.git"),
+            &(0..8)
+                .map(|ix| FakeCommit::new(sha(ix), format!("Commit {ix}")))
+                .collect::<Vec<_>>(),
+        );
+
+        let project = Project::test(fs.clone(), [Path::new("/project")], cx).await;
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(&*cx, |multi, _| multi.workspace().clone());
+        let git_graph = cx.new_window_entity(|window, cx| {
+            GitGraph::new(
+                repository.read(cx).id,
+                project.read(cx).git_store().clone(),
+                workspace.downgrade(),
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        (fs, workspace, git_graph, cx)
+    }
+
+    /// Marks the last commit as bad and the first one as good.
+    fn start_bisect(git_graph: &Entity<GitGraph>, cx: &mut VisualTestContext) {
+        git_graph.update(cx, |graph, cx| {
+            graph.mark_bisect_commit(BisectVerdict::Bad, None, cx);
+        });
+        cx.run_until_parked();
+        git_graph.update(cx, |graph, cx| {
+            graph.mark_bisect_commit(BisectVerdict::Good, Some(sha(0)), cx);
+        });
+        cx.run_until_parked();
+    }
+
+    fn first_bad(git_graph: &Entity<GitGraph>, cx: &mut VisualTestContext) -> Option<String> {
+        git_graph.read_with(&*cx, |graph, _| {
+            graph
+                .bisect
+                .state
+                .as_ref()
+                .and_then(|state| state.first_bad())
+                .map(ToString::to_string)
+        })
+    }
+
+    #[gpui::test]
+    async fn test_bisect_by_marking_commits(cx: &mut TestAppContext) {
+        let (fs, _, git_graph, cx) = build_graph(cx).await;
+        start_bisect(&git_graph, cx);
+        git_graph.read_with(&*cx, |graph, _| {
+            let state = graph.bisect.state.as_ref().unwrap();
+            assert_eq!(state.untested_count(), 6);
+            assert_eq!(graph.bisect.remaining().count(), 7);
+            assert_eq!(state.current.as_deref(), Some(sha(3).as_str()));
+        });
+        assert_eq!(head(&fs), sha(3));
+
+        while first_bad(&git_graph, cx).is_none() {
+            let verdict = if is_bad(&head(&fs)) {
+                BisectVerdict::Bad
+            } else {
+                BisectVerdict::Good
+            };
+            git_graph.update(cx, |graph, cx| {
+                graph.mark_bisect_commit(verdict, None, cx);
+            });
+            cx.run_until_parked();
+        }
+        assert_eq!(first_bad(&git_graph, cx), Some(sha(FIRST_BAD)));
+        git_graph.read_with(&*cx, |graph, _| {
+            assert_eq!(
+                graph
+                    .bisect
+                    .remaining()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+                [sha(FIRST_BAD)]
+            );
+        });
+
+        git_graph.update(cx, |graph, cx| graph.reset_bisect(cx));
+        cx.run_until_parked();
+        git_graph.read_with(&*cx, |graph, _| assert!(!graph.bisect.is_active()));
+        assert_eq!(head(&fs), sha(7));
+    }
+
+    #[gpui::test]
+    async fn test_bisect_by_running_a_task(cx: &mut TestAppContext) {
+        let (fs, workspace, git_graph, cx) = build_graph(cx).await;
+        let spawned_args = Arc::new(Mutex::new(Vec::new()));
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(BisectTerminalProvider {
+                fs: fs.clone(),
+                spawned_args: spawned_args.clone(),
+            });
+        });
+        start_bisect(&git_graph, cx);
+
+        let template = TaskTemplate {
+            label: "check".into(),
+            command: "check".into(),
+            args: vec!["$ZED_WORKTREE_ROOT".into()],
+            ..TaskTemplate::default()
+        };
+        git_graph.update_in(cx, |graph, window, cx| {
+            graph.run_bisect_task(TaskSourceKind::UserInput, template, window, cx);
+        });
+        cx.run_until_parked();
+
+        assert_eq!(first_bad(&git_graph, cx), Some(sha(FIRST_BAD)));
+        git_graph.read_with(&*cx, |graph, _| assert!(graph.bisect.running.is_none()));
+        let spawned_args = spawned_args.lock().unwrap();
+        assert!(!spawned_args.is_empty());
+        assert!(
+            spawned_args
+                .iter()
+                .all(|args| args == &["/project".to_string()])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_bisect_task_that_cannot_be_resolved(cx: &mut TestAppContext) {
+        let (fs, workspace, git_graph, cx) = build_graph(cx).await;
+        let spawned_args = Arc::new(Mutex::new(Vec::new()));
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(BisectTerminalProvider {
+                fs: fs.clone(),
+                spawned_args: spawned_args.clone(),
+            });
+        });
+        start_bisect(&git_graph, cx);
+
+        // There is no active file to test
+        let template = TaskTemplate {
+            label: "check file".into(),
+            command: "check".into(),
+            args: vec!["$ZED_FILE".into()],
+            ..TaskTemplate::default()
+        };
+        git_graph.update_in(cx, |graph, window, cx| {
+            graph.run_bisect_task(TaskSourceKind::UserInput, template, window, cx);
+        });
+        cx.run_until_parked();
+
+        git_graph.read_with(&*cx, |graph, _| assert!(graph.bisect.running.is_none()));
+        assert!(spawned_args.lock().unwrap().is_empty());
+        assert_eq!(first_bad(&git_graph, cx), None);
+        assert_eq!(head(&fs), sha(3));
+    }
+}
@@ -0,0 +1,200 @@
+use editor::Editor;
+use git::{log_filter::LogFilter, repository::LogSource};
+use gpui::{App, Entity, Window};
+use ui::{Tooltip, prelude::*};
+use workspace::item::ItemEvent;
+
+use crate::GitGraph;
+
+/// The editors of the bar for filtering the graph by author, path, date or branch.
+pub(crate) struct GraphFilters {
+    pub(crate) is_visible: bool,
+    author: Entity<Editor>,
+    paths: Entity<Editor>,
+    since: Entity<Editor>,
+    until: Entity<Editor>,
+    branches: Entity<Editor>,
+}
+
+impl GraphFilters {
+    pub(crate) fn new(window: &mut Window, cx: &mut Context<GitGraph>) -> Self {
+        let mut editor = |placeholder: &str| {
+            cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text(placeholder, window, cx);
+                editor
+            })
+        };
+        Self {
+            is_visible: false,
+            author: editor("Name or email…"),
+            paths: editor("src/**/*.rs, docs/…"),
+            since: editor("2 weeks ago, 2024-01-31…"),
+            until: editor("yesterday…"),
+            branches: editor("All branches"),
+        }
+    }
+
+    /// Builds a filter from the editors. Paths and branches are separated by
+    /// commas or whitespace.
+    fn read(&self, cx: &App) -> LogFilter {
+        let text = |editor: &Entity<Editor>| {
+            let text = editor.read(cx).text(cx).trim().to_string();
+            (!text.is_empty()).then_some(text)
+        };
+        let list = |editor: &Entity<Editor>| {
+            editor
+                .read(cx)
+                .text(cx)
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+        LogFilter {
+            author: text(&self.author),
+            paths: list(&self.paths),
+            since: text(&self.since),
+            until: text(&self.until),
+            branches: list(&self.branches),
+        }
+    }
+
+    fn fill(&self, filter: &LogFilter, window: &mut Window, cx: &mut App) {
+        let fields = [
+            (&self.author, filter.author.clone().unwrap_or_default()),
+            (&self.paths, filter.paths.join(", ")),
+            (&self.since, filter.since.clone().unwrap_or_default()),
+            (&self.until, filter.until.clone().unwrap_or_default()),
+            (&self.branches, filter.branches.join(", ")),
+        ];
+        for (editor, text) in fields {
+            editor.update(cx, |editor, cx| editor.set_text(text, window, cx));
+        }
+    }
+}
+
+impl GitGraph {
+    pub(crate) fn log_filter(&self) -> LogFilter {
+        match &self.log_source {
+            LogSource::Filtered(filter) => filter.clone(),
+            _ => LogFilter::default(),
+        }
+    }
+
+    /// Reloads the graph with only the commits matching `filter`, or with all
+    /// of them when it's empty.
+    pub(crate) fn set_log_filter(&mut self, filter: LogFilter, cx: &mut Context<Self>) {
+        let log_source = if filter.is_empty() {
+            LogSource::All
+        } else {
+            LogSource::Filtered(filter)
+        };
+        if log_source == self.log_source {
+            return;
+        }
+        self.log_source = log_source;
+        self.selected_entry_idx = None;
+        self.compare_entry_idx = None;
+        self.selected_commit_diff = None;
+        self.selected_commit_diff_stats = None;
+        self.pending_select_sha = None;
+        self.invalidate_state(cx);
+        // Persists the new filter.
+        cx.emit(ItemEvent::UpdateTab);
+    }
+
+    /// Shows `filter` in the filter bar and applies it, e.g. when restoring a graph.
+    pub(crate) fn restore_log_filter(
+        &mut self,
+        filter: LogFilter,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.filters.fill(&filter, window, cx);
+        self.filters.is_visible = !filter.is_empty();
+        self.set_log_filter(filter, cx);
+    }
+
+    pub(crate) fn toggle_filters(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.filters.is_visible = !self.filters.is_visible;
+        if self.filters.is_visible {
+            window.focus(&self.filters.author.focus_handle(cx), cx);
+        } else {
+            window.focus(&self.focus_handle, cx);
+        }
+        cx.notify();
+    }
+
+    fn apply_filters(&mut self, cx: &mut Context<Self>) {
+        let filter = self.filters.read(cx);
+        self.set_log_filter(filter, cx);
+    }
+
+    fn clear_filters(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.filters.fill(&LogFilter::default(), window, cx);
+        self.set_log_filter(LogFilter::default(), cx);
+    }
+
+    pub(crate) fn render_filter_bar(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        if !self.filters.is_visible {
+            return None;
+        }
+        let colors = cx.theme().colors();
+        let field = |label: &'static str, editor: &Entity<Editor>| {
+            v_flex()
+                .flex_1()
+                .min_w_0()
+                .gap_0p5()
+                .child(
+                    Label::new(label)
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .child(
+                    h_flex()
+                        .h_7()
+                        .px_1p5()
+                        .border_1()
+                        .border_color(colors.border_variant)
+                        .rounded_md()
+                        .bg(colors.toolbar_background)
+                        .child(editor.clone()),
+                )
+        };
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_1p5()
+                .items_end()
+                .border_b_1()
+                .border_color(colors.border_variant)
+                .on_action(cx.listener(|this, _: &menu::Confirm, _window, cx| {
+                    this.apply_filters(cx);
+                }))
+                .child(field("Author", &self.filters.author))
+                .child(field("Paths", &self.filters.paths))
+                .child(field("Since", &self.filters.since))
+                .child(field("Until", &self.filters.until))
+                .child(field("Branches", &self.filters.branches))
+                .child(
+                    Button::new("git-graph-apply-filters", "Apply")
+                        .label_size(LabelSize::Small)
+                        .tooltip(Tooltip::text("Only show the commits matching the filters"))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.apply_filters(cx);
+                        })),
+                )
+                .child(
+                    Button::new("git-graph-clear-filters", "Clear")
+                        .label_size(LabelSize::Small)
+                        .disabled(!matches!(self.log_source, LogSource::Filtered(_)))
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.clear_filters(window, cx);
+                        })),
+                ),
+        )
+    }
+}
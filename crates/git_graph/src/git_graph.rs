@@ -1,19 +1,33 @@
-use collections::{BTreeMap, HashMap, IndexSet};
+mod bisect;
+mod filter;
+mod ref_name_modal;
+
+use anyhow::Result;
+use bisect::BisectSession;
+use collections::{BTreeMap, HashMap, HashSet, IndexSet};
 use editor::Editor;
+use filter::GraphFilters;
+use futures::channel::oneshot;
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, Oid, ParsedGitRemote,
+    bisect::BisectVerdict,
+    log_filter::LogFilter,
     parse_git_remote_url,
     repository::{
-        CommitDiff, CommitFile, InitialGraphCommitData, LogOrder, LogSource, RepoPath,
+        CommitDiff, CommitFile, InitialGraphCommitData, LogOrder, LogSource, RepoPath, ResetMode,
         SearchCommitArgs,
     },
     status::{FileStatus, StatusCode, TrackedStatus},
 };
-use git_ui::{commit_tooltip::CommitAvatar, commit_view::CommitView, git_status_icon};
+use git_ui::{
+    commit_range_view::CommitRangeView, commit_tooltip::CommitAvatar, commit_view::CommitView,
+    git_panel::show_error_toast, git_status_icon,
+};
 use gpui::{
-    AnyElement, App, Bounds, ClickEvent, ClipboardItem, Corner, DefiniteLength, DragMoveEvent,
-    ElementId, Empty, Entity, EventEmitter, FocusHandle, Focusable, Hsla, PathBuilder, Pixels,
-    Point, ScrollStrategy, ScrollWheelEvent, SharedString, Subscription, Task, TextStyleRefinement,
+    AnyElement, App, Bounds, ClickEvent, ClipboardItem, Corner, DefiniteLength, DismissEvent,
+    DragMoveEvent, ElementId, Empty, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
+    MouseButton, MouseDownEvent, PathBuilder, Pixels, Point, PromptLevel, ScrollStrategy,
+    ScrollWheelEvent, SharedString, Subscription, Task, TextStyleRefinement,
     UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred, point, prelude::*,
     px, uniform_list,
 };
@@ -23,6 +37,7 @@ use project::git_store::{
     CommitDataState, GitGraphEvent, GitStore, GitStoreEvent, GraphDataResponse, Repository,
     RepositoryEvent, RepositoryId,
 };
+use ref_name_modal::RefNameModal;
 use search::{
     SearchOption, SearchOptions, SearchSource, SelectNextMatch, SelectPreviousMatch,
     ToggleCaseSensitive, buffer_search,
@@ -277,6 +292,33 @@ actions!(
         OpenCommitView,
         /// Focuses the search field.
         FocusSearch,
+        /// Applies the changes of the selected commit on top of the current branch.
+        CherryPick,
+        /// Creates a commit that undoes the changes of the selected commit.
+        Revert,
+        /// Creates a tag at the selected commit.
+        CreateTag,
+        /// Creates and checks out a branch at the selected commit.
+        CreateBranch,
+        /// Resets the current branch to the selected commit, keeping all changes staged.
+        SoftReset,
+        /// Resets the current branch to the selected commit, keeping all changes unstaged.
+        MixedReset,
+        /// Resets the current branch to the selected commit, discarding all changes.
+        HardReset,
+        /// Marks the selected commit as good, starting a bisect session if needed.
+        BisectGood,
+        /// Marks the selected commit as bad, starting a bisect session if needed.
+        BisectBad,
+        /// Skips the selected commit in the bisect session, starting one if needed.
+        BisectSkip,
+        /// Ends the bisect session and returns to the branch it was started from.
+        BisectReset,
+        /// Shows or hides the bar for filtering the graph by author, path, date or branch.
+        ToggleFilters,
+        /// Opens a diff between the selected commit and the one added to the
+        /// selection with a secondary click.
+        CompareCommits,
     ]
 );
 
@@ -905,6 +947,8 @@ pub struct GitGraph {
     table_interaction_state: Entity<TableInteractionState>,
     column_widths: Entity<RedistributableColumnsState>,
     selected_entry_idx: Option<usize>,
+    /// A second selected row, to compare with the selected one.
+    compare_entry_idx: Option<usize>,
     hovered_entry_idx: Option<usize>,
     graph_canvas_bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
     log_source: LogSource,
@@ -916,6 +960,8 @@ pub struct GitGraph {
     repo_id: RepositoryId,
     changed_files_scroll_handle: UniformListScrollHandle,
     pending_select_sha: Option<Oid>,
+    bisect: BisectSession,
+    filters: GraphFilters,
 }
 
 impl GitGraph {
@@ -1015,6 +1061,8 @@ impl GitGraph {
             editor
         });
 
+        let filters = GraphFilters::new(window, cx);
+
         let table_interaction_state = cx.new(|cx| TableInteractionState::new(cx));
         let column_widths = cx.new(|_cx| {
             RedistributableColumnsState::new(
@@ -1068,6 +1116,7 @@ impl GitGraph {
             table_interaction_state,
             column_widths,
             selected_entry_idx: None,
+            compare_entry_idx: None,
             hovered_entry_idx: None,
             graph_canvas_bounds: Rc::new(Cell::new(None)),
             selected_commit_diff: None,
@@ -1078,9 +1127,12 @@ impl GitGraph {
             repo_id,
             changed_files_scroll_handle: UniformListScrollHandle::new(),
             pending_select_sha: None,
+            bisect: BisectSession::default(),
+            filters,
         };
 
         this.fetch_initial_graph_data(cx);
+        this.refresh_bisect_state(cx);
         this
     }
 
@@ -1150,6 +1202,7 @@ impl GitGraph {
             }
             RepositoryEvent::HeadChanged | RepositoryEvent::BranchListChanged => {
                 self.pending_select_sha = None;
+                self.refresh_bisect_state(cx);
                 // Only invalidate if we scanned atleast once,
                 // meaning we are not inside the initial repo loading state
                 // NOTE: this fixes an loading performance regression
@@ -1157,7 +1210,7 @@ impl GitGraph {
                     self.invalidate_state(cx);
                 }
             }
-            RepositoryEvent::StashEntriesChanged if self.log_source == LogSource::All => {
+            RepositoryEvent::StashEntriesChanged if self.log_source.includes_all_refs() => {
                 self.pending_select_sha = None;
                 if repository.read(cx).scan_id > 1 {
                     self.invalidate_state(cx);
@@ -1179,6 +1232,24 @@ impl GitGraph {
         }
     }
 
+    /// The rows of the commits that may still be the first bad one in the
+    /// bisect session in progress.
+    fn bisect_rows(&self, cx: &App) -> HashSet<usize> {
+        let Some(repository) = self.get_repository(cx) else {
+            return HashSet::default();
+        };
+        let Some(graph_data) = repository
+            .read(cx)
+            .get_graph_data(self.log_source.clone(), self.log_order)
+        else {
+            return HashSet::default();
+        };
+        self.bisect
+            .remaining()
+            .filter_map(|oid| graph_data.commit_oid_to_index.get(oid).copied())
+            .collect()
+    }
+
     fn get_repository(&self, cx: &App) -> Option<Entity<Repository>> {
         let git_store = self.git_store.read(cx);
         git_store.repositories().get(&self.repo_id).cloned()
@@ -1362,6 +1433,7 @@ impl GitGraph {
 
     fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
         self.selected_entry_idx = None;
+        self.compare_entry_idx = None;
         self.selected_commit_diff = None;
         self.selected_commit_diff_stats = None;
         cx.notify();
@@ -1491,6 +1563,7 @@ impl GitGraph {
         }
 
         self.selected_entry_idx = Some(idx);
+        self.compare_entry_idx = None;
         self.selected_commit_diff = None;
         self.selected_commit_diff_stats = None;
         self.changed_files_scroll_handle
@@ -1581,6 +1654,7 @@ impl GitGraph {
         {
             self.repo_id = repo_id;
             self.invalidate_state(cx);
+            self.refresh_bisect_state(cx);
         }
     }
 
@@ -1640,6 +1714,303 @@ impl GitGraph {
         );
     }
 
+    /// Adds the commit at `entry_index` to the selection to compare it with
+    /// the selected one, or takes it out if it already is.
+    fn toggle_compare_entry(&mut self, entry_index: usize, cx: &mut Context<Self>) {
+        match self.selected_entry_idx {
+            None => self.select_entry(entry_index, ScrollStrategy::Nearest, cx),
+            Some(selected_entry_idx) if selected_entry_idx == entry_index => {}
+            Some(_) => {
+                self.compare_entry_idx =
+                    (self.compare_entry_idx != Some(entry_index)).then_some(entry_index);
+                cx.notify();
+            }
+        }
+    }
+
+    fn compare_commits(&mut self, _: &CompareCommits, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(selected_entry_idx), Some(compare_entry_idx)) =
+            (self.selected_entry_idx, self.compare_entry_idx)
+        else {
+            return;
+        };
+        // Rows further down are older, unless the graph is reversed.
+        let (mut base_idx, mut head_idx) = (
+            selected_entry_idx.max(compare_entry_idx),
+            selected_entry_idx.min(compare_entry_idx),
+        );
+        if self.log_order == LogOrder::ReverseChronological {
+            std::mem::swap(&mut base_idx, &mut head_idx);
+        }
+        let (Some(base), Some(head), Some(repository)) = (
+            self.graph_data.commits.get(base_idx),
+            self.graph_data.commits.get(head_idx),
+            self.get_repository(cx),
+        ) else {
+            return;
+        };
+
+        CommitRangeView::open(
+            base.data.sha.to_string(),
+            head.data.sha.to_string(),
+            repository.downgrade(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        entry_index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.compare_entry_idx == Some(entry_index) {
+            let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+                context_menu
+                    .context(self.focus_handle.clone())
+                    .action("Compare Commits", CompareCommits.boxed_clone())
+            });
+            self.set_context_menu(context_menu, position, window, cx);
+            return;
+        }
+
+        let Some(commit_entry) = self.graph_data.commits.get(entry_index) else {
+            return;
+        };
+        let tags = commit_entry
+            .data
+            .ref_names
+            .iter()
+            .filter_map(|name| name.strip_prefix("tag: "))
+            .map(|tag| tag.to_string())
+            .collect::<Vec<_>>();
+        self.select_entry(entry_index, ScrollStrategy::Nearest, cx);
+
+        let this = cx.weak_entity();
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            context_menu
+                .context(self.focus_handle.clone())
+                .action("Cherry-Pick Commit", CherryPick.boxed_clone())
+                .action("Revert Commit", Revert.boxed_clone())
+                .separator()
+                .action("Create Branch…", CreateBranch.boxed_clone())
+                .action("Create Tag…", CreateTag.boxed_clone())
+                .map(|mut context_menu| {
+                    for tag in tags {
+                        let this = this.clone();
+                        context_menu =
+                            context_menu.entry(format!("Delete Tag {tag}"), None, move |_, cx| {
+                                this.update(cx, |this, cx| this.delete_tag(tag.clone(), cx))
+                                    .ok();
+                            });
+                    }
+                    context_menu
+                })
+                .separator()
+                .action("Reset to Here (Soft)", SoftReset.boxed_clone())
+                .action("Reset to Here (Mixed)", MixedReset.boxed_clone())
+                .action("Reset to Here (Hard)…", HardReset.boxed_clone())
+                .map(|context_menu| self.bisect_menu_entries(context_menu))
+        });
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
+    fn set_context_menu(
+        &mut self,
+        context_menu: Entity<ContextMenu>,
+        position: Point<Pixels>,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn selected_commit_sha(&self) -> Option<String> {
+        let entry_index = self.selected_entry_idx?;
+        let commit_entry = self.graph_data.commits.get(entry_index)?;
+        Some(commit_entry.data.sha.to_string())
+    }
+
+    fn cherry_pick(&mut self, _: &CherryPick, _window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(sha), Some(repository)) = (self.selected_commit_sha(), self.get_repository(cx))
+        else {
+            return;
+        };
+        let receiver = repository.update(cx, |repository, _| repository.cherry_pick(sha));
+        self.run_git_operation("cherry-pick", receiver, false, cx);
+    }
+
+    fn revert(&mut self, _: &Revert, _window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(sha), Some(repository)) = (self.selected_commit_sha(), self.get_repository(cx))
+        else {
+            return;
+        };
+        let receiver = repository.update(cx, |repository, _| repository.revert(sha));
+        self.run_git_operation("revert", receiver, false, cx);
+    }
+
+    fn reset(&mut self, mode: ResetMode, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(sha), Some(repository)) = (self.selected_commit_sha(), self.get_repository(cx))
+        else {
+            return;
+        };
+
+        if !matches!(mode, ResetMode::Hard) {
+            let receiver = repository.update(cx, |repository, cx| repository.reset(sha, mode, cx));
+            self.run_git_operation("reset", receiver, false, cx);
+            return;
+        }
+
+        let short_sha = sha.get(..7).unwrap_or(&sha).to_string();
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &format!("Hard reset the current branch to {short_sha}?"),
+            Some("All uncommitted changes will be lost."),
+            &["Reset", "Cancel"],
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            if answer.await.ok() != Some(0) {
+                return;
+            }
+            this.update(cx, |this, cx| {
+                let receiver =
+                    repository.update(cx, |repository, cx| repository.reset(sha, mode, cx));
+                this.run_git_operation("reset", receiver, false, cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn create_tag(&mut self, _: &CreateTag, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(sha) = self.selected_commit_sha() else {
+            return;
+        };
+        let this = cx.weak_entity();
+        self.toggle_ref_name_modal(
+            format!("Create a tag at {}", sha.get(..7).unwrap_or(&sha)),
+            "Tag name…",
+            Some("Message (leave empty for a lightweight tag)…"),
+            move |name, message, cx| {
+                this.update(cx, |this, cx| {
+                    let Some(repository) = this.get_repository(cx) else {
+                        return;
+                    };
+                    let receiver = repository.update(cx, |repository, _| {
+                        repository.create_tag(name, sha, message)
+                    });
+                    this.run_git_operation("tag", receiver, true, cx);
+                })
+                .ok();
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn delete_tag(&mut self, name: String, cx: &mut Context<Self>) {
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        let receiver = repository.update(cx, |repository, _| repository.delete_tag(name));
+        self.run_git_operation("tag", receiver, true, cx);
+    }
+
+    fn create_branch(&mut self, _: &CreateBranch, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(sha) = self.selected_commit_sha() else {
+            return;
+        };
+        let this = cx.weak_entity();
+        self.toggle_ref_name_modal(
+            format!("Create a branch at {}", sha.get(..7).unwrap_or(&sha)),
+            "Branch name…",
+            None,
+            move |name, _, cx| {
+                this.update(cx, |this, cx| {
+                    let Some(repository) = this.get_repository(cx) else {
+                        return;
+                    };
+                    let receiver = repository.update(cx, |repository, _| {
+                        repository.create_branch(name, Some(sha))
+                    });
+                    this.run_git_operation("switch", receiver, false, cx);
+                })
+                .ok();
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn toggle_ref_name_modal(
+        &mut self,
+        title: String,
+        name_placeholder: &'static str,
+        message_placeholder: Option<&'static str>,
+        on_confirm: impl FnOnce(String, Option<String>, &mut App) + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    RefNameModal::new(
+                        title,
+                        name_placeholder,
+                        message_placeholder,
+                        on_confirm,
+                        window,
+                        cx,
+                    )
+                });
+            })
+            .ok();
+    }
+
+    /// Reports a failed git operation started from the graph. Operations that
+    /// only touch tags don't move HEAD or any branch, so the graph is reloaded
+    /// explicitly when `refresh` is set.
+    fn run_git_operation(
+        &mut self,
+        action_name: &'static str,
+        receiver: oneshot::Receiver<Result<()>>,
+        refresh: bool,
+        cx: &mut Context<Self>,
+    ) {
+        cx.spawn(async move |this, cx| {
+            let result = receiver.await.map_err(anyhow::Error::from).flatten();
+            this.update(cx, |this, cx| match result {
+                Ok(()) if refresh => this.invalidate_state(cx),
+                Ok(()) => {}
+                Err(error) => {
+                    if let Some(workspace) = this.workspace.upgrade() {
+                        show_error_toast(workspace, action_name, error, cx);
+                    }
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
     fn get_remote(
         &self,
         repository: &Repository,
@@ -1693,6 +2064,22 @@ impl GitGraph {
                         query_focus_handle,
                     )),
             )
+            .child({
+                let focus_handle = self.focus_handle.clone();
+                IconButton::new("git-graph-filters", IconName::Filter)
+                    .shape(ui::IconButtonShape::Square)
+                    .icon_size(IconSize::Small)
+                    .toggle_state(
+                        self.filters.is_visible
+                            || matches!(self.log_source, LogSource::Filtered(_)),
+                    )
+                    .tooltip(move |_, cx| {
+                        Tooltip::for_action_in("Filter Commits", &ToggleFilters, &focus_handle, cx)
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_filters(window, cx);
+                    }))
+            })
             .child(
                 h_flex()
                     .min_w_64()
@@ -2661,7 +3048,9 @@ impl Render for GitGraph {
                         ))
                         .child({
                             let row_height = self.row_height;
+                            let bisect_rows = Rc::new(self.bisect_rows(cx));
                             let selected_entry_idx = self.selected_entry_idx;
+                            let compare_entry_idx = self.compare_entry_idx;
                             let hovered_entry_idx = self.hovered_entry_idx;
                             let weak_self = cx.weak_entity();
                             let focus_handle = self.focus_handle.clone();
@@ -2723,9 +3112,12 @@ impl Render for GitGraph {
                                                             .width_config(table_width_config)
                                                             .map_row(move |(index, row), window, cx| {
                                                                 let is_selected =
-                                                                    selected_entry_idx == Some(index);
+                                                                    selected_entry_idx == Some(index)
+                                                                        || compare_entry_idx == Some(index);
                                                                 let is_hovered =
                                                                     hovered_entry_idx == Some(index);
+                                                                let is_bisect_candidate =
+                                                                    bisect_rows.contains(&index);
                                                                 let is_focused =
                                                                     focus_handle.is_focused(window);
                                                                 let weak = weak_self.clone();
@@ -2736,6 +3128,11 @@ impl Render for GitGraph {
                                                                     .colors()
                                                                     .element_hover
                                                                     .opacity(0.6);
+                                                                let bisect_bg = cx
+                                                                    .theme()
+                                                                    .status()
+                                                                    .info_background
+                                                                    .opacity(0.12);
                                                                 let selected_bg = if is_focused {
                                                                     cx.theme().colors().element_selected
                                                                 } else {
@@ -2743,6 +3140,9 @@ impl Render for GitGraph {
                                                                 };
 
                                                                 row.h(row_height)
+                                                                    .when(is_bisect_candidate, |row| {
+                                                                        row.bg(bisect_bg)
+                                                                    })
                                                                     .when(is_selected, |row| row.bg(selected_bg))
                                                                     .when(
                                                                         is_hovered && !is_selected,
@@ -2770,9 +3170,29 @@ impl Render for GitGraph {
                                                                             })
                                                                             .ok();
                                                                     })
+                                                                    .on_mouse_down(MouseButton::Right, {
+                                                                        let weak = weak.clone();
+                                                                        move |event: &MouseDownEvent, window, cx| {
+                                                                            weak.update(cx, |this, cx| {
+                                                                                this.deploy_context_menu(
+                                                                                    event.position,
+                                                                                    index,
+                                                                                    window,
+                                                                                    cx,
+                                                                                );
+                                                                            })
+                                                                            .ok();
+                                                                            cx.stop_propagation();
+                                                                        }
+                                                                    })
                                                                     .on_click(move |event, window, cx| {
                                                                         let click_count = event.click_count();
+                                                                        let is_secondary = event.modifiers().secondary();
                                                                         weak.update(cx, |this, cx| {
+                                                                            if is_secondary {
+                                                                                this.toggle_compare_entry(index, cx);
+                                                                                return;
+                                                                            }
                                                                             this.select_entry(
                                                                                 index,
                                                                                 ScrollStrategy::Center,
@@ -2842,6 +3262,35 @@ impl Render for GitGraph {
             .on_action(cx.listener(Self::select_next))
             .on_action(cx.listener(Self::select_last))
             .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cherry_pick))
+            .on_action(cx.listener(Self::revert))
+            .on_action(cx.listener(Self::create_tag))
+            .on_action(cx.listener(Self::create_branch))
+            .on_action(cx.listener(|this, _: &SoftReset, window, cx| {
+                this.reset(ResetMode::Soft, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &MixedReset, window, cx| {
+                this.reset(ResetMode::Mixed, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &HardReset, window, cx| {
+                this.reset(ResetMode::Hard, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &BisectGood, _window, cx| {
+                this.mark_selected_bisect_commit(BisectVerdict::Good, cx);
+            }))
+            .on_action(cx.listener(|this, _: &BisectBad, _window, cx| {
+                this.mark_selected_bisect_commit(BisectVerdict::Bad, cx);
+            }))
+            .on_action(cx.listener(|this, _: &BisectSkip, _window, cx| {
+                this.mark_selected_bisect_commit(BisectVerdict::Skip, cx);
+            }))
+            .on_action(cx.listener(|this, _: &BisectReset, _window, cx| {
+                this.reset_bisect(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ToggleFilters, window, cx| {
+                this.toggle_filters(window, cx);
+            }))
+            .on_action(cx.listener(Self::compare_commits))
             .on_action(cx.listener(|this, _: &SelectNextMatch, _window, cx| {
                 this.select_next_match(cx);
             }))
@@ -2857,6 +3306,8 @@ impl Render for GitGraph {
                 v_flex()
                     .size_full()
                     .child(self.render_search_bar(cx))
+                    .children(self.render_filter_bar(cx))
+                    .children(self.render_bisect_bar(cx))
                     .child(div().flex_1().child(content)),
             )
             .children(self.context_menu.as_ref().map(|(menu, position, _)| {
@@ -2959,9 +3410,14 @@ impl workspace::SerializableItem for GitGraph {
         cx: &mut App,
     ) -> Task<gpui::Result<Entity<Self>>> {
         let db = persistence::GitGraphsDb::global(cx);
-        let Some(repo_work_path) = db.get_git_graph(item_id, workspace_id).ok().flatten() else {
+        let Some((repo_work_path, log_filter)) =
+            db.get_git_graph(item_id, workspace_id).ok().flatten()
+        else {
             return Task::ready(Err(anyhow::anyhow!("No git graph to deserialize")));
         };
+        let log_filter = log_filter
+            .and_then(|log_filter| serde_json::from_str::<LogFilter>(&log_filter).ok())
+            .unwrap_or_default();
 
         let window_handle = window.window_handle();
         let project = project.read(cx);
@@ -2987,7 +3443,13 @@ impl workspace::SerializableItem for GitGraph {
                     return Err(anyhow::anyhow!("Repository not found for path: {:?}", path));
                 };
 
-                Ok(cx.new(|cx| GitGraph::new(repo_id, git_store, workspace, window, cx)))
+                Ok(cx.new(|cx| {
+                    let mut git_graph = GitGraph::new(repo_id, git_store, workspace, window, cx);
+                    if !log_filter.is_empty() {
+                        git_graph.restore_log_filter(log_filter, window, cx);
+                    }
+                    git_graph
+                }))
             })?
         })
     }
@@ -3008,10 +3470,14 @@ impl workspace::SerializableItem for GitGraph {
             .work_directory_abs_path
             .to_string_lossy()
             .to_string();
+        let log_filter = self.log_filter();
+        let log_filter = (!log_filter.is_empty())
+            .then(|| serde_json::to_string(&log_filter).ok())
+            .flatten();
 
         let db = persistence::GitGraphsDb::global(cx);
         Some(cx.background_spawn(async move {
-            db.save_git_graph(item_id, workspace_id, repo_working_path)
+            db.save_git_graph(item_id, workspace_id, repo_working_path, log_filter)
                 .await
         }))
     }
@@ -3051,6 +3517,9 @@ mod persistence {
             sql!(
                 ALTER TABLE git_graphs ADD COLUMN repo_working_path TEXT;
             ),
+            sql!(
+                ALTER TABLE git_graphs ADD COLUMN log_filter TEXT;
+            ),
         ];
     }
 
@@ -3061,10 +3530,11 @@ mod persistence {
             pub async fn save_git_graph(
                 item_id: workspace::ItemId,
                 workspace_id: workspace::WorkspaceId,
-                repo_working_path: String
+                repo_working_path: String,
+                log_filter: Option<String>
             ) -> Result<()> {
-                INSERT OR REPLACE INTO git_graphs(item_id, workspace_id, repo_working_path)
-                VALUES (?, ?, ?)
+                INSERT OR REPLACE INTO git_graphs(item_id, workspace_id, repo_working_path, log_filter)
+                VALUES (?, ?, ?, ?)
             }
         }
 
@@ -3072,8 +3542,8 @@ mod persistence {
             pub fn get_git_graph(
                 item_id: workspace::ItemId,
                 workspace_id: workspace::WorkspaceId
-            ) -> Result<Option<PathBuf>> {
-                SELECT repo_working_path
+            ) -> Result<Option<(PathBuf, Option<String>)>> {
+                SELECT repo_working_path, log_filter
                 FROM git_graphs
                 WHERE item_id = ? AND workspace_id = ?
             }
@@ -0,0 +1,114 @@
+use editor::Editor;
+use gpui::{App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString};
+use ui::prelude::*;
+use workspace::ModalView;
+
+type ConfirmHandler = Box<dyn FnOnce(String, Option<String>, &mut App)>;
+
+/// Prompts for the name of a branch or tag to create at a commit, and for an
+/// optional message when creating a tag.
+pub(crate) struct RefNameModal {
+    title: SharedString,
+    name_editor: Entity<Editor>,
+    message_editor: Option<Entity<Editor>>,
+    on_confirm: Option<ConfirmHandler>,
+}
+
+impl RefNameModal {
+    pub(crate) fn new(
+        title: impl Into<SharedString>,
+        name_placeholder: &str,
+        message_placeholder: Option<&str>,
+        on_confirm: impl FnOnce(String, Option<String>, &mut App) + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(name_placeholder, window, cx);
+            editor
+        });
+        let message_editor = message_placeholder.map(|placeholder| {
+            cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text(placeholder, window, cx);
+                editor
+            })
+        });
+        window.focus(&name_editor.focus_handle(cx), cx);
+
+        Self {
+            title: title.into(),
+            name_editor,
+            message_editor,
+            on_confirm: Some(Box::new(on_confirm)),
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let message = self
+            .message_editor
+            .as_ref()
+            .map(|editor| editor.read(cx).text(cx).trim().to_string())
+            .filter(|message| !message.is_empty());
+        if let Some(on_confirm) = self.on_confirm.take() {
+            on_confirm(name, message, cx);
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Focusable for RefNameModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for RefNameModal {}
+
+impl ModalView for RefNameModal {}
+
+impl Render for RefNameModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let border_color = cx.theme().colors().border_variant;
+        v_flex()
+            .key_context("RefNameModal")
+            .elevation_3(cx)
+            .w(rems(34.))
+            .overflow_hidden()
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .child(
+                div()
+                    .px_2()
+                    .pt_2()
+                    .child(Label::new(self.title.clone()).color(Color::Muted)),
+            )
+            .child(
+                div()
+                    .w_full()
+                    .p_2()
+                    .border_b_1()
+                    .border_color(border_color)
+                    .child(self.name_editor.clone()),
+            )
+            .when_some(self.message_editor.clone(), |this, message_editor| {
+                this.child(
+                    div()
+                        .w_full()
+                        .p_2()
+                        .border_b_1()
+                        .border_color(border_color)
+                        .child(message_editor),
+                )
+            })
+    }
+}
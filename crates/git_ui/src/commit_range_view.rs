@@ -0,0 +1,340 @@
+use anyhow::Result;
+use editor::{Editor, EditorEvent, MultiBuffer};
+use git::repository::{CommitFile, RepoPath};
+use git::status::{DiffTreeType, TreeDiffStatus};
+use gpui::{
+    AnyElement, App, AppContext as _, AsyncWindowContext, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, IntoElement, Render, SharedString, WeakEntity, Window,
+};
+use language::Capability;
+use project::git_store::Repository;
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use ui::{DiffStat, prelude::*};
+use workspace::{
+    Item, ItemNavHistory, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::NotifyTaskExt,
+    searchable::SearchableItemHandle,
+};
+
+use crate::commit_view::add_commit_files;
+
+/// Shows the changes between two arbitrary commits in a single multibuffer.
+pub struct CommitRangeView {
+    base: SharedString,
+    head: SharedString,
+    editor: Entity<Editor>,
+    multibuffer: Entity<MultiBuffer>,
+}
+
+impl CommitRangeView {
+    pub fn open(
+        base: String,
+        head: String,
+        repository: WeakEntity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(repository) = repository.upgrade() else {
+            return;
+        };
+        let workspace_handle = workspace.clone();
+        window
+            .spawn(cx, async move |cx| {
+                let files = load_range_files(&repository, &base, &head, cx).await?;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let existing = workspace.active_pane().read(cx).items().position(|item| {
+                        item.downcast::<CommitRangeView>().is_some_and(|view| {
+                            let view = view.read(cx);
+                            view.base.as_ref() == base.as_str()
+                                && view.head.as_ref() == head.as_str()
+                        })
+                    });
+                    if let Some(ix) = existing {
+                        workspace.active_pane().update(cx, |pane, cx| {
+                            pane.activate_item(ix, true, true, window, cx);
+                        });
+                        return;
+                    }
+
+                    let project = workspace.project().clone();
+                    let view = cx.new(|cx| {
+                        let multibuffer = cx.new(|_| MultiBuffer::new(Capability::ReadOnly));
+                        let editor = cx.new(|cx| {
+                            let mut editor = Editor::for_multibuffer(
+                                multibuffer.clone(),
+                                Some(project.clone()),
+                                window,
+                                cx,
+                            );
+                            editor.disable_inline_diagnostics();
+                            editor.set_show_bookmarks(false, cx);
+                            editor.set_show_breakpoints(false, cx);
+                            editor.set_expand_all_diff_hunks(cx);
+                            editor
+                        });
+
+                        let language_registry = project.read(cx).languages().clone();
+                        let first_worktree_id = project
+                            .read(cx)
+                            .worktrees(cx)
+                            .next()
+                            .map(|worktree| worktree.read(cx).id());
+                        let label = format!("{}..{}", short_sha(&base), short_sha(&head));
+                        let editor_handle = editor.downgrade();
+                        cx.spawn(async move |_, cx| {
+                            add_commit_files(
+                                files,
+                                &label,
+                                repository,
+                                first_worktree_id,
+                                language_registry,
+                                editor_handle,
+                                cx,
+                            )
+                            .await
+                        })
+                        .detach();
+
+                        Self {
+                            base: base.into(),
+                            head: head.into(),
+                            editor,
+                            multibuffer,
+                        }
+                    });
+                    workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+                })
+            })
+            .detach_and_notify_err(workspace_handle, window, cx);
+    }
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(0..7).unwrap_or(sha)
+}
+
+/// Loads the old and new text of every file that differs between `base` and
+/// `head`. `diff-tree` only reports the blob on the `base` side, so the tree
+/// is diffed in both directions to find the blobs on the `head` side as well.
+async fn load_range_files(
+    repository: &Entity<Repository>,
+    base: &str,
+    head: &str,
+    cx: &mut AsyncWindowContext,
+) -> Result<Vec<CommitFile>> {
+    let (forward, backward) = repository.update(cx, |repository, cx| {
+        (
+            repository.diff_tree(
+                DiffTreeType::Since {
+                    base: base.to_string().into(),
+                    head: head.to_string().into(),
+                },
+                cx,
+            ),
+            repository.diff_tree(
+                DiffTreeType::Since {
+                    base: head.to_string().into(),
+                    head: base.to_string().into(),
+                },
+                cx,
+            ),
+        )
+    });
+    let forward = forward.await??;
+    let backward = backward.await??;
+
+    let mut entries = forward.entries.into_iter().collect::<Vec<_>>();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut files = Vec::with_capacity(entries.len());
+    for (path, status) in entries {
+        let old_oid = match status {
+            TreeDiffStatus::Modified { old } | TreeDiffStatus::Deleted { old } => Some(old),
+            TreeDiffStatus::Added => None,
+        };
+        let new_oid = match backward.entries.get(&path) {
+            Some(TreeDiffStatus::Modified { old } | TreeDiffStatus::Deleted { old }) => Some(*old),
+            _ => None,
+        };
+        files.push(load_file(repository, path, old_oid, new_oid, cx).await);
+    }
+    Ok(files)
+}
+
+async fn load_file(
+    repository: &Entity<Repository>,
+    path: RepoPath,
+    old_oid: Option<git::Oid>,
+    new_oid: Option<git::Oid>,
+    cx: &mut AsyncWindowContext,
+) -> CommitFile {
+    let mut old_text = None;
+    if let Some(oid) = old_oid {
+        let task = repository.update(cx, |repository, cx| repository.load_blob_content(oid, cx));
+        old_text = Some(task.await);
+    }
+    let mut new_text = None;
+    if let Some(oid) = new_oid {
+        let task = repository.update(cx, |repository, cx| repository.load_blob_content(oid, cx));
+        new_text = Some(task.await);
+    }
+
+    // Blobs that aren't valid UTF-8 can't be loaded as text.
+    let is_binary = old_text.as_ref().is_some_and(|text| text.is_err())
+        || new_text.as_ref().is_some_and(|text| text.is_err());
+    CommitFile {
+        path,
+        old_text: old_text.map(|text| text.unwrap_or_default()),
+        new_text: new_text.map(|text| text.unwrap_or_default()),
+        is_binary,
+    }
+}
+
+impl EventEmitter<EditorEvent> for CommitRangeView {}
+
+impl Focusable for CommitRangeView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for CommitRangeView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitCommit).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        format!("{}..{}", short_sha(&self.base), short_sha(&self.head)).into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(format!("Changes from {} to {}", self.base, self.head).into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Commit Range View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for CommitRangeView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (additions, deletions) = self.multibuffer.read(cx).snapshot(cx).total_changed_lines();
+
+        v_flex()
+            .key_context("CommitRangeDiff")
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .py_2()
+                    .px_3()
+                    .gap_2()
+                    .w_full()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(format!(
+                            "Changes from {} to {}",
+                            short_sha(&self.base),
+                            short_sha(&self.head)
+                        ))
+                        .buffer_font(cx),
+                    )
+                    .when(additions > 0 || deletions > 0, |this| {
+                        this.child(DiffStat::new(
+                            "commit-range-diff-stat",
+                            additions as usize,
+                            deletions as usize,
+                        ))
+                    }),
+            )
+            .when(!self.editor.read(cx).is_empty(cx), |this| {
+                this.child(div().flex_grow().child(self.editor.clone()))
+            })
+    }
+}
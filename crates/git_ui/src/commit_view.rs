@@ -3,7 +3,7 @@ use buffer_diff::BufferDiff;
 use collections::HashMap;
 use editor::display_map::{BlockPlacement, BlockProperties, BlockStyle};
 use editor::{Addon, Editor, EditorEvent, ExcerptRange, MultiBuffer, multibuffer_context_lines};
-use git::repository::{CommitDetails, CommitDiff, RepoPath, is_binary_content};
+use git::repository::{CommitDetails, CommitDiff, CommitFile, RepoPath, is_binary_content};
 use git::status::{FileStatus, StatusCode, TrackedStatus};
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, ParsedGitRemote,
@@ -242,7 +242,7 @@ impl CommitView {
             editor
         });
 
-        let commit_sha = Arc::<str>::from(commit.sha.as_ref());
+        let short_sha = commit.sha.get(0..7).unwrap_or(&commit.sha).to_string();
 
         let first_worktree_id = project
             .read(cx)
@@ -251,127 +251,19 @@ impl CommitView {
             .map(|worktree| worktree.read(cx).id());
 
         let repository_clone = repository.clone();
-
-        cx.spawn(async move |this, cx| {
-            let mut binary_buffer_ids: HashSet<language::BufferId> = HashSet::default();
-            let mut file_statuses: HashMap<language::BufferId, FileStatus> = HashMap::default();
-
-            for file in commit_diff.files {
-                let is_created = file.old_text.is_none();
-                let is_deleted = file.new_text.is_none();
-                let raw_new_text = file.new_text.unwrap_or_default();
-                let raw_old_text = file.old_text;
-
-                let is_binary = file.is_binary
-                    || is_binary_content(raw_new_text.as_bytes())
-                    || raw_old_text
-                        .as_ref()
-                        .is_some_and(|text| is_binary_content(text.as_bytes()));
-
-                let new_text = if is_binary {
-                    "(binary file not shown)".to_string()
-                } else {
-                    raw_new_text
-                };
-                let old_text = if is_binary { None } else { raw_old_text };
-                let worktree_id = repository_clone
-                    .update(cx, |repository, cx| {
-                        repository
-                            .repo_path_to_project_path(&file.path, cx)
-                            .map(|path| path.worktree_id)
-                            .or(first_worktree_id)
-                    })
-                    .context("project has no worktrees")?;
-                let short_sha = commit_sha.get(0..7).unwrap_or(&commit_sha);
-                let file_name = file
-                    .path
-                    .file_name()
-                    .map(|name| name.to_string())
-                    .unwrap_or_else(|| file.path.display(PathStyle::local()).to_string());
-                let display_name = format!("{short_sha} - {file_name}");
-
-                let file = Arc::new(GitBlob {
-                    path: file.path.clone(),
-                    is_deleted,
-                    is_binary,
-                    worktree_id,
-                    display_name,
-                }) as Arc<dyn language::File>;
-
-                let buffer = build_buffer(new_text, file, &language_registry, cx).await?;
-                let buffer_id = cx.update(|cx| buffer.read(cx).remote_id());
-
-                let status_code = if is_created {
-                    StatusCode::Added
-                } else if is_deleted {
-                    StatusCode::Deleted
-                } else {
-                    StatusCode::Modified
-                };
-                file_statuses.insert(
-                    buffer_id,
-                    FileStatus::Tracked(TrackedStatus {
-                        index_status: status_code,
-                        worktree_status: StatusCode::Unmodified,
-                    }),
-                );
-
-                if is_binary {
-                    binary_buffer_ids.insert(buffer_id);
-                }
-
-                let buffer_diff = if is_binary {
-                    None
-                } else {
-                    Some(build_buffer_diff(old_text, &buffer, &language_registry, cx).await?)
-                };
-
-                this.update(cx, |this, cx| {
-                    this.multibuffer.update(cx, |multibuffer, cx| {
-                        let snapshot = buffer.read(cx).snapshot();
-                        let path = snapshot.file().unwrap().path().clone();
-                        let excerpt_ranges = if is_binary {
-                            vec![language::Point::zero()..snapshot.max_point()]
-                        } else if let Some(buffer_diff) = &buffer_diff {
-                            let diff_snapshot = buffer_diff.read(cx).snapshot(cx);
-                            let mut hunks = diff_snapshot.hunks(&snapshot).peekable();
-                            if hunks.peek().is_none() {
-                                vec![language::Point::zero()..snapshot.max_point()]
-                            } else {
-                                hunks
-                                    .map(|hunk| hunk.buffer_range.to_point(&snapshot))
-                                    .collect::<Vec<_>>()
-                            }
-                        } else {
-                            vec![language::Point::zero()..snapshot.max_point()]
-                        };
-
-                        let _is_newly_added = multibuffer.set_excerpts_for_path(
-                            PathKey::with_sort_prefix(FILE_NAMESPACE_SORT_PREFIX, path),
-                            buffer,
-                            excerpt_ranges,
-                            multibuffer_context_lines(cx),
-                            cx,
-                        );
-                        if let Some(buffer_diff) = buffer_diff {
-                            multibuffer.add_diff(buffer_diff, cx);
-                        }
-                    });
-                })?;
-            }
-
-            this.update(cx, |this, cx| {
-                this.editor.update(cx, |editor, _cx| {
-                    editor.register_addon(CommitDiffAddon { file_statuses });
-                });
-                if !binary_buffer_ids.is_empty() {
-                    this.editor.update(cx, |editor, cx| {
-                        editor.fold_buffers(binary_buffer_ids, cx);
-                    });
-                }
-            })?;
-
-            anyhow::Ok(())
+        let editor_handle = editor.downgrade();
+
+        cx.spawn(async move |_, cx| {
+            add_commit_files(
+                commit_diff.files,
+                &short_sha,
+                repository_clone,
+                first_worktree_id,
+                language_registry,
+                editor_handle,
+                cx,
+            )
+            .await
         })
         .detach();
 
@@ -721,6 +613,133 @@ impl language::File for GitBlob {
     }
 }
 
+/// Adds an excerpt for each file to the editor's multibuffer, diffed against
+/// the file's old text. The buffers are named after `label`, usually a short sha.
+pub(crate) async fn add_commit_files(
+    files: Vec<CommitFile>,
+    label: &str,
+    repository: Entity<Repository>,
+    first_worktree_id: Option<WorktreeId>,
+    language_registry: Arc<LanguageRegistry>,
+    editor: WeakEntity<Editor>,
+    cx: &mut AsyncApp,
+) -> Result<()> {
+    let mut binary_buffer_ids: HashSet<language::BufferId> = HashSet::default();
+    let mut file_statuses: HashMap<language::BufferId, FileStatus> = HashMap::default();
+
+    for file in files {
+        let is_created = file.old_text.is_none();
+        let is_deleted = file.new_text.is_none();
+        let raw_new_text = file.new_text.unwrap_or_default();
+        let raw_old_text = file.old_text;
+
+        let is_binary = file.is_binary
+            || is_binary_content(raw_new_text.as_bytes())
+            || raw_old_text
+                .as_ref()
+                .is_some_and(|text| is_binary_content(text.as_bytes()));
+
+        let new_text = if is_binary {
+            "(binary file not shown)".to_string()
+        } else {
+            raw_new_text
+        };
+        let old_text = if is_binary { None } else { raw_old_text };
+        let worktree_id = repository
+            .update(cx, |repository, cx| {
+                repository
+                    .repo_path_to_project_path(&file.path, cx)
+                    .map(|path| path.worktree_id)
+                    .or(first_worktree_id)
+            })
+            .context("project has no worktrees")?;
+        let file_name = file
+            .path
+            .file_name()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| file.path.display(PathStyle::local()).to_string());
+        let display_name = format!("{label} - {file_name}");
+
+        let file = Arc::new(GitBlob {
+            path: file.path.clone(),
+            is_deleted,
+            is_binary,
+            worktree_id,
+            display_name,
+        }) as Arc<dyn language::File>;
+
+        let buffer = build_buffer(new_text, file, &language_registry, cx).await?;
+        let buffer_id = cx.update(|cx| buffer.read(cx).remote_id());
+
+        let status_code = if is_created {
+            StatusCode::Added
+        } else if is_deleted {
+            StatusCode::Deleted
+        } else {
+            StatusCode::Modified
+        };
+        file_statuses.insert(
+            buffer_id,
+            FileStatus::Tracked(TrackedStatus {
+                index_status: status_code,
+                worktree_status: StatusCode::Unmodified,
+            }),
+        );
+
+        if is_binary {
+            binary_buffer_ids.insert(buffer_id);
+        }
+
+        let buffer_diff = if is_binary {
+            None
+        } else {
+            Some(build_buffer_diff(old_text, &buffer, &language_registry, cx).await?)
+        };
+
+        editor.update(cx, |editor, cx| {
+            editor.buffer().update(cx, |multibuffer, cx| {
+                let snapshot = buffer.read(cx).snapshot();
+                let path = snapshot.file().unwrap().path().clone();
+                let excerpt_ranges = if is_binary {
+                    vec![language::Point::zero()..snapshot.max_point()]
+                } else if let Some(buffer_diff) = &buffer_diff {
+                    let diff_snapshot = buffer_diff.read(cx).snapshot(cx);
+                    let mut hunks = diff_snapshot.hunks(&snapshot).peekable();
+                    if hunks.peek().is_none() {
+                        vec![language::Point::zero()..snapshot.max_point()]
+                    } else {
+                        hunks
+                            .map(|hunk| hunk.buffer_range.to_point(&snapshot))
+                            .collect::<Vec<_>>()
+                    }
+                } else {
+                    vec![language::Point::zero()..snapshot.max_point()]
+                };
+
+                let _is_newly_added = multibuffer.set_excerpts_for_path(
+                    PathKey::with_sort_prefix(FILE_NAMESPACE_SORT_PREFIX, path),
+                    buffer,
+                    excerpt_ranges,
+                    multibuffer_context_lines(cx),
+                    cx,
+                );
+                if let Some(buffer_diff) = buffer_diff {
+                    multibuffer.add_diff(buffer_diff, cx);
+                }
+            });
+        })?;
+    }
+
+    editor.update(cx, |editor, cx| {
+        editor.register_addon(CommitDiffAddon { file_statuses });
+        if !binary_buffer_ids.is_empty() {
+            editor.fold_buffers(binary_buffer_ids, cx);
+        }
+    })?;
+
+    Ok(())
+}
+
 async fn build_buffer(
     mut text: String,
     blob: Arc<dyn File>,
@@ -754,7 +773,7 @@ async fn build_buffer(
     Ok(buffer)
 }
 
-async fn build_buffer_diff(
+pub(crate) async fn build_buffer_diff(
     mut old_text: Option<String>,
     buffer: &Entity<Buffer>,
     language_registry: &Arc<LanguageRegistry>,
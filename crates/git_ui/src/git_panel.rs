@@ -3,8 +3,10 @@ use crate::commit_modal::CommitModal;
 use crate::commit_tooltip::CommitTooltip;
 use crate::commit_view::CommitView;
 use crate::git_panel_settings::GitPanelScrollbarAccessor;
+use crate::merge_editor::MergeEditor;
 use crate::project_diff::{self, BranchDiff, Diff, ProjectDiff};
 use crate::remote_output::{self, RemoteAction, SuccessMessage};
+use crate::submodule_diff_view::{SubmoduleDiffView, submodule_repository};
 use crate::{branch_picker, picker_prompt, render_remote_button};
 use crate::{
     file_history_view::FileHistoryView, git_panel_settings::GitPanelSettings, git_status_icon,
@@ -31,6 +33,7 @@ use git::repository::{
 };
 use git::stash::GitStash;
 use git::status::{DiffStat, StageStatus};
+use git::submodule::{Submodule, SubmoduleOperation, SubmoduleState};
 use git::{Amend, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
 use git::{
     ExpandCommitEditor, GitHostingProviderRegistry, RestoreTrackedFiles, StageAll, StashAll,
@@ -655,10 +658,24 @@ pub struct GitPanel {
     commit_template: Option<GitCommitTemplate>,
     bulk_staging: Option<BulkStaging>,
     stash_entries: GitStash,
+    submodules: Vec<Submodule>,
+    /// Repository state the submodules were loaded for
+    submodules_key: Option<SubmodulesKey>,
+    load_submodules_task: Task<()>,
 
     _settings_subscription: Subscription,
 }
 
+/// Parts of the repository state that `git submodule status` depends on
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SubmodulesKey {
+    repo_id: RepositoryId,
+    head_sha: Option<SharedString>,
+    /// Statuses of `.gitmodules` and of the known submodules, which change when submodules
+    /// are added or removed, or when other commits are checked out in them
+    statuses: Vec<Option<FileStatus>>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct BulkStaging {
     repo_id: RepositoryId,
@@ -843,6 +860,9 @@ impl GitPanel {
                 entry_count: 0,
                 bulk_staging: None,
                 stash_entries: Default::default(),
+                submodules: Vec::new(),
+                submodules_key: None,
+                load_submodules_task: Task::ready(()),
                 _settings_subscription,
             };
 
@@ -1287,6 +1307,15 @@ impl GitPanel {
             let workspace = self.workspace.upgrade()?;
             let git_repo = self.active_repository.as_ref()?;
 
+            if self
+                .submodules
+                .iter()
+                .any(|submodule| submodule.path == entry.repo_path)
+            {
+                self.open_submodule_diff(entry.repo_path.clone(), window, cx);
+                return Some(());
+            }
+
             if let Some(project_diff) = workspace.read(cx).active_item_as::<ProjectDiff>(cx)
                 && let Some(project_path) = project_diff.read(cx).active_path(cx)
                 && Some(&entry.repo_path)
@@ -1331,6 +1360,26 @@ impl GitPanel {
         });
     }
 
+    fn open_merge_editor(
+        &mut self,
+        _: &git::OpenMergeEditor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        maybe!({
+            let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
+            let active_repo = self.active_repository.clone()?;
+            MergeEditor::open(
+                entry.repo_path.clone(),
+                active_repo,
+                self.workspace.clone(),
+                window,
+                cx,
+            );
+            Some(())
+        });
+    }
+
     fn open_file(
         &mut self,
         _: &menu::SecondaryConfirm,
@@ -2921,6 +2970,167 @@ impl GitPanel {
             .detach_and_log_err(cx);
     }
 
+    pub(crate) fn run_submodule_operation(
+        &mut self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let askpass = self.askpass_delegate(format!("git {}", operation.name()), window, cx);
+        let this = cx.weak_entity();
+
+        window
+            .spawn(cx, async move |cx| {
+                let output = repo
+                    .update(cx, |repo, cx| {
+                        repo.submodule_operation(operation, paths, askpass, cx)
+                    })
+                    .await?;
+                this.update(cx, |this, cx| {
+                    let action = RemoteAction::Submodule(operation);
+                    match output {
+                        Ok(output) => this.show_remote_output(action, output, cx),
+                        Err(e) => this.show_error_toast(action.name(), e, cx),
+                    }
+                    this.submodules_key = None;
+                    this.load_submodules(cx);
+                })
+                .ok();
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
+    /// Reloads the submodules if the repository state they depend on changed since they were
+    /// last loaded, as listing them spawns git, or sends a request for remote repositories.
+    fn load_submodules(&mut self, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            self.submodules.clear();
+            self.submodules_key = None;
+            self.load_submodules_task = Task::ready(());
+            return;
+        };
+        let key = {
+            let snapshot = repo.read(cx);
+            let gitmodules_path = RepoPath::new(".gitmodules").ok();
+            SubmodulesKey {
+                repo_id: snapshot.id,
+                head_sha: snapshot
+                    .head_commit
+                    .as_ref()
+                    .map(|commit| commit.sha.clone()),
+                statuses: gitmodules_path
+                    .iter()
+                    .chain(self.submodules.iter().map(|submodule| &submodule.path))
+                    .map(|path| snapshot.status_for_path(path).map(|entry| entry.status))
+                    .collect(),
+            }
+        };
+        if self.submodules_key.as_ref() == Some(&key) {
+            return;
+        }
+        self.submodules_key = Some(key);
+
+        // Avoid spawning git for every status change in repositories that
+        // don't have any submodules.
+        let gitmodules_path = self
+            .project
+            .read(cx)
+            .is_local()
+            .then(|| repo.read(cx).work_directory_abs_path.join(".gitmodules"));
+        let fs = self.fs.clone();
+        self.load_submodules_task = cx.spawn(async move |this, cx| {
+            let has_submodules = match gitmodules_path {
+                Some(path) => fs.is_file(&path).await,
+                None => true,
+            };
+            let submodules = if has_submodules {
+                let submodules = repo.update(cx, |repo, _| repo.submodules());
+                submodules
+                    .await
+                    .map_err(anyhow::Error::from)
+                    .and_then(|submodules| submodules)
+                    .log_err()
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            this.update(cx, |this, cx| {
+                if this.submodules != submodules {
+                    this.submodules = submodules;
+                    cx.notify();
+                }
+            })
+            .ok();
+        });
+    }
+
+    fn open_submodule(&mut self, path: &RepoPath, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.as_ref() else {
+            return;
+        };
+        let git_store = self.project.read(cx).git_store().clone();
+        if let Some(submodule) = submodule_repository(&git_store, repo.read(cx), path, cx) {
+            submodule.update(cx, |submodule, cx| submodule.set_as_active_repository(cx));
+        }
+    }
+
+    fn open_submodule_diff(&mut self, path: RepoPath, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                SubmoduleDiffView::open(path, repo, workspace, window, cx);
+            })
+            .ok();
+    }
+
+    fn deploy_submodule_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(submodule) = self.submodules.get(ix) else {
+            return;
+        };
+        let path = submodule.path.clone();
+        let this = cx.weak_entity();
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            let operation_entry = |menu: ContextMenu, label, operation| {
+                let this = this.clone();
+                let path = path.clone();
+                menu.entry(label, None, move |window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.run_submodule_operation(operation, vec![path.clone()], window, cx)
+                    })
+                    .ok();
+                })
+            };
+            let menu = context_menu.context(self.focus_handle.clone());
+            let menu = operation_entry(menu, "Update Submodule", SubmoduleOperation::Update);
+            let menu = operation_entry(menu, "Initialize Submodule", SubmoduleOperation::Init);
+            let menu = operation_entry(menu, "Sync Submodule URL", SubmoduleOperation::Sync);
+            menu.separator().entry("View Changes", None, {
+                let this = this.clone();
+                let path = path.clone();
+                move |window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.open_submodule_diff(path.clone(), window, cx)
+                    })
+                    .ok();
+                }
+            })
+        });
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
     pub(crate) fn git_clone(&mut self, repo: String, window: &mut Window, cx: &mut Context<Self>) {
         let workspace = self.workspace.clone();
 
@@ -3568,6 +3778,8 @@ impl GitPanel {
         let mut max_width_estimate = 0usize;
         let mut max_width_item_index = None;
 
+        self.load_submodules(cx);
+
         let Some(repo) = self.active_repository.as_ref() else {
             // Just clear entries if no repository is active.
             cx.notify();
@@ -4951,6 +5163,85 @@ impl GitPanel {
         rems(1.75)
     }
 
+    fn render_submodules(&self, cx: &Context<Self>) -> Option<impl IntoElement> {
+        if self.submodules.is_empty() {
+            return None;
+        }
+        let repo = self.active_repository.as_ref()?.read(cx);
+        let git_store = self.project.read(cx).git_store().clone();
+
+        Some(
+            v_flex()
+                .id("submodules")
+                .flex_none()
+                .max_h(rems(12.))
+                .overflow_y_scroll()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    h_flex().h(self.list_item_height()).pl_3().child(
+                        Label::new("Submodules")
+                            .color(Color::Muted)
+                            .size(LabelSize::Small)
+                            .line_height_style(LineHeightStyle::UiLabel),
+                    ),
+                )
+                .children(self.submodules.iter().enumerate().map(|(ix, submodule)| {
+                    let change_count = submodule_repository(&git_store, repo, &submodule.path, cx)
+                        .map(|submodule| submodule.read(cx).status_summary().count)
+                        .unwrap_or(0);
+                    let state_color = match submodule.state {
+                        SubmoduleState::Uninitialized => Color::Disabled,
+                        SubmoduleState::UpToDate => Color::Muted,
+                        SubmoduleState::Modified => Color::VersionControlModified,
+                        SubmoduleState::Conflicted => Color::VersionControlConflict,
+                    };
+                    let path = submodule.path.clone();
+
+                    h_flex()
+                        .id(("submodule", ix))
+                        .h(self.list_item_height())
+                        .w_full()
+                        .pl_3()
+                        .pr_2()
+                        .gap_1p5()
+                        .hover(|s| s.bg(cx.theme().colors().ghost_element_hover))
+                        .active(|s| s.bg(cx.theme().colors().ghost_element_active))
+                        .child(
+                            Icon::new(IconName::Folder)
+                                .size(IconSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(
+                            h_flex().min_w_0().flex_1().child(
+                                Label::new(submodule.path.as_unix_str().to_string()).truncate(),
+                            ),
+                        )
+                        .when(change_count > 0, |this| {
+                            this.child(
+                                Label::new(format!("{change_count} changed"))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        })
+                        .child(
+                            Label::new(submodule.state.label())
+                                .size(LabelSize::Small)
+                                .color(state_color),
+                        )
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.open_submodule(&path, cx);
+                        }))
+                        .on_mouse_down(
+                            MouseButton::Right,
+                            cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                                this.deploy_submodule_context_menu(event.position, ix, window, cx)
+                            }),
+                        )
+                })),
+        )
+    }
+
     fn render_list_header(
         &self,
         ix: usize,
@@ -5051,6 +5342,7 @@ impl GitPanel {
         } else {
             "Discard Changes"
         };
+        let is_conflicted = entry.status.is_conflicted();
         let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
             let is_created = entry.status.is_created();
             context_menu
@@ -5065,6 +5357,9 @@ impl GitPanel {
                 .separator()
                 .action("Open Diff", menu::Confirm.boxed_clone())
                 .action("Open File", menu::SecondaryConfirm.boxed_clone())
+                .when(is_conflicted, |menu| {
+                    menu.action("Open Merge Editor", git::OpenMergeEditor.boxed_clone())
+                })
                 .separator()
                 .action_disabled_when(is_created, "View File History", Box::new(git::FileHistory))
         });
@@ -5781,6 +6076,7 @@ impl Render for GitPanel {
             .on_action(cx.listener(Self::open_diff))
             .on_action(cx.listener(Self::open_file))
             .on_action(cx.listener(Self::file_history))
+            .on_action(cx.listener(Self::open_merge_editor))
             .on_action(cx.listener(Self::focus_changes_list))
             .on_action(cx.listener(Self::focus_editor))
             .on_action(cx.listener(Self::expand_commit_editor))
@@ -5805,6 +6101,7 @@ impl Render for GitPanel {
                             this.child(self.render_empty_state(cx).into_any_element())
                         }
                     })
+                    .children(self.render_submodules(cx))
                     .children(self.render_footer(window, cx))
                     .when(self.amend_pending, |this| {
                         this.child(self.render_pending_amend(cx))
@@ -6548,7 +6845,7 @@ impl ansi::Handler for GitOutputHandler {
     }
 }
 
-pub(crate) fn show_error_toast(
+pub fn show_error_toast(
     workspace: Entity<Workspace>,
     action: impl Into<SharedString>,
     e: anyhow::Error,
@@ -8139,4 +8436,80 @@ mod tests {
             );
         });
     }
+
+    #[gpui::test]
+    async fn test_update_submodule(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                ".gitmodules": "[submodule \"lib\"]\n\tpath = lib\n",
+                "lib": {},
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_submodules_for_repo(dot_git, &[("lib", "new-sha", SubmoduleState::Modified)]);
+        fs.set_submodule_diff_for_repo(
+            dot_git,
+            "lib",
+            git::submodule::SubmoduleDiff {
+                old_sha: Some("old-sha".into()),
+                new_sha: Some("new-sha".into()),
+                added: vec![git::submodule::SubmoduleCommit {
+                    sha: "new-sha".into(),
+                    subject: "Add a feature".into(),
+                }],
+                removed: Vec::new(),
+            },
+        );
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+        panel.read_with(cx, |panel, _| {
+            assert_eq!(
+                panel
+                    .submodules
+                    .iter()
+                    .map(|submodule| (&*submodule.sha, submodule.state))
+                    .collect::<Vec<_>>(),
+                [("new-sha", SubmoduleState::Modified)]
+            );
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.open_submodule_diff(repo_path("lib"), window, cx);
+        });
+        cx.run_until_parked();
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_item_as::<SubmoduleDiffView>(cx).is_some());
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.run_submodule_operation(SubmoduleOperation::Update, Vec::new(), window, cx);
+        });
+        cx.run_until_parked();
+        panel.read_with(cx, |panel, _| {
+            assert_eq!(
+                panel
+                    .submodules
+                    .iter()
+                    .map(|submodule| (&*submodule.sha, submodule.state))
+                    .collect::<Vec<_>>(),
+                [("old-sha", SubmoduleState::UpToDate)]
+            );
+        });
+    }
 }
@@ -13,8 +13,11 @@ mod blame_ui;
 pub mod clone;
 
 use git::{
-    repository::{Branch, CommitDetails, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    repository::{
+        Branch, CommitDetails, RepoPath, Upstream, UpstreamTracking, UpstreamTrackingStatus,
+    },
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
+    submodule::SubmoduleOperation,
 };
 use gpui::{
     App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString,
@@ -33,6 +36,7 @@ use crate::{commit_view::CommitView, git_panel::GitPanel, text_diff_view::TextDi
 mod askpass_modal;
 pub mod branch_picker;
 mod commit_modal;
+pub mod commit_range_view;
 pub mod commit_tooltip;
 pub mod commit_view;
 mod conflict_view;
@@ -41,12 +45,16 @@ pub mod file_history_view;
 pub mod git_panel;
 mod git_panel_settings;
 pub mod git_picker;
+pub mod line_history_view;
+pub mod merge_editor;
 pub mod multi_diff_view;
 pub mod picker_prompt;
 pub mod project_diff;
+pub mod rebase_view;
 pub(crate) mod remote_output;
 pub mod repository_selector;
 pub mod stash_picker;
+pub mod submodule_diff_view;
 pub mod text_diff_view;
 pub mod worktree_names;
 pub mod worktree_picker;
@@ -69,6 +77,7 @@ pub fn init(cx: &mut App) {
         git_panel::register(workspace);
         repository_selector::register(workspace);
         git_picker::register(workspace);
+        rebase_view::register(workspace);
 
         workspace.register_action(
             |workspace, action: &zed_actions::CreateWorktree, window, cx| {
@@ -158,6 +167,35 @@ pub fn init(cx: &mut App) {
                     panel.fetch(false, window, cx);
                 });
             });
+            workspace.register_action(|workspace, _: &git::SubmoduleInit, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.run_submodule_operation(SubmoduleOperation::Init, Vec::new(), window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::SubmoduleUpdate, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.run_submodule_operation(
+                        SubmoduleOperation::Update,
+                        Vec::new(),
+                        window,
+                        cx,
+                    );
+                });
+            });
+            workspace.register_action(|workspace, _: &git::SubmoduleSync, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.run_submodule_operation(SubmoduleOperation::Sync, Vec::new(), window, cx);
+                });
+            });
             workspace.register_action(|workspace, _: &git::Push, window, cx| {
                 let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                     return;
@@ -279,44 +317,72 @@ pub fn init(cx: &mut App) {
             },
         );
         workspace.register_action(|workspace, _: &git::FileHistory, window, cx| {
-            let Some(active_item) = workspace.active_item(cx) else {
-                return;
-            };
-            let Some(editor) = active_item.downcast::<Editor>() else {
+            let Some((_, repo, repo_path)) = active_file_repository(workspace, cx) else {
                 return;
             };
-            let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+            file_history_view::FileHistoryView::open(
+                repo_path,
+                workspace.project().read(cx).git_store().downgrade(),
+                repo.downgrade(),
+                workspace.weak_handle(),
+                window,
+                cx,
+            );
+        });
+        workspace.register_action(|workspace, _: &git::LineHistory, window, cx| {
+            let Some((editor, repo, repo_path)) = active_file_repository(workspace, cx) else {
                 return;
             };
-            let Some(file) = buffer.read(cx).file() else {
+            let Some(rows) = editor.update(cx, |editor, cx| editor.selected_head_rows(cx)) else {
+                let toast = Toast::new(
+                    NotificationId::unique::<git::LineHistory>(),
+                    "The selected lines have no history, as they were added since the last commit",
+                );
+                workspace.show_toast(toast, cx);
                 return;
             };
-            let worktree_id = file.worktree_id(cx);
-            let project_path = ProjectPath {
-                worktree_id,
-                path: file.path().clone(),
-            };
-            let project = workspace.project();
-            let git_store = project.read(cx).git_store();
-            let Some((repo, repo_path)) = git_store
-                .read(cx)
-                .repository_and_path_for_project_path(&project_path, cx)
-            else {
-                return;
-            };
-            file_history_view::FileHistoryView::open(
+            line_history_view::LineHistoryView::open(
                 repo_path,
-                git_store.downgrade(),
+                rows,
+                workspace.project().read(cx).git_store().downgrade(),
                 repo.downgrade(),
                 workspace.weak_handle(),
                 window,
                 cx,
             );
         });
+        workspace.register_action(|workspace, _: &git::OpenMergeEditor, window, cx| {
+            let Some((_, repo, repo_path)) = active_file_repository(workspace, cx) else {
+                return;
+            };
+            merge_editor::MergeEditor::open(repo_path, repo, workspace.weak_handle(), window, cx);
+        });
     })
     .detach();
 }
 
+/// Returns the active editor along with the repository and path of the file
+/// it's editing, if any.
+fn active_file_repository(
+    workspace: &Workspace,
+    cx: &App,
+) -> Option<(Entity<Editor>, Entity<Repository>, RepoPath)> {
+    let editor = workspace.active_item(cx)?.downcast::<Editor>()?;
+    let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+    let file = buffer.read(cx).file()?;
+    let project_path = ProjectPath {
+        worktree_id: file.worktree_id(cx),
+        path: file.path().clone(),
+    };
+    let (repo, repo_path) = workspace
+        .project()
+        .read(cx)
+        .git_store()
+        .read(cx)
+        .repository_and_path_for_project_path(&project_path, cx)?;
+    Some((editor, repo, repo_path))
+}
+
 fn open_modified_files(
     workspace: &mut Workspace,
     window: &mut Window,
@@ -0,0 +1,401 @@
+use std::any::TypeId;
+use std::ops::Range;
+
+use git::line_history::{LineHistory, LineHistoryEntry};
+use git::repository::RepoPath;
+use git::{GitHostingProviderRegistry, GitRemote, SHORT_SHA_LENGTH, parse_git_remote_url};
+use gpui::{
+    AnyElement, AnyEntity, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
+    Render, ScrollHandle, WeakEntity, Window,
+};
+use project::git_store::{GitStore, Repository};
+use time::OffsetDateTime;
+use ui::{Chip, ListItem, WithScrollbar, prelude::*};
+use workspace::{Item, Workspace, item::ItemEvent, notifications::NotifyTaskExt};
+
+use crate::commit_tooltip::CommitAvatar;
+use crate::commit_view::CommitView;
+
+/// Shows the commits that changed a range of lines, each with the hunk it
+/// applied to them, as reported by `git log -L`.
+pub struct LineHistoryView {
+    history: LineHistory,
+    repository: WeakEntity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    remote: Option<GitRemote>,
+    selected_entry: Option<usize>,
+    scroll_handle: ScrollHandle,
+    focus_handle: FocusHandle,
+}
+
+impl LineHistoryView {
+    pub fn open(
+        path: RepoPath,
+        rows: Range<u32>,
+        git_store: WeakEntity<GitStore>,
+        repo: WeakEntity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(repository) = repo.upgrade() else {
+            return;
+        };
+        let Ok(line_history_task) = git_store.update(cx, |git_store, cx| {
+            git_store.line_history(&repository, path, rows, cx)
+        }) else {
+            return;
+        };
+
+        let workspace_handle = workspace.clone();
+        window
+            .spawn(cx, async move |cx| {
+                let history = line_history_task.await?;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let pane = workspace.active_pane().clone();
+                    let existing = pane.read(cx).items().position(|item| {
+                        item.downcast::<LineHistoryView>().is_some_and(|view| {
+                            let view = view.read(cx);
+                            view.history.path == history.path && view.history.rows == history.rows
+                        })
+                    });
+                    if let Some(ix) = existing {
+                        pane.update(cx, |pane, cx| {
+                            pane.activate_item(ix, true, true, window, cx);
+                        });
+                        return;
+                    }
+
+                    let workspace_handle = workspace.weak_handle();
+                    let view =
+                        cx.new(|cx| Self::new(history, repository, workspace_handle, window, cx));
+                    pane.update(cx, |pane, cx| {
+                        pane.add_item(Box::new(view), true, true, None, window, cx);
+                    });
+                })
+            })
+            .detach_and_notify_err(workspace_handle, window, cx);
+    }
+
+    fn new(
+        history: LineHistory,
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let snapshot = repository.read(cx).snapshot();
+        let remote = snapshot
+            .remote_upstream_url
+            .as_ref()
+            .or(snapshot.remote_origin_url.as_ref())
+            .and_then(|url| {
+                let provider_registry = GitHostingProviderRegistry::default_global(cx);
+                parse_git_remote_url(provider_registry, url).map(|(host, parsed)| GitRemote {
+                    host,
+                    owner: parsed.owner.into(),
+                    repo: parsed.repo.into(),
+                })
+            });
+
+        Self {
+            history,
+            repository: repository.downgrade(),
+            workspace,
+            remote,
+            selected_entry: None,
+            scroll_handle: ScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let entry_count = self.history.entries.len();
+        if entry_count == 0 {
+            return;
+        }
+        let ix = self.selected_entry.map_or(0, |ix| (ix + 1) % entry_count);
+        self.select_ix(ix, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let entry_count = self.history.entries.len();
+        if entry_count == 0 {
+            return;
+        }
+        let ix = self
+            .selected_entry
+            .map_or(entry_count - 1, |ix| (ix + entry_count - 1) % entry_count);
+        self.select_ix(ix, cx);
+    }
+
+    fn select_ix(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        self.scroll_handle.scroll_to_item(ix);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_entry {
+            self.open_commit_view(ix, window, cx);
+        }
+    }
+
+    /// Opens the commit, showing only the file as it was named at the time.
+    fn open_commit_view(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.history.entries.get(ix) else {
+            return;
+        };
+        CommitView::open(
+            entry.commit.sha.to_string(),
+            self.repository.clone(),
+            self.workspace.clone(),
+            None,
+            Some(entry.path.clone()),
+            window,
+            cx,
+        );
+    }
+
+    fn render_hunk(&self, hunk: &SharedString, cx: &App) -> impl IntoElement {
+        let status = cx.theme().status();
+        v_flex()
+            .py_1()
+            .font_buffer(cx)
+            .text_buffer(cx)
+            .children(hunk.lines().map(|line| {
+                let (color, background) = match line.as_bytes().first() {
+                    Some(b'+') => (Color::Default, Some(status.created_background)),
+                    Some(b'-') => (Color::Default, Some(status.deleted_background)),
+                    Some(b'@') => (Color::Muted, None),
+                    _ => (Color::Default, None),
+                };
+                h_flex()
+                    .px_3()
+                    .when_some(background, |this, background| this.bg(background))
+                    .child(
+                        Label::new(line.to_string())
+                            .color(color)
+                            .buffer_font(cx)
+                            .single_line(),
+                    )
+            }))
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: &LineHistoryEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let commit = &entry.commit;
+        let short_sha = commit.sha[..SHORT_SHA_LENGTH.min(commit.sha.len())].to_string();
+        let commit_time = OffsetDateTime::from_unix_timestamp(commit.commit_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::UNIX_EPOCH);
+        let relative_timestamp = time_format::format_localized_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC),
+            time_format::TimestampFormat::Relative,
+        );
+        let renamed_from = (entry.path != self.history.path).then(|| entry.path.clone());
+
+        v_flex()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                ListItem::new(("line-history-commit", ix))
+                    .toggle_state(Some(ix) == self.selected_entry)
+                    .child(
+                        h_flex()
+                            .h_8()
+                            .w_full()
+                            .pl_0p5()
+                            .pr_2p5()
+                            .gap_2()
+                            .child(div().flex_none().child(Chip::new(short_sha)))
+                            .child(
+                                CommitAvatar::new(
+                                    &commit.sha,
+                                    Some(commit.author_email.clone()),
+                                    self.remote.as_ref(),
+                                )
+                                .size(rems_from_px(20.))
+                                .render(window, cx),
+                            )
+                            .child(
+                                h_flex()
+                                    .min_w_0()
+                                    .w_full()
+                                    .gap_1()
+                                    .child(
+                                        Label::new(commit.author_name.clone())
+                                            .size(LabelSize::Small)
+                                            .truncate(),
+                                    )
+                                    .child(
+                                        Label::new(commit.subject.clone())
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted)
+                                            .truncate(),
+                                    ),
+                            )
+                            .when_some(renamed_from, |this, path| {
+                                this.child(
+                                    Label::new(path.as_unix_str().to_string())
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted)
+                                        .buffer_font(cx)
+                                        .flex_none(),
+                                )
+                            })
+                            .child(
+                                Label::new(relative_timestamp)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .flex_none(),
+                            ),
+                    )
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.selected_entry = Some(ix);
+                        cx.notify();
+                        this.open_commit_view(ix, window, cx);
+                    })),
+            )
+            .child(self.render_hunk(&entry.hunk, cx))
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for LineHistoryView {}
+
+impl Focusable for LineHistoryView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for LineHistoryView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = self
+            .history
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| self.render_entry(ix, entry, window, cx))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("LineHistoryView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .pl_3()
+                    .pr_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(format!(
+                            "{}:{}-{}",
+                            self.history.path.as_unix_str(),
+                            self.history.rows.start + 1,
+                            self.history.rows.end
+                        ))
+                        .color(Color::Muted)
+                        .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new(format!("{} commits", self.history.entries.len()))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .size_full()
+                    .child(
+                        v_flex()
+                            .id("line-history-list")
+                            .size_full()
+                            .overflow_y_scroll()
+                            .track_scroll(&self.scroll_handle)
+                            .children(entries),
+                    )
+                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+            )
+    }
+}
+
+impl Item for LineHistoryView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let file_name = self.history.path.file_name().unwrap_or("File");
+        format!(
+            "History: {file_name}:{}-{}",
+            self.history.rows.start + 1,
+            self.history.rows.end
+        )
+        .into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(
+            format!(
+                "Git history for lines {}-{} of {}",
+                self.history.rows.start + 1,
+                self.history.rows.end,
+                self.history.path.as_unix_str()
+            )
+            .into(),
+        )
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("line history")
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
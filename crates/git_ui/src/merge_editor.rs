@@ -0,0 +1,653 @@
+//! A three-way merge editor showing the base, ours and theirs versions of a
+//! conflicted file above the working copy, in which the conflicts are resolved.
+
+use anyhow::{Context as _, Result};
+use editor::{Editor, MultiBuffer};
+use git::repository::{ConflictStages, RepoPath};
+use gpui::{
+    App, AppContext as _, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, IntoElement, Render, SharedString, Subscription, Task, WeakEntity, Window,
+};
+use language::{Buffer, BufferEvent, Capability, Point};
+use project::{ConflictSet, Project, git_store::Repository};
+use std::{any::TypeId, sync::Arc};
+use ui::{Divider, Tooltip, prelude::*};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions},
+    notifications::NotifyTaskExt,
+};
+
+use crate::commit_view::build_buffer_diff;
+
+pub struct MergeEditor {
+    repository: Entity<Repository>,
+    repo_path: RepoPath,
+    result_buffer: Entity<Buffer>,
+    conflict_set: Entity<ConflictSet>,
+    base_editor: Entity<Editor>,
+    ours_editor: Entity<Editor>,
+    theirs_editor: Entity<Editor>,
+    result_editor: Entity<Editor>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl MergeEditor {
+    pub fn open(
+        repo_path: RepoPath,
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let workspace_handle = workspace.clone();
+        window
+            .spawn(cx, async move |cx| {
+                let existing = workspace.update_in(cx, |workspace, window, cx| {
+                    let pane = workspace.active_pane().clone();
+                    let ix = pane.read(cx).items().position(|item| {
+                        item.downcast::<MergeEditor>().is_some_and(|view| {
+                            let view = view.read(cx);
+                            view.repository == repository && view.repo_path == repo_path
+                        })
+                    })?;
+                    pane.update(cx, |pane, cx| {
+                        pane.activate_item(ix, true, true, window, cx)
+                    });
+                    Some(())
+                })?;
+                if existing.is_some() {
+                    return Ok(());
+                }
+
+                let project =
+                    workspace.read_with(cx, |workspace, _| workspace.project().clone())?;
+                let project_path = repository
+                    .read_with(cx, |repository, cx| {
+                        repository.repo_path_to_project_path(&repo_path, cx)
+                    })
+                    .context("conflicted file is outside of the project")?;
+                let stages = repository
+                    .update(cx, |repository, _| {
+                        repository.load_conflict_stages(repo_path.clone())
+                    })
+                    .await??;
+                let result_buffer = project
+                    .update(cx, |project, cx| project.open_buffer(project_path, cx))
+                    .await?;
+                let panes = load_stage_panes(stages, &result_buffer, &project, cx).await?;
+
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let view = cx.new(|cx| {
+                        Self::new(
+                            repo_path,
+                            repository,
+                            result_buffer,
+                            panes,
+                            project,
+                            window,
+                            cx,
+                        )
+                    });
+                    workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+                })
+            })
+            .detach_and_notify_err(workspace_handle, window, cx);
+    }
+
+    fn new(
+        repo_path: RepoPath,
+        repository: Entity<Repository>,
+        result_buffer: Entity<Buffer>,
+        panes: StagePanes,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let stage_editor =
+            |multibuffer: Entity<MultiBuffer>, window: &mut Window, cx: &mut Context<Self>| {
+                cx.new(|cx| {
+                    let mut editor =
+                        Editor::for_multibuffer(multibuffer, Some(project.clone()), window, cx);
+                    editor.set_read_only(true);
+                    editor.disable_diagnostics(cx);
+                    editor.set_show_breakpoints(false, cx);
+                    editor.set_expand_all_diff_hunks(cx);
+                    editor.set_render_diff_hunk_controls(
+                        Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                        cx,
+                    );
+                    editor
+                })
+            };
+        let base_editor = stage_editor(panes.base, window, cx);
+        let ours_editor = stage_editor(panes.ours, window, cx);
+        let theirs_editor = stage_editor(panes.theirs, window, cx);
+        // The result is the file itself, so the conflict view adds its
+        // per-conflict buttons to this editor like to any other.
+        let result_editor = cx
+            .new(|cx| Editor::for_buffer(result_buffer.clone(), Some(project.clone()), window, cx));
+
+        let git_store = project.read(cx).git_store().clone();
+        let conflict_set = git_store.update(cx, |git_store, cx| {
+            git_store.open_conflict_set(result_buffer.clone(), cx)
+        });
+        let subscriptions = vec![
+            cx.observe(&conflict_set, |_, _, cx| cx.notify()),
+            cx.subscribe(&result_buffer, |_, _, event: &BufferEvent, cx| {
+                if matches!(event, BufferEvent::DirtyChanged | BufferEvent::Saved) {
+                    cx.emit(ItemEvent::UpdateTab);
+                }
+            }),
+        ];
+
+        Self {
+            repository,
+            repo_path,
+            result_buffer,
+            conflict_set,
+            base_editor,
+            ours_editor,
+            theirs_editor,
+            result_editor,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn conflict_count(&self, cx: &App) -> usize {
+        self.conflict_set.read(cx).snapshot().conflicts.len()
+    }
+
+    /// Moves the cursor in the result to the first conflict after it, wrapping
+    /// around at the end of the file.
+    fn go_to_next_conflict(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let snapshot = self.result_buffer.read(cx).snapshot();
+        let conflicts = self.conflict_set.read(cx).snapshot().conflicts;
+        let cursor = self.result_editor.update(cx, |editor, cx| {
+            editor
+                .selections
+                .newest::<Point>(&editor.display_snapshot(cx))
+                .head()
+        });
+        let starts = conflicts
+            .iter()
+            .map(|conflict| snapshot.summary_for_anchor::<Point>(&conflict.range.start))
+            .collect::<Vec<_>>();
+        let Some(target) = starts
+            .iter()
+            .find(|start| start.row > cursor.row)
+            .or(starts.first())
+            .copied()
+        else {
+            return;
+        };
+        self.result_editor.update(cx, |editor, cx| {
+            editor.go_to_singleton_buffer_point(target, window, cx);
+        });
+        window.focus(&self.result_editor.focus_handle(cx), cx);
+    }
+
+    /// Resolves every remaining conflict with the same side.
+    fn accept_all(&mut self, ours: bool, cx: &mut Context<Self>) {
+        let conflicts = self.conflict_set.read(cx).snapshot().conflicts;
+        for conflict in conflicts.iter().rev() {
+            let kept = if ours {
+                conflict.ours.clone()
+            } else {
+                conflict.theirs.clone()
+            };
+            conflict.resolve(self.result_buffer.clone(), &[kept], cx);
+        }
+    }
+
+    fn render_pane(
+        &self,
+        title: SharedString,
+        editor: &Entity<Editor>,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .flex_1()
+            .min_w_0()
+            .size_full()
+            .child(
+                h_flex()
+                    .h_7()
+                    .px_3()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(title).size(LabelSize::Small).color(Color::Muted)),
+            )
+            .child(div().flex_1().min_h_0().child(editor.clone()))
+    }
+}
+
+struct StagePanes {
+    base: Entity<MultiBuffer>,
+    ours: Entity<MultiBuffer>,
+    theirs: Entity<MultiBuffer>,
+}
+
+/// Creates read-only buffers for the three versions of the file, with ours and
+/// theirs diffed against the base.
+async fn load_stage_panes(
+    stages: ConflictStages,
+    result_buffer: &Entity<Buffer>,
+    project: &Entity<Project>,
+    cx: &mut AsyncWindowContext,
+) -> Result<StagePanes> {
+    let language = result_buffer.read_with(cx, |buffer, _| buffer.language().cloned());
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    let stage_buffer = |text: Option<String>, cx: &mut AsyncWindowContext| {
+        cx.new(|cx| {
+            let mut buffer = Buffer::local(text.unwrap_or_default(), cx);
+            buffer.set_language(language.clone(), cx);
+            buffer.set_capability(Capability::ReadOnly, cx);
+            buffer
+        })
+    };
+    let base_buffer = stage_buffer(stages.base.clone(), cx);
+    let ours_buffer = stage_buffer(stages.ours, cx);
+    let theirs_buffer = stage_buffer(stages.theirs, cx);
+
+    let ours_diff =
+        build_buffer_diff(stages.base.clone(), &ours_buffer, &language_registry, cx).await?;
+    let theirs_diff =
+        build_buffer_diff(stages.base, &theirs_buffer, &language_registry, cx).await?;
+
+    Ok(StagePanes {
+        base: cx.new(|cx| MultiBuffer::singleton(base_buffer, cx)),
+        ours: cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(ours_buffer, cx);
+            multibuffer.add_diff(ours_diff, cx);
+            multibuffer
+        }),
+        theirs: cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(theirs_buffer, cx);
+            multibuffer.add_diff(theirs_diff, cx);
+            multibuffer
+        }),
+    })
+}
+
+impl EventEmitter<ItemEvent> for MergeEditor {}
+
+impl Focusable for MergeEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.result_editor.focus_handle(cx)
+    }
+}
+
+impl Render for MergeEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let conflict_count = self.conflict_count(cx);
+        let (ours_name, theirs_name) = self
+            .conflict_set
+            .read(cx)
+            .snapshot()
+            .conflicts
+            .first()
+            .map(|conflict| {
+                (
+                    conflict.ours_branch_name.clone(),
+                    conflict.theirs_branch_name.clone(),
+                )
+            })
+            .unwrap_or_else(|| ("Ours".into(), "Theirs".into()));
+        let status = if conflict_count == 0 {
+            "All conflicts resolved".to_string()
+        } else if conflict_count == 1 {
+            "1 conflict remaining".to_string()
+        } else {
+            format!("{conflict_count} conflicts remaining")
+        };
+
+        v_flex()
+            .key_context("MergeEditor")
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .pl_3()
+                    .pr_2()
+                    .gap_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Label::new(self.repo_path.as_unix_str().to_string())
+                                    .color(Color::Muted)
+                                    .buffer_font(cx),
+                            )
+                            .child(Label::new(status).size(LabelSize::Small).color(
+                                if conflict_count == 0 {
+                                    Color::Created
+                                } else {
+                                    Color::Warning
+                                },
+                            )),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Button::new("next-conflict", "Next Conflict")
+                                    .label_size(LabelSize::Small)
+                                    .disabled(conflict_count == 0)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.go_to_next_conflict(window, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("accept-all-ours", format!("Use All {ours_name}"))
+                                    .label_size(LabelSize::Small)
+                                    .disabled(conflict_count == 0)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.accept_all(true, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new(
+                                    "accept-all-theirs",
+                                    format!("Use All {theirs_name}"),
+                                )
+                                .label_size(LabelSize::Small)
+                                .disabled(conflict_count == 0)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.accept_all(false, cx);
+                                })),
+                            )
+                            .child(Divider::vertical())
+                            .child(
+                                Button::new("mark-resolved", "Save and Mark Resolved")
+                                    .label_size(LabelSize::Small)
+                                    .disabled(conflict_count > 0)
+                                    .tooltip(Tooltip::text(
+                                        "Save the result and stage it to mark the conflict as resolved",
+                                    ))
+                                    .on_click(cx.listener(|_, _, window, cx| {
+                                        window.dispatch_action(
+                                            Box::new(workspace::Save { save_intent: None }),
+                                            cx,
+                                        );
+                                    })),
+                            ),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .child(self.render_pane(ours_name, &self.ours_editor, cx))
+                    .child(Divider::vertical())
+                    .child(self.render_pane("Base".into(), &self.base_editor, cx))
+                    .child(Divider::vertical())
+                    .child(self.render_pane(theirs_name, &self.theirs_editor, cx)),
+            )
+            .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .child(self.render_pane("Result".into(), &self.result_editor, cx)),
+            )
+    }
+}
+
+impl Item for MergeEditor {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let file_name = self.repo_path.file_name().unwrap_or("File");
+        format!("Merge: {file_name}").into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(format!("Resolve conflicts in {}", self.repo_path.as_unix_str()).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitMergeConflict))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("merge editor")
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.result_buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.result_buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, _cx: &App) -> bool {
+        true
+    }
+
+    /// Saves the result, and once no conflict markers remain, stages it to
+    /// mark the file as resolved.
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let result_buffer = self.result_buffer.clone();
+        let save = project.update(cx, |project, cx| {
+            project.save_buffer(result_buffer.clone(), cx)
+        });
+        let repository = self.repository.clone();
+        let repo_path = self.repo_path.clone();
+        cx.spawn(async move |_, cx| {
+            save.await?;
+            // The conflict set is updated asynchronously, so the markers are looked
+            // up in the text that was just saved.
+            let snapshot = result_buffer.read_with(cx, |buffer, _| buffer.text_snapshot());
+            let is_resolved = cx
+                .background_spawn(async move { ConflictSet::parse(&snapshot).conflicts.is_empty() })
+                .await;
+            if is_resolved {
+                repository
+                    .update(cx, |repository, cx| {
+                        repository.stage_entries(vec![repo_path], cx)
+                    })
+                    .await?;
+            }
+            Ok(())
+        })
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.result_editor.focus_handle(cx), cx);
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.result_editor.clone().into())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fs::Fs as _;
+    use git::repository::repo_path;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    use super::*;
+    use crate::conflict_view::resolve_conflict;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_resolving_conflicts_in_merge_editor(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "file.txt": "<<<<<<< HEAD\nours one\n=======\ntheirs one\n>>>>>>> feature\nmiddle\n<<<<<<< HEAD\nours two\n=======\ntheirs two\n>>>>>>> feature\n",
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_conflict_stages_for_repo(
+            dot_git,
+            &[(
+                "file.txt",
+                ConflictStages {
+                    base: Some("base one\nmiddle\nbase two\n".into()),
+                    ours: Some("ours one\nmiddle\nours two\n".into()),
+                    theirs: Some("theirs one\nmiddle\ntheirs two\n".into()),
+                },
+            )],
+        );
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        cx.update(|window, cx| {
+            MergeEditor::open(
+                repo_path("file.txt"),
+                repository,
+                workspace.downgrade(),
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        let merge_editor = workspace.read_with(cx, |workspace, cx| {
+            workspace.active_item_as::<MergeEditor>(cx).unwrap()
+        });
+        merge_editor.read_with(cx, |merge_editor, cx| {
+            assert_eq!(
+                merge_editor.base_editor.read(cx).text(cx),
+                "base one\nmiddle\nbase two\n"
+            );
+            assert_eq!(
+                merge_editor.ours_editor.read(cx).text(cx),
+                "ours one\nmiddle\nours two\n"
+            );
+            assert_eq!(
+                merge_editor.theirs_editor.read(cx).text(cx),
+                "theirs one\nmiddle\ntheirs two\n"
+            );
+            assert_eq!(merge_editor.conflict_count(cx), 2);
+        });
+
+        // Accept the incoming side of the first conflict, as its button in the result does.
+        cx.update(|window, cx| {
+            let merge_editor = merge_editor.read(cx);
+            let conflict = merge_editor.conflict_set.read(cx).snapshot().conflicts[0].clone();
+            let theirs = conflict.theirs.clone();
+            resolve_conflict(
+                merge_editor.result_editor.downgrade(),
+                conflict,
+                vec![theirs],
+                window,
+                cx,
+            )
+        })
+        .await;
+        cx.run_until_parked();
+        merge_editor.read_with(cx, |merge_editor, cx| {
+            assert_eq!(
+                merge_editor.result_buffer.read(cx).text(),
+                "theirs one\nmiddle\n<<<<<<< HEAD\nours two\n=======\ntheirs two\n>>>>>>> feature\n"
+            );
+            assert_eq!(merge_editor.conflict_count(cx), 1);
+        });
+
+        // Saving while conflicts remain doesn't mark the file as resolved.
+        merge_editor
+            .update_in(cx, |merge_editor, window, cx| {
+                merge_editor.save(SaveOptions::default(), project.clone(), window, cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            fs.load(Path::new(path!("/project/file.txt")))
+                .await
+                .unwrap(),
+            "theirs one\nmiddle\n<<<<<<< HEAD\nours two\n=======\ntheirs two\n>>>>>>> feature\n"
+        );
+        assert_eq!(index_text(&fs, dot_git), None);
+
+        // Resolve the last conflict by hand, saving before the conflict set catches up.
+        merge_editor.update(cx, |merge_editor, cx| {
+            merge_editor.result_buffer.update(cx, |buffer, cx| {
+                let text = buffer.text();
+                let start = text.find("<<<<<<<").unwrap();
+                buffer.edit([(start..text.len(), "merged two\n")], None, cx);
+            });
+        });
+        merge_editor
+            .update_in(cx, |merge_editor, window, cx| {
+                merge_editor.save(SaveOptions::default(), project.clone(), window, cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            index_text(&fs, dot_git).as_deref(),
+            Some("theirs one\nmiddle\nmerged two\n")
+        );
+        merge_editor.read_with(cx, |merge_editor, cx| {
+            assert_eq!(merge_editor.conflict_count(cx), 0);
+            assert!(!merge_editor.is_dirty(cx));
+        });
+    }
+
+    fn index_text(fs: &FakeFs, dot_git: &Path) -> Option<String> {
+        fs.with_git_state(dot_git, false, |state| {
+            state.index_contents.get(&repo_path("file.txt")).cloned()
+        })
+        .unwrap()
+    }
+}
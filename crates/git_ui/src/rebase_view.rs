@@ -0,0 +1,1026 @@
+use editor::Editor;
+use git::{
+    SHORT_SHA_LENGTH,
+    rebase::{RebaseAction, RebaseOperation, RebaseState, RebaseTodoEntry},
+    repository::RepoPath,
+};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString,
+    Subscription, Task, WeakEntity, Window, actions,
+};
+use project::git_store::{Repository, RepositoryEvent};
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use workspace::{ModalView, Workspace};
+
+use crate::git_panel::show_error_toast;
+
+actions!(
+    rebase_view,
+    [
+        /// Moves the selected commit up in the rebase todo list.
+        MoveEntryUp,
+        /// Moves the selected commit down in the rebase todo list.
+        MoveEntryDown,
+        /// Cycles through the rebase actions for the selected commit.
+        CycleEntryAction,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::InteractiveRebase, window, cx| {
+        RebaseView::toggle(workspace, window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::RebaseContinue, _, cx| {
+        run_rebase_operation(workspace, RebaseOperation::Continue, cx);
+    });
+    workspace.register_action(|workspace, _: &git::RebaseSkip, _, cx| {
+        run_rebase_operation(workspace, RebaseOperation::Skip, cx);
+    });
+    workspace.register_action(|workspace, _: &git::RebaseAbort, _, cx| {
+        run_rebase_operation(workspace, RebaseOperation::Abort, cx);
+    });
+}
+
+fn run_rebase_operation(
+    workspace: &mut Workspace,
+    operation: RebaseOperation,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    let receiver = repository.update(cx, |repository, _| repository.rebase_operation(operation));
+    cx.spawn(async move |workspace, cx| {
+        let result = receiver.await?;
+        if let Err(error) = result {
+            workspace.update(cx, |_, cx| {
+                show_error_toast(cx.entity(), operation_name(operation), error, cx);
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+fn operation_name(operation: RebaseOperation) -> &'static str {
+    match operation {
+        RebaseOperation::Continue => "rebase --continue",
+        RebaseOperation::Skip => "rebase --skip",
+        RebaseOperation::Abort => "rebase --abort",
+    }
+}
+
+struct TodoRow {
+    entry: RebaseTodoEntry,
+    message_editor: Option<Entity<Editor>>,
+}
+
+enum Mode {
+    Loading,
+    Editing(Vec<TodoRow>),
+    InProgress(RebaseState),
+}
+
+/// A modal for editing the todo list of an interactive rebase and for driving
+/// a rebase that stopped because of conflicts or an `edit` entry.
+pub struct RebaseView {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    base_editor: Entity<Editor>,
+    focus_handle: FocusHandle,
+    mode: Mode,
+    selected_ix: usize,
+    error: Option<SharedString>,
+    refresh_task: Option<Task<()>>,
+    operation_task: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl RebaseView {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        let workspace_handle = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(repository, workspace_handle, window, cx)
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let base = repository
+            .read(cx)
+            .branch
+            .as_ref()
+            .and_then(|branch| branch.upstream.as_ref())
+            .map(|upstream| upstream.ref_name.to_string())
+            .unwrap_or_default();
+        let base_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Rebase onto branch or commit…", window, cx);
+            editor.set_text(base, window, cx);
+            editor
+        });
+        let subscription = cx.subscribe(&repository, |this, _, event: &RepositoryEvent, cx| {
+            if matches!(
+                event,
+                RepositoryEvent::StatusesChanged | RepositoryEvent::HeadChanged
+            ) && matches!(this.mode, Mode::InProgress(_))
+            {
+                this.refresh(cx);
+            }
+        });
+        let focus_handle = cx.focus_handle();
+        window.focus(&focus_handle, cx);
+
+        let mut this = Self {
+            repository,
+            workspace,
+            base_editor,
+            focus_handle,
+            mode: Mode::Loading,
+            selected_ix: 0,
+            error: None,
+            refresh_task: None,
+            operation_task: None,
+            _subscription: subscription,
+        };
+        this.refresh(cx);
+        this
+    }
+
+    /// Shows the progress of the rebase in progress, or the commits that would
+    /// be rebased onto the current base if there is none.
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        let state = self
+            .repository
+            .update(cx, |repository, _| repository.rebase_state());
+        self.refresh_task = Some(cx.spawn(async move |this, cx| {
+            let state = state.await.map_err(anyhow::Error::from).and_then(|s| s);
+            this.update(cx, |this, cx| match state {
+                Ok(Some(state)) => {
+                    this.mode = Mode::InProgress(state);
+                    cx.notify();
+                }
+                Ok(None) => {
+                    if matches!(this.mode, Mode::InProgress(_)) {
+                        cx.emit(DismissEvent);
+                    } else {
+                        this.load_todo(cx);
+                    }
+                }
+                Err(error) => {
+                    this.error = Some(error.to_string().into());
+                    cx.notify();
+                }
+            })
+            .ok();
+        }));
+    }
+
+    fn load_todo(&mut self, cx: &mut Context<Self>) {
+        let base = self.base_editor.read(cx).text(cx).trim().to_string();
+        if base.is_empty() {
+            self.mode = Mode::Editing(Vec::new());
+            cx.notify();
+            return;
+        }
+        let todo = self
+            .repository
+            .update(cx, |repository, _| repository.rebase_todo(base));
+        self.mode = Mode::Loading;
+        self.error = None;
+        self.refresh_task = Some(cx.spawn(async move |this, cx| {
+            let todo = todo.await.map_err(anyhow::Error::from).and_then(|t| t);
+            this.update(cx, |this, cx| {
+                match todo {
+                    Ok(entries) => {
+                        if entries.is_empty() {
+                            this.error = Some("No commits to rebase".into());
+                        }
+                        this.mode = Mode::Editing(
+                            entries
+                                .into_iter()
+                                .map(|entry| TodoRow {
+                                    entry,
+                                    message_editor: None,
+                                })
+                                .collect(),
+                        );
+                        this.selected_ix = 0;
+                    }
+                    Err(error) => {
+                        this.mode = Mode::Editing(Vec::new());
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn start_rebase(&mut self, cx: &mut Context<Self>) {
+        let Mode::Editing(rows) = &self.mode else {
+            return;
+        };
+        if rows.is_empty() || self.validation_error().is_some() {
+            return;
+        }
+        let base = self.base_editor.read(cx).text(cx).trim().to_string();
+        let entries = rows
+            .iter()
+            .map(|row| {
+                let mut entry = row.entry.clone();
+                if entry.action == RebaseAction::Reword {
+                    entry.message = row
+                        .message_editor
+                        .as_ref()
+                        .map(|editor| editor.read(cx).text(cx))
+                        .filter(|message| !message.trim().is_empty())
+                        .map(Into::into);
+                }
+                entry
+            })
+            .collect();
+        let rebase = self.repository.update(cx, |repository, _| {
+            repository.rebase_interactive(base, entries)
+        });
+        self.mode = Mode::Loading;
+        self.error = None;
+        self.operation_task = Some(cx.spawn(async move |this, cx| {
+            let result = rebase.await.map_err(anyhow::Error::from).and_then(|r| r);
+            this.update(cx, |this, cx| match result {
+                Ok(()) => {
+                    // Show the progress if the rebase stopped, otherwise dismiss.
+                    this.mode = Mode::InProgress(RebaseState::default());
+                    this.refresh(cx);
+                }
+                Err(error) => {
+                    this.load_todo(cx);
+                    this.show_error("rebase --interactive", error, cx);
+                }
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn run_operation(&mut self, operation: RebaseOperation, cx: &mut Context<Self>) {
+        let task = self
+            .repository
+            .update(cx, |repository, _| repository.rebase_operation(operation));
+        self.error = None;
+        self.operation_task = Some(cx.spawn(async move |this, cx| {
+            let result = task.await.map_err(anyhow::Error::from).and_then(|r| r);
+            this.update(cx, |this, cx| {
+                if let Err(error) = result {
+                    this.show_error(operation_name(operation), error, cx);
+                }
+                this.refresh(cx);
+            })
+            .ok();
+        }));
+    }
+
+    fn show_error(&mut self, operation: &str, error: anyhow::Error, cx: &mut Context<Self>) {
+        self.error = Some(error.to_string().into());
+        if let Some(workspace) = self.workspace.upgrade() {
+            show_error_toast(workspace, operation.to_string(), error, cx);
+        }
+        cx.notify();
+    }
+
+    fn validation_error(&self) -> Option<SharedString> {
+        let Mode::Editing(rows) = &self.mode else {
+            return None;
+        };
+        let first_kept = rows
+            .iter()
+            .find(|row| row.entry.action != RebaseAction::Drop)?;
+        first_kept.entry.action.melds_into_previous().then(|| {
+            format!(
+                "Cannot {} without a previous commit",
+                first_kept.entry.action.as_str()
+            )
+            .into()
+        })
+    }
+
+    fn set_action(
+        &mut self,
+        ix: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Mode::Editing(rows) = &mut self.mode else {
+            return;
+        };
+        let Some(row) = rows.get_mut(ix) else {
+            return;
+        };
+        row.entry.action = action;
+        if action == RebaseAction::Reword && row.message_editor.is_none() {
+            let subject = row.entry.subject.clone();
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::auto_height(1, 8, window, cx);
+                editor.set_text(subject.to_string(), window, cx);
+                editor
+            });
+            row.message_editor = Some(editor.clone());
+
+            // Replace the subject with the full commit message once it's loaded.
+            let details = self.repository.update(cx, |repository, _| {
+                repository.show(row.entry.sha.to_string())
+            });
+            cx.spawn_in(window, async move |_, cx| {
+                let details = details.await??;
+                editor.update_in(cx, |editor, window, cx| {
+                    if editor.text(cx) == subject.as_ref() {
+                        editor.set_text(details.message.trim_end().to_string(), window, cx);
+                    }
+                })
+            })
+            .detach_and_log_err(cx);
+        }
+        cx.notify();
+    }
+
+    fn cycle_action(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Mode::Editing(rows) = &self.mode else {
+            return;
+        };
+        let Some(row) = rows.get(ix) else {
+            return;
+        };
+        let position = RebaseAction::ALL
+            .iter()
+            .position(|action| *action == row.entry.action)
+            .unwrap_or(0);
+        let action = RebaseAction::ALL[(position + 1) % RebaseAction::ALL.len()];
+        self.set_action(ix, action, window, cx);
+    }
+
+    fn move_entry(&mut self, ix: usize, up: bool, cx: &mut Context<Self>) {
+        let Mode::Editing(rows) = &mut self.mode else {
+            return;
+        };
+        let target = if up {
+            ix.checked_sub(1)
+        } else {
+            Some(ix + 1).filter(|target| *target < rows.len())
+        };
+        if let Some(target) = target {
+            rows.swap(ix, target);
+            self.selected_ix = target;
+            cx.notify();
+        }
+    }
+
+    fn entry_count(&self) -> usize {
+        match &self.mode {
+            Mode::Editing(rows) => rows.len(),
+            _ => 0,
+        }
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_ix + 1 < self.entry_count() {
+            self.selected_ix += 1;
+            cx.notify();
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected_ix > 0 {
+            self.selected_ix -= 1;
+            cx.notify();
+        }
+    }
+
+    fn move_entry_up(&mut self, _: &MoveEntryUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_entry(self.selected_ix, true, cx);
+    }
+
+    fn move_entry_down(&mut self, _: &MoveEntryDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_entry(self.selected_ix, false, cx);
+    }
+
+    fn cycle_entry_action(
+        &mut self,
+        _: &CycleEntryAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.cycle_action(self.selected_ix, window, cx);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.base_editor.focus_handle(cx).is_focused(window) {
+            self.load_todo(cx);
+            window.focus(&self.focus_handle, cx);
+        } else {
+            self.start_rebase(cx);
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn open_conflict(&self, repo_path: &RepoPath, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(project_path) = self
+            .repository
+            .read(cx)
+            .repo_path_to_project_path(repo_path, cx)
+        else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .open_path(project_path, None, true, window, cx)
+                    .detach_and_log_err(cx);
+            })
+            .ok();
+        cx.emit(DismissEvent);
+    }
+
+    fn mark_resolved(&self, repo_path: RepoPath, cx: &mut Context<Self>) {
+        self.repository
+            .update(cx, |repository, cx| {
+                repository.stage_entries(vec![repo_path], cx)
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn action_color(action: RebaseAction) -> Color {
+        match action {
+            RebaseAction::Pick => Color::Default,
+            RebaseAction::Reword => Color::Info,
+            RebaseAction::Edit => Color::Accent,
+            RebaseAction::Squash | RebaseAction::Fixup => Color::Warning,
+            RebaseAction::Drop => Color::Error,
+        }
+    }
+
+    fn render_todo_row(
+        &self,
+        ix: usize,
+        row: &TodoRow,
+        row_count: usize,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let action = row.entry.action;
+        let short_sha: String = row.entry.sha.chars().take(SHORT_SHA_LENGTH).collect();
+        v_flex()
+            .child(
+                ListItem::new(("rebase-entry", ix))
+                    .inset(true)
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(ix == self.selected_ix)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.selected_ix = ix;
+                        cx.notify();
+                    }))
+                    .child(
+                        h_flex()
+                            .min_w_0()
+                            .w_full()
+                            .gap_2()
+                            .child(
+                                Button::new(("rebase-action", ix), action.as_str())
+                                    .label_size(LabelSize::Small)
+                                    .color(Self::action_color(action))
+                                    .tooltip(Tooltip::text("Change Action"))
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.selected_ix = ix;
+                                        this.cycle_action(ix, window, cx);
+                                    })),
+                            )
+                            .child(
+                                Label::new(short_sha)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .buffer_font(cx),
+                            )
+                            .child(
+                                div().min_w_0().flex_1().overflow_x_hidden().child(
+                                    Label::new(row.entry.subject.clone())
+                                        .truncate()
+                                        .when(action == RebaseAction::Drop, |label| {
+                                            label.strikethrough().color(Color::Muted)
+                                        }),
+                                ),
+                            ),
+                    )
+                    .end_slot(
+                        h_flex()
+                            .gap_0p5()
+                            .child(
+                                IconButton::new(("move-up", ix), IconName::ArrowUp)
+                                    .icon_size(IconSize::Small)
+                                    .disabled(ix == 0)
+                                    .tooltip(Tooltip::text("Move Up"))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.move_entry(ix, true, cx);
+                                    })),
+                            )
+                            .child(
+                                IconButton::new(("move-down", ix), IconName::ArrowDown)
+                                    .icon_size(IconSize::Small)
+                                    .disabled(ix + 1 == row_count)
+                                    .tooltip(Tooltip::text("Move Down"))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.move_entry(ix, false, cx);
+                                    })),
+                            ),
+                    ),
+            )
+            .when_some(
+                row.message_editor
+                    .clone()
+                    .filter(|_| action == RebaseAction::Reword),
+                |this, editor| {
+                    this.child(
+                        div()
+                            .mx_2()
+                            .mb_1()
+                            .p_1()
+                            .border_1()
+                            .rounded_sm()
+                            .border_color(cx.theme().colors().border_variant)
+                            .bg(cx.theme().colors().editor_background)
+                            .child(editor),
+                    )
+                },
+            )
+    }
+
+    fn render_editing(&self, rows: &[TodoRow], cx: &mut Context<Self>) -> impl IntoElement {
+        let row_count = rows.len();
+        let validation_error = self.validation_error();
+        v_flex()
+            .child(
+                v_flex()
+                    .id("rebase-todo")
+                    .max_h(rems(28.))
+                    .overflow_y_scroll()
+                    .py_1()
+                    .children(
+                        rows.iter()
+                            .enumerate()
+                            .map(|(ix, row)| self.render_todo_row(ix, row, row_count, cx)),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .w_full()
+                    .p_2()
+                    .gap_2()
+                    .justify_between()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(
+                            validation_error
+                                .clone()
+                                .or_else(|| self.error.clone())
+                                .unwrap_or_else(|| format!("{row_count} commits to rebase").into()),
+                        )
+                        .size(LabelSize::Small)
+                        .color(
+                            if validation_error.is_some() || self.error.is_some() {
+                                Color::Error
+                            } else {
+                                Color::Muted
+                            },
+                        ),
+                    )
+                    .child(
+                        Button::new("start-rebase", "Start Rebase")
+                            .disabled(row_count == 0 || validation_error.is_some())
+                            .on_click(cx.listener(|this, _, _, cx| this.start_rebase(cx))),
+                    ),
+            )
+    }
+
+    fn render_in_progress(&self, state: &RebaseState, cx: &mut Context<Self>) -> impl IntoElement {
+        let conflicts = self
+            .repository
+            .read(cx)
+            .cached_status()
+            .filter(|entry| entry.status.is_conflicted())
+            .map(|entry| entry.repo_path)
+            .collect::<Vec<_>>();
+        let stopped_ix = state.done.len().checked_sub(1);
+        let title = match (&state.head_name, &state.onto) {
+            (Some(head), Some(onto)) => format!(
+                "Rebasing {head} onto {}",
+                onto.chars().take(SHORT_SHA_LENGTH).collect::<String>()
+            ),
+            (Some(head), None) => format!("Rebasing {head}"),
+            _ => "Rebasing".to_string(),
+        };
+        let step = format!(
+            "{}/{}",
+            state.done.len(),
+            state.done.len() + state.remaining.len()
+        );
+
+        let entry_row = |ix: usize, entry: &RebaseTodoEntry, status: Option<IconName>| {
+            let short_sha: String = entry.sha.chars().take(SHORT_SHA_LENGTH).collect();
+            h_flex()
+                .px_2()
+                .py_0p5()
+                .gap_2()
+                .child(div().w_4().children(
+                    status.map(|icon| Icon::new(icon).size(IconSize::Small).color(Color::Muted)),
+                ))
+                .child(
+                    Label::new(entry.action.as_str())
+                        .size(LabelSize::Small)
+                        .color(Self::action_color(entry.action)),
+                )
+                .child(
+                    Label::new(short_sha)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .buffer_font(cx),
+                )
+                .child(
+                    div().min_w_0().flex_1().overflow_x_hidden().child(
+                        Label::new(entry.subject.clone())
+                            .truncate()
+                            .when(Some(ix) != stopped_ix && status.is_some(), |label| {
+                                label.color(Color::Muted)
+                            }),
+                    ),
+                )
+        };
+
+        v_flex()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .justify_between()
+                    .child(Label::new(title))
+                    .child(Label::new(step).size(LabelSize::Small).color(Color::Muted)),
+            )
+            .child(
+                v_flex()
+                    .id("rebase-progress")
+                    .max_h(rems(20.))
+                    .overflow_y_scroll()
+                    .children(state.done.iter().enumerate().map(|(ix, entry)| {
+                        let icon = if Some(ix) == stopped_ix {
+                            if conflicts.is_empty() {
+                                IconName::ChevronRight
+                            } else {
+                                IconName::Warning
+                            }
+                        } else {
+                            IconName::Check
+                        };
+                        entry_row(ix, entry, Some(icon))
+                    }))
+                    .children(
+                        state
+                            .remaining
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, entry)| entry_row(state.done.len() + ix, entry, None)),
+                    ),
+            )
+            .when(!conflicts.is_empty(), |this| {
+                this.child(
+                    v_flex()
+                        .border_t_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .py_1()
+                        .child(
+                            div().px_2().child(
+                                Label::new("Resolve conflicts, then mark the files as resolved")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                        )
+                        .children(conflicts.iter().enumerate().map(|(ix, repo_path)| {
+                            let open_path = repo_path.clone();
+                            let resolve_path = repo_path.clone();
+                            ListItem::new(("rebase-conflict", ix))
+                                .inset(true)
+                                .spacing(ListItemSpacing::Sparse)
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.open_conflict(&open_path, window, cx);
+                                }))
+                                .child(
+                                    h_flex()
+                                        .gap_2()
+                                        .child(
+                                            Icon::new(IconName::Warning)
+                                                .size(IconSize::Small)
+                                                .color(Color::Conflict),
+                                        )
+                                        .child(Label::new(repo_path.as_unix_str().to_string())),
+                                )
+                                .end_slot(
+                                    Button::new(("mark-resolved", ix), "Mark Resolved")
+                                        .label_size(LabelSize::Small)
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.mark_resolved(resolve_path.clone(), cx);
+                                        })),
+                                )
+                        })),
+                )
+            })
+            .child(
+                h_flex()
+                    .w_full()
+                    .p_2()
+                    .gap_1()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(div().flex_1().children(
+                        self.error.clone().map(|error| {
+                            Label::new(error).size(LabelSize::Small).color(Color::Error)
+                        }),
+                    ))
+                    .child(Button::new("rebase-abort", "Abort").on_click(
+                        cx.listener(|this, _, _, cx| {
+                            this.run_operation(RebaseOperation::Abort, cx)
+                        }),
+                    ))
+                    .child(Button::new("rebase-skip", "Skip").on_click(
+                        cx.listener(|this, _, _, cx| this.run_operation(RebaseOperation::Skip, cx)),
+                    ))
+                    .child(
+                        Button::new("rebase-continue", "Continue")
+                            .disabled(!conflicts.is_empty())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.run_operation(RebaseOperation::Continue, cx)
+                            })),
+                    ),
+            )
+    }
+}
+
+impl Focusable for RebaseView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<DismissEvent> for RebaseView {}
+
+impl ModalView for RebaseView {}
+
+impl Render for RebaseView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match &self.mode {
+            Mode::Loading => h_flex()
+                .p_2()
+                .child(
+                    Label::new("Loading…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element(),
+            Mode::Editing(rows) => self.render_editing(rows, cx).into_any_element(),
+            Mode::InProgress(state) => self.render_in_progress(state, cx).into_any_element(),
+        };
+
+        v_flex()
+            .key_context("RebaseView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::move_entry_up))
+            .on_action(cx.listener(Self::move_entry_down))
+            .on_action(cx.listener(Self::cycle_entry_action))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .overflow_hidden()
+            .when(!matches!(self.mode, Mode::InProgress(_)), |this| {
+                this.child(
+                    h_flex()
+                        .w_full()
+                        .p_2()
+                        .gap_2()
+                        .border_b_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .child(
+                            Icon::new(IconName::GitBranch)
+                                .size(IconSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(div().flex_1().child(self.base_editor.clone())),
+                )
+            })
+            .child(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use fs::FakeCommit;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+    }
+
+    fn todo_shas(view: &RebaseView) -> Vec<&str> {
+        match &view.mode {
+            Mode::Editing(rows) => rows.iter().map(|row| row.entry.sha.as_ref()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_interactive_rebase(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_history_for_repo(
+            dot_git,
+            &[
+                FakeCommit::new("base", "Base"),
+                FakeCommit::new("first", "First"),
+                FakeCommit::new("second", "Second"),
+                FakeCommit::new("third", "Third"),
+            ],
+        );
+        let history_subjects = || {
+            fs.with_git_state(dot_git, false, |state| {
+                state
+                    .history
+                    .iter()
+                    .map(|commit| commit.subject.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap()
+        };
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.run_until_parked();
+
+        let view = workspace.update_in(cx, |workspace, window, cx| {
+            RebaseView::toggle(workspace, window, cx);
+            workspace.active_modal::<RebaseView>(cx).unwrap()
+        });
+        view.update_in(cx, |view, window, cx| {
+            view.base_editor
+                .update(cx, |editor, cx| editor.set_text("base", window, cx));
+            view.load_todo(cx);
+        });
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            assert_eq!(todo_shas(view), ["first", "second", "third"]);
+        });
+
+        // Drop the second commit, then stop after moving the third one first
+        view.update_in(cx, |view, window, cx| {
+            view.set_action(1, RebaseAction::Drop, window, cx);
+            view.move_entry(2, true, cx);
+            view.move_entry(1, true, cx);
+            view.set_action(0, RebaseAction::Edit, window, cx);
+            assert_eq!(todo_shas(view), ["third", "first", "second"]);
+            view.start_rebase(cx);
+        });
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            let Mode::InProgress(state) = &view.mode else {
+                panic!("expected the rebase to stop at the edited commit");
+            };
+            assert_eq!(state.stopped_sha.as_deref(), Some("third"));
+            assert_eq!(state.done.len(), 1);
+            assert_eq!(state.remaining.len(), 2);
+        });
+        assert_eq!(history_subjects(), ["Base", "Third"]);
+
+        view.update(cx, |view, cx| {
+            view.run_operation(RebaseOperation::Continue, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(history_subjects(), ["Base", "Third", "First"]);
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<RebaseView>(cx).is_none());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_abort_interactive_rebase(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_history_for_repo(
+            dot_git,
+            &[
+                FakeCommit::new("base", "Base"),
+                FakeCommit::new("first", "First"),
+                FakeCommit::new("second", "Second"),
+            ],
+        );
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+        let mut todo = repository
+            .update(cx, |repository, _| repository.rebase_todo("base".into()))
+            .await
+            .unwrap()
+            .unwrap();
+        todo[0].action = RebaseAction::Edit;
+        todo[1].action = RebaseAction::Squash;
+        repository
+            .update(cx, |repository, _| {
+                repository.rebase_interactive("base".into(), todo)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let state = repository
+            .update(cx, |repository, _| repository.rebase_state())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            state.map(|state| state.stopped_sha),
+            Some(Some("first".into()))
+        );
+
+        repository
+            .update(cx, |repository, _| {
+                repository.rebase_operation(RebaseOperation::Abort)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let state = repository
+            .update(cx, |repository, _| repository.rebase_state())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(state, None);
+        let history = fs
+            .with_git_state(dot_git, false, |state| {
+                (
+                    state.refs["HEAD"].clone(),
+                    state
+                        .history
+                        .iter()
+                        .map(|commit| commit.sha.clone())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .unwrap();
+        assert_eq!(
+            history,
+            (
+                "second".to_string(),
+                vec!["base".to_string(), "first".into(), "second".into()]
+            )
+        );
+    }
+}
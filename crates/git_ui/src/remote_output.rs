@@ -1,6 +1,7 @@
 use anyhow::Context as _;
 
 use git::repository::{Remote, RemoteCommandOutput};
+use git::submodule::SubmoduleOperation;
 use linkify::{LinkFinder, LinkKind};
 use ui::SharedString;
 use util::ResultExt as _;
@@ -10,6 +11,7 @@ pub enum RemoteAction {
     Fetch(Option<Remote>),
     Pull(Remote),
     Push(SharedString, Remote),
+    Submodule(SubmoduleOperation),
 }
 
 impl RemoteAction {
@@ -18,6 +20,7 @@ impl RemoteAction {
             RemoteAction::Fetch(_) => "fetch",
             RemoteAction::Pull(_) => "pull",
             RemoteAction::Push(_, _) => "push",
+            RemoteAction::Submodule(operation) => operation.name(),
         }
     }
 }
@@ -162,6 +165,22 @@ pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> Succ
                 style: style.unwrap_or(SuccessStyle::ToastWithLog { output }),
             }
         }
+        RemoteAction::Submodule(operation) => {
+            let message = match operation {
+                SubmoduleOperation::Init => "Initialized submodules",
+                SubmoduleOperation::Update => "Updated submodules",
+                SubmoduleOperation::Sync => "Synchronized submodule URLs",
+            };
+            let style = if output.stdout.is_empty() && output.stderr.is_empty() {
+                SuccessStyle::Toast
+            } else {
+                SuccessStyle::ToastWithLog { output }
+            };
+            SuccessMessage {
+                message: message.into(),
+                style,
+            }
+        }
     }
 }
 
@@ -0,0 +1,327 @@
+use git::{
+    SHORT_SHA_LENGTH,
+    repository::RepoPath,
+    submodule::{SubmoduleCommit, SubmoduleDiff},
+};
+use gpui::{
+    AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement, Render,
+    SharedString, Subscription, Task, WeakEntity, Window,
+};
+use project::git_store::{GitStore, Repository, RepositoryEvent};
+use std::any::TypeId;
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use workspace::{Item, Workspace, item::ItemEvent};
+
+use crate::commit_view::CommitView;
+
+/// Returns the repository that the git store discovered for the submodule at
+/// `path` inside `superproject`, if it is checked out.
+pub(crate) fn submodule_repository(
+    git_store: &Entity<GitStore>,
+    superproject: &Repository,
+    path: &RepoPath,
+    cx: &App,
+) -> Option<Entity<Repository>> {
+    let abs_path = superproject
+        .work_directory_abs_path
+        .join(path.as_std_path());
+    git_store
+        .read(cx)
+        .repositories()
+        .values()
+        .find(|repository| *repository.read(cx).work_directory_abs_path == *abs_path)
+        .cloned()
+}
+
+/// Shows the commits that a change of a submodule pointer brings in or drops.
+pub struct SubmoduleDiffView {
+    repository: Entity<Repository>,
+    git_store: WeakEntity<GitStore>,
+    workspace: WeakEntity<Workspace>,
+    path: RepoPath,
+    diff: Option<SubmoduleDiff>,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    load_task: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl SubmoduleDiffView {
+    pub fn open(
+        path: RepoPath,
+        repository: Entity<Repository>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .enumerate()
+            .find_map(|(ix, item)| {
+                let view = item.downcast::<SubmoduleDiffView>()?;
+                let view = view.read(cx);
+                (view.path == path && view.repository == repository).then_some(ix)
+            });
+        if let Some(ix) = existing {
+            workspace.active_pane().update(cx, |pane, cx| {
+                pane.activate_item(ix, true, true, window, cx);
+            });
+            return;
+        }
+
+        let git_store = workspace.project().read(cx).git_store().downgrade();
+        let workspace_handle = workspace.weak_handle();
+        let view =
+            cx.new(|cx| Self::new(path, repository, git_store, workspace_handle, window, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        path: RepoPath,
+        repository: Entity<Repository>,
+        git_store: WeakEntity<GitStore>,
+        workspace: WeakEntity<Workspace>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscription = cx.subscribe(&repository, |this, _, event: &RepositoryEvent, cx| {
+            if matches!(
+                event,
+                RepositoryEvent::StatusesChanged | RepositoryEvent::HeadChanged
+            ) {
+                this.refresh(cx);
+            }
+        });
+        let mut this = Self {
+            repository,
+            git_store,
+            workspace,
+            path,
+            diff: None,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            load_task: None,
+            _subscription: subscription,
+        };
+        this.refresh(cx);
+        this
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        let path = self.path.clone();
+        let diff = self
+            .repository
+            .update(cx, |repository, _| repository.submodule_diff(path));
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            let diff = diff.await.map_err(anyhow::Error::from).and_then(|d| d);
+            this.update(cx, |this, cx| {
+                match diff {
+                    Ok(diff) => {
+                        this.diff = Some(diff);
+                        this.error = None;
+                    }
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn open_commit(&mut self, sha: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(git_store) = self.git_store.upgrade() else {
+            return;
+        };
+        let Some(submodule) =
+            submodule_repository(&git_store, self.repository.read(cx), &self.path, cx)
+        else {
+            return;
+        };
+        CommitView::open(
+            sha.to_string(),
+            submodule.downgrade(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+    }
+
+    fn render_section(
+        &self,
+        title: &'static str,
+        icon: IconName,
+        color: Color,
+        commits: &[SubmoduleCommit],
+        can_open: bool,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        v_flex()
+            .gap_0p5()
+            .child(
+                h_flex().px_3().pt_2().child(
+                    Label::new(format!("{title} ({})", commits.len()))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            )
+            .children(commits.iter().enumerate().map(|(ix, commit)| {
+                let sha = commit.sha.clone();
+                let short_sha = commit.sha[..SHORT_SHA_LENGTH.min(commit.sha.len())].to_string();
+                ListItem::new(SharedString::from(format!("{title}-{ix}")))
+                    .spacing(ListItemSpacing::Sparse)
+                    .disabled(!can_open)
+                    .start_slot(Icon::new(icon).size(IconSize::Small).color(color))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .min_w_0()
+                            .child(
+                                Label::new(short_sha)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .buffer_font(cx),
+                            )
+                            .child(Label::new(commit.subject.clone()).truncate()),
+                    )
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.open_commit(sha.clone(), window, cx);
+                    }))
+            }))
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for SubmoduleDiffView {}
+
+impl Focusable for SubmoduleDiffView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for SubmoduleDiffView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let can_open = self.git_store.upgrade().is_some_and(|git_store| {
+            submodule_repository(&git_store, self.repository.read(cx), &self.path, cx).is_some()
+        });
+        let short = |sha: &Option<SharedString>| match sha {
+            Some(sha) => sha[..SHORT_SHA_LENGTH.min(sha.len())].to_string(),
+            None => "none".to_string(),
+        };
+
+        v_flex()
+            .id("submodule_diff_view")
+            .key_context("SubmoduleDiffView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .pl_3()
+                    .pr_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(self.path.as_unix_str().to_string())
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .when_some(self.diff.as_ref(), |this, diff| {
+                        this.child(
+                            Label::new(format!(
+                                "{} → {}",
+                                short(&diff.old_sha),
+                                short(&diff.new_sha)
+                            ))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                        )
+                    }),
+            )
+            .map(|this| match (&self.error, &self.diff) {
+                (Some(error), _) => this.child(
+                    div()
+                        .p_3()
+                        .child(Label::new(error.clone()).color(Color::Error)),
+                ),
+                (None, None) => this.child(
+                    div()
+                        .p_3()
+                        .child(Label::new("Loading…").color(Color::Muted)),
+                ),
+                (None, Some(diff)) if diff.added.is_empty() && diff.removed.is_empty() => this
+                    .child(div().p_3().child(
+                        Label::new("The submodule pointer is unchanged").color(Color::Muted),
+                    )),
+                (None, Some(diff)) => {
+                    let added = (!diff.added.is_empty()).then(|| {
+                        self.render_section(
+                            "Added Commits",
+                            IconName::Plus,
+                            Color::Created,
+                            &diff.added,
+                            can_open,
+                            cx,
+                        )
+                    });
+                    let removed = (!diff.removed.is_empty()).then(|| {
+                        self.render_section(
+                            "Removed Commits",
+                            IconName::Dash,
+                            Color::Deleted,
+                            &diff.removed,
+                            can_open,
+                            cx,
+                        )
+                    });
+                    this.children(added).children(removed)
+                }
+            })
+    }
+}
+
+impl Item for SubmoduleDiffView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let name = self.path.file_name().unwrap_or("Submodule");
+        format!("Submodule: {name}").into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(format!("Submodule changes for {}", self.path.as_unix_str()).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("submodule diff")
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,572 @@
+//! A tree of the items of an LSP call or type hierarchy, whose levels are fetched from the
+//! language server when they are first expanded.
+
+use std::ops::Range;
+
+use anyhow::Result;
+use editor::Editor;
+use gpui::{
+    Context, Entity, Render, ScrollStrategy, SharedString, Task, UniformListScrollHandle,
+    WeakEntity, Window,
+};
+use language::{Anchor, Buffer, OffsetRangeExt as _};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use util::ResultExt as _;
+use workspace::Workspace;
+
+pub struct HierarchyTree<T> {
+    workspace: WeakEntity<Workspace>,
+    scroll_handle: UniformListScrollHandle,
+    root_items: Vec<T>,
+    nodes: Vec<HierarchyNode<T>>,
+    /// The nodes currently shown, in display order.
+    entries: Vec<usize>,
+    selected_node: Option<usize>,
+    roots_state: RootsState,
+}
+
+enum RootsState {
+    Idle,
+    Loading(Task<()>),
+    Error(SharedString),
+}
+
+pub struct HierarchyNode<T> {
+    pub item: T,
+    pub parent: Option<usize>,
+    pub depth: usize,
+    pub expanded: bool,
+    children: NodeChildren,
+}
+
+enum NodeChildren {
+    NotLoaded,
+    Loading(Task<()>),
+    Loaded(Vec<usize>),
+}
+
+impl<T> HierarchyNode<T> {
+    /// Whether the node is shown as expanded, or `None` when it is known to have no children.
+    pub fn toggle_state(&self) -> Option<bool> {
+        match &self.children {
+            NodeChildren::Loaded(children) if children.is_empty() => None,
+            _ => Some(self.expanded),
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.expanded && matches!(self.children, NodeChildren::Loading(_))
+    }
+}
+
+impl<T: Clone> HierarchyTree<T> {
+    pub fn new(workspace: WeakEntity<Workspace>) -> Self {
+        Self {
+            workspace,
+            scroll_handle: UniformListScrollHandle::new(),
+            root_items: Vec::new(),
+            nodes: Vec::new(),
+            entries: Vec::new(),
+            selected_node: None,
+            roots_state: RootsState::Idle,
+        }
+    }
+
+    pub fn root_items(&self) -> &[T] {
+        &self.root_items
+    }
+
+    pub fn scroll_handle(&self) -> &UniformListScrollHandle {
+        &self.scroll_handle
+    }
+
+    pub fn is_loading_roots(&self) -> bool {
+        matches!(self.roots_state, RootsState::Loading(_))
+    }
+
+    /// Why the roots could not be loaded.
+    pub fn error(&self) -> Option<&SharedString> {
+        match &self.roots_state {
+            RootsState::Error(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The index and node shown at the given entry.
+    pub fn entry(&self, entry_ix: usize) -> Option<(usize, &HierarchyNode<T>)> {
+        let node_ix = *self.entries.get(entry_ix)?;
+        Some((node_ix, &self.nodes[node_ix]))
+    }
+
+    pub fn entry_depth(&self, entry_ix: usize) -> Option<usize> {
+        Some(self.entry(entry_ix)?.1.depth)
+    }
+
+    pub fn selected_node(&self) -> Option<usize> {
+        self.selected_node
+    }
+
+    pub fn selected_entry_ix(&self) -> Option<usize> {
+        let selected_node = self.selected_node?;
+        self.entries.iter().position(|ix| *ix == selected_node)
+    }
+
+    fn update_entries(&mut self) {
+        let mut entries = Vec::new();
+        let mut stack = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.parent.is_none())
+            .map(|(ix, _)| ix)
+            .rev()
+            .collect::<Vec<_>>();
+        while let Some(ix) = stack.pop() {
+            entries.push(ix);
+            let node = &self.nodes[ix];
+            if node.expanded
+                && let NodeChildren::Loaded(children) = &node.children
+            {
+                stack.extend(children.iter().rev());
+            }
+        }
+        self.entries = entries;
+    }
+}
+
+/// A view showing a [`HierarchyTree`], which knows how to fetch the children of its items.
+///
+/// Provides the tree's navigation, to be registered as the view's action handlers.
+pub trait HierarchyTreeView: Render {
+    type Item: Clone + 'static;
+
+    fn tree(&self) -> &HierarchyTree<Self::Item>;
+
+    fn tree_mut(&mut self) -> &mut HierarchyTree<Self::Item>;
+
+    /// Fetches the items shown below the given one, e.g. its callers.
+    fn fetch_children(
+        &mut self,
+        item: &Self::Item,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Self::Item>>>;
+
+    /// The buffer and the range in it to reveal when the item is opened.
+    fn item_location(item: &Self::Item) -> (Entity<Buffer>, Range<Anchor>);
+
+    /// Replaces the tree with the items returned by `roots`, once they are loaded.
+    fn load_roots(&mut self, roots: Task<Result<Vec<Self::Item>>>, cx: &mut Context<Self>) {
+        self.tree_mut().roots_state = RootsState::Loading(cx.spawn(async move |this, cx| {
+            let roots = roots.await;
+            this.update(cx, |this, cx| {
+                let roots = match roots {
+                    Ok(roots) => {
+                        this.tree_mut().roots_state = RootsState::Idle;
+                        roots
+                    }
+                    Err(error) => {
+                        this.tree_mut().roots_state =
+                            RootsState::Error(format!("{error:#}").into());
+                        Vec::new()
+                    }
+                };
+                this.tree_mut().root_items = roots;
+                this.rebuild(cx);
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    /// Drops the fetched items, e.g. after the direction of the hierarchy changed, and expands
+    /// the roots again.
+    fn rebuild(&mut self, cx: &mut Context<Self>) {
+        let tree = self.tree_mut();
+        tree.nodes = tree
+            .root_items
+            .iter()
+            .map(|item| HierarchyNode {
+                item: item.clone(),
+                parent: None,
+                depth: 0,
+                expanded: false,
+                children: NodeChildren::NotLoaded,
+            })
+            .collect();
+        tree.selected_node = (!tree.nodes.is_empty()).then_some(0);
+        for ix in 0..self.tree().nodes.len() {
+            self.expand(ix, cx);
+        }
+        self.tree_mut().update_entries();
+        cx.notify();
+    }
+
+    fn expand(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(node) = self.tree_mut().nodes.get_mut(ix) else {
+            return;
+        };
+        node.expanded = true;
+        if let NodeChildren::NotLoaded = node.children {
+            let item = node.item.clone();
+            let children = self.fetch_children(&item, cx);
+            self.tree_mut().nodes[ix].children =
+                NodeChildren::Loading(cx.spawn(async move |this, cx| {
+                    let children = children.await.log_err().unwrap_or_default();
+                    this.update(cx, |this, cx| this.insert_children(ix, children, cx))
+                        .ok();
+                }));
+        }
+        self.tree_mut().update_entries();
+        cx.notify();
+    }
+
+    fn collapse(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let tree = self.tree_mut();
+        if let Some(node) = tree.nodes.get_mut(ix) {
+            node.expanded = false;
+            tree.update_entries();
+            cx.notify();
+        }
+    }
+
+    fn toggle_expanded(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if self.tree().nodes.get(ix).is_some_and(|node| node.expanded) {
+            self.collapse(ix, cx);
+        } else {
+            self.expand(ix, cx);
+        }
+    }
+
+    fn insert_children(&mut self, parent: usize, items: Vec<Self::Item>, cx: &mut Context<Self>) {
+        let tree = self.tree_mut();
+        let depth = tree.nodes[parent].depth + 1;
+        let mut children = Vec::with_capacity(items.len());
+        for item in items {
+            children.push(tree.nodes.len());
+            tree.nodes.push(HierarchyNode {
+                item,
+                parent: Some(parent),
+                depth,
+                expanded: false,
+                children: NodeChildren::NotLoaded,
+            });
+        }
+        tree.nodes[parent].children = NodeChildren::Loaded(children);
+        tree.update_entries();
+        cx.notify();
+    }
+
+    fn select_entry(&mut self, entry_ix: usize, cx: &mut Context<Self>) {
+        let tree = self.tree_mut();
+        if let Some(node_ix) = tree.entries.get(entry_ix) {
+            tree.selected_node = Some(*node_ix);
+            tree.scroll_handle
+                .scroll_to_item(entry_ix, ScrollStrategy::Center);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let tree = self.tree();
+        let entry_ix = tree
+            .selected_entry_ix()
+            .map_or(0, |ix| (ix + 1).min(tree.entries.len().saturating_sub(1)));
+        self.select_entry(entry_ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let tree = self.tree();
+        let entry_ix = tree
+            .selected_entry_ix()
+            .map_or(tree.entries.len().saturating_sub(1), |ix| {
+                ix.saturating_sub(1)
+            });
+        self.select_entry(entry_ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_entry(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        let entry_ix = self.tree().entries.len().saturating_sub(1);
+        self.select_entry(entry_ix, cx);
+    }
+
+    /// Expands the selected entry, or selects its first child if it is already expanded.
+    fn expand_selected_entry(&mut self, cx: &mut Context<Self>) {
+        let tree = self.tree();
+        let Some(selected_node) = tree.selected_node else {
+            return;
+        };
+        if tree.nodes[selected_node].expanded {
+            if let Some(entry_ix) = tree.selected_entry_ix()
+                && tree
+                    .entries
+                    .get(entry_ix + 1)
+                    .is_some_and(|ix| tree.nodes[*ix].parent == Some(selected_node))
+            {
+                self.select_entry(entry_ix + 1, cx);
+            }
+        } else {
+            self.expand(selected_node, cx);
+        }
+    }
+
+    /// Collapses the selected entry, or selects its parent if it is already collapsed.
+    fn collapse_selected_entry(&mut self, cx: &mut Context<Self>) {
+        let tree = self.tree();
+        let Some(selected_node) = tree.selected_node else {
+            return;
+        };
+        let node = &tree.nodes[selected_node];
+        if node.expanded {
+            self.collapse(selected_node, cx);
+        } else if let Some(parent) = node.parent {
+            self.tree_mut().selected_node = Some(parent);
+            if let Some(entry_ix) = self.tree().selected_entry_ix() {
+                self.select_entry(entry_ix, cx);
+            }
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_node) = self.tree().selected_node {
+            self.open_node(selected_node, true, window, cx);
+        }
+    }
+
+    /// Selects the clicked node and reveals its item, focusing the editor on double clicks.
+    fn click_node(
+        &mut self,
+        ix: usize,
+        click_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.tree_mut().selected_node = Some(ix);
+        cx.notify();
+        self.open_node(ix, click_count > 1, window, cx);
+    }
+
+    /// Reveals the item of the node in an editor.
+    fn open_node(&mut self, ix: usize, focus: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let tree = self.tree();
+        let Some(node) = tree.nodes.get(ix) else {
+            return;
+        };
+        let (buffer, range) = Self::item_location(&node.item);
+        tree.workspace
+            .update(cx, |workspace, cx| {
+                let pane = workspace.active_pane().clone();
+                let editor = workspace.open_project_item::<Editor>(
+                    pane,
+                    buffer.clone(),
+                    true,
+                    focus,
+                    true,
+                    true,
+                    window,
+                    cx,
+                );
+                let range = range.to_point(&buffer.read(cx).snapshot());
+                editor.update(cx, |editor, cx| {
+                    editor.go_to_singleton_buffer_range(range, window, cx);
+                });
+            })
+            .log_err();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{IntoElement, TestAppContext, VisualTestContext, div};
+
+    /// Item `n` has the children `10n + 1` and `10n + 2`, down to three digit items.
+    struct TestView {
+        tree: HierarchyTree<u32>,
+        reversed: bool,
+        fetched: Vec<u32>,
+    }
+
+    impl Render for TestView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div()
+        }
+    }
+
+    impl HierarchyTreeView for TestView {
+        type Item = u32;
+
+        fn tree(&self) -> &HierarchyTree<u32> {
+            &self.tree
+        }
+
+        fn tree_mut(&mut self) -> &mut HierarchyTree<u32> {
+            &mut self.tree
+        }
+
+        fn fetch_children(&mut self, item: &u32, _: &mut Context<Self>) -> Task<Result<Vec<u32>>> {
+            self.fetched.push(*item);
+            let mut children = if *item >= 100 {
+                Vec::new()
+            } else {
+                vec![item * 10 + 1, item * 10 + 2]
+            };
+            if self.reversed {
+                children.reverse();
+            }
+            Task::ready(Ok(children))
+        }
+
+        fn item_location(_: &u32) -> (Entity<Buffer>, Range<Anchor>) {
+            unreachable!("the test view does not open items")
+        }
+    }
+
+    fn build_view(cx: &mut TestAppContext) -> (Entity<TestView>, &mut VisualTestContext) {
+        let (view, cx) = cx.add_window_view(|_, _| TestView {
+            tree: HierarchyTree::new(WeakEntity::new_invalid()),
+            reversed: false,
+            fetched: Vec::new(),
+        });
+        view.update(cx, |view, cx| {
+            view.load_roots(Task::ready(Ok(vec![1, 2])), cx)
+        });
+        cx.run_until_parked();
+        (view, cx)
+    }
+
+    fn visible_items(view: &Entity<TestView>, cx: &mut VisualTestContext) -> Vec<u32> {
+        view.read_with(cx, |view, _| {
+            (0..view.tree.entry_count())
+                .filter_map(|entry_ix| Some(view.tree.entry(entry_ix)?.1.item))
+                .collect()
+        })
+    }
+
+    fn selected_item(view: &Entity<TestView>, cx: &mut VisualTestContext) -> Option<u32> {
+        view.read_with(cx, |view, _| {
+            let node_ix = view.tree.selected_node()?;
+            Some(view.tree.nodes[node_ix].item)
+        })
+    }
+
+    fn node_ix(view: &Entity<TestView>, item: u32, cx: &mut VisualTestContext) -> usize {
+        view.read_with(cx, |view, _| {
+            view.tree
+                .nodes
+                .iter()
+                .position(|node| node.item == item)
+                .unwrap()
+        })
+    }
+
+    #[gpui::test]
+    async fn test_children_are_fetched_once(cx: &mut TestAppContext) {
+        let (view, cx) = build_view(cx);
+        assert_eq!(visible_items(&view, cx), [1, 11, 12, 2, 21, 22]);
+        view.read_with(cx, |view, _| assert_eq!(view.fetched, [1, 2]));
+
+        // Collapsing and expanding again shows the loaded children without fetching them.
+        let ix = node_ix(&view, 1, cx);
+        view.update(cx, |view, cx| view.toggle_expanded(ix, cx));
+        assert_eq!(visible_items(&view, cx), [1, 2, 21, 22]);
+        view.update(cx, |view, cx| view.toggle_expanded(ix, cx));
+        cx.run_until_parked();
+        assert_eq!(visible_items(&view, cx), [1, 11, 12, 2, 21, 22]);
+        view.read_with(cx, |view, _| assert_eq!(view.fetched, [1, 2]));
+
+        // Nested levels are fetched when they are first expanded.
+        let ix = node_ix(&view, 12, cx);
+        view.update(cx, |view, cx| view.expand(ix, cx));
+        cx.run_until_parked();
+        assert_eq!(visible_items(&view, cx), [1, 11, 12, 121, 122, 2, 21, 22]);
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.fetched, [1, 2, 12]);
+            let node = &view.tree.nodes[ix];
+            assert_eq!(node.toggle_state(), Some(true));
+            assert!(!node.is_loading());
+        });
+
+        // Items known to have no children cannot be toggled.
+        let ix = node_ix(&view, 121, cx);
+        view.update(cx, |view, cx| view.expand(ix, cx));
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.tree.nodes[ix].toggle_state(), None)
+        });
+        assert_eq!(visible_items(&view, cx), [1, 11, 12, 121, 122, 2, 21, 22]);
+    }
+
+    #[gpui::test]
+    async fn test_rebuild_fetches_children_again(cx: &mut TestAppContext) {
+        let (view, cx) = build_view(cx);
+        let ix = node_ix(&view, 11, cx);
+        view.update(cx, |view, cx| view.expand(ix, cx));
+        cx.run_until_parked();
+        assert_eq!(visible_items(&view, cx), [1, 11, 111, 112, 12, 2, 21, 22]);
+
+        // After the direction changes, only the roots are kept and expanded again.
+        view.update(cx, |view, cx| {
+            view.reversed = true;
+            view.rebuild(cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(visible_items(&view, cx), [1, 12, 11, 2, 22, 21]);
+        assert_eq!(selected_item(&view, cx), Some(1));
+        view.read_with(cx, |view, _| assert_eq!(view.fetched, [1, 2, 11, 1, 2]));
+    }
+
+    #[gpui::test]
+    async fn test_keyboard_navigation(cx: &mut TestAppContext) {
+        let (view, cx) = build_view(cx);
+        assert_eq!(selected_item(&view, cx), Some(1));
+
+        view.update_in(cx, |view, window, cx| {
+            view.select_next(&SelectNext, window, cx)
+        });
+        assert_eq!(selected_item(&view, cx), Some(11));
+
+        // Expanding a collapsed entry loads its children, then moves into them.
+        view.update(cx, |view, cx| view.expand_selected_entry(cx));
+        cx.run_until_parked();
+        assert_eq!(visible_items(&view, cx), [1, 11, 111, 112, 12, 2, 21, 22]);
+        assert_eq!(selected_item(&view, cx), Some(11));
+        view.update(cx, |view, cx| view.expand_selected_entry(cx));
+        assert_eq!(selected_item(&view, cx), Some(111));
+
+        // Collapsing a collapsed entry moves to its parent, then collapses the parent.
+        view.update(cx, |view, cx| view.collapse_selected_entry(cx));
+        assert_eq!(selected_item(&view, cx), Some(11));
+        view.update(cx, |view, cx| view.collapse_selected_entry(cx));
+        assert_eq!(visible_items(&view, cx), [1, 11, 12, 2, 21, 22]);
+        assert_eq!(selected_item(&view, cx), Some(11));
+
+        view.update_in(cx, |view, window, cx| {
+            view.select_previous(&SelectPrevious, window, cx)
+        });
+        assert_eq!(selected_item(&view, cx), Some(1));
+        view.update_in(cx, |view, window, cx| {
+            view.select_previous(&SelectPrevious, window, cx)
+        });
+        assert_eq!(selected_item(&view, cx), Some(1));
+        view.update_in(cx, |view, window, cx| {
+            view.select_last(&SelectLast, window, cx)
+        });
+        assert_eq!(selected_item(&view, cx), Some(22));
+        view.update_in(cx, |view, window, cx| {
+            view.select_next(&SelectNext, window, cx)
+        });
+        assert_eq!(selected_item(&view, cx), Some(22));
+        view.update_in(cx, |view, window, cx| {
+            view.select_first(&SelectFirst, window, cx)
+        });
+        assert_eq!(selected_item(&view, cx), Some(1));
+    }
+}
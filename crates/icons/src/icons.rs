@@ -92,6 +92,8 @@ pub enum IconName {
     DebugIgnoreBreakpoints,
     DebugLogBreakpoint,
     DebugPause,
+    DebugReverseContinue,
+    DebugStepBack,
     DebugStepInto,
     DebugStepOut,
     DebugStepOver,
@@ -1,21 +1,39 @@
-use chrono::{Datelike, Local, NaiveTime, Timelike};
+mod journal_browser;
+mod notes;
+mod templates;
+
+use chrono::{Local, NaiveTime};
 use editor::scroll::Autoscroll;
 use editor::{Editor, SelectionEffects};
-use gpui::{App, AppContext as _, Context, Window, actions};
+use gpui::{App, AppContext as _, Context, Task, WeakEntity, Window, actions};
+pub use journal_browser::JournalBrowser;
+use notes::{Direction, JournalNote};
 pub use settings::HourFormat;
 use settings::{RegisterSetting, Settings};
 use std::{
     fs::OpenOptions,
+    io::Write as _,
     path::{Path, PathBuf},
     sync::Arc,
 };
+use templates::TemplateVariables;
 use workspace::{AppState, OpenResult, OpenVisible, Workspace};
 
 actions!(
     journal,
     [
         /// Creates a new journal entry for today.
-        NewJournalEntry
+        NewJournalEntry,
+        /// Opens the note of the current week, creating it if needed.
+        OpenWeeklyNote,
+        /// Opens the note of the current month, creating it if needed.
+        OpenMonthlyNote,
+        /// Opens the previous journal note of the same kind as the active one.
+        OpenPreviousNote,
+        /// Opens the next journal note of the same kind as the active one.
+        OpenNextNote,
+        /// Toggles a picker searching all journal notes.
+        ToggleBrowser
     ]
 );
 
@@ -30,6 +48,20 @@ pub struct JournalSettings {
     ///
     /// Default: hour12
     pub hour_format: HourFormat,
+    /// Template of the heading inserted for every new entry.
+    /// When unset, the heading is the current time.
+    ///
+    /// Default: null
+    pub entry_template: Option<String>,
+    /// Template of the contents of new daily notes.
+    /// When unset, daily notes are created empty.
+    ///
+    /// Default: null
+    pub daily_template: Option<String>,
+    /// Template of the contents of new weekly notes.
+    pub weekly_template: String,
+    /// Template of the contents of new monthly notes.
+    pub monthly_template: String,
 }
 
 impl settings::Settings for JournalSettings {
@@ -39,6 +71,10 @@ impl settings::Settings for JournalSettings {
         Self {
             path: journal.path.unwrap(),
             hour_format: journal.hour_format.unwrap(),
+            entry_template: journal.entry_template,
+            daily_template: journal.daily_template,
+            weekly_template: journal.weekly_template.unwrap(),
+            monthly_template: journal.monthly_template.unwrap(),
         }
     }
 }
@@ -49,51 +85,211 @@ pub fn init(_: Arc<AppState>, cx: &mut App) {
             workspace.register_action(|workspace, _: &NewJournalEntry, window, cx| {
                 new_journal_entry(workspace, window, cx);
             });
+            workspace.register_action(|workspace, _: &OpenWeeklyNote, window, cx| {
+                let this_week = JournalNote::weekly_containing(Local::now().date_naive());
+                open_period_note(workspace, this_week, window, cx);
+            });
+            workspace.register_action(|workspace, _: &OpenMonthlyNote, window, cx| {
+                let this_month = JournalNote::monthly_containing(Local::now().date_naive());
+                open_period_note(workspace, this_month, window, cx);
+            });
+            workspace.register_action(|workspace, _: &OpenPreviousNote, window, cx| {
+                open_adjacent_note(workspace, Direction::Previous, window, cx);
+            });
+            workspace.register_action(|workspace, _: &OpenNextNote, window, cx| {
+                open_adjacent_note(workspace, Direction::Next, window, cx);
+            });
+            workspace.register_action(|workspace, _: &ToggleBrowser, window, cx| {
+                if let Some(journal_dir) = settings_journal_dir(cx) {
+                    JournalBrowser::toggle(workspace, journal_dir, window, cx);
+                }
+            });
         },
     )
     .detach();
 }
 
 pub fn new_journal_entry(workspace: &Workspace, window: &mut Window, cx: &mut App) {
-    let settings = JournalSettings::get_global(cx);
-    let journal_dir = match journal_dir(&settings.path) {
-        Some(journal_dir) => journal_dir,
-        None => {
-            log::error!("Can't determine journal directory");
-            return;
-        }
+    let Some(journal_dir) = settings_journal_dir(cx) else {
+        return;
     };
-    let journal_dir_clone = journal_dir.clone();
+    let settings = JournalSettings::get_global(cx).clone();
 
     let now = Local::now();
-    let month_dir = journal_dir
-        .join(format!("{:02}", now.year()))
-        .join(format!("{:02}", now.month()));
-    let entry_path = month_dir.join(format!("{:02}.md", now.day()));
+    let today = now.date_naive();
     let now = now.time();
-    let entry_heading = heading_entry(now, &settings.hour_format);
-
-    let create_entry = cx.background_spawn(async move {
-        std::fs::create_dir_all(month_dir)?;
-        OpenOptions::new()
-            .create(true)
-            .truncate(false)
-            .write(true)
-            .open(&entry_path)?;
-        Ok::<_, std::io::Error>((journal_dir, entry_path))
+    let entry_heading = match &settings.entry_template {
+        Some(entry_template) => {
+            TemplateVariables::for_date(today, now, &settings.hour_format).render(entry_template)
+        }
+        None => heading_entry(now, &settings.hour_format),
+    };
+
+    let create_entry = cx.background_spawn({
+        let journal_dir = journal_dir.clone();
+        async move {
+            create_note(
+                &journal_dir,
+                JournalNote::Daily(today),
+                settings.daily_template.as_deref(),
+                now,
+                &settings.hour_format,
+            )
+        }
+    });
+    let open_entry = open_journal_note(workspace, journal_dir, create_entry, window, cx);
+
+    window
+        .spawn(cx, async move |cx| {
+            if let Some(editor) = open_entry.await? {
+                editor.update_in(cx, |editor, window, cx| {
+                    let len = editor.buffer().read(cx).len(cx);
+                    editor.change_selections(
+                        SelectionEffects::scroll(Autoscroll::center()),
+                        window,
+                        cx,
+                        |s| s.select_ranges([len..len]),
+                    );
+                    if len.0 > 0 {
+                        editor.insert("\n\n", window, cx);
+                    }
+                    editor.insert(&entry_heading, window, cx);
+                    editor.insert("\n\n", window, cx);
+                })?;
+            }
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+}
+
+/// Open the weekly or monthly note, creating it from its template if needed
+fn open_period_note(workspace: &Workspace, note: JournalNote, window: &mut Window, cx: &mut App) {
+    let Some(journal_dir) = settings_journal_dir(cx) else {
+        return;
+    };
+    let settings = JournalSettings::get_global(cx).clone();
+    let template = match note {
+        JournalNote::Weekly { .. } => settings.weekly_template,
+        JournalNote::Monthly { .. } => settings.monthly_template,
+        JournalNote::Daily(_) => String::new(),
+    };
+
+    let hour_format = settings.hour_format;
+    let now = Local::now().time();
+    let create_period_note = cx.background_spawn({
+        let journal_dir = journal_dir.clone();
+        async move {
+            create_note(
+                &journal_dir,
+                note,
+                Some(template.as_str()),
+                now,
+                &hour_format,
+            )
+        }
     });
+    open_journal_note(workspace, journal_dir, create_period_note, window, cx)
+        .detach_and_log_err(cx);
+}
+
+/// Open the closest existing note of the same kind as the active one.
+/// When the active item isn't a journal note, the closest daily note before or after today is opened.
+fn open_adjacent_note(
+    workspace: &Workspace,
+    direction: Direction,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let Some(journal_dir) = settings_journal_dir(cx) else {
+        return;
+    };
+    let active_note = workspace
+        .active_item(cx)
+        .and_then(|item| item.project_path(cx))
+        .and_then(|project_path| {
+            workspace
+                .project()
+                .read(cx)
+                .absolute_path(&project_path, cx)
+        })
+        .and_then(|path| JournalNote::from_path(&journal_dir, &path))
+        .unwrap_or_else(|| JournalNote::Daily(Local::now().date_naive()));
+
+    let find_note = cx.background_spawn({
+        let journal_dir = journal_dir.clone();
+        async move {
+            let existing_notes = notes::existing_notes(&journal_dir);
+            notes::adjacent_note(&existing_notes, active_note, direction)
+        }
+    });
+    let workspace = workspace.weak_handle();
+    window
+        .spawn(cx, async move |cx| {
+            let Some(note) = find_note.await else {
+                log::info!("No journal note {direction:?} to {}", active_note.title());
+                return anyhow::Ok(());
+            };
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    let note_path = Task::ready(Ok(note.path(&journal_dir)));
+                    open_journal_note(workspace, journal_dir, note_path, window, cx)
+                })?
+                .await?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+}
+
+/// Create the note from the template if it doesn't exist yet, returning its path
+fn create_note(
+    journal_dir: &Path,
+    note: JournalNote,
+    template: Option<&str>,
+    now: NaiveTime,
+    hour_format: &HourFormat,
+) -> std::io::Result<PathBuf> {
+    let path = note.path(journal_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if path.exists() {
+        return Ok(path);
+    }
+
+    let contents = template
+        .map(|template| {
+            TemplateVariables::for_new_note(journal_dir, note, now, hour_format).render(template)
+        })
+        .unwrap_or_default();
+    match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(mut file) => file.write_all(contents.as_bytes())?,
+        Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(error) => return Err(error),
+    }
+    Ok(path)
+}
 
+/// Open the note at the path resolved by the task, in a new workspace of the journal directory
+/// if it isn't part of the current workspace. Resolves to the note's editor.
+fn open_journal_note(
+    workspace: &Workspace,
+    journal_dir: PathBuf,
+    note_path: Task<std::io::Result<PathBuf>>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Task<anyhow::Result<Option<WeakEntity<Editor>>>> {
     let worktrees = workspace.visible_worktrees(cx).collect::<Vec<_>>();
     let mut open_new_workspace = true;
     'outer: for worktree in worktrees.iter() {
         let worktree_root = worktree.read(cx).abs_path();
-        if *worktree_root == journal_dir_clone {
+        if *worktree_root == journal_dir {
             open_new_workspace = false;
             break;
         }
         for directory in worktree.read(cx).directories(true, 1) {
             let full_directory_path = worktree_root.join(directory.path.as_std_path());
-            if full_directory_path.ends_with(&journal_dir_clone) {
+            if full_directory_path.ends_with(&journal_dir) {
                 open_new_workspace = false;
                 break 'outer;
             }
@@ -103,43 +299,26 @@ pub fn new_journal_entry(workspace: &Workspace, window: &mut Window, cx: &mut Ap
     let app_state = workspace.app_state().clone();
     let view_snapshot = workspace.weak_handle();
 
-    window
-        .spawn(cx, async move |cx| {
-            let (journal_dir, entry_path) = create_entry.await?;
-            let opened = if open_new_workspace {
-                let OpenResult {
-                    window: new_workspace,
-                    ..
-                } = cx
-                    .update(|_window, cx| {
-                        workspace::open_paths(
-                            &[journal_dir],
-                            app_state,
-                            workspace::OpenOptions::default(),
-                            cx,
-                        )
-                    })?
-                    .await?;
-                new_workspace
-                    .update(cx, |multi_workspace, window, cx| {
-                        let workspace = multi_workspace.workspace().clone();
-                        workspace.update(cx, |workspace, cx| {
-                            workspace.open_paths(
-                                vec![entry_path],
-                                workspace::OpenOptions {
-                                    visible: Some(OpenVisible::All),
-                                    ..Default::default()
-                                },
-                                None,
-                                window,
-                                cx,
-                            )
-                        })
-                    })?
-                    .await
-            } else {
-                view_snapshot
-                    .update_in(cx, |workspace, window, cx| {
+    window.spawn(cx, async move |cx| {
+        let entry_path = note_path.await?;
+        let opened = if open_new_workspace {
+            let OpenResult {
+                window: new_workspace,
+                ..
+            } = cx
+                .update(|_window, cx| {
+                    workspace::open_paths(
+                        &[journal_dir],
+                        app_state,
+                        workspace::OpenOptions::default(),
+                        cx,
+                    )
+                })?
+                .await?;
+            new_workspace
+                .update(cx, |multi_workspace, window, cx| {
+                    let workspace = multi_workspace.workspace().clone();
+                    workspace.update(cx, |workspace, cx| {
                         workspace.open_paths(
                             vec![entry_path],
                             workspace::OpenOptions {
@@ -150,32 +329,40 @@ pub fn new_journal_entry(workspace: &Workspace, window: &mut Window, cx: &mut Ap
                             window,
                             cx,
                         )
-                    })?
-                    .await
-            };
-
-            if let Some(Some(Ok(item))) = opened.first()
-                && let Some(editor) = item.downcast::<Editor>().map(|editor| editor.downgrade())
-            {
-                editor.update_in(cx, |editor, window, cx| {
-                    let len = editor.buffer().read(cx).len(cx);
-                    editor.change_selections(
-                        SelectionEffects::scroll(Autoscroll::center()),
+                    })
+                })?
+                .await
+        } else {
+            view_snapshot
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.open_paths(
+                        vec![entry_path],
+                        workspace::OpenOptions {
+                            visible: Some(OpenVisible::All),
+                            ..Default::default()
+                        },
+                        None,
                         window,
                         cx,
-                        |s| s.select_ranges([len..len]),
-                    );
-                    if len.0 > 0 {
-                        editor.insert("\n\n", window, cx);
-                    }
-                    editor.insert(&entry_heading, window, cx);
-                    editor.insert("\n\n", window, cx);
-                })?;
-            }
+                    )
+                })?
+                .await
+        };
 
-            anyhow::Ok(())
-        })
-        .detach_and_log_err(cx);
+        Ok(opened
+            .first()
+            .and_then(|item| item.as_ref()?.as_ref().ok())
+            .and_then(|item| item.downcast::<Editor>())
+            .map(|editor| editor.downgrade()))
+    })
+}
+
+fn settings_journal_dir(cx: &App) -> Option<PathBuf> {
+    let journal_dir = journal_dir(&JournalSettings::get_global(cx).path);
+    if journal_dir.is_none() {
+        log::error!("Can't determine journal directory");
+    }
+    journal_dir
 }
 
 fn journal_dir(path: &str) -> Option<PathBuf> {
@@ -191,17 +378,7 @@ fn journal_dir(path: &str) -> Option<PathBuf> {
 }
 
 fn heading_entry(now: NaiveTime, hour_format: &HourFormat) -> String {
-    match hour_format {
-        HourFormat::Hour24 => {
-            let hour = now.hour();
-            format!("# {}:{:02}", hour, now.minute())
-        }
-        HourFormat::Hour12 => {
-            let (pm, hour) = now.hour12();
-            let am_or_pm = if pm { "PM" } else { "AM" };
-            format!("# {}:{:02} {}", hour, now.minute(), am_or_pm)
-        }
-    }
+    format!("# {}", templates::format_time(now, hour_format))
 }
 
 #[cfg(test)]
@@ -237,6 +414,137 @@ mod tests {
         }
     }
 
+    mod notes_tests {
+        use super::super::notes::*;
+        use chrono::NaiveDate;
+        use std::path::Path;
+
+        fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+            NaiveDate::from_ymd_opt(year, month, day).unwrap()
+        }
+
+        #[test]
+        fn test_note_paths_round_trip() {
+            let journal_dir = Path::new("/journal");
+            for note in [
+                JournalNote::Daily(date(2024, 3, 9)),
+                JournalNote::weekly_containing(date(2024, 3, 9)),
+                JournalNote::monthly_containing(date(2024, 3, 9)),
+            ] {
+                let path = note.path(journal_dir);
+                assert_eq!(JournalNote::from_path(journal_dir, &path), Some(note));
+            }
+            assert_eq!(
+                JournalNote::weekly_containing(date(2024, 3, 9)).link_target(),
+                "2024/week-10.md"
+            );
+            assert_eq!(
+                JournalNote::monthly_containing(date(2024, 3, 9)).link_target(),
+                "2024/03.md"
+            );
+            assert_eq!(
+                JournalNote::from_relative_path(Path::new("2024/02/30.md")),
+                None
+            );
+            assert_eq!(
+                JournalNote::from_relative_path(Path::new("2024/notes.md")),
+                None
+            );
+        }
+
+        #[test]
+        fn test_note_periods() {
+            // ISO week 1 of 2025 starts in 2024
+            let week = JournalNote::weekly_containing(date(2024, 12, 31));
+            assert_eq!(
+                week,
+                JournalNote::Weekly {
+                    year: 2025,
+                    week: 1
+                }
+            );
+            assert_eq!(week.days(), Some(date(2024, 12, 30)..=date(2025, 1, 5)));
+
+            let month = JournalNote::monthly_containing(date(2024, 2, 10));
+            assert_eq!(month.days(), Some(date(2024, 2, 1)..=date(2024, 2, 29)));
+            assert_eq!(month.title(), "February 2024");
+        }
+
+        #[test]
+        fn test_adjacent_notes() {
+            let notes = [
+                JournalNote::Daily(date(2024, 1, 1)),
+                JournalNote::Daily(date(2024, 1, 5)),
+                JournalNote::Daily(date(2024, 2, 1)),
+                JournalNote::monthly_containing(date(2023, 12, 1)),
+                JournalNote::monthly_containing(date(2024, 1, 1)),
+            ];
+
+            let today = JournalNote::Daily(date(2024, 1, 10));
+            assert_eq!(
+                adjacent_note(&notes, today, Direction::Previous),
+                Some(JournalNote::Daily(date(2024, 1, 5)))
+            );
+            assert_eq!(
+                adjacent_note(&notes, today, Direction::Next),
+                Some(JournalNote::Daily(date(2024, 2, 1)))
+            );
+            assert_eq!(
+                adjacent_note(&notes, notes[4], Direction::Previous),
+                Some(notes[3])
+            );
+            assert_eq!(adjacent_note(&notes, notes[4], Direction::Next), None);
+            assert_eq!(
+                daily_notes_within(&notes, notes[4]),
+                vec![notes[0], notes[1]]
+            );
+        }
+    }
+
+    mod templates_tests {
+        use super::super::notes::JournalNote;
+        use super::super::templates::*;
+        use super::super::*;
+        use chrono::NaiveDate;
+
+        #[test]
+        fn test_render_template() {
+            let date = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+            let time = NaiveTime::from_hms_opt(15, 5, 0).unwrap();
+            let mut variables = TemplateVariables::for_date(date, time, &HourFormat::Hour24);
+            variables.insert(OPEN_TASKS, "- [ ] Water plants".to_string());
+
+            assert_eq!(
+                variables.render(
+                    "# $JOURNAL_WEEKDAY, ${JOURNAL_DATE} at $JOURNAL_TIME (week $JOURNAL_WEEK)\n\n\
+                     $JOURNAL_OPEN_TASKS\n$HOME_UNKNOWN costs $5"
+                ),
+                "# Saturday, 2024-03-09 at 15:05 (week 10)\n\n- [ ] Water plants\n$HOME_UNKNOWN costs $5"
+            );
+        }
+
+        #[test]
+        fn test_open_tasks() {
+            let text = "# 9:00 AM\n\n- [x] Done\n- [ ] Call Bob\n  * [ ] Nested\n- [] Not a task\n";
+            assert_eq!(open_tasks(text), vec!["- [ ] Call Bob", "* [ ] Nested"]);
+        }
+
+        #[test]
+        fn test_rollup() {
+            let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+            let tuesday = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+            let rollup = rollup(&[
+                (JournalNote::Daily(monday), "- [ ] Review PR\n".to_string()),
+                (JournalNote::Daily(tuesday), "Nothing to do".to_string()),
+            ]);
+            assert_eq!(
+                rollup,
+                "- [Monday, March 4](../2024/03/04.md)\n  - [ ] Review PR\n\
+                 - [Tuesday, March 5](../2024/03/05.md)"
+            );
+        }
+    }
+
     mod journal_dir_tests {
         use super::super::*;
 
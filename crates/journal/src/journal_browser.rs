@@ -0,0 +1,306 @@
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ParentElement,
+    Render, Styled, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use std::{path::PathBuf, sync::Arc};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+use crate::{
+    notes::{self, JournalNote},
+    open_journal_note,
+};
+
+/// Modal listing all notes of the journal, searching their titles and contents
+pub struct JournalBrowser {
+    picker: Entity<Picker<JournalBrowserDelegate>>,
+}
+
+impl JournalBrowser {
+    pub fn toggle(
+        workspace: &mut Workspace,
+        journal_dir: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let weak_workspace = cx.weak_entity();
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            JournalBrowser::new(weak_workspace, journal_dir, window, cx)
+        });
+    }
+
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        journal_dir: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate =
+            JournalBrowserDelegate::new(cx.entity().downgrade(), workspace, journal_dir.clone());
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        let load_notes = cx.background_spawn(async move { load_notes(journal_dir) });
+        let picker_handle = picker.downgrade();
+        cx.spawn_in(window, async move |_, cx| {
+            let notes = load_notes.await;
+            picker_handle
+                .update_in(cx, |picker, window, cx| {
+                    picker.delegate.set_notes(notes);
+                    picker.refresh(window, cx);
+                })
+                .log_err();
+        })
+        .detach();
+
+        Self { picker }
+    }
+}
+
+impl Render for JournalBrowser {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("JournalBrowser")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for JournalBrowser {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for JournalBrowser {}
+impl ModalView for JournalBrowser {}
+
+struct LoadedNote {
+    note: JournalNote,
+    title: String,
+    contents: String,
+}
+
+/// All notes, most recent first
+fn load_notes(journal_dir: PathBuf) -> Vec<LoadedNote> {
+    let mut notes = notes::existing_notes(&journal_dir)
+        .into_iter()
+        .map(|note| LoadedNote {
+            note,
+            title: note.title(),
+            contents: std::fs::read_to_string(note.path(&journal_dir)).unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    notes.sort_by_key(|loaded_note| {
+        let first_day = loaded_note.note.days().map(|days| *days.start());
+        std::cmp::Reverse((first_day, loaded_note.note))
+    });
+    notes
+}
+
+pub struct JournalBrowserDelegate {
+    journal_browser: WeakEntity<JournalBrowser>,
+    workspace: WeakEntity<Workspace>,
+    journal_dir: PathBuf,
+    notes: Vec<LoadedNote>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl JournalBrowserDelegate {
+    fn new(
+        journal_browser: WeakEntity<JournalBrowser>,
+        workspace: WeakEntity<Workspace>,
+        journal_dir: PathBuf,
+    ) -> Self {
+        Self {
+            journal_browser,
+            workspace,
+            journal_dir,
+            notes: Vec::new(),
+            candidates: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+
+    fn set_notes(&mut self, notes: Vec<LoadedNote>) {
+        // Titles come first, so that match positions below their length highlight the title
+        self.candidates = notes
+            .iter()
+            .enumerate()
+            .map(|(ix, loaded_note)| {
+                StringMatchCandidate::new(
+                    ix,
+                    &format!("{} {}", loaded_note.title, loaded_note.contents),
+                )
+            })
+            .collect();
+        self.notes = notes;
+    }
+
+    /// Line of the contents containing the first match outside of the title,
+    /// or the first line of text if only the title matched
+    fn snippet(loaded_note: &LoadedNote, positions: &[usize]) -> String {
+        let contents_start = loaded_note.title.len() + 1;
+        let matched_line = positions
+            .iter()
+            .find(|position| **position >= contents_start)
+            .and_then(|position| {
+                let offset = position - contents_start;
+                let line_start = loaded_note.contents[..offset]
+                    .rfind('\n')
+                    .map_or(0, |ix| ix + 1);
+                loaded_note.contents[line_start..].lines().next()
+            });
+        matched_line
+            .or_else(|| {
+                loaded_note
+                    .contents
+                    .lines()
+                    .find(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            })
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    }
+}
+
+impl PickerDelegate for JournalBrowserDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search journal…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let note = self.notes[mat.candidate_id].note;
+            let path = note.path(&self.journal_dir);
+            let journal_dir = self.journal_dir.clone();
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    open_journal_note(workspace, journal_dir, Task::ready(Ok(path)), window, cx)
+                        .detach_and_log_err(cx);
+                })
+                .log_err();
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.journal_browser
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No journal notes found".into())
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                let mut matches = match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await;
+                // Most recent notes first among equally good matches
+                matches.sort_by(|a, b| {
+                    b.score
+                        .total_cmp(&a.score)
+                        .then_with(|| a.candidate_id.cmp(&b.candidate_id))
+                });
+                matches
+            };
+
+            this.update(cx, |this, cx| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = 0;
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let loaded_note = self.notes.get(mat.candidate_id)?;
+        let title_positions = mat
+            .positions
+            .iter()
+            .copied()
+            .filter(|position| *position < loaded_note.title.len())
+            .collect::<Vec<_>>();
+        let snippet = Self::snippet(loaded_note, &mat.positions);
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .child(HighlightedLabel::new(
+                            loaded_note.title.clone(),
+                            title_positions,
+                        ))
+                        .child(
+                            Label::new(snippet)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        ),
+                ),
+        )
+    }
+}
@@ -0,0 +1,210 @@
+//! Locations of journal notes and discovery of the existing ones.
+//!
+//! Daily notes are stored as `YYYY/MM/DD.md`, weekly notes as `YYYY/week-WW.md`
+//! (with ISO week numbers) and monthly notes as `YYYY/MM.md`, relative to the journal directory.
+
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+use std::{
+    mem,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// A note of the journal, identified by the period it covers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JournalNote {
+    Daily(NaiveDate),
+    /// Note of an ISO week
+    Weekly {
+        year: i32,
+        week: u32,
+    },
+    Monthly {
+        year: i32,
+        month: u32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Previous,
+    Next,
+}
+
+impl JournalNote {
+    pub fn weekly_containing(date: NaiveDate) -> Self {
+        let week = date.iso_week();
+        JournalNote::Weekly {
+            year: week.year(),
+            week: week.week(),
+        }
+    }
+
+    pub fn monthly_containing(date: NaiveDate) -> Self {
+        JournalNote::Monthly {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+
+    /// First and last day of the period covered by the note
+    pub fn days(&self) -> Option<RangeInclusive<NaiveDate>> {
+        match *self {
+            JournalNote::Daily(date) => Some(date..=date),
+            JournalNote::Weekly { year, week } => {
+                let monday = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+                let sunday = NaiveDate::from_isoywd_opt(year, week, Weekday::Sun)?;
+                Some(monday..=sunday)
+            }
+            JournalNote::Monthly { year, month } => {
+                let first_day = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let last_day = first_day.checked_add_months(Months::new(1))?.pred_opt()?;
+                Some(first_day..=last_day)
+            }
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match *self {
+            JournalNote::Daily(date) => date.format("%A, %B %-d, %Y").to_string(),
+            JournalNote::Weekly { year, week } => format!("Week {week}, {year}"),
+            JournalNote::Monthly { .. } => self
+                .days()
+                .map(|days| days.start().format("%B %Y").to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn is_same_kind(&self, other: &JournalNote) -> bool {
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+
+    fn relative_components(&self) -> Vec<String> {
+        match *self {
+            JournalNote::Daily(date) => vec![
+                format!("{:02}", date.year()),
+                format!("{:02}", date.month()),
+                format!("{:02}.md", date.day()),
+            ],
+            JournalNote::Weekly { year, week } => {
+                vec![format!("{year:02}"), format!("week-{week:02}.md")]
+            }
+            JournalNote::Monthly { year, month } => {
+                vec![format!("{year:02}"), format!("{month:02}.md")]
+            }
+        }
+    }
+
+    pub fn path(&self, journal_dir: &Path) -> PathBuf {
+        self.relative_components()
+            .into_iter()
+            .fold(journal_dir.to_path_buf(), |path, component| {
+                path.join(component)
+            })
+    }
+
+    /// Markdown link target of the note, relative to the journal directory
+    pub fn link_target(&self) -> String {
+        self.relative_components().join("/")
+    }
+
+    /// Parses the path of a note relative to the journal directory
+    pub fn from_relative_path(path: &Path) -> Option<Self> {
+        let components = path
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect::<Option<Vec<_>>>()?;
+        let parse_number = |text: &str| -> Option<u32> {
+            text.chars()
+                .all(|ch| ch.is_ascii_digit())
+                .then(|| text.parse().ok())
+                .flatten()
+        };
+
+        let note = match components.as_slice() {
+            [year, month, day] => {
+                let year = parse_number(year)? as i32;
+                let month = parse_number(month)?;
+                let day = parse_number(day.strip_suffix(".md")?)?;
+                JournalNote::Daily(NaiveDate::from_ymd_opt(year, month, day)?)
+            }
+            [year, file_name] => {
+                let year = parse_number(year)? as i32;
+                let stem = file_name.strip_suffix(".md")?;
+                match stem.strip_prefix("week-") {
+                    Some(week) => JournalNote::Weekly {
+                        year,
+                        week: parse_number(week)?,
+                    },
+                    None => JournalNote::Monthly {
+                        year,
+                        month: parse_number(stem)?,
+                    },
+                }
+            }
+            _ => return None,
+        };
+        note.days().is_some().then_some(note)
+    }
+
+    /// Parses the absolute path of a note, if it's inside the journal directory
+    pub fn from_path(journal_dir: &Path, path: &Path) -> Option<Self> {
+        Self::from_relative_path(path.strip_prefix(journal_dir).ok()?)
+    }
+}
+
+/// Notes stored in the journal directory, in chronological order for each kind of note
+pub fn existing_notes(journal_dir: &Path) -> Vec<JournalNote> {
+    let mut notes = Vec::new();
+    let mut pending_dirs = vec![PathBuf::new()];
+    while let Some(relative_dir) = pending_dirs.pop() {
+        let Ok(dir_entries) = std::fs::read_dir(journal_dir.join(&relative_dir)) else {
+            continue;
+        };
+        for dir_entry in dir_entries.flatten() {
+            let relative_path = relative_dir.join(dir_entry.file_name());
+            if dir_entry
+                .file_type()
+                .is_ok_and(|file_type| file_type.is_dir())
+            {
+                // Notes are at most two directories deep
+                if relative_path.components().count() < 2 {
+                    pending_dirs.push(relative_path);
+                }
+            } else if let Some(note) = JournalNote::from_relative_path(&relative_path) {
+                notes.push(note);
+            }
+        }
+    }
+    notes.sort();
+    notes
+}
+
+/// The closest existing note of the same kind before or after the given one,
+/// which doesn't need to exist itself
+pub fn adjacent_note(
+    notes: &[JournalNote],
+    note: JournalNote,
+    direction: Direction,
+) -> Option<JournalNote> {
+    let same_kind = notes
+        .iter()
+        .copied()
+        .filter(|other| other.is_same_kind(&note));
+    match direction {
+        Direction::Previous => same_kind.filter(|other| *other < note).max(),
+        Direction::Next => same_kind.filter(|other| *other > note).min(),
+    }
+}
+
+/// Daily notes within the period covered by the note, in chronological order
+pub fn daily_notes_within(notes: &[JournalNote], note: JournalNote) -> Vec<JournalNote> {
+    let Some(days) = note.days() else {
+        return Vec::new();
+    };
+    notes
+        .iter()
+        .copied()
+        .filter(|other| matches!(other, JournalNote::Daily(date) if days.contains(date)))
+        .collect()
+}
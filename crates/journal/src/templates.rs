@@ -0,0 +1,135 @@
+//! Templates of journal notes and entries.
+//!
+//! Variables use the syntax of task variables, e.g. `$JOURNAL_DATE` or `${JOURNAL_DATE}`.
+//! Unknown variables are left as written.
+
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike};
+use settings::HourFormat;
+use std::{collections::HashMap, path::Path};
+
+use crate::notes::{self, Direction, JournalNote};
+
+pub const DATE: &str = "JOURNAL_DATE";
+pub const TIME: &str = "JOURNAL_TIME";
+pub const WEEKDAY: &str = "JOURNAL_WEEKDAY";
+pub const DAY: &str = "JOURNAL_DAY";
+pub const WEEK: &str = "JOURNAL_WEEK";
+pub const MONTH: &str = "JOURNAL_MONTH";
+pub const MONTH_NAME: &str = "JOURNAL_MONTH_NAME";
+pub const YEAR: &str = "JOURNAL_YEAR";
+/// Unchecked tasks of the previous note of the same kind
+pub const OPEN_TASKS: &str = "JOURNAL_OPEN_TASKS";
+/// Links to the daily notes of a week or month, with their unchecked tasks
+pub const ROLLUP: &str = "JOURNAL_ROLLUP";
+
+#[derive(Clone, Debug, Default)]
+pub struct TemplateVariables(HashMap<&'static str, String>);
+
+impl TemplateVariables {
+    pub fn for_date(date: NaiveDate, time: NaiveTime, hour_format: &HourFormat) -> Self {
+        let mut variables = Self::default();
+        variables.insert(DATE, date.format("%Y-%m-%d").to_string());
+        variables.insert(TIME, format_time(time, hour_format));
+        variables.insert(WEEKDAY, date.format("%A").to_string());
+        variables.insert(DAY, format!("{:02}", date.day()));
+        variables.insert(WEEK, format!("{:02}", date.iso_week().week()));
+        variables.insert(MONTH, format!("{:02}", date.month()));
+        variables.insert(MONTH_NAME, date.format("%B").to_string());
+        variables.insert(YEAR, date.year().to_string());
+        variables
+    }
+
+    /// Variables for the contents of a new note, reading the existing notes it refers to.
+    /// Dates are the first day of the period covered by the note.
+    pub fn for_new_note(
+        journal_dir: &Path,
+        note: JournalNote,
+        time: NaiveTime,
+        hour_format: &HourFormat,
+    ) -> Self {
+        let Some(days) = note.days() else {
+            return Self::default();
+        };
+        let mut variables = Self::for_date(*days.start(), time, hour_format);
+        if let JournalNote::Weekly { year, .. } = note {
+            variables.insert(YEAR, year.to_string());
+        }
+
+        let existing_notes = notes::existing_notes(journal_dir);
+        let read_note =
+            |note: JournalNote| std::fs::read_to_string(note.path(journal_dir)).unwrap_or_default();
+
+        let open_tasks = notes::adjacent_note(&existing_notes, note, Direction::Previous)
+            .map(|previous_note| open_tasks(&read_note(previous_note)).join("\n"))
+            .unwrap_or_default();
+        variables.insert(OPEN_TASKS, open_tasks);
+
+        let rollup = if matches!(note, JournalNote::Daily(_)) {
+            String::new()
+        } else {
+            let daily_notes = notes::daily_notes_within(&existing_notes, note)
+                .into_iter()
+                .map(|daily_note| (daily_note, read_note(daily_note)))
+                .collect::<Vec<_>>();
+            rollup(&daily_notes)
+        };
+        variables.insert(ROLLUP, rollup);
+
+        variables
+    }
+
+    pub fn insert(&mut self, name: &'static str, value: String) {
+        self.0.insert(name, value);
+    }
+
+    pub fn render(&self, template: &str) -> String {
+        shellexpand::env_with_context_no_errors(template, |name| self.0.get(name)).into_owned()
+    }
+}
+
+pub fn format_time(time: NaiveTime, hour_format: &HourFormat) -> String {
+    match hour_format {
+        HourFormat::Hour24 => format!("{}:{:02}", time.hour(), time.minute()),
+        HourFormat::Hour12 => {
+            let (pm, hour) = time.hour12();
+            let am_or_pm = if pm { "PM" } else { "AM" };
+            format!("{}:{:02} {}", hour, time.minute(), am_or_pm)
+        }
+    }
+}
+
+/// Unchecked Markdown tasks, e.g. `- [ ] Call Bob`, without their indentation
+pub fn open_tasks(text: &str) -> Vec<&str> {
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| {
+            ["- [ ] ", "* [ ] ", "+ [ ] "]
+                .iter()
+                .any(|marker| line.starts_with(marker))
+        })
+        .collect()
+}
+
+/// Markdown list linking to the daily notes, with their unchecked tasks nested below.
+/// Links are relative to notes stored at the top of a year directory.
+pub fn rollup(daily_notes: &[(JournalNote, String)]) -> String {
+    let mut rollup = String::new();
+    for (note, contents) in daily_notes {
+        let JournalNote::Daily(date) = note else {
+            continue;
+        };
+        if !rollup.is_empty() {
+            rollup.push('\n');
+        }
+        rollup.push_str(&format!(
+            "- [{}](../{})",
+            date.format("%A, %B %-d"),
+            note.link_target()
+        ));
+        for task in open_tasks(contents) {
+            rollup.push_str("\n  ");
+            rollup.push_str(task);
+        }
+    }
+    rollup
+}
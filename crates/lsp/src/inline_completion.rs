@@ -0,0 +1,150 @@
+//! `textDocument/inlineCompletion`, introduced in LSP 3.18 and not yet covered by `lsp_types`.
+//!
+//! [LSP Specification](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.18/specification/#textDocument_inlineCompletion)
+
+use lsp_types::{Command, Range, TextDocumentPositionParams, WorkDoneProgressParams};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The `textDocument/inlineCompletion` request.
+pub enum InlineCompletionRequest {}
+
+impl lsp_types::request::Request for InlineCompletionRequest {
+    type Params = InlineCompletionParams;
+    type Result = Option<InlineCompletionResponse>;
+    const METHOD: &'static str = "textDocument/inlineCompletion";
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineCompletionParams {
+    #[serde(flatten)]
+    pub text_document_position: TextDocumentPositionParams,
+    pub context: InlineCompletionContext,
+    #[serde(flatten)]
+    pub work_done_progress_params: WorkDoneProgressParams,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineCompletionContext {
+    pub trigger_kind: InlineCompletionTriggerKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_completion_info: Option<SelectedCompletionInfo>,
+}
+
+/// Describes the completion item currently selected in the completion menu, if any.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectedCompletionInfo {
+    pub range: Range,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum InlineCompletionTriggerKind {
+    /// Completion was triggered explicitly by the user.
+    Invoked = 1,
+    /// Completion was triggered automatically while editing.
+    Automatic = 2,
+}
+
+impl From<InlineCompletionTriggerKind> for u8 {
+    fn from(kind: InlineCompletionTriggerKind) -> Self {
+        kind as u8
+    }
+}
+
+impl TryFrom<u8> for InlineCompletionTriggerKind {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::Invoked),
+            2 => Ok(Self::Automatic),
+            _ => Err(format!("invalid inline completion trigger kind {value}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InlineCompletionResponse {
+    Array(Vec<InlineCompletionItem>),
+    List(InlineCompletionList),
+}
+
+impl InlineCompletionResponse {
+    pub fn into_items(self) -> Vec<InlineCompletionItem> {
+        match self {
+            Self::Array(items) => items,
+            Self::List(list) => list.items,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InlineCompletionList {
+    pub items: Vec<InlineCompletionItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineCompletionItem {
+    pub insert_text: InlineCompletionText,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter_text: Option<String>,
+    /// The range to replace, defaulting to an insertion at the requested position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<Range>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InlineCompletionText {
+    Plain(String),
+    Snippet(StringValue),
+}
+
+/// A snippet string, as used by `InlineCompletionItem::insert_text`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StringValue {
+    /// Always `"snippet"`.
+    pub kind: String,
+    pub value: String,
+}
+
+/// Reads `capabilities.inlineCompletionProvider` out of a raw `initialize` response.
+///
+/// The provider is either a boolean or an options object, the latter meaning the capability
+/// is supported.
+pub(crate) fn inline_completion_provider(initialize_result: &Value) -> bool {
+    match initialize_result.pointer("/capabilities/inlineCompletionProvider") {
+        Some(Value::Bool(supported)) => *supported,
+        Some(Value::Object(_)) => true,
+        _ => false,
+    }
+}
+
+/// Adds the `textDocument.inlineCompletion` client capability to raw `initialize` params, so
+/// that servers offer inline completions to clients that declare support for them.
+pub(crate) fn add_inline_completion_capability(initialize_params: &mut Value) {
+    let Some(capabilities) = initialize_params
+        .get_mut("capabilities")
+        .and_then(Value::as_object_mut)
+    else {
+        return;
+    };
+    let text_document = capabilities
+        .entry("textDocument")
+        .or_insert_with(|| Value::Object(Default::default()));
+    if let Some(text_document) = text_document.as_object_mut() {
+        text_document.insert(
+            "inlineCompletion".to_string(),
+            serde_json::json!({ "dynamicRegistration": true }),
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod inline_completion;
 mod input_handler;
 
 pub use lsp_types::request::*;
@@ -37,7 +38,7 @@ use std::{
     pin::Pin,
     sync::{
         Arc, Weak,
-        atomic::{AtomicI32, Ordering::SeqCst},
+        atomic::{AtomicBool, AtomicI32, Ordering::SeqCst},
     },
     task::Poll,
     time::{Duration, Instant},
@@ -95,6 +96,16 @@ pub struct LanguageServerBinaryOptions {
 
 struct NotificationSerializer(Box<dyn FnOnce() -> String + Send + Sync>);
 
+/// The `initialize` request with untyped params and response, so that capabilities missing
+/// from [`ClientCapabilities`] and [`ServerCapabilities`] can be sent and read too.
+enum RawInitialize {}
+
+impl request::Request for RawInitialize {
+    type Params = Value;
+    type Result = Value;
+    const METHOD: &'static str = request::Initialize::METHOD;
+}
+
 /// A running language server process.
 pub struct LanguageServer {
     server_id: LanguageServerId,
@@ -106,6 +117,9 @@ pub struct LanguageServer {
     process_name: Arc<str>,
     binary: LanguageServerBinary,
     capabilities: RwLock<ServerCapabilities>,
+    /// Whether the server supports `textDocument/inlineCompletion`, which is not part of
+    /// [`ServerCapabilities`] yet.
+    inline_completion_provider: AtomicBool,
     /// Configuration sent to the server, stored for display in the language server logs
     /// buffer. This is represented as the message sent to the LSP in order to avoid cloning it (can
     /// be large in cases like sending schemas to the json server).
@@ -593,6 +607,7 @@ impl LanguageServer {
                 .unwrap_or_default(),
             binary,
             capabilities: Default::default(),
+            inline_completion_provider: AtomicBool::new(false),
             configuration,
             code_action_kinds,
             next_id: Default::default(),
@@ -992,6 +1007,19 @@ impl LanguageServer {
                         dynamic_registration: Some(true),
                         ..DocumentSymbolClientCapabilities::default()
                     }),
+                    call_hierarchy: Some(CallHierarchyClientCapabilities {
+                        dynamic_registration: Some(false),
+                    }),
+                    type_hierarchy: Some(TypeHierarchyClientCapabilities {
+                        dynamic_registration: Some(false),
+                    }),
+                    selection_range: Some(SelectionRangeClientCapabilities {
+                        dynamic_registration: Some(false),
+                    }),
+                    document_link: Some(DocumentLinkClientCapabilities {
+                        dynamic_registration: Some(false),
+                        tooltip_support: Some(true),
+                    }),
                     diagnostic: Some(DiagnosticClientCapabilities {
                         dynamic_registration: Some(true),
                         related_document_support: Some(true),
@@ -1056,8 +1084,11 @@ impl LanguageServer {
         cx: &App,
     ) -> Task<Result<Arc<Self>>> {
         cx.background_spawn(async move {
+            let mut params =
+                serde_json::to_value(params).context("serializing initialize params")?;
+            inline_completion::add_inline_completion_capability(&mut params);
             let response = self
-                .request::<request::Initialize>(params, timeout)
+                .request::<RawInitialize>(params, timeout)
                 .await
                 .into_response()
                 .with_context(|| {
@@ -1067,11 +1098,16 @@ impl LanguageServer {
                         self.server_id()
                     )
                 })?;
+            let inline_completion_provider =
+                inline_completion::inline_completion_provider(&response);
+            let response = serde_json::from_value::<InitializeResult>(response)
+                .context("parsing initialize response")?;
             if let Some(info) = response.server_info {
                 self.version = info.version.map(SharedString::from);
                 self.process_name = info.name.into();
             }
             self.capabilities = RwLock::new(response.capabilities);
+            self.inline_completion_provider = AtomicBool::new(inline_completion_provider);
             self.configuration = configuration;
 
             self.notify::<notification::Initialized>(InitializedParams {})?;
@@ -1353,6 +1389,17 @@ impl LanguageServer {
         update(self.capabilities.write().deref_mut());
     }
 
+    /// Whether the running language server provides `textDocument/inlineCompletion`.
+    pub fn supports_inline_completions(&self) -> bool {
+        self.inline_completion_provider.load(SeqCst)
+    }
+
+    /// Update whether the running language server provides `textDocument/inlineCompletion`,
+    /// for servers that register the capability dynamically.
+    pub fn set_inline_completion_provider(&self, supported: bool) {
+        self.inline_completion_provider.store(supported, SeqCst);
+    }
+
     /// Get the individual configuration settings for the running language server.
     /// Does not include globally applied settings (which are stored in ProjectSettings::GlobalLspSettings).
     pub fn configuration(&self) -> &Value {
@@ -2220,6 +2267,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_add_inline_completion_capability() {
+        let mut params = serde_json::json!({
+            "capabilities": { "textDocument": { "hover": { "dynamicRegistration": true } } }
+        });
+        inline_completion::add_inline_completion_capability(&mut params);
+        assert_eq!(
+            params,
+            serde_json::json!({
+                "capabilities": {
+                    "textDocument": {
+                        "hover": { "dynamicRegistration": true },
+                        "inlineCompletion": { "dynamicRegistration": true }
+                    }
+                }
+            })
+        );
+
+        let mut params = serde_json::json!({ "capabilities": {} });
+        inline_completion::add_inline_completion_capability(&mut params);
+        assert_eq!(
+            params,
+            serde_json::json!({
+                "capabilities": {
+                    "textDocument": { "inlineCompletion": { "dynamicRegistration": true } }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_inline_completion_provider() {
+        use inline_completion::{InlineCompletionResponse, InlineCompletionText};
+
+        let provider = |capabilities: Value| {
+            inline_completion::inline_completion_provider(
+                &serde_json::json!({ "capabilities": capabilities }),
+            )
+        };
+        assert!(provider(
+            serde_json::json!({ "inlineCompletionProvider": true })
+        ));
+        assert!(provider(
+            serde_json::json!({ "inlineCompletionProvider": { "workDoneProgress": false } })
+        ));
+        assert!(!provider(
+            serde_json::json!({ "inlineCompletionProvider": false })
+        ));
+        assert!(!provider(serde_json::json!({ "hoverProvider": true })));
+
+        let response = serde_json::from_value::<InlineCompletionResponse>(serde_json::json!({
+            "items": [
+                { "insertText": "world" },
+                {
+                    "insertText": { "kind": "snippet", "value": "world($0)" },
+                    "range": {
+                        "start": { "line": 0, "character": 6 },
+                        "end": { "line": 0, "character": 8 }
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+        let items = response.into_items();
+        assert_eq!(items.len(), 2);
+        assert!(
+            matches!(&items[0].insert_text, InlineCompletionText::Plain(text) if text == "world")
+        );
+        assert!(items[0].range.is_none());
+        assert!(matches!(
+            &items[1].insert_text,
+            InlineCompletionText::Snippet(snippet) if snippet.value == "world($0)"
+        ));
+        assert_eq!(items[1].range.unwrap().start, Position::new(0, 6));
+    }
+
     #[gpui::test]
     async fn test_initialize_params_has_root_path_and_root_uri(cx: &mut TestAppContext) {
         cx.update(|cx| {
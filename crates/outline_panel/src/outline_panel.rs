@@ -1,4 +1,5 @@
 mod outline_panel_settings;
+mod type_hierarchy_view;
 
 use anyhow::Context as _;
 use collections::{BTreeSet, HashMap, HashSet};
@@ -655,6 +656,7 @@ pub fn init(cx: &mut App) {
         });
     })
     .detach();
+    type_hierarchy_view::init(cx);
 }
 
 impl OutlinePanel {
@@ -4671,35 +4673,11 @@ impl OutlinePanel {
                                 }
                             })
                             .with_render_fn(cx.entity(), move |outline_panel, params, _, _| {
-                                const LEFT_OFFSET: Pixels = px(14.);
-
-                                let indent_size = params.indent_size;
-                                let item_height = params.item_height;
                                 let active_indent_guide_ix = find_active_indent_guide_ix(
                                     outline_panel,
                                     &params.indent_guides,
                                 );
-
-                                params
-                                    .indent_guides
-                                    .into_iter()
-                                    .enumerate()
-                                    .map(|(ix, layout)| {
-                                        let bounds = Bounds::new(
-                                            point(
-                                                layout.offset.x * indent_size + LEFT_OFFSET,
-                                                layout.offset.y * item_height,
-                                            ),
-                                            size(px(1.), layout.length * item_height),
-                                        );
-                                        ui::RenderedIndentGuide {
-                                            bounds,
-                                            layout,
-                                            is_active: active_indent_guide_ix == Some(ix),
-                                            hitbox: None,
-                                        }
-                                    })
-                                    .collect()
+                                render_indent_guides(params, active_indent_guide_ix)
                             }),
                     )
                 })
@@ -5070,21 +5048,33 @@ fn find_active_indent_guide_ix(
     let SelectedEntry::Valid(_, target_ix) = &outline_panel.selected_entry else {
         return None;
     };
-    let target_depth = outline_panel
-        .cached_entries
-        .get(*target_ix)
-        .map(|cached_entry| cached_entry.depth)?;
-
-    let (target_ix, target_depth) = if let Some(target_depth) = outline_panel
-        .cached_entries
-        .get(target_ix + 1)
-        .filter(|cached_entry| cached_entry.depth > target_depth)
-        .map(|entry| entry.depth)
-    {
-        (target_ix + 1, target_depth.saturating_sub(1))
-    } else {
-        (*target_ix, target_depth.saturating_sub(1))
-    };
+    active_indent_guide_ix(
+        *target_ix,
+        |ix| {
+            outline_panel
+                .cached_entries
+                .get(ix)
+                .map(|cached_entry| cached_entry.depth)
+        },
+        candidates,
+    )
+}
+
+/// Finds the indent guide enclosing the selected entry of a tree list, or its children
+/// if it is expanded.
+pub(crate) fn active_indent_guide_ix(
+    target_ix: usize,
+    depth_at: impl Fn(usize) -> Option<usize>,
+    candidates: &[IndentGuideLayout],
+) -> Option<usize> {
+    let target_depth = depth_at(target_ix)?;
+
+    let (target_ix, target_depth) =
+        if let Some(target_depth) = depth_at(target_ix + 1).filter(|depth| *depth > target_depth) {
+            (target_ix + 1, target_depth.saturating_sub(1))
+        } else {
+            (target_ix, target_depth.saturating_sub(1))
+        };
 
     candidates
         .iter()
@@ -5097,6 +5087,37 @@ fn find_active_indent_guide_ix(
         .map(|(ix, _)| ix)
 }
 
+/// Lays out the indent guides of a tree list whose rows are indented [`ListItem`]s.
+pub(crate) fn render_indent_guides(
+    params: ui::RenderIndentGuideParams,
+    active_indent_guide_ix: Option<usize>,
+) -> smallvec::SmallVec<[ui::RenderedIndentGuide; 12]> {
+    const LEFT_OFFSET: Pixels = px(14.);
+
+    let indent_size = params.indent_size;
+    let item_height = params.item_height;
+    params
+        .indent_guides
+        .into_iter()
+        .enumerate()
+        .map(|(ix, layout)| {
+            let bounds = Bounds::new(
+                point(
+                    layout.offset.x * indent_size + LEFT_OFFSET,
+                    layout.offset.y * item_height,
+                ),
+                size(px(1.), layout.length * item_height),
+            );
+            ui::RenderedIndentGuide {
+                bounds,
+                layout,
+                is_active: active_indent_guide_ix == Some(ix),
+                hitbox: None,
+            }
+        })
+        .collect()
+}
+
 fn subscribe_for_editor_events(
     editor: &Entity<Editor>,
     window: &mut Window,
@@ -0,0 +1,426 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use editor::Editor;
+use gpui::{
+    App, ClickEvent, Context, Entity, EventEmitter, FocusHandle, Focusable, KeyContext,
+    MouseButton, Task, Window, actions, uniform_list,
+};
+use hierarchy_tree::{HierarchyNode, HierarchyTree, HierarchyTreeView};
+use language::ToPoint as _;
+use project::{Project, TypeHierarchyItem};
+use settings::Settings;
+use ui::{IndentGuideColors, ListItem, Tooltip, prelude::*};
+use workspace::{
+    Workspace,
+    item::{Item, ItemEvent},
+};
+use zed_actions::type_hierarchy::ShowTypeHierarchy;
+
+use crate::{
+    CollapseSelectedEntry, ExpandSelectedEntry, active_indent_guide_ix,
+    outline_panel_settings::{OutlinePanelSettings, ShowIndentGuides},
+    render_indent_guides,
+};
+
+actions!(
+    type_hierarchy,
+    [
+        /// Switches the type hierarchy between supertypes and subtypes.
+        ToggleTypeHierarchyDirection,
+    ]
+);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(show_type_hierarchy);
+    })
+    .detach();
+}
+
+fn show_type_hierarchy(
+    workspace: &mut Workspace,
+    _: &ShowTypeHierarchy,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = ({
+        let editor = editor.read(cx);
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+
+    let existing = workspace
+        .active_pane()
+        .read(cx)
+        .items()
+        .find_map(|item| item.downcast::<TypeHierarchyView>());
+    let view = if let Some(existing) = existing {
+        workspace.activate_item(&existing, true, true, window, cx);
+        existing
+    } else {
+        let view = cx.new(|cx| TypeHierarchyView::new(workspace, cx));
+        workspace.add_item_to_active_pane(Box::new(view.clone()), None, true, window, cx);
+        view
+    };
+    view.update(cx, |view, cx| {
+        view.show_type_hierarchy(buffer, position, cx);
+    });
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeHierarchyDirection {
+    /// Expanding an entry lists the types it extends or implements.
+    Supertypes,
+    /// Expanding an entry lists the types extending or implementing it.
+    Subtypes,
+}
+
+/// Shows the supertypes or subtypes of the type the hierarchy was requested
+/// for, as a tree whose levels are fetched from the language server when they
+/// are first expanded.
+pub struct TypeHierarchyView {
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    direction: TypeHierarchyDirection,
+    tree: HierarchyTree<TypeHierarchyItem>,
+}
+
+impl TypeHierarchyView {
+    fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        Self {
+            project: workspace.project().clone(),
+            focus_handle: cx.focus_handle(),
+            direction: TypeHierarchyDirection::Subtypes,
+            tree: HierarchyTree::new(workspace.weak_handle()),
+        }
+    }
+
+    /// Rebuilds the hierarchy around the type at the given position.
+    pub fn show_type_hierarchy(
+        &mut self,
+        buffer: Entity<language::Buffer>,
+        position: language::Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let prepare = self.project.update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, position, cx)
+        });
+        self.load_roots(prepare, cx);
+    }
+
+    pub fn direction(&self) -> TypeHierarchyDirection {
+        self.direction
+    }
+
+    pub fn set_direction(&mut self, direction: TypeHierarchyDirection, cx: &mut Context<Self>) {
+        if self.direction != direction {
+            self.direction = direction;
+            self.rebuild(cx);
+        }
+    }
+
+    fn toggle_direction(
+        &mut self,
+        _: &ToggleTypeHierarchyDirection,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let direction = match self.direction {
+            TypeHierarchyDirection::Supertypes => TypeHierarchyDirection::Subtypes,
+            TypeHierarchyDirection::Subtypes => TypeHierarchyDirection::Supertypes,
+        };
+        self.set_direction(direction, cx);
+    }
+
+    fn expand_selected(&mut self, _: &ExpandSelectedEntry, _: &mut Window, cx: &mut Context<Self>) {
+        self.expand_selected_entry(cx);
+    }
+
+    fn collapse_selected(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.collapse_selected_entry(cx);
+    }
+
+    fn render_entries(&self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range
+            .filter_map(|entry_ix| {
+                let (node_ix, node) = self.tree.entry(entry_ix)?;
+                Some(
+                    self.render_node(entry_ix, node_ix, node, cx)
+                        .into_any_element(),
+                )
+            })
+            .collect()
+    }
+
+    fn render_node(
+        &self,
+        entry_ix: usize,
+        node_ix: usize,
+        node: &HierarchyNode<TypeHierarchyItem>,
+        cx: &mut Context<Self>,
+    ) -> ListItem {
+        let settings = OutlinePanelSettings::get_global(cx);
+        let toggle = node.toggle_state();
+        let is_loading = node.is_loading();
+
+        let buffer = node.item.location.buffer.read(cx);
+        let file_name = buffer
+            .file()
+            .map(|file| file.file_name(cx).to_string())
+            .unwrap_or_default();
+        let row = node.item.selection_range.start.to_point(buffer).row + 1;
+
+        ListItem::new(("type-hierarchy-entry", entry_ix))
+            .indent_level(node.depth)
+            .indent_step_size(px(settings.indent_size))
+            .toggle_state(self.tree.selected_node() == Some(node_ix))
+            .toggle(toggle)
+            .on_toggle(cx.listener(move |this, _, _, cx| {
+                this.toggle_expanded(node_ix, cx);
+            }))
+            .child(
+                h_flex()
+                    .min_w_0()
+                    .gap_1()
+                    .child(Label::new(node.item.name.clone()).single_line())
+                    .children(node.item.detail.clone().map(|detail| {
+                        Label::new(detail)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate()
+                    })),
+            )
+            .end_slot(
+                h_flex()
+                    .gap_1()
+                    .when(is_loading, |this| {
+                        this.child(
+                            Label::new("Loading…")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .child(
+                        Label::new(format!("{file_name}:{row}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                this.click_node(node_ix, event.click_count(), window, cx);
+            }))
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let direction_button =
+            |id: &'static str, label: &'static str, direction: TypeHierarchyDirection| {
+                Button::new(id, label)
+                    .label_size(LabelSize::Small)
+                    .toggle_state(self.direction == direction)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.set_direction(direction, cx);
+                    }))
+            };
+
+        h_flex()
+            .h(Tab::container_height(cx))
+            .px_2()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(self.title()).size(LabelSize::Small).truncate())
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        direction_button(
+                            "supertypes",
+                            "Supertypes",
+                            TypeHierarchyDirection::Supertypes,
+                        )
+                        .tooltip(Tooltip::text("Show the types it extends or implements")),
+                    )
+                    .child(
+                        direction_button("subtypes", "Subtypes", TypeHierarchyDirection::Subtypes)
+                            .tooltip(Tooltip::text("Show the types extending or implementing it")),
+                    ),
+            )
+    }
+
+    fn title(&self) -> SharedString {
+        self.tree
+            .root_items()
+            .first()
+            .map(|item| SharedString::from(item.name.clone()))
+            .unwrap_or_else(|| "Type Hierarchy".into())
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("TypeHierarchyView");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        let (title, description) = if self.tree.is_loading_roots() {
+            ("Loading…".into(), None)
+        } else if let Some(error) = self.tree.error() {
+            ("No Type Hierarchy".into(), Some(error.clone()))
+        } else {
+            (
+                SharedString::from("No Type Hierarchy"),
+                Some("Use \"Show Type Hierarchy\" on a type to list its subtypes here.".into()),
+            )
+        };
+        v_flex()
+            .size_full()
+            .p_4()
+            .gap_1()
+            .items_center()
+            .justify_center()
+            .child(Label::new(title))
+            .children(description.map(|description| {
+                Label::new(description)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+            }))
+    }
+}
+
+impl Render for TypeHierarchyView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let settings = OutlinePanelSettings::get_global(cx);
+        let indent_size = settings.indent_size;
+        let show_indent_guides = settings.indent_guides.show == ShowIndentGuides::Always;
+
+        v_flex()
+            .id("type-hierarchy-view")
+            .size_full()
+            .overflow_hidden()
+            .bg(cx.theme().colors().panel_background)
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::expand_selected))
+            .on_action(cx.listener(Self::collapse_selected))
+            .on_action(cx.listener(Self::toggle_direction))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, window, cx| {
+                    window.focus(&this.focus_handle, cx);
+                }),
+            )
+            .child(self.render_header(cx))
+            .map(|this| {
+                if self.tree.entry_count() == 0 {
+                    this.child(self.render_empty_state())
+                } else {
+                    this.child(
+                        uniform_list(
+                            "type-hierarchy",
+                            self.tree.entry_count(),
+                            cx.processor(|this, range: Range<usize>, _, cx| {
+                                this.render_entries(range, cx)
+                            }),
+                        )
+                        .size_full()
+                        .track_scroll(self.tree.scroll_handle())
+                        .when(show_indent_guides, |list| {
+                            list.with_decoration(
+                                ui::indent_guides(px(indent_size), IndentGuideColors::panel(cx))
+                                    .with_compute_indents_fn(cx.entity(), |this, range, _, _| {
+                                        range
+                                            .filter_map(|entry_ix| this.tree.entry_depth(entry_ix))
+                                            .collect()
+                                    })
+                                    .with_render_fn(cx.entity(), |this, params, _, _| {
+                                        let active_indent_guide_ix =
+                                            this.tree.selected_entry_ix().and_then(|entry_ix| {
+                                                active_indent_guide_ix(
+                                                    entry_ix,
+                                                    |ix| this.tree.entry_depth(ix),
+                                                    &params.indent_guides,
+                                                )
+                                            });
+                                        render_indent_guides(params, active_indent_guide_ix)
+                                    }),
+                            )
+                        }),
+                    )
+                }
+            })
+    }
+}
+
+impl HierarchyTreeView for TypeHierarchyView {
+    type Item = TypeHierarchyItem;
+
+    fn tree(&self) -> &HierarchyTree<TypeHierarchyItem> {
+        &self.tree
+    }
+
+    fn tree_mut(&mut self) -> &mut HierarchyTree<TypeHierarchyItem> {
+        &mut self.tree
+    }
+
+    fn fetch_children(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.project.update(cx, |project, cx| match self.direction {
+            TypeHierarchyDirection::Supertypes => project.supertypes(item, cx),
+            TypeHierarchyDirection::Subtypes => project.subtypes(item, cx),
+        })
+    }
+
+    fn item_location(
+        item: &TypeHierarchyItem,
+    ) -> (Entity<language::Buffer>, Range<language::Anchor>) {
+        (item.location.buffer.clone(), item.selection_range.clone())
+    }
+}
+
+impl Focusable for TypeHierarchyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<ItemEvent> for TypeHierarchyView {}
+
+impl Item for TypeHierarchyView {
+    type Event = ItemEvent;
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        match self.tree.root_items().first() {
+            Some(item) => format!("Type Hierarchy: {}", item.name).into(),
+            None => "Type Hierarchy".into(),
+        }
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::ListTree))
+    }
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
@@ -1,12 +1,14 @@
 use std::{collections::BTreeMap, ops::Range, path::Path, sync::Arc};
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use futures::{StreamExt, TryFutureExt, TryStreamExt, stream::FuturesUnordered};
-use gpui::{App, AppContext, Context, Entity, Subscription, Task};
+use gpui::{App, AppContext, AsyncApp, Context, Entity, EventEmitter, Subscription, Task};
 use itertools::Itertools;
-use language::{Buffer, BufferEvent};
-use std::collections::HashMap;
+use language::{Buffer, BufferEvent, proto::serialize_anchor as serialize_text_anchor};
+use rpc::{AnyProtoClient, TypedEnvelope, proto};
+use std::collections::{HashMap, HashSet};
 use text::{BufferSnapshot, Point};
+use util::ResultExt;
 
 use crate::{ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
 
@@ -19,13 +21,85 @@ impl BookmarkAnchor {
     }
 }
 
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct SerializedBookmark(pub u32);
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SerializedBookmark {
+    pub row: u32,
+    pub label: Option<Arc<str>>,
+    pub note: Option<Arc<str>>,
+    /// Position of the bookmark among the bookmarks of its file, as arranged by the user
+    pub order: u32,
+}
+
+impl SerializedBookmark {
+    pub fn new(row: u32) -> Self {
+        Self {
+            row,
+            label: None,
+            note: None,
+            order: 0,
+        }
+    }
+}
+
+/// A bookmark in a loaded buffer, following the text it was placed on
+#[derive(Clone, Debug)]
+pub struct Bookmark {
+    anchor: BookmarkAnchor,
+    label: Option<Arc<str>>,
+    note: Option<Arc<str>>,
+    owner: Option<u64>,
+}
+
+impl Bookmark {
+    fn new(anchor: BookmarkAnchor) -> Self {
+        Self {
+            anchor,
+            label: None,
+            note: None,
+            owner: None,
+        }
+    }
+
+    pub fn anchor(&self) -> BookmarkAnchor {
+        self.anchor
+    }
+
+    pub fn label(&self) -> Option<&Arc<str>> {
+        self.label.as_ref()
+    }
+
+    pub fn note(&self) -> Option<&Arc<str>> {
+        self.note.as_ref()
+    }
+
+    /// The collaborator who added the bookmark, `None` for bookmarks of the project's host
+    pub fn owner(&self) -> Option<u64> {
+        self.owner
+    }
+
+    fn to_proto(&self) -> proto::Bookmark {
+        proto::Bookmark {
+            position: Some(serialize_text_anchor(&self.anchor.0)),
+            label: self.label.as_ref().map(|label| label.to_string()),
+            note: self.note.as_ref().map(|note| note.to_string()),
+            owner_id: self.owner,
+        }
+    }
+
+    fn from_proto(bookmark: proto::Bookmark) -> Option<Self> {
+        Some(Self {
+            anchor: BookmarkAnchor(language::proto::deserialize_anchor(bookmark.position?)?),
+            label: bookmark.label.map(Arc::from),
+            note: bookmark.note.map(Arc::from),
+            owner: bookmark.owner_id,
+        })
+    }
+}
 
 #[derive(Debug)]
 pub struct BufferBookmarks {
     buffer: Entity<Buffer>,
-    bookmarks: Vec<BookmarkAnchor>,
+    bookmarks: Vec<Bookmark>,
     _subscription: Subscription,
 }
 
@@ -52,9 +126,18 @@ impl BufferBookmarks {
         &self.buffer
     }
 
-    pub fn bookmarks(&self) -> &[BookmarkAnchor] {
+    pub fn bookmarks(&self) -> &[Bookmark] {
         &self.bookmarks
     }
+
+    /// Index of the bookmark on the row, if its anchor can still be resolved
+    fn index_of_row(&self, row: u32, cx: &App) -> Option<usize> {
+        let snapshot = self.buffer.read(cx).text_snapshot();
+        self.bookmarks.iter().position(|bookmark| {
+            let anchor = bookmark.anchor.anchor();
+            snapshot.can_resolve(&anchor) && anchor.summary::<Point>(&snapshot).row == row
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -77,23 +160,456 @@ impl BookmarkEntry {
             BookmarkEntry::Unloaded(_) => None,
         }
     }
+
+    fn len(&self) -> usize {
+        match self {
+            BookmarkEntry::Loaded(buffer_bookmarks) => buffer_bookmarks.bookmarks.len(),
+            BookmarkEntry::Unloaded(bookmarks) => bookmarks.len(),
+        }
+    }
+
+    fn index_of_row(&self, row: u32, cx: &App) -> Option<usize> {
+        match self {
+            BookmarkEntry::Loaded(buffer_bookmarks) => buffer_bookmarks.index_of_row(row, cx),
+            BookmarkEntry::Unloaded(bookmarks) => {
+                bookmarks.iter().position(|bookmark| bookmark.row == row)
+            }
+        }
+    }
+
+    /// Bookmarks that were not serialized always belong to the project's host
+    fn owner(&self, index: usize) -> Option<u64> {
+        match self {
+            BookmarkEntry::Loaded(buffer_bookmarks) => buffer_bookmarks
+                .bookmarks
+                .get(index)
+                .and_then(|bookmark| bookmark.owner),
+            BookmarkEntry::Unloaded(_) => None,
+        }
+    }
+
+    fn label_and_note_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<(&mut Option<Arc<str>>, &mut Option<Arc<str>>)> {
+        match self {
+            BookmarkEntry::Loaded(buffer_bookmarks) => buffer_bookmarks
+                .bookmarks
+                .get_mut(index)
+                .map(|bookmark| (&mut bookmark.label, &mut bookmark.note)),
+            BookmarkEntry::Unloaded(bookmarks) => bookmarks
+                .get_mut(index)
+                .map(|bookmark| (&mut bookmark.label, &mut bookmark.note)),
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        match self {
+            BookmarkEntry::Loaded(buffer_bookmarks) => {
+                buffer_bookmarks.bookmarks.remove(index);
+            }
+            BookmarkEntry::Unloaded(bookmarks) => {
+                bookmarks.remove(index);
+            }
+        }
+    }
+
+    fn retain(&mut self, mut keep: impl FnMut(Option<u64>) -> bool) {
+        match self {
+            BookmarkEntry::Loaded(buffer_bookmarks) => buffer_bookmarks
+                .bookmarks
+                .retain(|bookmark| keep(bookmark.owner)),
+            BookmarkEntry::Unloaded(bookmarks) => bookmarks.retain(|_| keep(None)),
+        }
+    }
+
+    fn move_to(&mut self, index: usize, new_index: usize) {
+        fn move_item<T>(items: &mut Vec<T>, index: usize, new_index: usize) {
+            let item = items.remove(index);
+            items.insert(new_index.min(items.len()), item);
+        }
+        match self {
+            BookmarkEntry::Loaded(buffer_bookmarks) => {
+                move_item(&mut buffer_bookmarks.bookmarks, index, new_index)
+            }
+            BookmarkEntry::Unloaded(bookmarks) => move_item(bookmarks, index, new_index),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BookmarkStoreEvent {
+    /// Bookmarks were added, removed, reordered or edited
+    BookmarksUpdated,
+}
+
+/// An edit of a single bookmark, replicated between the host of a project and its guests
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum BookmarkOperation {
+    Add {
+        label: Option<Arc<str>>,
+        note: Option<Arc<str>>,
+    },
+    Remove,
+    SetLabel(Option<Arc<str>>),
+    SetNote(Option<Arc<str>>),
+    Move {
+        new_index: usize,
+    },
+}
+
+impl BookmarkOperation {
+    fn to_proto(&self) -> proto::update_bookmark::Operation {
+        use proto::update_bookmark::Operation;
+        let text = |text: &Option<Arc<str>>| text.as_ref().map(|text| text.to_string());
+        match self {
+            BookmarkOperation::Add { label, note } => Operation::Add(proto::AddBookmark {
+                label: text(label),
+                note: text(note),
+            }),
+            BookmarkOperation::Remove => Operation::Remove(proto::RemoveBookmark {}),
+            BookmarkOperation::SetLabel(label) => {
+                Operation::SetLabel(proto::SetBookmarkLabel { label: text(label) })
+            }
+            BookmarkOperation::SetNote(note) => {
+                Operation::SetNote(proto::SetBookmarkNote { note: text(note) })
+            }
+            BookmarkOperation::Move { new_index } => Operation::Reorder(proto::ReorderBookmark {
+                new_index: *new_index as u32,
+            }),
+        }
+    }
+
+    fn from_proto(operation: proto::update_bookmark::Operation) -> Self {
+        use proto::update_bookmark::Operation;
+        match operation {
+            Operation::Add(add) => BookmarkOperation::Add {
+                label: add.label.map(Arc::from),
+                note: add.note.map(Arc::from),
+            },
+            Operation::Remove(_) => BookmarkOperation::Remove,
+            Operation::SetLabel(set_label) => {
+                BookmarkOperation::SetLabel(set_label.label.map(Arc::from))
+            }
+            Operation::SetNote(set_note) => {
+                BookmarkOperation::SetNote(set_note.note.map(Arc::from))
+            }
+            Operation::Reorder(reorder) => BookmarkOperation::Move {
+                new_index: reorder.new_index as usize,
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
+struct RemoteBookmarkStore {
+    upstream_client: AnyProtoClient,
+    upstream_project_id: u64,
+}
+
+/// Guests of a shared project and clients of a remote server replicate the bookmarks of the
+/// host, applying their own edits optimistically and sending them upstream.
+#[derive(Clone)]
+enum BookmarkStoreMode {
+    Local,
+    Remote(RemoteBookmarkStore),
 }
 
 pub struct BookmarkStore {
     buffer_store: Entity<BufferStore>,
     worktree_store: Entity<WorktreeStore>,
     bookmarks: BTreeMap<Arc<Path>, BookmarkEntry>,
+    downstream_client: Option<(AnyProtoClient, u64)>,
+    /// User ids of the guests of the shared project, owning the bookmarks they add
+    collaborators: HashMap<proto::PeerId, u64>,
+    mode: BookmarkStoreMode,
 }
 
+impl EventEmitter<BookmarkStoreEvent> for BookmarkStore {}
+
 impl BookmarkStore {
-    pub fn new(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
+    pub fn init(client: &AnyProtoClient) {
+        client.add_entity_request_handler(Self::handle_update_bookmark);
+        client.add_entity_request_handler(Self::handle_clear_bookmarks);
+        client.add_entity_message_handler(Self::handle_bookmarks_for_file);
+    }
+
+    pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
+        Self {
+            buffer_store,
+            worktree_store,
+            bookmarks: BTreeMap::new(),
+            downstream_client: None,
+            collaborators: HashMap::new(),
+            mode: BookmarkStoreMode::Local,
+        }
+    }
+
+    pub(crate) fn remote(
+        upstream_project_id: u64,
+        upstream_client: AnyProtoClient,
+        buffer_store: Entity<BufferStore>,
+        worktree_store: Entity<WorktreeStore>,
+    ) -> Self {
         Self {
             buffer_store,
             worktree_store,
             bookmarks: BTreeMap::new(),
+            downstream_client: None,
+            collaborators: HashMap::new(),
+            mode: BookmarkStoreMode::Remote(RemoteBookmarkStore {
+                upstream_client,
+                upstream_project_id,
+            }),
+        }
+    }
+
+    pub fn shared(
+        &mut self,
+        project_id: u64,
+        downstream_client: AnyProtoClient,
+        cx: &mut Context<Self>,
+    ) {
+        self.downstream_client = Some((downstream_client, project_id));
+        self.broadcast(cx);
+
+        // Bookmarks restored from the database are sent once their files are opened
+        cx.spawn(async move |this, cx| {
+            let this = this.upgrade().context("bookmark store was dropped")?;
+            Self::resolve_all(&this, cx).await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn unshared(&mut self, cx: &mut Context<Self>) {
+        self.downstream_client.take();
+        self.collaborators.clear();
+
+        cx.notify();
+    }
+
+    pub(crate) fn add_collaborator(&mut self, peer_id: proto::PeerId, user_id: u64) {
+        self.collaborators.insert(peer_id, user_id);
+    }
+
+    pub(crate) fn remove_collaborator(&mut self, peer_id: &proto::PeerId) {
+        self.collaborators.remove(peer_id);
+    }
+
+    pub(crate) fn update_collaborator_peer_id(
+        &mut self,
+        old_peer_id: &proto::PeerId,
+        new_peer_id: proto::PeerId,
+    ) {
+        if let Some(user_id) = self.collaborators.remove(old_peer_id) {
+            self.collaborators.insert(new_peer_id, user_id);
+        }
+    }
+
+    /// The user on whose behalf a request is made, `None` for the project's host.
+    /// Requests of guests are attributed to them, while requests forwarded by the
+    /// client of a remote server carry the user they were made by.
+    fn requester(&self, sender_id: proto::PeerId, owner_id: Option<u64>) -> Option<u64> {
+        self.collaborators.get(&sender_id).copied().or(owner_id)
+    }
+
+    fn can_modify(owner: Option<u64>, requester: Option<u64>) -> bool {
+        requester.is_none() || owner == requester
+    }
+
+    /// Sends the bookmarks of all loaded files to the guests of the project
+    pub(crate) fn broadcast(&self, cx: &App) {
+        if self.downstream_client.is_some() {
+            for (path, entry) in &self.bookmarks {
+                if entry.loaded().is_some() {
+                    self.send_bookmarks_for_file(path, cx);
+                }
+            }
+        }
+    }
+
+    fn send_bookmarks_for_file(&self, abs_path: &Arc<Path>, cx: &App) {
+        let Some((client, project_id)) = &self.downstream_client else {
+            return;
+        };
+        let bookmarks = match self.bookmarks.get(abs_path) {
+            Some(BookmarkEntry::Loaded(buffer_bookmarks)) => {
+                let snapshot = buffer_bookmarks.buffer.read(cx).text_snapshot();
+                buffer_bookmarks
+                    .bookmarks
+                    .iter()
+                    .filter(|bookmark| snapshot.can_resolve(&bookmark.anchor.anchor()))
+                    .map(Bookmark::to_proto)
+                    .collect()
+            }
+            // Unloaded bookmarks are sent once they're resolved to anchors
+            Some(BookmarkEntry::Unloaded(_)) => return,
+            None => Vec::new(),
+        };
+        client
+            .send(proto::BookmarksForFile {
+                project_id: *project_id,
+                path: abs_path.to_string_lossy().into_owned(),
+                bookmarks,
+            })
+            .log_err();
+    }
+
+    /// Propagates an operation applied to this store: upstream to the host if this
+    /// store replicates a remote project, or downstream to the guests otherwise.
+    fn sync_operation(
+        &self,
+        abs_path: &Arc<Path>,
+        position: Option<text::Anchor>,
+        operation: BookmarkOperation,
+        requester: Option<u64>,
+        cx: &App,
+    ) {
+        match &self.mode {
+            BookmarkStoreMode::Remote(remote) => {
+                let Some(position) = position else {
+                    log::warn!(
+                        "Not syncing an edit of an unloaded bookmark in {}",
+                        abs_path.display()
+                    );
+                    return;
+                };
+                let request = remote.upstream_client.request(proto::UpdateBookmark {
+                    project_id: remote.upstream_project_id,
+                    path: abs_path.to_string_lossy().into_owned(),
+                    position: Some(serialize_text_anchor(&position)),
+                    owner_id: requester,
+                    operation: Some(operation.to_proto()),
+                });
+                cx.background_spawn(request).detach_and_log_err(cx);
+            }
+            BookmarkStoreMode::Local => self.send_bookmarks_for_file(abs_path, cx),
         }
     }
 
+    async fn handle_bookmarks_for_file(
+        this: Entity<Self>,
+        message: TypedEnvelope<proto::BookmarksForFile>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let abs_path = Arc::<Path>::from(Path::new(&message.payload.path));
+        let buffer = if message.payload.bookmarks.is_empty() {
+            None
+        } else {
+            let open_buffer = this
+                .update(&mut cx, |this, cx| {
+                    let path = this
+                        .worktree_store
+                        .read(cx)
+                        .project_path_for_absolute_path(&abs_path, cx)?;
+                    Some(
+                        this.buffer_store
+                            .update(cx, |buffer_store, cx| buffer_store.open_buffer(path, cx)),
+                    )
+                })
+                .context("Invalid project path")?;
+            Some(open_buffer.await?)
+        };
+
+        this.update(&mut cx, |this, cx| {
+            match buffer {
+                Some(buffer) => {
+                    let mut buffer_bookmarks = match this.bookmarks.remove(&abs_path) {
+                        Some(BookmarkEntry::Loaded(buffer_bookmarks)) => buffer_bookmarks,
+                        _ => BufferBookmarks::new(buffer, cx),
+                    };
+                    buffer_bookmarks.bookmarks = message
+                        .payload
+                        .bookmarks
+                        .into_iter()
+                        .filter_map(Bookmark::from_proto)
+                        .collect();
+                    this.bookmarks
+                        .insert(abs_path.clone(), BookmarkEntry::Loaded(buffer_bookmarks));
+                }
+                None => {
+                    this.bookmarks.remove(&abs_path);
+                }
+            }
+
+            // Guests of a shared remote project replicate the bookmarks of the remote server
+            this.send_bookmarks_for_file(&abs_path, cx);
+            cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+            cx.notify();
+        });
+
+        Ok(())
+    }
+
+    async fn handle_update_bookmark(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateBookmark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let sender_id = envelope.original_sender_id.unwrap_or(envelope.sender_id);
+        let payload = envelope.payload;
+        let abs_path = Arc::<Path>::from(Path::new(&payload.path));
+        let operation = BookmarkOperation::from_proto(
+            payload
+                .operation
+                .context("Bookmark operation not present in RPC payload")?,
+        );
+        let position = language::proto::deserialize_anchor(
+            payload
+                .position
+                .context("Anchor not present in RPC payload")?,
+        )
+        .context("Anchor deserialization failed")?;
+
+        let buffer = this
+            .update(&mut cx, |this, cx| {
+                let path = this
+                    .worktree_store
+                    .read(cx)
+                    .project_path_for_absolute_path(&abs_path, cx)?;
+                this.buffer_store.read(cx).get_by_path(&path)
+            })
+            .context("Could not find buffer for a given path")?;
+        buffer
+            .update(&mut cx, |buffer, _| buffer.wait_for_anchors([position]))
+            .await?;
+
+        this.update(&mut cx, |this, cx| {
+            let requester = this.requester(sender_id, payload.owner_id);
+            let row = position
+                .summary::<Point>(&buffer.read(cx).text_snapshot())
+                .row;
+            let result = match operation.clone() {
+                BookmarkOperation::Add { label, note } => {
+                    this.add_bookmark(&abs_path, &buffer, position, label, note, requester, cx)
+                }
+                operation => this.apply_operation(&abs_path, row, operation, requester, cx),
+            };
+            match result {
+                Ok(true) => {
+                    this.sync_operation(&abs_path, Some(position), operation, requester, cx)
+                }
+                Ok(false) => {}
+                // Reverts the optimistic edit of the requester
+                Err(_) => this.send_bookmarks_for_file(&abs_path, cx),
+            }
+            result.map(|_| proto::Ack {})
+        })
+    }
+
+    async fn handle_clear_bookmarks(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::ClearBookmarks>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let sender_id = envelope.original_sender_id.unwrap_or(envelope.sender_id);
+        this.update(&mut cx, |this, cx| {
+            let requester = this.requester(sender_id, envelope.payload.owner_id);
+            this.clear_bookmarks_for(requester, cx);
+        });
+        Ok(proto::Ack {})
+    }
+
     pub fn load_serialized_bookmarks(
         &mut self,
         bookmark_rows: BTreeMap<Arc<Path>, Vec<SerializedBookmark>>,
@@ -101,7 +617,7 @@ impl BookmarkStore {
     ) -> Task<Result<()>> {
         self.bookmarks.clear();
 
-        for (path, rows) in bookmark_rows {
+        for (path, mut rows) in bookmark_rows {
             if rows.is_empty() {
                 continue;
             }
@@ -109,6 +625,7 @@ impl BookmarkStore {
             let count = rows.len();
             log::debug!("Stored {count} unloaded bookmark(s) at {}", path.display());
 
+            rows.sort_by_key(|bookmark| (bookmark.order, bookmark.row));
             self.bookmarks.insert(path, BookmarkEntry::Unloaded(rows));
         }
 
@@ -129,33 +646,50 @@ impl BookmarkStore {
         let snapshot = buffer.read(cx).snapshot();
         let max_point = snapshot.max_point();
 
-        let anchors: Vec<BookmarkAnchor> = rows
+        let anchors: Vec<Bookmark> = rows
             .iter()
             .filter_map(|bookmark_row| {
-                let point = Point::new(bookmark_row.0, 0);
+                let point = Point::new(bookmark_row.row, 0);
 
                 if point > max_point {
                     log::warn!(
                         "Skipping out-of-range bookmark: {} row {} (file has {} rows)",
                         abs_path.display(),
-                        bookmark_row.0,
+                        bookmark_row.row,
                         max_point.row
                     );
                     return None;
                 }
 
                 let anchor = snapshot.anchor_after(point);
-                Some(BookmarkAnchor(anchor))
+                Some(Bookmark {
+                    anchor: BookmarkAnchor(anchor),
+                    label: bookmark_row.label.clone(),
+                    note: bookmark_row.note.clone(),
+                    owner: None,
+                })
             })
             .collect();
 
         if anchors.is_empty() {
             self.bookmarks.remove(abs_path);
         } else {
+            // Bookmarks restored by the client of a remote project are added to the remote project
+            if let BookmarkStoreMode::Remote(_) = &self.mode {
+                for bookmark in &anchors {
+                    let operation = BookmarkOperation::Add {
+                        label: bookmark.label.clone(),
+                        note: bookmark.note.clone(),
+                    };
+                    self.sync_operation(abs_path, Some(bookmark.anchor.0), operation, None, cx);
+                }
+            }
+
             let mut buffer_bookmarks = BufferBookmarks::new(buffer.clone(), cx);
             buffer_bookmarks.bookmarks = anchors;
             self.bookmarks
                 .insert(abs_path.clone(), BookmarkEntry::Loaded(buffer_bookmarks));
+            self.send_bookmarks_for_file(abs_path, cx);
         }
     }
 
@@ -180,31 +714,206 @@ impl BookmarkStore {
 
         self.resolve_anchors_if_needed(&abs_path, &buffer, cx);
 
+        let row = anchor
+            .summary::<Point>(&buffer.read(cx).text_snapshot())
+            .row;
+        let has_bookmark = self
+            .bookmarks
+            .get(&abs_path)
+            .and_then(|entry| entry.index_of_row(row, cx))
+            .is_some();
+
+        if has_bookmark {
+            self.update_bookmark(&abs_path, row, BookmarkOperation::Remove, cx);
+        } else if let Ok(true) = self.add_bookmark(&abs_path, &buffer, anchor, None, None, None, cx)
+        {
+            let operation = BookmarkOperation::Add {
+                label: None,
+                note: None,
+            };
+            self.sync_operation(&abs_path, Some(anchor), operation, None, cx);
+        }
+    }
+
+    /// Sets the label of the bookmark on the given row, an empty label removing it
+    pub fn set_bookmark_label(
+        &mut self,
+        abs_path: &Path,
+        row: u32,
+        label: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let operation = BookmarkOperation::SetLabel(Self::non_empty_text(label));
+        self.update_bookmark(abs_path, row, operation, cx);
+    }
+
+    /// Sets the note of the bookmark on the given row, an empty note removing it
+    pub fn set_bookmark_note(
+        &mut self,
+        abs_path: &Path,
+        row: u32,
+        note: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let operation = BookmarkOperation::SetNote(Self::non_empty_text(note));
+        self.update_bookmark(abs_path, row, operation, cx);
+    }
+
+    fn non_empty_text(text: &str) -> Option<Arc<str>> {
+        let text = text.trim();
+        (!text.is_empty()).then(|| Arc::from(text))
+    }
+
+    /// Removes the bookmark on the given row
+    pub fn remove_bookmark(&mut self, abs_path: &Path, row: u32, cx: &mut Context<Self>) {
+        self.update_bookmark(abs_path, row, BookmarkOperation::Remove, cx);
+    }
+
+    /// Moves the bookmark on the given row to another position among the bookmarks of its file
+    pub fn move_bookmark(
+        &mut self,
+        abs_path: &Path,
+        row: u32,
+        new_index: usize,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_bookmark(abs_path, row, BookmarkOperation::Move { new_index }, cx);
+    }
+
+    /// Applies an edit of the local user and propagates it to the other replicas
+    fn update_bookmark(
+        &mut self,
+        abs_path: &Path,
+        row: u32,
+        operation: BookmarkOperation,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = self
+            .bookmarks
+            .get_key_value(abs_path)
+            .map(|(path, _)| path.clone())
+        else {
+            return;
+        };
+        // The anchor identifies the bookmark for other replicas, and must be taken before removing it
+        let position = self.bookmarks.get(&abs_path).and_then(|entry| {
+            let buffer_bookmarks = entry.loaded()?;
+            let index = buffer_bookmarks.index_of_row(row, cx)?;
+            Some(buffer_bookmarks.bookmarks[index].anchor.0)
+        });
+
+        match self.apply_operation(&abs_path, row, operation.clone(), None, cx) {
+            Ok(true) => self.sync_operation(&abs_path, position, operation, None, cx),
+            Ok(false) => {}
+            Err(error) => log::error!("Failed to update bookmark: {error:#}"),
+        }
+    }
+
+    /// Adds a bookmark unless the row already has one, returning whether it was added
+    fn add_bookmark(
+        &mut self,
+        abs_path: &Arc<Path>,
+        buffer: &Entity<Buffer>,
+        anchor: text::Anchor,
+        label: Option<Arc<str>>,
+        note: Option<Arc<str>>,
+        owner: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Result<bool> {
+        self.resolve_anchors_if_needed(abs_path, buffer, cx);
+
         let entry = self
             .bookmarks
             .entry(abs_path.clone())
             .or_insert_with(|| BookmarkEntry::Loaded(BufferBookmarks::new(buffer.clone(), cx)));
-
         let BookmarkEntry::Loaded(buffer_bookmarks) = entry else {
-            unreachable!("resolve_if_needed should have converted to Loaded");
+            unreachable!("resolve_anchors_if_needed should have converted to Loaded");
         };
 
-        let snapshot = buffer.read(cx).text_snapshot();
-
-        let existing_index = buffer_bookmarks.bookmarks.iter().position(|existing| {
-            existing.0.summary::<Point>(&snapshot).row == anchor.summary::<Point>(&snapshot).row
+        let row = anchor
+            .summary::<Point>(&buffer.read(cx).text_snapshot())
+            .row;
+        if buffer_bookmarks.index_of_row(row, cx).is_some() {
+            return Ok(false);
+        }
+        buffer_bookmarks.bookmarks.push(Bookmark {
+            label,
+            note,
+            owner,
+            ..Bookmark::new(BookmarkAnchor(anchor))
         });
 
-        if let Some(index) = existing_index {
-            buffer_bookmarks.bookmarks.remove(index);
-            if buffer_bookmarks.bookmarks.is_empty() {
-                self.bookmarks.remove(&abs_path);
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+        cx.notify();
+        Ok(true)
+    }
+
+    /// Applies an operation to the bookmark on the row on behalf of a user, `None` standing for
+    /// the local user or the project's host. Returns whether any bookmark changed.
+    fn apply_operation(
+        &mut self,
+        abs_path: &Arc<Path>,
+        row: u32,
+        operation: BookmarkOperation,
+        requester: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Result<bool> {
+        let Some(entry) = self.bookmarks.get_mut(abs_path) else {
+            return Ok(false);
+        };
+        let Some(index) = entry.index_of_row(row, cx) else {
+            return Ok(false);
+        };
+        anyhow::ensure!(
+            Self::can_modify(entry.owner(index), requester),
+            "bookmark in {} on row {row} is owned by another user",
+            abs_path.display()
+        );
+
+        match operation {
+            BookmarkOperation::Add { .. } => return Ok(false),
+            BookmarkOperation::Remove => {
+                entry.remove(index);
+                if entry.is_empty() {
+                    self.bookmarks.remove(abs_path);
+                }
+            }
+            BookmarkOperation::SetLabel(new_label) => {
+                if let Some((label, _)) = entry.label_and_note_mut(index) {
+                    *label = new_label;
+                }
+            }
+            BookmarkOperation::SetNote(new_note) => {
+                if let Some((_, note)) = entry.label_and_note_mut(index) {
+                    *note = new_note;
+                }
+            }
+            BookmarkOperation::Move { new_index } => {
+                let new_index = new_index.min(entry.len() - 1);
+                if index == new_index {
+                    return Ok(false);
+                }
+                entry.move_to(index, new_index);
             }
-        } else {
-            buffer_bookmarks.bookmarks.push(BookmarkAnchor(anchor));
         }
 
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
         cx.notify();
+        Ok(true)
+    }
+
+    /// The owner of the bookmark on the given row, `None` if it belongs to the project's host
+    pub fn bookmark_owner(&self, abs_path: &Path, row: u32, cx: &App) -> Option<u64> {
+        let entry = self.bookmarks.get(abs_path)?;
+        entry.owner(entry.index_of_row(row, cx)?)
+    }
+
+    /// The buffer of the bookmarked file, if its bookmarks have been resolved to anchors
+    pub fn loaded_buffer(&self, abs_path: &Path) -> Option<&Entity<Buffer>> {
+        self.bookmarks
+            .get(abs_path)
+            .and_then(BookmarkEntry::loaded)
+            .map(BufferBookmarks::buffer)
     }
 
     /// Returns the bookmarks for a given buffer within an optional range.
@@ -232,6 +941,7 @@ impl BookmarkStore {
             .iter()
             .filter_map({
                 move |bookmark| {
+                    let bookmark = bookmark.anchor;
                     if !buffer_snapshot.can_resolve(&bookmark.anchor()) {
                         return None;
                     }
@@ -242,7 +952,7 @@ impl BookmarkStore {
                         return None;
                     }
 
-                    Some(*bookmark)
+                    Some(bookmark)
                 }
             })
             .collect()
@@ -302,27 +1012,44 @@ impl BookmarkStore {
         self.bookmarks
             .iter()
             .filter_map(|(path, entry)| {
-                let mut rows = match entry {
-                    BookmarkEntry::Unloaded(rows) => rows.clone(),
+                let rows = match entry {
+                    BookmarkEntry::Unloaded(rows) => rows
+                        .iter()
+                        .enumerate()
+                        .map(|(order, bookmark)| SerializedBookmark {
+                            order: order as u32,
+                            ..bookmark.clone()
+                        })
+                        .collect::<Vec<_>>(),
                     BookmarkEntry::Loaded(buffer_bookmarks) => {
                         let snapshot = buffer_bookmarks.buffer.read(cx).snapshot();
                         buffer_bookmarks
                             .bookmarks
                             .iter()
-                            .filter_map(|bookmark| {
-                                if !snapshot.can_resolve(&bookmark.anchor()) {
+                            .enumerate()
+                            .filter_map(|(order, bookmark)| {
+                                let anchor = bookmark.anchor.anchor();
+                                if !snapshot.can_resolve(&anchor) {
                                     return None;
                                 }
-                                let row =
-                                    snapshot.summary_for_anchor::<Point>(&bookmark.anchor()).row;
-                                Some(SerializedBookmark(row))
+                                let row = snapshot.summary_for_anchor::<Point>(&anchor).row;
+                                Some(SerializedBookmark {
+                                    row,
+                                    label: bookmark.label.clone(),
+                                    note: bookmark.note.clone(),
+                                    order: order as u32,
+                                })
                             })
                             .collect()
                     }
                 };
 
-                rows.sort();
-                rows.dedup();
+                // Bookmarks whose lines were merged by edits collapse into the first of them
+                let mut seen_rows = HashSet::default();
+                let rows = rows
+                    .into_iter()
+                    .filter(|bookmark| seen_rows.insert(bookmark.row))
+                    .collect::<Vec<_>>();
 
                 if rows.is_empty() {
                     None
@@ -346,8 +1073,10 @@ impl BookmarkStore {
                 let ranges: Vec<Range<Point>> = bookmarks
                     .bookmarks()
                     .iter()
-                    .map(|anchor| {
-                        let row = snapshot.summary_for_anchor::<Point>(&anchor.anchor()).row;
+                    .map(|bookmark| {
+                        let row = snapshot
+                            .summary_for_anchor::<Point>(&bookmark.anchor().anchor())
+                            .row;
                         Point::row_range(row..row)
                     })
                     .collect();
@@ -363,7 +1092,10 @@ impl BookmarkStore {
     }
 
     /// Opens buffers for all unloaded bookmark entries and resolves them to anchors. This is used to show all bookmarks in a large multi-buffer.
-    async fn resolve_all(this: &Entity<Self>, cx: &mut (impl AppContext + Clone)) -> Result<()> {
+    pub async fn resolve_all(
+        this: &Entity<Self>,
+        cx: &mut (impl AppContext + Clone),
+    ) -> Result<()> {
         let unloaded_paths: Vec<Arc<Path>> = cx.read_entity(&this, |this, _| {
             this.bookmarks
                 .iter()
@@ -412,7 +1144,34 @@ impl BookmarkStore {
     }
 
     pub fn clear_bookmarks(&mut self, cx: &mut Context<Self>) {
-        self.bookmarks.clear();
+        self.clear_bookmarks_for(None, cx);
+    }
+
+    /// Removes all bookmarks the user may modify, `None` standing for the local user or the
+    /// project's host, and propagates the removal to the other replicas
+    fn clear_bookmarks_for(&mut self, requester: Option<u64>, cx: &mut Context<Self>) {
+        let paths = self.bookmarks.keys().cloned().collect::<Vec<_>>();
+        for entry in self.bookmarks.values_mut() {
+            entry.retain(|owner| !Self::can_modify(owner, requester));
+        }
+        self.bookmarks.retain(|_, entry| !entry.is_empty());
+
+        match &self.mode {
+            BookmarkStoreMode::Remote(remote) => {
+                let request = remote.upstream_client.request(proto::ClearBookmarks {
+                    project_id: remote.upstream_project_id,
+                    owner_id: requester,
+                });
+                cx.background_spawn(request).detach_and_log_err(cx);
+            }
+            BookmarkStoreMode::Local => {
+                for path in &paths {
+                    self.send_bookmarks_for_file(path, cx);
+                }
+            }
+        }
+
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
         cx.notify();
     }
 }
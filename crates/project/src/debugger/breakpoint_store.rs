@@ -370,6 +370,26 @@ impl BreakpointStore {
         });
     }
 
+    /// Finds the path and row of the breakpoint the adapter of `session_id` knows as `id`.
+    pub(crate) fn breakpoint_position_by_session_id(
+        &self,
+        session_id: SessionId,
+        id: u64,
+        cx: &App,
+    ) -> Option<(Arc<Path>, u32)> {
+        self.breakpoints.iter().find_map(|(path, breakpoints)| {
+            let breakpoint = breakpoints.breakpoints.iter().find(|breakpoint| {
+                breakpoint
+                    .session_state
+                    .get(&session_id)
+                    .is_some_and(|state| state.id == id)
+            })?;
+            let snapshot = breakpoints.buffer.read(cx).text_snapshot();
+            let row = breakpoint.position().summary::<Point>(&snapshot).row;
+            Some((path.clone(), row))
+        })
+    }
+
     pub(super) fn mark_breakpoints_verified(
         &mut self,
         session_id: SessionId,
@@ -1,13 +1,16 @@
 use super::breakpoint_store::{
-    BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
+    BreakpointState, BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason,
+    SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints, SetVariableValueCommand,
-    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
-    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, GotoCommand, GotoTargetsCommand,
+    Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand,
+    NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand, ReverseContinueCommand,
+    ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints,
+    SetInstructionBreakpointsCommand, SetVariableValueCommand, StackTraceCommand, StepBackCommand,
+    StepCommand, StepInCommand, StepOutCommand, TerminateCommand, TerminateThreadsCommand,
+    ThreadsCommand, VariablesCommand,
 };
 use super::dap_store::DapStore;
 use crate::debugger::breakpoint_store::BreakpointSessionState;
@@ -70,6 +73,8 @@ use worktree::Worktree;
 
 const MAX_TRACKED_OUTPUT_EVENTS: usize = 5000;
 const DEBUG_HISTORY_LIMIT: usize = 10;
+/// How many instructions a reverse step in may step back through before giving up.
+const MAX_REVERSE_STEP_IN_INSTRUCTIONS: usize = 256;
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, PartialOrd, Ord, Eq)]
 #[repr(transparent)]
@@ -686,6 +691,27 @@ pub struct SessionSnapshot {
 
 type IsEnabled = bool;
 
+/// DAP has no request for stepping backwards into a function, so it is emulated
+/// by stepping back one instruction at a time, until the thread either returns
+/// into a function called by the previous statement or turns out to have
+/// stepped back past a statement that calls nothing.
+///
+/// The stops in between are neither recorded in the history nor reported, so
+/// the emulation looks like a single step.
+#[derive(Debug)]
+struct ReverseStepIn {
+    thread_id: ThreadId,
+    depth: usize,
+    start_line: u64,
+    /// The line of the statement being stepped back through, once reached.
+    previous_line: Option<u64>,
+    steps_left: usize,
+    /// Whether the thread has stopped since the emulation started.
+    has_stopped: bool,
+    /// Whether the thread is taking the final step, which stops as usual.
+    is_last_step: bool,
+}
+
 #[derive(Copy, Clone, Default, Debug, PartialEq, PartialOrd, Eq, Ord)]
 pub struct OutputToken(pub usize);
 /// Represents a current state of a single debug adapter and provides ways to mutate it.
@@ -702,6 +728,13 @@ pub struct Session {
     parent_session: Option<Entity<Session>>,
     output_token: OutputToken,
     output: Box<circular_buffer::CircularBuffer<MAX_TRACKED_OUTPUT_EVENTS, dap::OutputEvent>>,
+    /// Messages printed by logpoints, kept apart from the program's output.
+    logpoint_output_token: OutputToken,
+    logpoint_output:
+        Box<circular_buffer::CircularBuffer<MAX_TRACKED_OUTPUT_EVENTS, dap::OutputEvent>>,
+    /// How many times each source breakpoint, keyed by path and row, was hit in this session.
+    breakpoint_hit_counts: HashMap<(Arc<Path>, u32), u64>,
+    reverse_step_in: Option<ReverseStepIn>,
     watchers: HashMap<SharedString, Watcher>,
     is_session_terminated: bool,
     requests: HashMap<TypeId, HashMap<RequestSlot, Shared<Task<Option<()>>>>>,
@@ -709,6 +742,8 @@ pub struct Session {
     ignore_breakpoints: bool,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
     data_breakpoints: BTreeMap<String, DataBreakpointState>,
+    /// Keyed by the memory reference of the instruction.
+    instruction_breakpoints: BTreeMap<String, dap::InstructionBreakpoint>,
     background_tasks: Vec<Task<()>>,
     restart_task: Option<Task<()>>,
     task_context: SharedTaskContext,
@@ -876,6 +911,10 @@ impl Session {
                 watchers: HashMap::default(),
                 output_token: OutputToken(0),
                 output: circular_buffer::CircularBuffer::boxed(),
+                logpoint_output_token: OutputToken(0),
+                logpoint_output: circular_buffer::CircularBuffer::boxed(),
+                breakpoint_hit_counts: HashMap::default(),
+                reverse_step_in: None,
                 requests: HashMap::default(),
                 background_tasks: Vec::default(),
                 restart_task: None,
@@ -883,6 +922,7 @@ impl Session {
                 ignore_breakpoints: false,
                 breakpoint_store,
                 data_breakpoints: Default::default(),
+                instruction_breakpoints: Default::default(),
                 exception_breakpoints: Default::default(),
                 label,
                 adapter,
@@ -1335,6 +1375,26 @@ impl Session {
         breakpoint: SourceBreakpoint,
         active_thread_id: ThreadId,
         cx: &mut Context<Self>,
+    ) {
+        self.run_to_position_in_direction(breakpoint, active_thread_id, false, cx);
+    }
+
+    /// Runs backwards until the thread reaches the position.
+    pub fn reverse_run_to_position(
+        &mut self,
+        breakpoint: SourceBreakpoint,
+        active_thread_id: ThreadId,
+        cx: &mut Context<Self>,
+    ) {
+        self.run_to_position_in_direction(breakpoint, active_thread_id, true, cx);
+    }
+
+    fn run_to_position_in_direction(
+        &mut self,
+        breakpoint: SourceBreakpoint,
+        active_thread_id: ThreadId,
+        reverse: bool,
+        cx: &mut Context<Self>,
     ) {
         match &mut self.state {
             SessionState::Running(local_mode) => {
@@ -1358,7 +1418,11 @@ impl Session {
                 cx.spawn(async move |this, cx| {
                     task.await;
                     this.update(cx, |this, cx| {
-                        this.continue_thread(active_thread_id, cx);
+                        if reverse {
+                            this.reverse_continue_thread(active_thread_id, cx);
+                        } else {
+                            this.continue_thread(active_thread_id, cx);
+                        }
                     })
                 })
                 .detach();
@@ -1389,6 +1453,67 @@ impl Session {
         )
     }
 
+    pub fn has_new_logpoint_output(&self, last_update: OutputToken) -> bool {
+        self.logpoint_output_token.0 > last_update.0
+    }
+
+    pub fn logpoint_output(
+        &self,
+        since: OutputToken,
+    ) -> (impl Iterator<Item = &dap::OutputEvent>, OutputToken) {
+        let events_since = self.logpoint_output_token.0.saturating_sub(since.0);
+        let clamped_events_since = events_since.min(self.logpoint_output.len());
+        (
+            self.logpoint_output
+                .range(self.logpoint_output.len() - clamped_events_since..),
+            self.logpoint_output_token,
+        )
+    }
+
+    pub fn breakpoint_hit_count(&self, path: &Arc<Path>, row: u32) -> u64 {
+        self.breakpoint_hit_counts
+            .get(&(path.clone(), row))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Counts a hit of the breakpoints the adapter stopped at.
+    fn record_breakpoint_hits(&mut self, event: &StoppedEvent, cx: &App) {
+        if !matches!(event.reason, dap::StoppedEventReason::Breakpoint) {
+            return;
+        }
+        let session_id = self.session_id();
+        let breakpoint_store = self.breakpoint_store.read(cx);
+        for id in event.hit_breakpoint_ids.iter().flatten() {
+            if let Some(position) =
+                breakpoint_store.breakpoint_position_by_session_id(session_id, *id, cx)
+            {
+                *self.breakpoint_hit_counts.entry(position).or_default() += 1;
+            }
+        }
+    }
+
+    /// Adapters report where a message was logged from, which tells logpoint
+    /// messages apart from the program's own output.
+    fn logpoint_position(&self, event: &dap::OutputEvent, cx: &App) -> Option<(Arc<Path>, u32)> {
+        let path = event.source.as_ref()?.path.as_deref()?;
+        let row = (event.line? as u32).checked_sub(1)?;
+        let (_, breakpoint) =
+            self.breakpoint_store
+                .read(cx)
+                .breakpoint_at_row(Path::new(path), row, cx)?;
+        breakpoint
+            .bp
+            .message
+            .is_some()
+            .then(|| (Arc::from(Path::new(path)), row))
+    }
+
+    fn push_logpoint_output(&mut self, event: OutputEvent) {
+        self.logpoint_output.push_back(event);
+        self.logpoint_output_token.0 += 1;
+    }
+
     pub fn respond_to_client(
         &self,
         request_seq: u64,
@@ -1462,7 +1587,17 @@ impl Session {
     }
 
     fn handle_stopped_event(&mut self, event: StoppedEvent, cx: &mut Context<Self>) {
-        self.push_to_history();
+        let reverse_step_in = self.reverse_step_in.take().filter(|step| {
+            event.thread_id == Some(step.thread_id.0)
+                && matches!(event.reason, dap::StoppedEventReason::Step)
+        });
+        if reverse_step_in
+            .as_ref()
+            .is_none_or(|step| !step.has_stopped)
+        {
+            self.push_to_history();
+        }
+        self.record_breakpoint_hits(&event, cx);
 
         self.state.stopped();
         // todo(debugger): Find a clean way to get around the clone
@@ -1507,6 +1642,16 @@ impl Session {
         self.invalidate_generic();
         self.active_snapshot.threads.clear();
         self.active_snapshot.variables.clear();
+
+        if let Some(mut step) = reverse_step_in
+            && !step.is_last_step
+        {
+            step.has_stopped = true;
+            self.continue_reverse_step_in(step, cx);
+            cx.notify();
+            return;
+        }
+
         cx.emit(SessionEvent::Stopped(
             event
                 .thread_id
@@ -1574,7 +1719,12 @@ impl Session {
                     return;
                 }
 
-                self.push_output(event);
+                if let Some(position) = self.logpoint_position(&event, cx) {
+                    *self.breakpoint_hit_counts.entry(position).or_default() += 1;
+                    self.push_logpoint_output(event);
+                } else {
+                    self.push_output(event);
+                }
                 cx.notify();
             }
             Events::Breakpoint(event) => self.breakpoint_store.update(cx, |store, _| {
@@ -2086,6 +2236,69 @@ impl Session {
         self.send_data_breakpoints(cx);
     }
 
+    pub fn instruction_breakpoints(&self) -> impl Iterator<Item = &dap::InstructionBreakpoint> {
+        self.instruction_breakpoints.values()
+    }
+
+    pub fn has_instruction_breakpoint(&self, instruction_reference: &str) -> bool {
+        self.instruction_breakpoints
+            .contains_key(instruction_reference)
+    }
+
+    pub fn toggle_instruction_breakpoint(
+        &mut self,
+        instruction_reference: String,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .instruction_breakpoints
+            .remove(&instruction_reference)
+            .is_none()
+        {
+            self.instruction_breakpoints.insert(
+                instruction_reference.clone(),
+                dap::InstructionBreakpoint {
+                    instruction_reference,
+                    offset: None,
+                    condition: None,
+                    hit_condition: None,
+                    mode: None,
+                },
+            );
+        }
+        self.send_instruction_breakpoints(cx);
+        cx.notify();
+    }
+
+    fn send_instruction_breakpoints(&mut self, cx: &mut Context<Self>) {
+        if let Some(mode) = self.as_running() {
+            let command = SetInstructionBreakpointsCommand {
+                breakpoints: self.instruction_breakpoints.values().cloned().collect(),
+            };
+            mode.request(command).detach_and_log_err(cx);
+        }
+    }
+
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset`
+    /// instructions away from `memory_reference`.
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::DisassembledInstruction>>> {
+        self.request(
+            DisassembleCommand {
+                memory_reference,
+                instruction_offset,
+                instruction_count,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
     pub fn breakpoints_enabled(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -2159,6 +2372,7 @@ impl Session {
     }
 
     pub fn pause_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        self.reverse_step_in = None;
         self.request(
             PauseCommand {
                 thread_id: thread_id.0,
@@ -2170,6 +2384,7 @@ impl Session {
     }
 
     pub fn restart_stack_frame(&mut self, stack_frame_id: u64, cx: &mut Context<Self>) {
+        self.reverse_step_in = None;
         self.request(
             RestartStackFrameCommand { stack_frame_id },
             Self::empty_response,
@@ -2282,6 +2497,7 @@ impl Session {
 
     pub fn continue_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
         self.select_historic_snapshot(None, cx);
+        self.reverse_step_in = None;
 
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
@@ -2319,6 +2535,7 @@ impl Session {
         cx: &mut Context<Self>,
     ) {
         self.select_historic_snapshot(None, cx);
+        self.reverse_step_in = None;
 
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
@@ -2351,6 +2568,7 @@ impl Session {
         cx: &mut Context<Self>,
     ) {
         self.select_historic_snapshot(None, cx);
+        self.reverse_step_in = None;
 
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
@@ -2383,6 +2601,7 @@ impl Session {
         cx: &mut Context<Self>,
     ) {
         self.select_historic_snapshot(None, cx);
+        self.reverse_step_in = None;
 
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
@@ -2415,6 +2634,7 @@ impl Session {
         cx: &mut Context<Self>,
     ) {
         self.select_historic_snapshot(None, cx);
+        self.reverse_step_in = None;
 
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
@@ -2441,6 +2661,190 @@ impl Session {
         .detach();
     }
 
+    pub fn reverse_continue_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        self.select_historic_snapshot(None, cx);
+        self.reverse_step_in = None;
+
+        let supports_single_thread_execution_requests =
+            self.capabilities.supports_single_thread_execution_requests;
+        self.active_snapshot
+            .thread_states
+            .continue_thread(thread_id);
+        self.request(
+            ReverseContinueCommand {
+                thread_id: thread_id.0,
+                single_thread: supports_single_thread_execution_requests,
+            },
+            Self::on_step_response::<ReverseContinueCommand>(thread_id),
+            cx,
+        )
+        .detach();
+    }
+
+    /// Steps backwards into the function called by the previous statement,
+    /// stopping at its last instruction. Behaves like [`Self::step_back`] when
+    /// the statement calls nothing.
+    pub fn reverse_step_in(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        let supports_instruction_steps = self
+            .capabilities
+            .supports_stepping_granularity
+            .unwrap_or_default();
+        let stack_frames = self
+            .active_snapshot
+            .threads
+            .get(&thread_id)
+            .map(|thread| thread.stack_frames.as_slice())
+            .unwrap_or_default();
+        let Some(top_frame) = stack_frames.first().filter(|_| supports_instruction_steps) else {
+            self.step_back(thread_id, SteppingGranularity::Statement, cx);
+            return;
+        };
+
+        let step = ReverseStepIn {
+            thread_id,
+            depth: stack_frames.len(),
+            start_line: top_frame.dap.line,
+            previous_line: None,
+            steps_left: MAX_REVERSE_STEP_IN_INSTRUCTIONS,
+            has_stopped: false,
+            is_last_step: false,
+        };
+        self.step_back(thread_id, SteppingGranularity::Instruction, cx);
+        self.reverse_step_in = Some(step);
+    }
+
+    fn continue_reverse_step_in(&mut self, step: ReverseStepIn, cx: &mut Context<Self>) {
+        let thread_id = step.thread_id;
+        // Kept while the stack is fetched, so that stepping or continuing in the
+        // meantime cancels the emulation.
+        self.reverse_step_in = Some(step);
+        let stack_frames = self.request(
+            StackTraceCommand {
+                thread_id: thread_id.0,
+                start_frame: None,
+                levels: None,
+            },
+            |_, stack_frames, _| stack_frames.log_err(),
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            let stack_frames = stack_frames.await;
+            this.update(cx, |this, cx| {
+                let Some(mut step) = this.reverse_step_in.take() else {
+                    return;
+                };
+                let Some(stack_frames) = stack_frames else {
+                    this.finish_reverse_step_in(thread_id, cx);
+                    return;
+                };
+                // Returning into a callee deepens the stack, and stepping back
+                // past the start of the function makes it shallower.
+                if stack_frames.len() != step.depth {
+                    this.finish_reverse_step_in(thread_id, cx);
+                    return;
+                }
+                let line = stack_frames.first().map(|frame| frame.line);
+                match step.previous_line {
+                    None if line != Some(step.start_line) => step.previous_line = line,
+                    Some(previous_line) if line != Some(previous_line) => {
+                        // The previous statement calls nothing, and the thread is
+                        // now one instruction before it.
+                        this.step_over(thread_id, SteppingGranularity::Instruction, cx);
+                        step.is_last_step = true;
+                        this.reverse_step_in = Some(step);
+                        return;
+                    }
+                    _ => {}
+                }
+                if step.steps_left == 0 {
+                    this.finish_reverse_step_in(thread_id, cx);
+                    return;
+                }
+                step.steps_left -= 1;
+                this.step_back(thread_id, SteppingGranularity::Instruction, cx);
+                this.reverse_step_in = Some(step);
+            })
+            .ok()
+        })
+        .detach();
+    }
+
+    /// Reports the stop a reverse step in ended at, which was skipped while it
+    /// was unclear whether the emulation would step further.
+    fn finish_reverse_step_in(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        cx.emit(SessionEvent::Stopped(Some(thread_id)));
+        cx.emit(SessionEvent::InvalidateInlineValue);
+        cx.notify();
+    }
+
+    /// Runs backwards to the statement that called the current function.
+    pub fn reverse_step_out(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        let Some((path, line)) = self
+            .active_snapshot
+            .threads
+            .get(&thread_id)
+            .and_then(|thread| thread.stack_frames.get(1))
+            .and_then(|caller| {
+                let path = caller.dap.source.as_ref()?.path.as_ref()?;
+                Some((Arc::<Path>::from(Path::new(path)), caller.dap.line))
+            })
+        else {
+            log::warn!("Cannot step back out of a frame without a caller source");
+            return;
+        };
+
+        self.reverse_run_to_position(
+            SourceBreakpoint {
+                row: line.saturating_sub(1) as u32,
+                path,
+                message: None,
+                condition: None,
+                hit_condition: None,
+                state: BreakpointState::Enabled,
+            },
+            thread_id,
+            cx,
+        );
+    }
+
+    /// Moves the thread to the first location the adapter can jump to on the
+    /// given row, without running the code in between.
+    pub fn go_to_position(
+        &mut self,
+        path: Arc<Path>,
+        row: u32,
+        thread_id: ThreadId,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<()>> {
+        let targets = self.request(
+            GotoTargetsCommand {
+                path: path.to_string_lossy().into_owned(),
+                line: row as u64 + 1,
+            },
+            |_, targets, _| targets.log_err(),
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            let target = targets.await?.into_iter().next()?;
+            let response = this
+                .update(cx, |this, cx| {
+                    this.select_historic_snapshot(None, cx);
+                    this.reverse_step_in = None;
+                    this.active_snapshot.thread_states.process_step(thread_id);
+                    this.request(
+                        GotoCommand {
+                            thread_id: thread_id.0,
+                            target_id: target.id,
+                        },
+                        Self::on_step_response::<GotoCommand>(thread_id),
+                        cx,
+                    )
+                })
+                .ok()?;
+            response.await.map(|_| ())
+        })
+    }
+
     pub fn stack_frames(
         &mut self,
         thread_id: ThreadId,
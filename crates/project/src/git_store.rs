@@ -30,20 +30,24 @@ use futures::{
 };
 use git::{
     BuildPermalinkParams, GitHostingProviderRegistry, Oid, RunHook,
+    bisect::{BisectState, BisectVerdict},
     blame::Blame,
+    line_history::{LineHistory, LineHistoryEntry},
     parse_git_remote_url,
+    rebase::{RebaseAction, RebaseOperation, RebaseState, RebaseTodoEntry},
     repository::{
-        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, CreateWorktreeTarget,
-        DiffType, FetchOptions, GitCommitTemplate, GitRepository, GitRepositoryCheckpoint,
-        GraphCommitData, InitialGraphCommitData, LogOrder, LogSource, PushOptions, Remote,
-        RemoteCommandOutput, RepoPath, ResetMode, SearchCommitArgs, UpstreamTrackingStatus,
-        Worktree as GitWorktree,
+        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, ConflictStages,
+        CreateWorktreeTarget, DiffType, FetchOptions, GitCommitTemplate, GitRepository,
+        GitRepositoryCheckpoint, GraphCommitData, InitialGraphCommitData, LogOrder, LogSource,
+        PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode, SearchCommitArgs,
+        UpstreamTrackingStatus, Worktree as GitWorktree,
     },
     stash::{GitStash, StashEntry},
     status::{
         self, DiffStat, DiffTreeType, FileStatus, GitSummary, StatusCode, TrackedStatus, TreeDiff,
         TreeDiffStatus, UnmergedStatus, UnmergedStatusCode,
     },
+    submodule::{Submodule, SubmoduleCommit, SubmoduleDiff, SubmoduleOperation, SubmoduleState},
 };
 use gpui::{
     App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
@@ -560,6 +564,21 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_stash_pop);
         client.add_entity_request_handler(Self::handle_stash_apply);
         client.add_entity_request_handler(Self::handle_stash_drop);
+        client.add_entity_request_handler(Self::handle_rebase_todo);
+        client.add_entity_request_handler(Self::handle_rebase_interactive);
+        client.add_entity_request_handler(Self::handle_rebase_operation);
+        client.add_entity_request_handler(Self::handle_get_rebase_state);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_bisect_start);
+        client.add_entity_request_handler(Self::handle_bisect_mark);
+        client.add_entity_request_handler(Self::handle_bisect_reset);
+        client.add_entity_request_handler(Self::handle_get_bisect_state);
+        client.add_entity_request_handler(Self::handle_get_submodules);
+        client.add_entity_request_handler(Self::handle_submodule_operation);
+        client.add_entity_request_handler(Self::handle_submodule_diff);
         client.add_entity_request_handler(Self::handle_commit);
         client.add_entity_request_handler(Self::handle_run_hook);
         client.add_entity_request_handler(Self::handle_reset);
@@ -572,6 +591,8 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_diff_checkpoints);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_file_history);
+        client.add_entity_request_handler(Self::handle_line_history);
+        client.add_entity_request_handler(Self::handle_load_conflict_stages);
         client.add_entity_request_handler(Self::handle_checkout_files);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
         client.add_entity_request_handler(Self::handle_set_index_text);
@@ -1277,6 +1298,18 @@ impl GitStore {
         cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
     }
 
+    pub fn line_history(
+        &self,
+        repo: &Entity<Repository>,
+        path: RepoPath,
+        rows: Range<u32>,
+        cx: &mut App,
+    ) -> Task<Result<LineHistory>> {
+        let rx = repo.update(cx, |repo, _| repo.line_history(path, rows));
+
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
     pub fn get_permalink_to_line(
         &self,
         buffer: &Entity<Buffer>,
@@ -2314,6 +2347,320 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_rebase_todo(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseTodo>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseTodoResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_todo(envelope.payload.base)
+            })
+            .await??;
+
+        Ok(proto::GitRebaseTodoResponse {
+            entries: entries.iter().map(rebase_todo_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_rebase_interactive(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseInteractive>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let entries = envelope
+            .payload
+            .entries
+            .iter()
+            .map(proto_to_rebase_todo_entry)
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_interactive(envelope.payload.base, entries)
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let operation = match envelope.payload.operation() {
+            proto::git_rebase_operation::Operation::Continue => RebaseOperation::Continue,
+            proto::git_rebase_operation::Operation::Skip => RebaseOperation::Skip,
+            proto::git_rebase_operation::Operation::Abort => RebaseOperation::Abort,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_operation(operation)
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_rebase_state(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetRebaseState>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitGetRebaseStateResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let state = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_state()
+            })
+            .await??;
+
+        Ok(proto::GitGetRebaseStateResponse {
+            state: state.as_ref().map(rebase_state_to_proto),
+        })
+    }
+
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.cherry_pick(envelope.payload.commit)
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.revert(envelope.payload.commit)
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(
+                    envelope.payload.name,
+                    envelope.payload.commit,
+                    envelope.payload.message,
+                )
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(envelope.payload.name)
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_start(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectStart>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_start()
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_mark(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectMark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let verdict = match envelope.payload.verdict() {
+            proto::git_bisect_mark::Verdict::Good => BisectVerdict::Good,
+            proto::git_bisect_mark::Verdict::Bad => BisectVerdict::Bad,
+            proto::git_bisect_mark::Verdict::Skip => BisectVerdict::Skip,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_mark(verdict, envelope.payload.commit)
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_reset()
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_bisect_state(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetBisectState>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitGetBisectStateResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let state = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_state()
+            })
+            .await??;
+
+        Ok(proto::GitGetBisectStateResponse {
+            state: state.as_ref().map(bisect_state_to_proto),
+        })
+    }
+
+    async fn handle_get_submodules(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetSubmodules>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitGetSubmodulesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let submodules = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.submodules()
+            })
+            .await??;
+
+        Ok(proto::GitGetSubmodulesResponse {
+            submodules: submodules.iter().map(submodule_to_proto).collect(),
+        })
+    }
+
+    async fn handle_submodule_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = match envelope.payload.operation() {
+            proto::git_submodule_operation::Operation::Init => SubmoduleOperation::Init,
+            proto::git_submodule_operation::Operation::Update => SubmoduleOperation::Update,
+            proto::git_submodule_operation::Operation::Sync => SubmoduleOperation::Sync,
+        };
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|path| RepoPath::from_proto(path))
+            .collect::<Result<Vec<_>>>()?;
+        let askpass_id = envelope.payload.askpass_id;
+
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.submodule_operation(operation, paths, askpass, cx)
+            })
+            .await??;
+
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
+    async fn handle_submodule_diff(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitSubmoduleDiffResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+
+        let diff = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.submodule_diff(path)
+            })
+            .await??;
+
+        Ok(proto::GitSubmoduleDiffResponse {
+            old_sha: diff.old_sha.map(String::from),
+            new_sha: diff.new_sha.map(String::from),
+            added: diff.added.iter().map(submodule_commit_to_proto).collect(),
+            removed: diff.removed.iter().map(submodule_commit_to_proto).collect(),
+        })
+    }
+
     async fn handle_set_index_text(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::SetIndexText>,
@@ -2609,10 +2956,11 @@ impl GitStore {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
         let branch_name = envelope.payload.branch_name;
+        let base_branch = envelope.payload.base_branch;
 
         repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.create_branch(branch_name, None)
+                repository_handle.create_branch(branch_name, base_branch)
             })
             .await??;
 
@@ -2920,31 +3268,90 @@ impl GitStore {
         })
     }
 
-    async fn handle_reset(
+    async fn handle_line_history(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitReset>,
+        envelope: TypedEnvelope<proto::GitLineHistory>,
         mut cx: AsyncApp,
-    ) -> Result<proto::Ack> {
+    ) -> Result<proto::GitLineHistoryResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+        let rows = envelope.payload.start_row..envelope.payload.end_row;
 
-        let mode = match envelope.payload.mode() {
-            git_reset::ResetMode::Soft => ResetMode::Soft,
-            git_reset::ResetMode::Mixed => ResetMode::Mixed,
-        };
-
-        repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.reset(envelope.payload.commit, mode, cx)
+        let line_history = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.line_history(path, rows)
             })
             .await??;
-        Ok(proto::Ack {})
-    }
 
-    async fn handle_checkout_files(
-        this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
-        mut cx: AsyncApp,
+        Ok(proto::GitLineHistoryResponse {
+            entries: line_history
+                .entries
+                .into_iter()
+                .map(|entry| proto::LineHistoryEntry {
+                    commit: Some(proto::FileHistoryEntry {
+                        sha: entry.commit.sha.to_string(),
+                        subject: entry.commit.subject.to_string(),
+                        message: entry.commit.message.to_string(),
+                        commit_timestamp: entry.commit.commit_timestamp,
+                        author_name: entry.commit.author_name.to_string(),
+                        author_email: entry.commit.author_email.to_string(),
+                    }),
+                    path: entry.path.to_proto(),
+                    hunk: entry.hunk.to_string(),
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_load_conflict_stages(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLoadConflictStages>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLoadConflictStagesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+
+        let stages = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_conflict_stages(path)
+            })
+            .await??;
+
+        Ok(proto::GitLoadConflictStagesResponse {
+            base: stages.base,
+            ours: stages.ours,
+            theirs: stages.theirs,
+        })
+    }
+
+    async fn handle_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let mode = match envelope.payload.mode() {
+            git_reset::ResetMode::Soft => ResetMode::Soft,
+            git_reset::ResetMode::Mixed => ResetMode::Mixed,
+            git_reset::ResetMode::Hard => ResetMode::Hard,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.reset(envelope.payload.commit, mode, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_checkout_files(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
@@ -4212,7 +4619,7 @@ impl Repository {
             RepositoryEvent::StashEntriesChanged => {
                 if this.scan_id > 1 {
                     this.initial_graph_data
-                        .retain(|(log_source, _), _| *log_source != LogSource::All);
+                        .retain(|(log_source, _), _| !log_source.includes_all_refs());
                 }
             }
             _ => {}
@@ -4717,6 +5124,7 @@ impl Repository {
                             mode: match reset_mode {
                                 ResetMode::Soft => git_reset::ResetMode::Soft.into(),
                                 ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
+                                ResetMode::Hard => git_reset::ResetMode::Hard.into(),
                             },
                         })
                         .await?;
@@ -4840,6 +5248,84 @@ impl Repository {
         })
     }
 
+    pub fn line_history(
+        &mut self,
+        path: RepoPath,
+        rows: Range<u32>,
+    ) -> oneshot::Receiver<Result<LineHistory>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.line_history(path, rows).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitLineHistory {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                            start_row: rows.start,
+                            end_row: rows.end,
+                        })
+                        .await?;
+                    let entries = response
+                        .entries
+                        .into_iter()
+                        .map(|entry| {
+                            let commit = entry.commit.context("missing commit")?;
+                            Ok(LineHistoryEntry {
+                                commit: git::repository::FileHistoryEntry {
+                                    sha: commit.sha.into(),
+                                    subject: commit.subject.into(),
+                                    message: commit.message.into(),
+                                    commit_timestamp: commit.commit_timestamp,
+                                    author_name: commit.author_name.into(),
+                                    author_email: commit.author_email.into(),
+                                },
+                                path: RepoPath::from_proto(&entry.path)?,
+                                hunk: entry.hunk.into(),
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(LineHistory {
+                        entries,
+                        path,
+                        rows,
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn load_conflict_stages(
+        &mut self,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<ConflictStages>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.load_conflict_stages(path).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitLoadConflictStages {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                        })
+                        .await?;
+                    Ok(ConflictStages {
+                        base: response.base,
+                        ours: response.ours,
+                        theirs: response.theirs,
+                    })
+                }
+            }
+        })
+    }
+
     pub fn get_graph_data(
         &self,
         log_source: LogSource,
@@ -5414,162 +5900,623 @@ impl Repository {
         self.stash_entries(to_stash, cx)
     }
 
-    pub fn stash_entries(
-        &mut self,
-        entries: Vec<RepoPath>,
-        cx: &mut Context<Self>,
-    ) -> Task<anyhow::Result<()>> {
+    pub fn stash_entries(
+        &mut self,
+        entries: Vec<RepoPath>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(None, move |git_repo, _cx| async move {
+                    match git_repo {
+                        RepositoryState::Local(LocalRepositoryState {
+                            backend,
+                            environment,
+                            ..
+                        }) => backend.stash_paths(entries, environment).await,
+                        RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                            client
+                                .request(proto::Stash {
+                                    project_id: project_id.0,
+                                    repository_id: id.to_proto(),
+                                    paths: entries
+                                        .into_iter()
+                                        .map(|repo_path| repo_path.to_proto())
+                                        .collect(),
+                                })
+                                .await?;
+                            Ok(())
+                        }
+                    }
+                })
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    pub fn stash_pop(
+        &mut self,
+        index: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(None, move |git_repo, _cx| async move {
+                    match git_repo {
+                        RepositoryState::Local(LocalRepositoryState {
+                            backend,
+                            environment,
+                            ..
+                        }) => backend.stash_pop(index, environment).await,
+                        RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                            client
+                                .request(proto::StashPop {
+                                    project_id: project_id.0,
+                                    repository_id: id.to_proto(),
+                                    stash_index: index.map(|i| i as u64),
+                                })
+                                .await
+                                .context("sending stash pop request")?;
+                            Ok(())
+                        }
+                    }
+                })
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    pub fn stash_apply(
+        &mut self,
+        index: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(None, move |git_repo, _cx| async move {
+                    match git_repo {
+                        RepositoryState::Local(LocalRepositoryState {
+                            backend,
+                            environment,
+                            ..
+                        }) => backend.stash_apply(index, environment).await,
+                        RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                            client
+                                .request(proto::StashApply {
+                                    project_id: project_id.0,
+                                    repository_id: id.to_proto(),
+                                    stash_index: index.map(|i| i as u64),
+                                })
+                                .await
+                                .context("sending stash apply request")?;
+                            Ok(())
+                        }
+                    }
+                })
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    pub fn stash_drop(
+        &mut self,
+        index: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<anyhow::Result<()>> {
+        let id = self.id;
+        let updates_tx = self
+            .git_store()
+            .and_then(|git_store| match &git_store.read(cx).state {
+                GitStoreState::Local { downstream, .. } => downstream
+                    .as_ref()
+                    .map(|downstream| downstream.updates_tx.clone()),
+                _ => None,
+            });
+        let this = cx.weak_entity();
+        self.send_job(None, move |git_repo, mut cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => {
+                    // TODO would be nice to not have to do this manually
+                    let result = backend.stash_drop(index, environment).await;
+                    if result.is_ok()
+                        && let Ok(stash_entries) = backend.stash_entries().await
+                    {
+                        let snapshot = this.update(&mut cx, |this, cx| {
+                            this.snapshot.stash_entries = stash_entries;
+                            cx.emit(RepositoryEvent::StashEntriesChanged);
+                            this.snapshot.clone()
+                        })?;
+                        if let Some(updates_tx) = updates_tx {
+                            updates_tx
+                                .unbounded_send(DownstreamUpdate::UpdateRepository(snapshot))
+                                .ok();
+                        }
+                    }
+
+                    result
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::StashDrop {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            stash_index: index.map(|i| i as u64),
+                        })
+                        .await
+                        .context("sending stash pop request")?;
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_todo(&mut self, base: String) -> oneshot::Receiver<Result<Vec<RebaseTodoEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_todo(base).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitRebaseTodo {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                        })
+                        .await?;
+
+                    Ok(response
+                        .entries
+                        .iter()
+                        .map(proto_to_rebase_todo_entry)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_interactive(
+        &mut self,
+        base: String,
+        entries: Vec<RebaseTodoEntry>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git rebase --interactive".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_interactive(base, entries, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseInteractive {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                base,
+                                entries: entries.iter().map(rebase_todo_entry_to_proto).collect(),
+                            })
+                            .await
+                            .context("sending interactive rebase request")?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_operation(
+        &mut self,
+        operation: RebaseOperation,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let job_description = match operation {
+            RebaseOperation::Continue => "git rebase --continue",
+            RebaseOperation::Skip => "git rebase --skip",
+            RebaseOperation::Abort => "git rebase --abort",
+        };
+        self.send_job(
+            Some(job_description.into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_operation(operation, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseOperation {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                operation: match operation {
+                                    RebaseOperation::Continue => {
+                                        proto::git_rebase_operation::Operation::Continue
+                                    }
+                                    RebaseOperation::Skip => {
+                                        proto::git_rebase_operation::Operation::Skip
+                                    }
+                                    RebaseOperation::Abort => {
+                                        proto::git_rebase_operation::Operation::Abort
+                                    }
+                                }
+                                .into(),
+                            })
+                            .await
+                            .context("sending rebase request")?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_state(&mut self) -> oneshot::Receiver<Result<Option<RebaseState>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_state().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetRebaseState {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.state.as_ref().map(proto_to_rebase_state))
+                }
+            }
+        })
+    }
+
+    pub fn cherry_pick(&mut self, commit: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git cherry-pick {commit}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.cherry_pick(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn revert(&mut self, commit: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git revert {commit}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.revert(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRevert {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn create_tag(
+        &mut self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag {name}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.create_tag(name, commit, message, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCreateTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                                commit,
+                                message,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn delete_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag -d {name}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.delete_tag(name, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_start(&mut self) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git bisect start".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_start(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectStart {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_mark(
+        &mut self,
+        verdict: BisectVerdict,
+        commit: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git bisect {}", verdict.as_str()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_mark(verdict, commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectMark {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                verdict: match verdict {
+                                    BisectVerdict::Good => proto::git_bisect_mark::Verdict::Good,
+                                    BisectVerdict::Bad => proto::git_bisect_mark::Verdict::Bad,
+                                    BisectVerdict::Skip => proto::git_bisect_mark::Verdict::Skip,
+                                }
+                                .into(),
+                                commit,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_reset(&mut self) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git bisect reset".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_reset(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectReset {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_state(&mut self) -> oneshot::Receiver<Result<Option<BisectState>>> {
         let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.bisect_state().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetBisectState {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
 
-        cx.spawn(async move |this, cx| {
-            this.update(cx, |this, _| {
-                this.send_job(None, move |git_repo, _cx| async move {
-                    match git_repo {
-                        RepositoryState::Local(LocalRepositoryState {
-                            backend,
-                            environment,
-                            ..
-                        }) => backend.stash_paths(entries, environment).await,
-                        RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                            client
-                                .request(proto::Stash {
-                                    project_id: project_id.0,
-                                    repository_id: id.to_proto(),
-                                    paths: entries
-                                        .into_iter()
-                                        .map(|repo_path| repo_path.to_proto())
-                                        .collect(),
-                                })
-                                .await?;
-                            Ok(())
-                        }
-                    }
-                })
-            })?
-            .await??;
-            Ok(())
+                    Ok(response.state.as_ref().map(proto_to_bisect_state))
+                }
+            }
         })
     }
 
-    pub fn stash_pop(
-        &mut self,
-        index: Option<usize>,
-        cx: &mut Context<Self>,
-    ) -> Task<anyhow::Result<()>> {
+    pub fn submodules(&mut self) -> oneshot::Receiver<Result<Vec<Submodule>>> {
         let id = self.id;
-        cx.spawn(async move |this, cx| {
-            this.update(cx, |this, _| {
-                this.send_job(None, move |git_repo, _cx| async move {
-                    match git_repo {
-                        RepositoryState::Local(LocalRepositoryState {
-                            backend,
-                            environment,
-                            ..
-                        }) => backend.stash_pop(index, environment).await,
-                        RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                            client
-                                .request(proto::StashPop {
-                                    project_id: project_id.0,
-                                    repository_id: id.to_proto(),
-                                    stash_index: index.map(|i| i as u64),
-                                })
-                                .await
-                                .context("sending stash pop request")?;
-                            Ok(())
-                        }
-                    }
-                })
-            })?
-            .await??;
-            Ok(())
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.submodules().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetSubmodules {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    response.submodules.iter().map(proto_to_submodule).collect()
+                }
+            }
         })
     }
 
-    pub fn stash_apply(
+    pub fn submodule_operation(
         &mut self,
-        index: Option<usize>,
-        cx: &mut Context<Self>,
-    ) -> Task<anyhow::Result<()>> {
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
         let id = self.id;
-        cx.spawn(async move |this, cx| {
-            this.update(cx, |this, _| {
-                this.send_job(None, move |git_repo, _cx| async move {
-                    match git_repo {
-                        RepositoryState::Local(LocalRepositoryState {
-                            backend,
-                            environment,
-                            ..
-                        }) => backend.stash_apply(index, environment).await,
-                        RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                            client
-                                .request(proto::StashApply {
-                                    project_id: project_id.0,
-                                    repository_id: id.to_proto(),
-                                    stash_index: index.map(|i| i as u64),
-                                })
-                                .await
-                                .context("sending stash apply request")?;
-                            Ok(())
-                        }
+
+        self.send_job(
+            Some(format!("git {}", operation.name()).into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .submodule_operation(operation, paths, askpass, environment, cx)
+                            .await
                     }
-                })
-            })?
-            .await??;
-            Ok(())
-        })
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+
+                        let operation = match operation {
+                            SubmoduleOperation::Init => {
+                                proto::git_submodule_operation::Operation::Init
+                            }
+                            SubmoduleOperation::Update => {
+                                proto::git_submodule_operation::Operation::Update
+                            }
+                            SubmoduleOperation::Sync => {
+                                proto::git_submodule_operation::Operation::Sync
+                            }
+                        };
+                        let response = client
+                            .request(proto::GitSubmoduleOperation {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                operation: operation.into(),
+                                paths: paths.iter().map(|path| path.to_proto()).collect(),
+                            })
+                            .await?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
     }
 
-    pub fn stash_drop(
-        &mut self,
-        index: Option<usize>,
-        cx: &mut Context<Self>,
-    ) -> oneshot::Receiver<anyhow::Result<()>> {
+    pub fn submodule_diff(&mut self, path: RepoPath) -> oneshot::Receiver<Result<SubmoduleDiff>> {
         let id = self.id;
-        let updates_tx = self
-            .git_store()
-            .and_then(|git_store| match &git_store.read(cx).state {
-                GitStoreState::Local { downstream, .. } => downstream
-                    .as_ref()
-                    .map(|downstream| downstream.updates_tx.clone()),
-                _ => None,
-            });
-        let this = cx.weak_entity();
-        self.send_job(None, move |git_repo, mut cx| async move {
+        self.send_job(None, move |git_repo, _cx| async move {
             match git_repo {
-                RepositoryState::Local(LocalRepositoryState {
-                    backend,
-                    environment,
-                    ..
-                }) => {
-                    // TODO would be nice to not have to do this manually
-                    let result = backend.stash_drop(index, environment).await;
-                    if result.is_ok()
-                        && let Ok(stash_entries) = backend.stash_entries().await
-                    {
-                        let snapshot = this.update(&mut cx, |this, cx| {
-                            this.snapshot.stash_entries = stash_entries;
-                            cx.emit(RepositoryEvent::StashEntriesChanged);
-                            this.snapshot.clone()
-                        })?;
-                        if let Some(updates_tx) = updates_tx {
-                            updates_tx
-                                .unbounded_send(DownstreamUpdate::UpdateRepository(snapshot))
-                                .ok();
-                        }
-                    }
-
-                    result
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.submodule_diff(path).await
                 }
                 RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                    client
-                        .request(proto::StashDrop {
+                    let response = client
+                        .request(proto::GitSubmoduleDiff {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
-                            stash_index: index.map(|i| i as u64),
+                            path: path.to_proto(),
                         })
-                        .await
-                        .context("sending stash pop request")?;
-                    Ok(())
+                        .await?;
+
+                    Ok(SubmoduleDiff {
+                        old_sha: response.old_sha.map(Into::into),
+                        new_sha: response.new_sha.map(Into::into),
+                        added: response
+                            .added
+                            .iter()
+                            .map(proto_to_submodule_commit)
+                            .collect(),
+                        removed: response
+                            .removed
+                            .iter()
+                            .map(proto_to_submodule_commit)
+                            .collect(),
+                    })
                 }
             }
         })
@@ -6592,6 +7539,7 @@ impl Repository {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
                             branch_name,
+                            base_branch,
                         })
                         .await?;
 
@@ -7125,7 +8073,7 @@ impl Repository {
         })
     }
 
-    fn load_blob_content(&mut self, oid: Oid, cx: &App) -> Task<Result<String>> {
+    pub fn load_blob_content(&mut self, oid: Oid, cx: &App) -> Task<Result<String>> {
         let repository_id = self.snapshot.id;
         let rx = self.send_job(None, move |state, _| async move {
             match state {
@@ -7713,6 +8661,131 @@ fn proto_to_branch(proto: &proto::Branch) -> git::repository::Branch {
     }
 }
 
+fn rebase_todo_entry_to_proto(entry: &RebaseTodoEntry) -> proto::RebaseTodoEntry {
+    use proto::rebase_todo_entry::RebaseAction as ProtoAction;
+    proto::RebaseTodoEntry {
+        action: match entry.action {
+            RebaseAction::Pick => ProtoAction::Pick,
+            RebaseAction::Reword => ProtoAction::Reword,
+            RebaseAction::Edit => ProtoAction::Edit,
+            RebaseAction::Squash => ProtoAction::Squash,
+            RebaseAction::Fixup => ProtoAction::Fixup,
+            RebaseAction::Drop => ProtoAction::Drop,
+        }
+        .into(),
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+        message: entry.message.as_ref().map(|message| message.to_string()),
+    }
+}
+
+fn proto_to_rebase_todo_entry(proto: &proto::RebaseTodoEntry) -> RebaseTodoEntry {
+    use proto::rebase_todo_entry::RebaseAction as ProtoAction;
+    RebaseTodoEntry {
+        action: match proto.action() {
+            ProtoAction::Pick => RebaseAction::Pick,
+            ProtoAction::Reword => RebaseAction::Reword,
+            ProtoAction::Edit => RebaseAction::Edit,
+            ProtoAction::Squash => RebaseAction::Squash,
+            ProtoAction::Fixup => RebaseAction::Fixup,
+            ProtoAction::Drop => RebaseAction::Drop,
+        },
+        sha: proto.sha.clone().into(),
+        subject: proto.subject.clone().into(),
+        message: proto.message.clone().map(Into::into),
+    }
+}
+
+fn rebase_state_to_proto(state: &RebaseState) -> proto::RebaseState {
+    proto::RebaseState {
+        head_name: state.head_name.as_ref().map(|name| name.to_string()),
+        onto: state.onto.as_ref().map(|onto| onto.to_string()),
+        stopped_sha: state.stopped_sha.as_ref().map(|sha| sha.to_string()),
+        done: state.done.iter().map(rebase_todo_entry_to_proto).collect(),
+        remaining: state
+            .remaining
+            .iter()
+            .map(rebase_todo_entry_to_proto)
+            .collect(),
+    }
+}
+
+fn proto_to_rebase_state(proto: &proto::RebaseState) -> RebaseState {
+    RebaseState {
+        head_name: proto.head_name.clone().map(Into::into),
+        onto: proto.onto.clone().map(Into::into),
+        stopped_sha: proto.stopped_sha.clone().map(Into::into),
+        done: proto.done.iter().map(proto_to_rebase_todo_entry).collect(),
+        remaining: proto
+            .remaining
+            .iter()
+            .map(proto_to_rebase_todo_entry)
+            .collect(),
+    }
+}
+
+fn bisect_state_to_proto(state: &BisectState) -> proto::BisectState {
+    proto::BisectState {
+        current: state.current.as_ref().map(|sha| sha.to_string()),
+        bad: state.bad.as_ref().map(|sha| sha.to_string()),
+        good: state.good.iter().map(|sha| sha.to_string()).collect(),
+        skipped: state.skipped.iter().map(|sha| sha.to_string()).collect(),
+        remaining: state.remaining.iter().map(|sha| sha.to_string()).collect(),
+    }
+}
+
+fn proto_to_bisect_state(proto: &proto::BisectState) -> BisectState {
+    BisectState {
+        current: proto.current.clone().map(Into::into),
+        bad: proto.bad.clone().map(Into::into),
+        good: proto.good.iter().cloned().map(Into::into).collect(),
+        skipped: proto.skipped.iter().cloned().map(Into::into).collect(),
+        remaining: proto.remaining.iter().cloned().map(Into::into).collect(),
+    }
+}
+
+fn submodule_to_proto(submodule: &Submodule) -> proto::Submodule {
+    let state = match submodule.state {
+        SubmoduleState::Uninitialized => proto::submodule::State::Uninitialized,
+        SubmoduleState::UpToDate => proto::submodule::State::UpToDate,
+        SubmoduleState::Modified => proto::submodule::State::Modified,
+        SubmoduleState::Conflicted => proto::submodule::State::Conflicted,
+    };
+    proto::Submodule {
+        path: submodule.path.to_proto(),
+        sha: submodule.sha.to_string(),
+        state: state.into(),
+    }
+}
+
+fn proto_to_submodule(proto: &proto::Submodule) -> Result<Submodule> {
+    let state = match proto.state() {
+        proto::submodule::State::Uninitialized => SubmoduleState::Uninitialized,
+        proto::submodule::State::UpToDate => SubmoduleState::UpToDate,
+        proto::submodule::State::Modified => SubmoduleState::Modified,
+        proto::submodule::State::Conflicted => SubmoduleState::Conflicted,
+    };
+    Ok(Submodule {
+        path: RepoPath::from_proto(&proto.path)?,
+        sha: proto.sha.clone().into(),
+        state,
+    })
+}
+
+fn submodule_commit_to_proto(commit: &SubmoduleCommit) -> proto::SubmoduleCommit {
+    proto::SubmoduleCommit {
+        sha: commit.sha.to_string(),
+        subject: commit.subject.to_string(),
+    }
+}
+
+fn proto_to_submodule_commit(proto: &proto::SubmoduleCommit) -> SubmoduleCommit {
+    SubmoduleCommit {
+        sha: proto.sha.clone().into(),
+        subject: proto.subject.clone().into(),
+    }
+}
+
 fn commit_details_to_proto(commit: &CommitDetails) -> proto::GitCommitDetails {
     proto::GitCommitDetails {
         sha: commit.sha.to_string(),
@@ -12,6 +12,7 @@
 pub mod clangd_ext;
 mod code_lens;
 mod document_colors;
+mod document_links;
 mod document_symbols;
 mod folding_ranges;
 mod inlay_hints;
@@ -24,6 +25,7 @@ pub mod vue_language_server_ext;
 
 use self::code_lens::CodeLensData;
 use self::document_colors::DocumentColorData;
+use self::document_links::DocumentLinkData;
 use self::document_symbols::DocumentSymbolsData;
 use self::inlay_hints::BufferInlayHints;
 use crate::{
@@ -144,6 +146,7 @@ use util::{
 };
 
 pub use document_colors::DocumentColors;
+pub use document_links::LspDocumentLink;
 pub use folding_ranges::LspFoldingRange;
 pub use fs::*;
 pub use language::Location;
@@ -283,6 +286,7 @@ pub struct DocumentDiagnostics {
 struct DynamicRegistrations {
     did_change_watched_files: HashMap<String, Vec<FileSystemWatcher>>,
     diagnostics: HashMap<Option<String>, DiagnosticServerCapabilities>,
+    inline_completions: HashMap<String, Option<lsp::DocumentSelector>>,
 }
 
 pub struct LocalLspStore {
@@ -3975,6 +3979,7 @@ pub struct BufferLspData {
     code_lens: Option<CodeLensData>,
     semantic_tokens: Option<SemanticTokensData>,
     folding_ranges: Option<FoldingRangeData>,
+    document_links: Option<DocumentLinkData>,
     document_symbols: Option<DocumentSymbolsData>,
     inlay_hints: BufferInlayHints,
     lsp_requests: HashMap<LspKey, HashMap<LspRequestId, Task<()>>>,
@@ -3995,6 +4000,7 @@ impl BufferLspData {
             code_lens: None,
             semantic_tokens: None,
             folding_ranges: None,
+            document_links: None,
             document_symbols: None,
             inlay_hints: BufferInlayHints::new(buffer, cx),
             lsp_requests: HashMap::default(),
@@ -4021,6 +4027,10 @@ impl BufferLspData {
             folding_ranges.ranges.remove(&for_server);
         }
 
+        if let Some(document_links) = &mut self.document_links {
+            document_links.links.remove(&for_server);
+        }
+
         if let Some(document_symbols) = &mut self.document_symbols {
             document_symbols.remove_server_data(for_server);
         }
@@ -4137,6 +4147,7 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_apply_code_action);
         client.add_entity_request_handler(Self::handle_get_project_symbols);
         client.add_entity_request_handler(Self::handle_resolve_inlay_hint);
+        client.add_entity_request_handler(Self::handle_resolve_document_link);
         client.add_entity_request_handler(Self::handle_get_color_presentation);
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
@@ -4150,6 +4161,13 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_get_completions);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentHighlights>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentSymbols>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSubtypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSelectionRanges>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
@@ -8298,6 +8316,47 @@ impl LspStore {
             )
     }
 
+    /// Returns the first running language server for the buffer that provides inline
+    /// completions, honoring the document selectors of dynamic registrations.
+    pub fn inline_completion_server_for_local_buffer(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+    ) -> Option<Arc<LanguageServer>> {
+        let local = self.as_local()?;
+        let language = buffer.language().map(|language| language.name());
+        let abs_path = File::from_dyn(buffer.file()).map(|file| file.abs_path(cx));
+        self.running_language_servers_for_local_buffer(buffer, cx)
+            .find(|(adapter, server)| {
+                if !server.supports_inline_completions() {
+                    return false;
+                }
+                let Some(registrations) = local
+                    .language_server_dynamic_registrations
+                    .get(&server.server_id())
+                    .filter(|registrations| !registrations.inline_completions.is_empty())
+                else {
+                    // The capability was advertised statically and applies to every buffer.
+                    return true;
+                };
+                let language_id = language.as_ref().map(|name| adapter.language_id(name));
+                registrations
+                    .inline_completions
+                    .values()
+                    .any(|selector| match selector {
+                        None => true,
+                        Some(filters) => filters.iter().any(|filter| {
+                            document_filter_matches(
+                                filter,
+                                language_id.as_deref(),
+                                abs_path.as_deref(),
+                            )
+                        }),
+                    })
+            })
+            .map(|(_, server)| server.clone())
+    }
+
     pub fn language_servers_for_local_buffer(
         &self,
         buffer: &Buffer,
@@ -8511,6 +8570,42 @@ impl LspStore {
         Ok(())
     }
 
+    /// Replaces the diagnostics of the given server in each of the files, making each diagnostic its own group.
+    /// Used for the diagnostics that don't come from language servers, e.g. the problems found in the output of tasks.
+    pub fn replace_diagnostics(
+        &mut self,
+        server_id: LanguageServerId,
+        diagnostics_by_path: Vec<(PathBuf, Vec<DiagnosticEntry<Unclipped<PointUtf16>>>)>,
+        cx: &mut Context<Self>,
+    ) -> anyhow::Result<()> {
+        let worktree_store = self.worktree_store.read(cx);
+        let local = self
+            .as_local_mut()
+            .context("cannot replace diagnostics on a remote LspStore")?;
+        let updates = diagnostics_by_path
+            .into_iter()
+            .filter(|(abs_path, _)| worktree_store.find_worktree(abs_path, cx).is_some())
+            .map(|(abs_path, mut diagnostics)| {
+                for entry in &mut diagnostics {
+                    entry.diagnostic.group_id = post_inc(&mut local.next_diagnostic_group_id);
+                    entry.diagnostic.is_primary = true;
+                }
+                DocumentDiagnosticsUpdate {
+                    diagnostics: DocumentDiagnostics {
+                        diagnostics,
+                        document_abs_path: abs_path,
+                        version: None,
+                    },
+                    result_id: None,
+                    server_id,
+                    disk_based_sources: Cow::Borrowed(&[]),
+                    registration_id: None,
+                }
+            })
+            .collect();
+        self.merge_diagnostic_entries(updates, |_, _, _| false, cx)
+    }
+
     pub fn merge_diagnostic_entries<'a>(
         &mut self,
         diagnostic_updates: Vec<DocumentDiagnosticsUpdate<'a, DocumentDiagnostics>>,
@@ -9041,14 +9136,13 @@ impl LspStore {
             cx.clone(),
         )
         .await?;
+        let server_to_query = match request.server_id() {
+            Some(server_id) => LanguageServerToQuery::Other(server_id),
+            None => LanguageServerToQuery::FirstCapable,
+        };
         let response = this
             .update(&mut cx, |this, cx| {
-                this.request_lsp(
-                    buffer_handle.clone(),
-                    LanguageServerToQuery::FirstCapable,
-                    request,
-                    cx,
-                )
+                this.request_lsp(buffer_handle.clone(), server_to_query, request, cx)
             })
             .await?;
         this.update(&mut cx, |this, cx| {
@@ -9110,6 +9204,18 @@ impl LspStore {
                 )
                 .await?;
             }
+            Request::GetDocumentLinks(get_document_links) => {
+                Self::query_lsp_locally::<GetDocumentLinks>(
+                    lsp_store,
+                    server_id,
+                    sender_id,
+                    lsp_request_id,
+                    get_document_links,
+                    None,
+                    &mut cx,
+                )
+                .await?;
+            }
             Request::GetDocumentSymbols(get_document_symbols) => {
                 Self::query_lsp_locally::<GetDocumentSymbols>(
                     lsp_store,
@@ -12849,6 +12955,22 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/inlineCompletion" => {
+                    let document_selector = reg
+                        .register_options
+                        .map(serde_json::from_value::<lsp::TextDocumentRegistrationOptions>)
+                        .transpose()?
+                        .and_then(|options| options.document_selector);
+                    if let Some(local) = self.as_local_mut() {
+                        local
+                            .language_server_dynamic_registrations
+                            .entry(server_id)
+                            .or_default()
+                            .inline_completions
+                            .insert(reg.id.clone(), document_selector);
+                    }
+                    server.set_inline_completion_provider(true);
+                }
                 _ => log::warn!("unhandled capability registration: {reg:?}"),
             }
         }
@@ -13052,6 +13174,22 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/inlineCompletion" => {
+                    let removed_last_inline_completion_provider = self
+                        .as_local_mut()
+                        .and_then(|local| {
+                            local
+                                .language_server_dynamic_registrations
+                                .get_mut(&server_id)
+                        })
+                        .is_none_or(|registrations| {
+                            registrations.inline_completions.remove(&unreg.id);
+                            registrations.inline_completions.is_empty()
+                        });
+                    if removed_last_inline_completion_provider {
+                        server.set_inline_completion_provider(false);
+                    }
+                }
                 _ => log::warn!("unhandled capability unregistration: {unreg:?}"),
             }
         }
@@ -13370,6 +13508,37 @@ fn parse_register_capabilities<T: serde::de::DeserializeOwned>(
     })
 }
 
+/// Whether a buffer with the given LSP language id and path is selected by `filter`.
+/// Only `file` documents are ever sent to language servers.
+fn document_filter_matches(
+    filter: &lsp::DocumentFilter,
+    language_id: Option<&str>,
+    abs_path: Option<&Path>,
+) -> bool {
+    if filter
+        .language
+        .as_deref()
+        .is_some_and(|language| Some(language) != language_id)
+    {
+        return false;
+    }
+    if filter
+        .scheme
+        .as_deref()
+        .is_some_and(|scheme| scheme != "file")
+    {
+        return false;
+    }
+    match filter.pattern.as_deref() {
+        Some(pattern) => abs_path.is_some_and(|abs_path| {
+            Glob::new(pattern)
+                .map(|glob| glob.compile_matcher().is_match(abs_path))
+                .unwrap_or(false)
+        }),
+        None => true,
+    }
+}
+
 fn server_capabilities_support_range_formatting(capabilities: &lsp::ServerCapabilities) -> bool {
     matches!(
         capabilities.document_range_formatting_provider.as_ref(),
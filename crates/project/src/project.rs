@@ -158,7 +158,7 @@ pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use prettier::RANGE_FORMAT_SUFFIX as TEST_PRETTIER_RANGE_FORMAT_SUFFIX;
 pub use task_inventory::{
     BasicContextProvider, ContextProviderWithTasks, DebugScenarioContext, Inventory, TaskContexts,
-    TaskSourceKind,
+    TaskDependency, TaskSourceKind,
 };
 
 pub use buffer_store::ProjectTransaction;
@@ -858,6 +858,47 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+/// A function, method or constructor that a call hierarchy is built around.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The whole symbol, including its body.
+    pub location: Location,
+    /// The part of the symbol to reveal when it is selected, usually its name.
+    pub selection_range: Range<language::Anchor>,
+    /// The item as sent by the language server, passed back to it when querying the calls.
+    pub lsp_item: lsp::CallHierarchyItem,
+    /// The language server that returned the item, and so the one to query for its calls.
+    pub server_id: LanguageServerId,
+}
+
+/// An edge of a call hierarchy.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    /// The caller for incoming calls, the callee for outgoing ones.
+    pub item: CallHierarchyItem,
+    /// The ranges of the caller where the calls are made.
+    pub call_sites: Vec<Location>,
+}
+
+/// A class, interface, trait or other type that a type hierarchy is built around.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The whole type definition.
+    pub location: Location,
+    /// The part of the type to reveal when it is selected, usually its name.
+    pub selection_range: Range<language::Anchor>,
+    /// The item as sent by the language server, passed back to it when querying related types.
+    pub lsp_item: lsp::TypeHierarchyItem,
+    /// The language server that returned the item, and so the one to query for related types.
+    pub server_id: LanguageServerId,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -892,6 +933,7 @@ enum EntitySubscription {
     LspStore(PendingEntitySubscription<LspStore>),
     SettingsObserver(PendingEntitySubscription<SettingsObserver>),
     DapStore(PendingEntitySubscription<DapStore>),
+    BookmarkStore(PendingEntitySubscription<BookmarkStore>),
     BreakpointStore(PendingEntitySubscription<BreakpointStore>),
 }
 
@@ -1147,6 +1189,7 @@ impl Project {
         TaskStore::init(Some(&client));
         ToolchainStore::init(&client);
         DapStore::init(&client, cx);
+        BookmarkStore::init(&client);
         BreakpointStore::init(&client);
         context_server_store::init(cx);
     }
@@ -1209,7 +1252,7 @@ impl Project {
                 .detach();
 
             let bookmark_store =
-                cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
+                cx.new(|_| BookmarkStore::local(worktree_store.clone(), buffer_store.clone()));
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::local(worktree_store.clone(), buffer_store.clone()));
@@ -1338,6 +1381,7 @@ impl Project {
                 git_diff_debouncer: DebouncedDelay::new(),
                 terminals: Terminals {
                     local_handles: Vec::new(),
+                    task_problems: HashMap::default(),
                 },
                 node: Some(node),
                 search_history: Self::new_search_history(),
@@ -1463,8 +1507,14 @@ impl Project {
             });
             cx.subscribe(&lsp_store, Self::on_lsp_store_event).detach();
 
-            let bookmark_store =
-                cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
+            let bookmark_store = cx.new(|_| {
+                BookmarkStore::remote(
+                    REMOTE_SERVER_PROJECT_ID,
+                    remote_proto.clone(),
+                    buffer_store.clone(),
+                    worktree_store.clone(),
+                )
+            });
 
             let breakpoint_store = cx.new(|_| {
                 BreakpointStore::remote(
@@ -1581,6 +1631,7 @@ impl Project {
                 git_diff_debouncer: DebouncedDelay::new(),
                 terminals: Terminals {
                     local_handles: Vec::new(),
+                    task_problems: HashMap::default(),
                 },
                 node: Some(node),
                 search_history: Self::new_search_history(),
@@ -1602,6 +1653,7 @@ impl Project {
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.worktree_store);
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.lsp_store);
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.dap_store);
+            remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.bookmark_store);
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.breakpoint_store);
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.settings_observer);
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.git_store);
@@ -1628,6 +1680,7 @@ impl Project {
             TaskStore::init(Some(&remote_proto));
             ToolchainStore::init(&remote_proto);
             DapStore::init(&remote_proto, cx);
+            BookmarkStore::init(&remote_proto);
             BreakpointStore::init(&remote_proto);
             GitStore::init(&remote_proto);
             AgentServerStore::init_remote(&remote_proto);
@@ -1658,6 +1711,9 @@ impl Project {
                 client.subscribe_to_entity::<SettingsObserver>(remote_id)?,
             ),
             EntitySubscription::DapStore(client.subscribe_to_entity::<DapStore>(remote_id)?),
+            EntitySubscription::BookmarkStore(
+                client.subscribe_to_entity::<BookmarkStore>(remote_id)?,
+            ),
             EntitySubscription::BreakpointStore(
                 client.subscribe_to_entity::<BreakpointStore>(remote_id)?,
             ),
@@ -1689,7 +1745,7 @@ impl Project {
 
     async fn from_join_project_response(
         response: TypedEnvelope<proto::JoinProjectResponse>,
-        subscriptions: [EntitySubscription; 8],
+        subscriptions: [EntitySubscription; 9],
         client: Arc<Client>,
         run_tasks: bool,
         user_store: Entity<UserStore>,
@@ -1725,8 +1781,14 @@ impl Project {
         let environment =
             cx.new(|cx| ProjectEnvironment::new(None, worktree_store.downgrade(), None, true, cx));
 
-        let bookmark_store =
-            cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
+        let bookmark_store = cx.new(|_| {
+            BookmarkStore::remote(
+                remote_id,
+                client.clone().into(),
+                buffer_store.clone(),
+                worktree_store.clone(),
+            )
+        });
 
         let breakpoint_store = cx.new(|_| {
             BreakpointStore::remote(
@@ -1870,6 +1932,7 @@ impl Project {
                 git_diff_debouncer: DebouncedDelay::new(),
                 terminals: Terminals {
                     local_handles: Vec::new(),
+                    task_problems: HashMap::default(),
                 },
                 node: None,
                 search_history: Self::new_search_history(),
@@ -1921,6 +1984,9 @@ impl Project {
                 EntitySubscription::DapStore(subscription) => {
                     subscription.set_entity(&dap_store, &cx)
                 }
+                EntitySubscription::BookmarkStore(subscription) => {
+                    subscription.set_entity(&bookmark_store, &cx)
+                }
                 EntitySubscription::BreakpointStore(subscription) => {
                     subscription.set_entity(&breakpoint_store, &cx)
                 }
@@ -2729,6 +2795,9 @@ impl Project {
             self.collab_client
                 .subscribe_to_entity(project_id)?
                 .set_entity(&self.dap_store, &cx.to_async()),
+            self.collab_client
+                .subscribe_to_entity(project_id)?
+                .set_entity(&self.bookmark_store, &cx.to_async()),
             self.collab_client
                 .subscribe_to_entity(project_id)?
                 .set_entity(&self.breakpoint_store, &cx.to_async()),
@@ -2746,6 +2815,9 @@ impl Project {
         self.lsp_store.update(cx, |lsp_store, cx| {
             lsp_store.shared(project_id, self.collab_client.clone().into(), cx)
         });
+        self.bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.shared(project_id, self.collab_client.clone().into(), cx)
+        });
         self.breakpoint_store.update(cx, |breakpoint_store, _| {
             breakpoint_store.shared(project_id, self.collab_client.clone().into())
         });
@@ -2851,6 +2923,9 @@ impl Project {
             self.task_store.update(cx, |task_store, cx| {
                 task_store.unshared(cx);
             });
+            self.bookmark_store.update(cx, |bookmark_store, cx| {
+                bookmark_store.unshared(cx);
+            });
             self.breakpoint_store.update(cx, |breakpoint_store, cx| {
                 breakpoint_store.unshared(cx);
             });
@@ -4279,6 +4354,124 @@ impl Project {
         )
     }
 
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Lists the calls made to the item, grouped by caller.
+    pub fn incoming_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetIncomingCalls {
+                item: item.lsp_item.clone(),
+                server_id: item.server_id,
+            },
+            cx,
+        )
+    }
+
+    /// Lists the calls made by the item, grouped by callee.
+    pub fn outgoing_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetOutgoingCalls {
+                item: item.lsp_item.clone(),
+                server_id: item.server_id,
+            },
+            cx,
+        )
+    }
+
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Lists the types the item directly extends or implements.
+    pub fn supertypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetSupertypes {
+                item: item.lsp_item.clone(),
+                server_id: item.server_id,
+            },
+            cx,
+        )
+    }
+
+    /// Lists the types that directly extend or implement the item.
+    pub fn subtypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetSubtypes {
+                item: item.lsp_item.clone(),
+                server_id: item.server_id,
+            },
+            cx,
+        )
+    }
+
+    /// Returns, for each position, the ranges a language server would expand a selection at
+    /// that position to, from the innermost to the outermost.
+    pub fn selection_ranges<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<T>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<Anchor>>>>> {
+        let positions = positions
+            .into_iter()
+            .map(|position| position.to_point_utf16(buffer.read(cx)))
+            .collect();
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSelectionRanges { positions },
+            cx,
+        )
+    }
+
     pub fn symbols(&self, query: &str, cx: &mut Context<Self>) -> Task<Result<Vec<Symbol>>> {
         self.lsp_store
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
@@ -5150,6 +5343,10 @@ impl Project {
             this.buffer_store.update(cx, |buffer_store, _| {
                 buffer_store.forget_shared_buffers_for(&collaborator.peer_id);
             });
+            this.bookmark_store.update(cx, |bookmark_store, cx| {
+                bookmark_store.add_collaborator(collaborator.peer_id, collaborator.user_id);
+                bookmark_store.broadcast(cx);
+            });
             this.breakpoint_store.read(cx).broadcast();
             cx.emit(Event::CollaboratorJoined(collaborator.peer_id));
             this.collaborators
@@ -5184,6 +5381,9 @@ impl Project {
             this.buffer_store.update(cx, |buffer_store, _| {
                 buffer_store.update_peer_id(&old_peer_id, new_peer_id)
             });
+            this.bookmark_store.update(cx, |bookmark_store, _| {
+                bookmark_store.update_collaborator_peer_id(&old_peer_id, new_peer_id)
+            });
 
             if is_host {
                 this.buffer_store
@@ -5222,6 +5422,9 @@ impl Project {
             this.git_store.update(cx, |git_store, _| {
                 git_store.forget_shared_diffs_for(&peer_id);
             });
+            this.bookmark_store.update(cx, |bookmark_store, _| {
+                bookmark_store.remove_collaborator(&peer_id);
+            });
 
             cx.emit(Event::CollaboratorLeft(peer_id));
             Ok(())
@@ -6035,6 +6238,28 @@ impl Project {
             .any(|capabilities| capabilities.semantic_tokens_provider.is_some())
     }
 
+    pub fn any_language_server_supports_selection_ranges(&self, buffer: &Buffer, cx: &App) -> bool {
+        let Some(language) = buffer.language().cloned() else {
+            return false;
+        };
+        let lsp_store = self.lsp_store.read(cx);
+        let relevant_language_servers = lsp_store
+            .languages
+            .lsp_adapters(&language.name())
+            .into_iter()
+            .map(|lsp_adapter| lsp_adapter.name())
+            .collect::<HashSet<_>>();
+        lsp_store
+            .language_server_statuses()
+            .filter_map(|(server_id, server_status)| {
+                relevant_language_servers
+                    .contains(&server_status.name)
+                    .then_some(server_id)
+            })
+            .filter_map(|server_id| lsp_store.lsp_server_capabilities.get(&server_id))
+            .any(GetSelectionRanges::check_server_capabilities)
+    }
+
     pub fn language_server_id_for_name(
         &self,
         buffer: &Buffer,
@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Context as _, Result, bail};
 use collections::{HashMap, HashSet, VecDeque};
 use dap::DapRegistry;
 use gpui::{App, AppContext as _, Context, Entity, SharedString, Task, WeakEntity};
@@ -165,6 +165,14 @@ pub enum TaskSourceKind {
     },
 }
 
+/// A task to run before another one, along with the tasks it depends on itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskDependency {
+    pub source_kind: TaskSourceKind,
+    pub template: TaskTemplate,
+    pub dependencies: Vec<TaskDependency>,
+}
+
 /// A collection of task contexts, derived from the current state of the workspace.
 /// Only contains worktrees that are visible and with their root being a directory.
 #[derive(Debug, Default)]
@@ -657,6 +665,22 @@ impl Inventory {
             .collect()
     }
 
+    /// Resolves the labels from [`TaskTemplate::depends_on`] into the worktree and global task templates they refer to, recursively.
+    /// Worktree tasks take precedence over global tasks with the same label.
+    /// Fails if a dependency is not found, or if the dependencies form a cycle.
+    pub fn task_dependencies(
+        &self,
+        template: &TaskTemplate,
+        worktree: Option<WorktreeId>,
+    ) -> Result<Vec<TaskDependency>> {
+        let templates = worktree
+            .into_iter()
+            .flat_map(|worktree| self.worktree_templates_from_settings(worktree))
+            .chain(self.global_templates_from_settings())
+            .collect::<Vec<_>>();
+        resolve_task_dependencies(template, &templates, &mut vec![template.label.clone()])
+    }
+
     fn global_templates_from_settings(
         &self,
     ) -> impl '_ + Iterator<Item = (TaskSourceKind, TaskTemplate)> {
@@ -885,6 +909,47 @@ impl Inventory {
     }
 }
 
+fn resolve_task_dependencies(
+    template: &TaskTemplate,
+    templates: &[(TaskSourceKind, TaskTemplate)],
+    dependents: &mut Vec<String>,
+) -> Result<Vec<TaskDependency>> {
+    template
+        .depends_on
+        .iter()
+        .map(|label| {
+            if let Some(cycle_start) = dependents.iter().position(|dependent| dependent == label) {
+                bail!(
+                    "Task dependency cycle: {}",
+                    dependents[cycle_start..]
+                        .iter()
+                        .chain(Some(label))
+                        .join(" -> ")
+                );
+            }
+            let (source_kind, dependency) = templates
+                .iter()
+                .find(|(_, candidate)| &candidate.label == label)
+                .with_context(|| {
+                    format!(
+                        "Task `{}` depends on unknown task `{label}`",
+                        template.label
+                    )
+                })?;
+
+            dependents.push(label.clone());
+            let dependencies = resolve_task_dependencies(dependency, templates, dependents);
+            dependents.pop();
+
+            Ok(TaskDependency {
+                source_kind: source_kind.clone(),
+                template: dependency.clone(),
+                dependencies: dependencies?,
+            })
+        })
+        .collect()
+}
+
 fn task_lru_comparator(
     (kind_a, task_a, lru_score_a): &(TaskSourceKind, ResolvedTask, u32),
     (kind_b, task_b, lru_score_b): &(TaskSourceKind, ResolvedTask, u32),
@@ -1,10 +1,11 @@
 use anyhow::Result;
-use collections::HashMap;
-use gpui::{App, AppContext as _, Context, Entity, Task, WeakEntity};
+use collections::{HashMap, HashSet};
+use gpui::{App, AppContext as _, Context, Entity, Subscription, Task, WeakEntity};
 
 use futures::{FutureExt, future::Shared};
 use itertools::Itertools as _;
-use language::LanguageName;
+use language::{Diagnostic, DiagnosticEntry, LanguageName, PointUtf16, Unclipped};
+use lsp::{DiagnosticSeverity, LanguageServerId, NumberOrString};
 use remote::RemoteClient;
 use settings::{Settings, SettingsLocation};
 use smol::channel::bounded;
@@ -12,17 +13,34 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use task::{Shell, ShellBuilder, ShellKind, SpawnInTerminal};
+use task::{
+    ProblemCollector, ProblemSeverity, Shell, ShellBuilder, ShellKind, SpawnInTerminal, TaskId,
+};
 use terminal::{
-    TaskState, TaskStatus, Terminal, TerminalBuilder, insert_zed_terminal_env,
-    terminal_settings::TerminalSettings,
+    TaskState, TaskStatus, Terminal, TerminalBuilder, TerminalOutputReader,
+    insert_zed_terminal_env, terminal_settings::TerminalSettings,
+};
+use util::{
+    ResultExt as _, command::new_std_command, get_default_system_shell, maybe, rel_path::RelPath,
 };
-use util::{command::new_std_command, get_default_system_shell, maybe, rel_path::RelPath};
 
 use crate::{Project, ProjectPath};
 
 pub struct Terminals {
     pub(crate) local_handles: Vec<WeakEntity<terminal::Terminal>>,
+    /// Problems found in the output of the tasks with problem matchers, by task.
+    pub(crate) task_problems: HashMap<TaskId, TaskProblems>,
+}
+
+/// Problems found by the problem matchers of a task in the output of its last run, reported as diagnostics.
+pub(crate) struct TaskProblems {
+    /// A language server id that no language server uses, to report the diagnostics of all runs of the task with.
+    server_id: LanguageServerId,
+    collector: ProblemCollector,
+    output_reader: TerminalOutputReader,
+    /// Files that have diagnostics reported, to clear them with the next report.
+    reported_paths: HashSet<PathBuf>,
+    _subscription: Subscription,
 }
 
 impl Project {
@@ -92,6 +110,7 @@ impl Project {
         let (completion_tx, completion_rx) = bounded(1);
 
         let local_path = if is_via_remote { None } else { path.clone() };
+        let problems_cwd = local_path.clone();
         let task_state = Some(TaskState {
             spawned_task: spawn_task.clone(),
             status: TaskStatus::Running,
@@ -265,6 +284,7 @@ impl Project {
                 this.terminals
                     .local_handles
                     .push(terminal_handle.downgrade());
+                this.match_task_problems(&terminal_handle, problems_cwd.as_deref(), cx);
 
                 let id = terminal_handle.entity_id();
                 cx.observe_release(&terminal_handle, move |project, _terminal, cx| {
@@ -285,6 +305,139 @@ impl Project {
         })
     }
 
+    /// Runs the problem matchers of the task in the terminal over its output as it arrives,
+    /// reporting the problems found as diagnostics, in place of the ones of the previous run of the task.
+    fn match_task_problems(
+        &mut self,
+        terminal: &Entity<Terminal>,
+        cwd: Option<&Path>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(spawned_task) = terminal.read(cx).task().map(|task| &task.spawned_task) else {
+            return;
+        };
+        if spawned_task.problem_matchers.is_empty() {
+            return;
+        }
+        // Diagnostics are stored on the host, which doesn't see the output of the tasks run by its guests.
+        if !self.is_local() {
+            log::warn!(
+                "Problem matchers of task {:?} are only supported in local projects",
+                spawned_task.label
+            );
+            return;
+        }
+        let collector = match ProblemCollector::new(&spawned_task.problem_matchers, cwd) {
+            Ok(collector) => collector,
+            Err(error) => {
+                log::error!(
+                    "Invalid problem matchers of task {:?}: {error:#}",
+                    spawned_task.label
+                );
+                return;
+            }
+        };
+
+        let task_id = spawned_task.id.clone();
+        let (server_id, reported_paths) = match self.terminals.task_problems.remove(&task_id) {
+            Some(previous_run) => (previous_run.server_id, previous_run.reported_paths),
+            None => (self.languages.next_language_server_id(), HashSet::default()),
+        };
+        let subscription = cx.subscribe(terminal, {
+            let task_id = task_id.clone();
+            move |project, terminal, event, cx| {
+                if *event == terminal::Event::Wakeup {
+                    project.read_task_output(&task_id, &terminal, cx);
+                }
+            }
+        });
+        self.terminals.task_problems.insert(
+            task_id.clone(),
+            TaskProblems {
+                server_id,
+                collector,
+                output_reader: TerminalOutputReader::default(),
+                reported_paths,
+                _subscription: subscription,
+            },
+        );
+        self.report_task_problems(&task_id, cx);
+    }
+
+    fn read_task_output(
+        &mut self,
+        task_id: &TaskId,
+        terminal: &Entity<Terminal>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(task_problems) = self.terminals.task_problems.get_mut(task_id) else {
+            return;
+        };
+        let terminal = terminal.read(cx);
+        let task_finished = terminal
+            .task()
+            .is_none_or(|task| task.status != TaskStatus::Running);
+        let mut changed = false;
+        for line in task_problems
+            .output_reader
+            .read_lines(terminal, task_finished)
+        {
+            changed |= task_problems.collector.push_line(&line);
+        }
+        if changed {
+            self.report_task_problems(task_id, cx);
+        }
+    }
+
+    fn report_task_problems(&mut self, task_id: &TaskId, cx: &mut Context<Self>) {
+        let Some(task_problems) = self.terminals.task_problems.get_mut(task_id) else {
+            return;
+        };
+        let mut diagnostics_by_path = task_problems
+            .reported_paths
+            .drain()
+            .map(|path| (path, Vec::new()))
+            .collect::<HashMap<_, Vec<_>>>();
+        for problem in task_problems.collector.problems() {
+            let start = PointUtf16::new(problem.start.row, problem.start.column);
+            let end = problem
+                .end
+                .map_or(start, |end| PointUtf16::new(end.row, end.column));
+            diagnostics_by_path
+                .entry(problem.path.clone())
+                .or_default()
+                .push(DiagnosticEntry {
+                    range: Unclipped(start)..Unclipped(end),
+                    diagnostic: Diagnostic {
+                        source: problem.source.clone(),
+                        code: problem.code.clone().map(NumberOrString::String),
+                        severity: match problem.severity {
+                            ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                            ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                            ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+                            ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+                        },
+                        message: problem.message.clone(),
+                        // The tools report the problems of the files on disk, without the unsaved edits.
+                        is_disk_based: true,
+                        ..Diagnostic::default()
+                    },
+                });
+        }
+        task_problems.reported_paths = diagnostics_by_path
+            .iter()
+            .filter(|(_, diagnostics)| !diagnostics.is_empty())
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        let server_id = task_problems.server_id;
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store
+                .replace_diagnostics(server_id, diagnostics_by_path.into_iter().collect(), cx)
+                .log_err();
+        });
+    }
+
     pub fn create_terminal_shell(
         &mut self,
         cwd: Option<PathBuf>,
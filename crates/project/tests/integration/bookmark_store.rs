@@ -75,7 +75,9 @@ mod integration {
             let path = project_path(path_str);
             map.insert(
                 path.clone(),
-                rows.iter().map(|&row| SerializedBookmark(row)).collect(),
+                rows.iter()
+                    .map(|&row| SerializedBookmark::new(row))
+                    .collect(),
             );
         }
         map
@@ -113,7 +115,7 @@ mod integration {
         let file_bookmarks = bookmarks
             .get(&path)
             .unwrap_or_else(|| panic!("Expected bookmarks for {}", path.display()));
-        let rows: Vec<u32> = file_bookmarks.iter().map(|b| b.0).collect();
+        let rows: Vec<u32> = file_bookmarks.iter().map(|b| b.row).collect();
         assert_eq!(rows, expected_rows, "Bookmark rows for {}", path.display());
     }
 
@@ -293,7 +295,7 @@ mod integration {
             .get(&project_path(path!("/project/file1.rs")))
             .unwrap()
             .iter()
-            .map(|b| b.0)
+            .map(|b| b.row)
             .collect();
         let mut deduped = rows.clone();
         deduped.dedup();
@@ -682,4 +684,114 @@ mod integration {
         assert_bookmark_rows(&bookmarks, path!("/project/renamed.rs"), &[1]);
         assert_bookmark_rows(&bookmarks, path!("/project/untouched.rs"), &[0, 2]);
     }
+
+    #[gpui::test]
+    async fn test_labels_and_notes_follow_edits_and_round_trip(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.executor().allow_parking();
+
+        let fs = fs::FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({"file.rs": "aaa\nbbb\nccc\nddd\n"}),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let buffer = open_buffer(&project, path!("/project/file.rs"), cx).await;
+        add_bookmarks(&project, &buffer, &[1, 3], cx);
+
+        let path = project_path(path!("/project/file.rs"));
+        project.update(cx, |project, cx| {
+            project.bookmark_store().update(cx, |store, cx| {
+                store.set_bookmark_label(&path, 1, "  Entry point ", cx);
+                store.set_bookmark_note(&path, 3, "Check the error handling", cx);
+            });
+        });
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(0..0, "new_first_line\n")], None, cx);
+        });
+
+        let serialized = get_all_bookmarks(&project, cx);
+        let file_bookmarks = &serialized[&path];
+        assert_eq!(file_bookmarks[0].row, 2);
+        assert_eq!(file_bookmarks[0].label.as_deref(), Some("Entry point"));
+        assert_eq!(file_bookmarks[0].note, None);
+        assert_eq!(file_bookmarks[1].row, 4);
+        assert_eq!(file_bookmarks[1].label, None);
+        assert_eq!(
+            file_bookmarks[1].note.as_deref(),
+            Some("Check the error handling")
+        );
+
+        clear_bookmarks(&project, cx);
+        restore_bookmarks(&project, serialized.clone(), cx).await;
+        assert_eq!(get_all_bookmarks(&project, cx), serialized);
+
+        // Empty labels remove the label
+        project.update(cx, |project, cx| {
+            project.bookmark_store().update(cx, |store, cx| {
+                store.set_bookmark_label(&path, 2, "", cx);
+            });
+        });
+        assert_eq!(get_all_bookmarks(&project, cx)[&path][0].label, None);
+    }
+
+    #[gpui::test]
+    async fn test_move_and_remove_bookmarks(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.executor().allow_parking();
+
+        let fs = fs::FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({"file.rs": "aaa\nbbb\nccc\nddd\n"}),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let buffer = open_buffer(&project, path!("/project/file.rs"), cx).await;
+        add_bookmarks(&project, &buffer, &[0, 1, 2], cx);
+
+        let path = project_path(path!("/project/file.rs"));
+        project.update(cx, |project, cx| {
+            project.bookmark_store().update(cx, |store, cx| {
+                store.move_bookmark(&path, 2, 0, cx);
+            });
+        });
+        let bookmarks = get_all_bookmarks(&project, cx);
+        assert_bookmark_rows(&bookmarks, path!("/project/file.rs"), &[2, 0, 1]);
+        let orders: Vec<u32> = bookmarks[&path].iter().map(|b| b.order).collect();
+        assert_eq!(orders, vec![0, 1, 2]);
+
+        // The arranged order survives serialization
+        clear_bookmarks(&project, cx);
+        restore_bookmarks(&project, bookmarks, cx).await;
+        assert_bookmark_rows(
+            &get_all_bookmarks(&project, cx),
+            path!("/project/file.rs"),
+            &[2, 0, 1],
+        );
+
+        // Unloaded bookmarks can be edited as well
+        project.update(cx, |project, cx| {
+            project.bookmark_store().update(cx, |store, cx| {
+                store.remove_bookmark(&path, 0, cx);
+                store.move_bookmark(&path, 1, 0, cx);
+            });
+        });
+        assert_bookmark_rows(
+            &get_all_bookmarks(&project, cx),
+            path!("/project/file.rs"),
+            &[1, 2],
+        );
+
+        project.update(cx, |project, cx| {
+            project.bookmark_store().update(cx, |store, cx| {
+                store.remove_bookmark(&path, 1, cx);
+                store.remove_bookmark(&path, 2, cx);
+            });
+        });
+        assert!(get_all_bookmarks(&project, cx).is_empty());
+    }
 }
@@ -2804,6 +2804,76 @@ async fn test_omitted_diagnostics(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[cfg(not(windows))]
+#[gpui::test]
+async fn test_task_problem_matcher_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.executor().allow_parking();
+
+    let dir = TempTree::new(json!({
+        "src": {
+            "main.c": "int main() {}",
+            "util.c": "void util() {}",
+        }
+    }));
+    let project = Project::test(Arc::new(RealFs::new(None, cx.executor())), [dir.path()], cx).await;
+
+    let run_task = async |output: &str, cx: &mut gpui::TestAppContext| {
+        let spawn_task = task::SpawnInTerminal {
+            id: task::TaskId("build".to_string()),
+            label: "build".to_string(),
+            command: Some("echo".to_string()),
+            args: vec![output.to_string()],
+            cwd: Some(dir.path().to_path_buf()),
+            problem_matchers: vec![task::ProblemMatcher::builtin("$gcc").unwrap()],
+            ..Default::default()
+        };
+        let terminal = project
+            .update(cx, |project, cx| {
+                project.create_terminal_task(spawn_task, cx)
+            })
+            .await
+            .unwrap();
+        terminal
+            .read_with(cx, |terminal, cx| terminal.wait_for_completed_task(cx))
+            .await;
+        cx.executor().run_until_parked();
+    };
+    let summaries = |cx: &mut gpui::TestAppContext| {
+        project.update(cx, |project, cx| {
+            project
+                .diagnostic_summaries(false, cx)
+                .map(|(path, _, summary)| (path.path.as_unix_str().to_string(), summary))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    run_task("src/main.c:1:5: error: expected ';'", cx).await;
+    assert_eq!(
+        summaries(cx),
+        vec![(
+            "src/main.c".to_string(),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 0,
+            }
+        )]
+    );
+
+    // Running the task again replaces the problems reported by its previous run.
+    run_task("src/util.c:1:6: warning: unused function 'util'", cx).await;
+    assert_eq!(
+        summaries(cx),
+        vec![(
+            "src/util.c".to_string(),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 1,
+            }
+        )]
+    );
+}
+
 #[gpui::test]
 async fn test_disk_based_diagnostics_progress(cx: &mut gpui::TestAppContext) {
     init_test(cx);
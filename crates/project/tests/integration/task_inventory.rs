@@ -608,6 +608,107 @@ async fn test_zed_tasks_take_precedence_over_vscode(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_task_dependencies(cx: &mut TestAppContext) {
+    init_test(cx);
+    let inventory = cx.update(|cx| Inventory::new(cx));
+    let worktree_id = WorktreeId::from_usize(0);
+
+    inventory.update(cx, |inventory, _| {
+        inventory
+            .update_file_based_tasks(
+                TaskSettingsLocation::Global(tasks_file()),
+                Some(&mock_tasks_from_names(["lint"])),
+            )
+            .unwrap();
+        inventory
+            .update_file_based_tasks(
+                TaskSettingsLocation::Worktree(SettingsLocation {
+                    worktree_id,
+                    path: rel_path(".zed"),
+                }),
+                Some(
+                    &json!([
+                        { "label": "build", "command": "make", "depends_on": ["lint"] },
+                        { "label": "test", "command": "make test" },
+                        { "label": "all", "depends_on": ["build", "test"], "depends_order": "sequence" },
+                        { "label": "ping", "command": "ping", "depends_on": ["pong"] },
+                        { "label": "pong", "command": "pong", "depends_on": ["ping"] },
+                        { "label": "broken", "command": "echo", "depends_on": ["missing"] },
+                    ])
+                    .to_string(),
+                ),
+            )
+            .unwrap();
+    });
+
+    async fn dependencies(
+        inventory: &Entity<Inventory>,
+        worktree_id: WorktreeId,
+        label: &str,
+        cx: &mut TestAppContext,
+    ) -> anyhow::Result<Vec<TaskDependency>> {
+        let template = inventory
+            .update(cx, |inventory, cx| {
+                inventory.task_template_by_label(None, Some(worktree_id), label, cx)
+            })
+            .await
+            .unwrap();
+        inventory.read_with(cx, |inventory, _| {
+            inventory.task_dependencies(&template, Some(worktree_id))
+        })
+    }
+    fn labels(dependencies: &[TaskDependency]) -> Vec<(String, Vec<String>)> {
+        dependencies
+            .iter()
+            .map(|dependency| {
+                (
+                    dependency.template.label.clone(),
+                    dependency
+                        .dependencies
+                        .iter()
+                        .map(|dependency| dependency.template.label.clone())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    assert_eq!(
+        labels(
+            &dependencies(&inventory, worktree_id, "all", cx)
+                .await
+                .unwrap()
+        ),
+        vec![
+            ("build".to_string(), vec!["lint".to_string()]),
+            ("test".to_string(), Vec::new()),
+        ]
+    );
+    assert_eq!(
+        labels(
+            &dependencies(&inventory, worktree_id, "test", cx)
+                .await
+                .unwrap()
+        ),
+        Vec::new()
+    );
+    assert_eq!(
+        dependencies(&inventory, worktree_id, "ping", cx)
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Task dependency cycle: ping -> pong -> ping"
+    );
+    assert_eq!(
+        dependencies(&inventory, worktree_id, "broken", cx)
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Task `broken` depends on unknown task `missing`"
+    );
+}
+
 fn init_test(_cx: &mut TestAppContext) {
     zlog::init_test();
     TaskStore::init(None);
@@ -100,6 +100,10 @@ messages!(
     (GetDocumentSymbolsResponse, Background),
     (GetHover, Background),
     (GetHoverResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetIncomingCallsResponse, Background),
+    (GetOutgoingCalls, Background),
+    (GetOutgoingCallsResponse, Background),
     (GetNotifications, Foreground),
     (GetNotificationsResponse, Foreground),
     (GetCrashFiles, Background),
@@ -114,8 +118,14 @@ messages!(
     (GetProjectSymbolsResponse, Background),
     (GetReferences, Background),
     (GetReferencesResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
     (GetSignatureHelp, Background),
     (GetSignatureHelpResponse, Background),
+    (GetSubtypes, Background),
+    (GetSubtypesResponse, Background),
+    (GetSupertypes, Background),
+    (GetSupertypesResponse, Background),
     (GetTypeDefinition, Background),
     (GetTypeDefinitionResponse, Background),
     (GetImplementation, Background),
@@ -193,8 +203,12 @@ messages!(
     (PerformRename, Background),
     (PerformRenameResponse, Background),
     (Ping, Foreground),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
     (PrepareRename, Background),
     (PrepareRenameResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
     (ProjectEntryResponse, Foreground),
     (RefreshInlayHints, Background),
     (RefreshSemanticTokens, Background),
@@ -220,12 +234,16 @@ messages!(
     (ResolveCompletionDocumentationResponse, Background),
     (ResolveInlayHint, Background),
     (ResolveInlayHintResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
     (GetDocumentColor, Background),
     (GetDocumentColorResponse, Background),
     (GetColorPresentation, Background),
     (GetColorPresentationResponse, Background),
     (GetFoldingRanges, Background),
     (GetFoldingRangesResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
     (RefreshCodeLens, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
@@ -251,6 +269,9 @@ messages!(
     (SyncExtensionsResponse, Background),
     (BreakpointsForFile, Background),
     (ToggleBreakpoint, Foreground),
+    (BookmarksForFile, Background),
+    (UpdateBookmark, Foreground),
+    (ClearBookmarks, Foreground),
     (SynchronizeBuffers, Foreground),
     (SynchronizeBuffersResponse, Foreground),
     (TaskContext, Background),
@@ -304,6 +325,30 @@ messages!(
     (GitCompareCheckpointsResponse, Background),
     (GitDiffCheckpoints, Background),
     (GitDiffCheckpointsResponse, Background),
+    (GitRebaseTodo, Background),
+    (GitRebaseTodoResponse, Background),
+    (GitRebaseInteractive, Background),
+    (GitRebaseOperation, Background),
+    (GitGetRebaseState, Background),
+    (GitGetRebaseStateResponse, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitBisectStart, Background),
+    (GitBisectMark, Background),
+    (GitBisectReset, Background),
+    (GitGetBisectState, Background),
+    (GitGetBisectStateResponse, Background),
+    (GitGetSubmodules, Background),
+    (GitGetSubmodulesResponse, Background),
+    (GitSubmoduleOperation, Background),
+    (GitSubmoduleDiff, Background),
+    (GitSubmoduleDiffResponse, Background),
+    (GitLineHistory, Background),
+    (GitLineHistoryResponse, Background),
+    (GitLoadConflictStages, Background),
+    (GitLoadConflictStagesResponse, Background),
     (SetIndexText, Background),
     (Push, Background),
     (Fetch, Background),
@@ -410,10 +455,15 @@ request_messages!(
     (GetDocumentHighlights, GetDocumentHighlightsResponse),
     (GetDocumentSymbols, GetDocumentSymbolsResponse),
     (GetHover, GetHoverResponse),
+    (GetIncomingCalls, GetIncomingCallsResponse),
+    (GetOutgoingCalls, GetOutgoingCallsResponse),
     (GetNotifications, GetNotificationsResponse),
     (GetProjectSymbols, GetProjectSymbolsResponse),
     (GetReferences, GetReferencesResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
     (GetSignatureHelp, GetSignatureHelpResponse),
+    (GetSupertypes, GetSupertypesResponse),
+    (GetSubtypes, GetSubtypesResponse),
     (OpenUnstagedDiff, OpenUnstagedDiffResponse),
     (OpenUncommittedDiff, OpenUncommittedDiffResponse),
     (GetTypeDefinition, GetTypeDefinitionResponse),
@@ -444,7 +494,9 @@ request_messages!(
     (OpenNewBuffer, OpenBufferResponse),
     (PerformRename, PerformRenameResponse),
     (Ping, Ack),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
     (PrepareRename, PrepareRenameResponse),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
     (RefreshInlayHints, Ack),
     (RefreshSemanticTokens, Ack),
     (RefreshCodeLens, Ack),
@@ -464,8 +516,10 @@ request_messages!(
         ResolveCompletionDocumentationResponse
     ),
     (ResolveInlayHint, ResolveInlayHintResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
     (GetDocumentColor, GetDocumentColorResponse),
     (GetFoldingRanges, GetFoldingRangesResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
     (GetColorPresentation, GetColorPresentationResponse),
     (RespondToChannelInvite, Ack),
     (RespondToContactRequest, Ack),
@@ -537,6 +591,23 @@ request_messages!(
     (GitRestoreArchiveCheckpoint, Ack),
     (GitCompareCheckpoints, GitCompareCheckpointsResponse),
     (GitDiffCheckpoints, GitDiffCheckpointsResponse),
+    (GitRebaseTodo, GitRebaseTodoResponse),
+    (GitRebaseInteractive, Ack),
+    (GitRebaseOperation, Ack),
+    (GitGetRebaseState, GitGetRebaseStateResponse),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitBisectStart, Ack),
+    (GitBisectMark, Ack),
+    (GitBisectReset, Ack),
+    (GitGetBisectState, GitGetBisectStateResponse),
+    (GitGetSubmodules, GitGetSubmodulesResponse),
+    (GitSubmoduleOperation, RemoteMessageResponse),
+    (GitSubmoduleDiff, GitSubmoduleDiffResponse),
+    (GitLineHistory, GitLineHistoryResponse),
+    (GitLoadConflictStages, GitLoadConflictStagesResponse),
     (GitReset, Ack),
     (GitDeleteBranch, Ack),
     (GitCheckoutFiles, Ack),
@@ -555,6 +626,8 @@ request_messages!(
     (GitDiff, GitDiffResponse),
     (GitInit, Ack),
     (ToggleBreakpoint, Ack),
+    (UpdateBookmark, Ack),
+    (ClearBookmarks, Ack),
     (GetDebugAdapterBinary, DebugAdapterBinary),
     (RunDebugLocators, DebugRequest),
     (GetDocumentDiagnostics, GetDocumentDiagnosticsResponse),
@@ -588,6 +661,7 @@ lsp_messages!(
     (GetReferences, GetReferencesResponse, true),
     (GetDocumentColor, GetDocumentColorResponse, true),
     (GetFoldingRanges, GetFoldingRangesResponse, true),
+    (GetDocumentLinks, GetDocumentLinksResponse, true),
     (GetDocumentSymbols, GetDocumentSymbolsResponse, true),
     (GetHover, GetHoverResponse, true),
     (GetCodeActions, GetCodeActionsResponse, true),
@@ -623,6 +697,7 @@ entity_messages!(
     CreateProjectEntry,
     GetDocumentColor,
     GetFoldingRanges,
+    GetDocumentLinks,
     DeleteProjectEntry,
     ExpandProjectEntry,
     ExpandAllForProjectEntry,
@@ -638,9 +713,14 @@ entity_messages!(
     GetDocumentHighlights,
     GetDocumentSymbols,
     GetHover,
+    GetIncomingCalls,
+    GetOutgoingCalls,
     GetProjectSymbols,
     GetReferences,
+    GetSelectionRanges,
     GetSignatureHelp,
+    GetSupertypes,
+    GetSubtypes,
     OpenUnstagedDiff,
     OpenUncommittedDiff,
     GetTypeDefinition,
@@ -664,7 +744,9 @@ entity_messages!(
     OpenBufferForSymbol,
     OpenCommitMessageBuffer,
     PerformRename,
+    PrepareCallHierarchy,
     PrepareRename,
+    PrepareTypeHierarchy,
     RefreshInlayHints,
     RefreshSemanticTokens,
     RefreshCodeLens,
@@ -673,6 +755,7 @@ entity_messages!(
     RenameProjectEntry,
     ResolveCompletionDocumentation,
     ResolveInlayHint,
+    ResolveDocumentLink,
     SaveBuffer,
     Stage,
     StartLanguageServer,
@@ -748,6 +831,9 @@ entity_messages!(
     GitInit,
     BreakpointsForFile,
     ToggleBreakpoint,
+    BookmarksForFile,
+    UpdateBookmark,
+    ClearBookmarks,
     RunDebugLocators,
     GetDebugAdapterBinary,
     LogToDebugConsole,
@@ -769,6 +855,23 @@ entity_messages!(
     GitRepairWorktrees,
     GitCreateArchiveCheckpoint,
     GitRestoreArchiveCheckpoint,
+    GitRebaseTodo,
+    GitRebaseInteractive,
+    GitRebaseOperation,
+    GitGetRebaseState,
+    GitCherryPick,
+    GitRevert,
+    GitCreateTag,
+    GitDeleteTag,
+    GitBisectStart,
+    GitBisectMark,
+    GitBisectReset,
+    GitGetBisectState,
+    GitGetSubmodules,
+    GitSubmoduleOperation,
+    GitSubmoduleDiff,
+    GitLineHistory,
+    GitLoadConflictStages,
     GitCreateWorktree,
     GitRemoveWorktree,
     GitRenameWorktree,
@@ -965,6 +1068,7 @@ impl LspQuery {
             Some(lsp_query::Request::GetDocumentColor(_)) => ("GetDocumentColor", false),
             Some(lsp_query::Request::GetFoldingRanges(_)) => ("GetFoldingRanges", false),
             Some(lsp_query::Request::GetDocumentSymbols(_)) => ("GetDocumentSymbols", false),
+            Some(lsp_query::Request::GetDocumentLinks(_)) => ("GetDocumentLinks", false),
             Some(lsp_query::Request::InlayHints(_)) => ("InlayHints", false),
             Some(lsp_query::Request::SemanticTokens(_)) => ("SemanticTokens", false),
             None => ("<unknown>", true),
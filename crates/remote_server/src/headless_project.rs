@@ -16,6 +16,7 @@ use project::{
     AgentRegistryStore, LspStore, LspStoreEvent, ManifestTree, PrettierStore, ProjectEnvironment,
     ProjectPath, ToolchainStore, WorktreeId,
     agent_server_store::AgentServerStore,
+    bookmark_store::BookmarkStore,
     buffer_store::{BufferStore, BufferStoreEvent},
     context_server_store::ContextServerStore,
     debugger::{breakpoint_store::BreakpointStore, dap_store::DapStore},
@@ -56,6 +57,7 @@ pub struct HeadlessProject {
     pub lsp_store: Entity<LspStore>,
     pub task_store: Entity<TaskStore>,
     pub dap_store: Entity<DapStore>,
+    pub bookmark_store: Entity<BookmarkStore>,
     pub breakpoint_store: Entity<BreakpointStore>,
     pub agent_server_store: Entity<AgentServerStore>,
     pub context_server_store: Entity<ContextServerStore>,
@@ -139,6 +141,14 @@ impl HeadlessProject {
             buffer_store
         });
 
+        let bookmark_store = cx.new(|cx| {
+            let mut bookmark_store =
+                BookmarkStore::local(worktree_store.clone(), buffer_store.clone());
+            bookmark_store.shared(REMOTE_SERVER_PROJECT_ID, session.clone(), cx);
+
+            bookmark_store
+        });
+
         let breakpoint_store = cx.new(|_| {
             let mut breakpoint_store =
                 BreakpointStore::local(worktree_store.clone(), buffer_store.clone());
@@ -282,6 +292,7 @@ impl HeadlessProject {
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &task_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &toolchain_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &dap_store);
+        session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &bookmark_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &breakpoint_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &settings_observer);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &git_store);
@@ -334,6 +345,7 @@ impl HeadlessProject {
         DapStore::init(&session, cx);
         // todo(debugger): Re init breakpoint store when we set it up for collab
         BreakpointStore::init(&session);
+        BookmarkStore::init(&session);
         GitStore::init(&session);
         AgentServerStore::init_headless(&session);
         ContextServerStore::init_headless(&session);
@@ -348,6 +360,7 @@ impl HeadlessProject {
             lsp_store,
             task_store,
             dap_store,
+            bookmark_store,
             breakpoint_store,
             agent_server_store,
             context_server_store,
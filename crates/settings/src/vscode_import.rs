@@ -177,6 +177,8 @@ impl VsCodeSettings {
             audio: None,
             auto_update: None,
             base_keymap: Some(BaseKeymapContent::VSCode),
+            bookmarks_panel: None,
+            call_hierarchy_panel: None,
             calls: None,
             collaboration_panel: None,
             debugger: None,
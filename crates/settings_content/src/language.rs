@@ -86,6 +86,8 @@ pub enum EditPredictionProvider {
     Ollama,
     OpenAiCompatibleApi,
     Mercury,
+    /// Predictions from a language server providing `textDocument/inlineCompletion`.
+    LanguageServer,
     Experimental(&'static str),
 }
 
@@ -106,6 +108,7 @@ impl<'de> Deserialize<'de> for EditPredictionProvider {
             Ollama,
             OpenAiCompatibleApi,
             Mercury,
+            LanguageServer,
             Experimental(String),
         }
 
@@ -117,6 +120,7 @@ impl<'de> Deserialize<'de> for EditPredictionProvider {
             Content::Ollama => EditPredictionProvider::Ollama,
             Content::OpenAiCompatibleApi => EditPredictionProvider::OpenAiCompatibleApi,
             Content::Mercury => EditPredictionProvider::Mercury,
+            Content::LanguageServer => EditPredictionProvider::LanguageServer,
             Content::Experimental(name)
                 if name == EXPERIMENTAL_ZETA2_EDIT_PREDICTION_PROVIDER_NAME =>
             {
@@ -142,6 +146,7 @@ impl EditPredictionProvider {
             | EditPredictionProvider::Ollama
             | EditPredictionProvider::OpenAiCompatibleApi
             | EditPredictionProvider::Mercury
+            | EditPredictionProvider::LanguageServer
             | EditPredictionProvider::Experimental(_) => false,
         }
     }
@@ -155,6 +160,7 @@ impl EditPredictionProvider {
             EditPredictionProvider::Experimental(_) | EditPredictionProvider::None => None,
             EditPredictionProvider::Ollama => Some("Ollama"),
             EditPredictionProvider::OpenAiCompatibleApi => Some("OpenAI-Compatible API"),
+            EditPredictionProvider::LanguageServer => Some("Language Server"),
         }
     }
 }
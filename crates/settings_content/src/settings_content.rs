@@ -130,6 +130,12 @@ pub struct SettingsContent {
     /// Default: VSCode
     pub base_keymap: Option<BaseKeymapContent>,
 
+    /// Configuration for the bookmarks panel visual settings.
+    pub bookmarks_panel: Option<PanelSettingsContent>,
+
+    /// Configuration for the call hierarchy panel visual settings.
+    pub call_hierarchy_panel: Option<PanelSettingsContent>,
+
     /// Configuration for the collab panel visual settings.
     pub collaboration_panel: Option<PanelSettingsContent>,
 
@@ -896,6 +902,24 @@ pub struct JournalSettingsContent {
     ///
     /// Default: hour12
     pub hour_format: Option<HourFormat>,
+    /// Template of the heading inserted for every new entry.
+    /// When unset, the heading is the current time.
+    ///
+    /// Default: null
+    pub entry_template: Option<String>,
+    /// Template of the contents of new daily notes.
+    /// When unset, daily notes are created empty.
+    ///
+    /// Default: null
+    pub daily_template: Option<String>,
+    /// Template of the contents of new weekly notes.
+    ///
+    /// Default: "# Week $JOURNAL_WEEK, $JOURNAL_YEAR\n\n$JOURNAL_ROLLUP\n"
+    pub weekly_template: Option<String>,
+    /// Template of the contents of new monthly notes.
+    ///
+    /// Default: "# $JOURNAL_MONTH_NAME $JOURNAL_YEAR\n\n$JOURNAL_ROLLUP\n"
+    pub monthly_template: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
@@ -0,0 +1,683 @@
+//! Problem matchers extract problems (errors, warnings, etc.) reported by the tools run as tasks from their output,
+//! so that they can be shown as diagnostics.
+//!
+//! The format follows the one of VS Code's `tasks.json`, including the names of its most common built-in matchers.
+
+use anyhow::Context as _;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+use crate::serde_helpers::one_or_many;
+
+/// A problem matcher of a task: either a name of a built-in one (e.g. `$rustc`), or a custom definition.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcherDefinition {
+    Named(String),
+    Custom(ProblemMatcher),
+}
+
+impl ProblemMatcherDefinition {
+    /// Returns the definition of the matcher, or `None` if it references an unknown built-in matcher.
+    pub fn resolve(&self) -> Option<ProblemMatcher> {
+        match self {
+            ProblemMatcherDefinition::Named(name) => ProblemMatcher::builtin(name),
+            ProblemMatcherDefinition::Custom(matcher) => Some(matcher.clone()),
+        }
+    }
+}
+
+/// Describes how to find problems in the output of a task.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemMatcher {
+    /// Name of the tool reporting the problems, shown as the source of the diagnostics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// How to interpret the file paths reported by the tool:
+    /// * `relative` — relative paths are resolved against `base_directory` (default)
+    /// * `absolute` — paths are used as they are
+    #[serde(default)]
+    pub file_location: FileLocation,
+    /// Directory to resolve relative file paths against, defaults to the working directory of the task.
+    /// May use task variables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_directory: Option<String>,
+    /// Severity of the problems that the pattern doesn't capture one for.
+    #[serde(default)]
+    pub severity: ProblemSeverity,
+    /// Patterns matching consecutive lines of the output, that together describe a problem.
+    #[serde(deserialize_with = "one_or_many")]
+    #[schemars(with = "ProblemPatterns")]
+    pub pattern: Vec<ProblemPattern>,
+    /// For tasks running in the background (e.g. watching for changes), a regex matching
+    /// the line that starts a new run of the tool, discarding the problems reported so far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub begins_pattern: Option<String>,
+}
+
+/// Schema of [`ProblemMatcher::pattern`], which also accepts a single pattern.
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum ProblemPatterns {
+    One(ProblemPattern),
+    Many(Vec<ProblemPattern>),
+}
+
+/// A regex for a line of the output, with the indices of the capture groups containing parts of a problem.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemPattern {
+    pub regexp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// Group with the whole location of the problem, as `line`, `line,column` or `line,column,end_line,end_column`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<usize>,
+    /// Whether the last pattern of a multiline matcher may match several lines in a row,
+    /// each of them reporting a problem.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub r#loop: bool,
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FileLocation {
+    #[default]
+    Relative,
+    Absolute,
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl ProblemSeverity {
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "error" | "fatal" | "fatal error" => Some(Self::Error),
+            "warning" | "warn" => Some(Self::Warning),
+            "info" | "information" | "note" => Some(Self::Info),
+            "hint" | "help" => Some(Self::Hint),
+            _ => None,
+        }
+    }
+}
+
+/// A problem found in the output of a task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub path: PathBuf,
+    pub severity: ProblemSeverity,
+    /// Zero-based position where the problem starts.
+    pub start: ProblemPoint,
+    /// Zero-based position where the problem ends, if the tool reported it.
+    pub end: Option<ProblemPoint>,
+    pub code: Option<String>,
+    pub message: String,
+    pub source: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProblemPoint {
+    pub row: u32,
+    pub column: u32,
+}
+
+impl ProblemMatcher {
+    /// Names of the built-in problem matchers.
+    pub const BUILTIN_NAMES: &[&str] = &[
+        "$tsc",
+        "$tsc-watch",
+        "$gcc",
+        "$rustc",
+        "$eslint-compact",
+        "$eslint-stylish",
+    ];
+
+    /// Returns the built-in matcher with the given name, the ones VS Code provides.
+    pub fn builtin(name: &str) -> Option<Self> {
+        const TSC_PATTERN: &str = r"^([^\s].*)[\(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+TS(\d+)\s*:\s*(.*)$";
+        let tsc = || ProblemMatcher {
+            source: Some("ts".to_string()),
+            pattern: vec![ProblemPattern {
+                regexp: TSC_PATTERN.to_string(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                code: Some(5),
+                message: Some(6),
+                ..ProblemPattern::default()
+            }],
+            ..ProblemMatcher::default()
+        };
+
+        let matcher = match name {
+            "$tsc" => tsc(),
+            "$tsc-watch" => ProblemMatcher {
+                begins_pattern: Some(
+                    r"(?:Starting compilation in watch mode|File change detected\. Starting incremental compilation)\.\.\."
+                        .to_string(),
+                ),
+                ..tsc()
+            },
+            "$gcc" => ProblemMatcher {
+                source: Some("gcc".to_string()),
+                pattern: vec![ProblemPattern {
+                    regexp: r"^(.*?):(\d+):(\d*):?\s+(?:fatal\s+)?(warning|error):\s+(.*)$"
+                        .to_string(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    severity: Some(4),
+                    message: Some(5),
+                    ..ProblemPattern::default()
+                }],
+                ..ProblemMatcher::default()
+            },
+            "$rustc" => ProblemMatcher {
+                source: Some("rustc".to_string()),
+                pattern: vec![
+                    ProblemPattern {
+                        regexp: r"^(warning|warn|error)(?:\[(.*?)\])?: (.*)$".to_string(),
+                        severity: Some(1),
+                        code: Some(2),
+                        message: Some(3),
+                        ..ProblemPattern::default()
+                    },
+                    ProblemPattern {
+                        regexp: r"^\s*-->\s+(.*?):(\d+):(\d+)\s*$".to_string(),
+                        file: Some(1),
+                        line: Some(2),
+                        column: Some(3),
+                        ..ProblemPattern::default()
+                    },
+                ],
+                ..ProblemMatcher::default()
+            },
+            "$eslint-compact" => ProblemMatcher {
+                source: Some("eslint".to_string()),
+                pattern: vec![ProblemPattern {
+                    regexp: r"^(.+):\sline\s(\d+),\scol\s(\d+),\s(Error|Warning|Info)\s-\s(.+)\s\((.+)\)$"
+                        .to_string(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    severity: Some(4),
+                    message: Some(5),
+                    code: Some(6),
+                    ..ProblemPattern::default()
+                }],
+                ..ProblemMatcher::default()
+            },
+            "$eslint-stylish" => ProblemMatcher {
+                source: Some("eslint".to_string()),
+                pattern: vec![
+                    ProblemPattern {
+                        regexp: r"^((?:[a-zA-Z]:)*[./\\]+.*?)$".to_string(),
+                        file: Some(1),
+                        ..ProblemPattern::default()
+                    },
+                    ProblemPattern {
+                        regexp: r"^\s+(\d+):(\d+)\s+(error|warning|info)\s+(.+?)(?:\s\s+(.*))?$"
+                            .to_string(),
+                        line: Some(1),
+                        column: Some(2),
+                        severity: Some(3),
+                        message: Some(4),
+                        code: Some(5),
+                        r#loop: true,
+                        ..ProblemPattern::default()
+                    },
+                ],
+                ..ProblemMatcher::default()
+            },
+            _ => return None,
+        };
+        Some(matcher)
+    }
+}
+
+/// Runs problem matchers over the output of a task, line by line, keeping the problems found so far.
+pub struct ProblemCollector {
+    matchers: Vec<ActiveMatcher>,
+}
+
+struct ActiveMatcher {
+    source: Option<String>,
+    base_directory: Option<PathBuf>,
+    severity: ProblemSeverity,
+    patterns: Vec<(Regex, ProblemPattern)>,
+    begins_pattern: Option<Regex>,
+    /// Number of patterns matched by the preceding lines.
+    matched_patterns: usize,
+    /// Problem parts captured by the patterns matched so far.
+    captured: CapturedProblem,
+    problems: Vec<Problem>,
+}
+
+#[derive(Clone, Default)]
+struct CapturedProblem {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<ProblemSeverity>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl ProblemCollector {
+    /// Relative paths reported by the matchers without a base directory are resolved against `cwd`.
+    pub fn new(matchers: &[ProblemMatcher], cwd: Option<&Path>) -> anyhow::Result<Self> {
+        let matchers = matchers
+            .iter()
+            .map(|matcher| ActiveMatcher::new(matcher, cwd))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self { matchers })
+    }
+
+    /// Matches a line of the output, returns whether the problems changed.
+    pub fn push_line(&mut self, line: &str) -> bool {
+        let line = line.trim_end();
+        let mut changed = false;
+        for matcher in &mut self.matchers {
+            changed |= matcher.push_line(line);
+        }
+        changed
+    }
+
+    pub fn problems(&self) -> impl Iterator<Item = &Problem> {
+        self.matchers
+            .iter()
+            .flat_map(|matcher| matcher.problems.iter())
+    }
+}
+
+impl ActiveMatcher {
+    fn new(matcher: &ProblemMatcher, cwd: Option<&Path>) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            !matcher.pattern.is_empty(),
+            "problem matcher has no patterns"
+        );
+        let patterns = matcher
+            .pattern
+            .iter()
+            .map(|pattern| {
+                let regex = Regex::new(&pattern.regexp)
+                    .with_context(|| format!("invalid problem pattern {:?}", pattern.regexp))?;
+                anyhow::Ok((regex, pattern.clone()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let begins_pattern = matcher
+            .begins_pattern
+            .as_deref()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .with_context(|| format!("invalid problem matcher begins pattern {pattern:?}"))
+            })
+            .transpose()?;
+        let base_directory = match matcher.file_location {
+            FileLocation::Absolute => None,
+            FileLocation::Relative => match (cwd, matcher.base_directory.as_deref()) {
+                (Some(cwd), Some(base_directory)) => Some(cwd.join(base_directory)),
+                (None, Some(base_directory)) => Some(PathBuf::from(base_directory)),
+                (cwd, None) => cwd.map(Path::to_path_buf),
+            },
+        };
+
+        Ok(Self {
+            source: matcher.source.clone(),
+            base_directory,
+            severity: matcher.severity,
+            patterns,
+            begins_pattern,
+            matched_patterns: 0,
+            captured: CapturedProblem::default(),
+            problems: Vec::new(),
+        })
+    }
+
+    fn push_line(&mut self, line: &str) -> bool {
+        if self
+            .begins_pattern
+            .as_ref()
+            .is_some_and(|begins_pattern| begins_pattern.is_match(line))
+        {
+            self.matched_patterns = 0;
+            return !std::mem::take(&mut self.problems).is_empty();
+        }
+
+        if self.matched_patterns > 0 {
+            let (regex, pattern) = &self.patterns[self.matched_patterns];
+            if let Some(captures) = regex.captures(line) {
+                let mut captured = self.captured.clone();
+                captured.update(&captures, pattern);
+                if self.matched_patterns + 1 < self.patterns.len() {
+                    self.captured = captured;
+                    self.matched_patterns += 1;
+                    return false;
+                }
+                // A looping last pattern stays active for the next lines, with the captures of the previous patterns.
+                if !pattern.r#loop {
+                    self.matched_patterns = 0;
+                }
+                return self.report(captured);
+            }
+            self.matched_patterns = 0;
+        }
+
+        let (regex, pattern) = &self.patterns[0];
+        let Some(captures) = regex.captures(line) else {
+            return false;
+        };
+        let mut captured = CapturedProblem::default();
+        captured.update(&captures, pattern);
+        if self.patterns.len() == 1 {
+            self.report(captured)
+        } else {
+            self.captured = captured;
+            self.matched_patterns = 1;
+            false
+        }
+    }
+
+    fn report(&mut self, captured: CapturedProblem) -> bool {
+        let (Some(file), Some(message)) = (captured.file, captured.message) else {
+            return false;
+        };
+        let path = match &self.base_directory {
+            Some(base_directory) => base_directory.join(file),
+            None => PathBuf::from(file),
+        }
+        .components()
+        .filter(|component| component != &Component::CurDir)
+        .collect();
+        // Lines and columns are reported one-based.
+        let start = ProblemPoint {
+            row: captured.line.unwrap_or(1).saturating_sub(1),
+            column: captured.column.unwrap_or(1).saturating_sub(1),
+        };
+        let end = captured.end_line.map(|end_line| ProblemPoint {
+            row: end_line.saturating_sub(1),
+            column: captured
+                .end_column
+                .map_or(start.column, |column| column.saturating_sub(1)),
+        });
+        self.problems.push(Problem {
+            path,
+            severity: captured.severity.unwrap_or(self.severity),
+            start,
+            end,
+            code: captured.code,
+            message,
+            source: self.source.clone(),
+        });
+        true
+    }
+}
+
+impl CapturedProblem {
+    fn update(&mut self, captures: &regex::Captures, pattern: &ProblemPattern) {
+        let group = |index: Option<usize>| {
+            index
+                .and_then(|index| captures.get(index))
+                .map(|group| group.as_str())
+                .filter(|text| !text.is_empty())
+        };
+        let number = |index: Option<usize>| group(index).and_then(|text| text.parse::<u32>().ok());
+
+        if let Some(file) = group(pattern.file) {
+            self.file = Some(file.to_string());
+        }
+        if let Some(line) = number(pattern.line) {
+            self.line = Some(line);
+        }
+        if let Some(column) = number(pattern.column) {
+            self.column = Some(column);
+        }
+        if let Some(location) = group(pattern.location) {
+            let numbers = location
+                .split(',')
+                .map(|number| number.trim().parse::<u32>().ok())
+                .collect::<Vec<_>>();
+            match numbers.as_slice() {
+                [line] => self.line = *line,
+                [line, column] => (self.line, self.column) = (*line, *column),
+                [line, column, end_line, end_column] => {
+                    (self.line, self.column) = (*line, *column);
+                    (self.end_line, self.end_column) = (*end_line, *end_column);
+                }
+                _ => {}
+            }
+        }
+        if let Some(end_line) = number(pattern.end_line) {
+            self.end_line = Some(end_line);
+        }
+        if let Some(end_column) = number(pattern.end_column) {
+            self.end_column = Some(end_column);
+        }
+        if let Some(severity) = group(pattern.severity).and_then(ProblemSeverity::parse) {
+            self.severity = Some(severity);
+        }
+        if let Some(code) = group(pattern.code) {
+            self.code = Some(code.to_string());
+        }
+        if let Some(message) = group(pattern.message) {
+            self.message = Some(message.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(matcher: &str, output: &str) -> Vec<Problem> {
+        let matcher = ProblemMatcher::builtin(matcher).unwrap();
+        let mut collector = ProblemCollector::new(&[matcher], Some(Path::new("/project"))).unwrap();
+        for line in output.lines() {
+            collector.push_line(line);
+        }
+        collector.problems().cloned().collect()
+    }
+
+    #[test]
+    fn test_builtin_matchers_are_valid() {
+        for name in ProblemMatcher::BUILTIN_NAMES {
+            let matcher = ProblemMatcher::builtin(name).unwrap();
+            ProblemCollector::new(&[matcher], None).unwrap();
+        }
+        assert_eq!(ProblemMatcher::builtin("$unknown"), None);
+    }
+
+    #[test]
+    fn test_rustc_matcher() {
+        let problems = collect(
+            "$rustc",
+            "   Compiling example v0.1.0 (/project)
+warning: unused variable: `x`
+ --> src/main.rs:2:9
+  |
+2 |     let x = 5;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+
+error[E0308]: mismatched types
+  --> src/lib.rs:10:5
+   |
+error: aborting due to 1 previous error
+",
+        );
+        assert_eq!(
+            problems,
+            vec![
+                Problem {
+                    path: PathBuf::from("/project/src/main.rs"),
+                    severity: ProblemSeverity::Warning,
+                    start: ProblemPoint { row: 1, column: 8 },
+                    end: None,
+                    code: None,
+                    message: "unused variable: `x`".to_string(),
+                    source: Some("rustc".to_string()),
+                },
+                Problem {
+                    path: PathBuf::from("/project/src/lib.rs"),
+                    severity: ProblemSeverity::Error,
+                    start: ProblemPoint { row: 9, column: 4 },
+                    end: None,
+                    code: Some("E0308".to_string()),
+                    message: "mismatched types".to_string(),
+                    source: Some("rustc".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tsc_and_gcc_matchers() {
+        let problems = collect(
+            "$tsc",
+            "src/index.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.
+src/util.ts:12:1 - warning TS6133: 'unused' is declared but its value is never read.",
+        );
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (
+                    problem.path.clone(),
+                    problem.start,
+                    problem.severity,
+                    problem.code.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    PathBuf::from("/project/src/index.ts"),
+                    ProblemPoint { row: 2, column: 6 },
+                    ProblemSeverity::Error,
+                    Some("2322".to_string())
+                ),
+                (
+                    PathBuf::from("/project/src/util.ts"),
+                    ProblemPoint { row: 11, column: 0 },
+                    ProblemSeverity::Warning,
+                    Some("6133".to_string())
+                ),
+            ]
+        );
+
+        let problems = collect(
+            "$gcc",
+            "main.c: In function 'main':
+/abs/main.c:4:5: fatal error: stdio.h: No such file or directory",
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].path, PathBuf::from("/abs/main.c"));
+        assert_eq!(problems[0].message, "stdio.h: No such file or directory");
+    }
+
+    #[test]
+    fn test_looping_pattern() {
+        let problems = collect(
+            "$eslint-stylish",
+            "./src/a.js
+  1:10  error    'foo' is defined but never used  no-unused-vars
+  2:1   warning  Unexpected console statement      no-console
+
+./src/b.js
+  7:3  error  Missing semicolon  semi
+",
+        );
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (
+                    problem.path.to_string_lossy().into_owned(),
+                    problem.start.row,
+                    problem.code.clone().unwrap_or_default()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "/project/src/a.js".to_string(),
+                    0,
+                    "no-unused-vars".to_string()
+                ),
+                ("/project/src/a.js".to_string(), 1, "no-console".to_string()),
+                ("/project/src/b.js".to_string(), 6, "semi".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_background_matcher_restarts() {
+        let matcher = ProblemMatcher::builtin("$tsc-watch").unwrap();
+        let mut collector = ProblemCollector::new(&[matcher], Some(Path::new("/project"))).unwrap();
+        assert!(!collector.push_line("[10:00:00 AM] Starting compilation in watch mode..."));
+        assert!(collector.push_line("src/a.ts(1,1): error TS1005: ';' expected."));
+        assert_eq!(collector.problems().count(), 1);
+
+        assert!(
+            collector.push_line(
+                "[10:00:05 AM] File change detected. Starting incremental compilation..."
+            )
+        );
+        assert_eq!(collector.problems().count(), 0);
+    }
+
+    #[test]
+    fn test_custom_matcher_definition() {
+        let definition: ProblemMatcherDefinition = serde_json::from_str(
+            r#"{
+                "source": "lint",
+                "base_directory": "web",
+                "severity": "warning",
+                "pattern": { "regexp": "^(.*):(\\d+,\\d+): (.*)$", "file": 1, "location": 2, "message": 3 }
+            }"#,
+        )
+        .unwrap();
+        let matcher = definition.resolve().unwrap();
+        let mut collector = ProblemCollector::new(&[matcher], Some(Path::new("/project"))).unwrap();
+        assert!(collector.push_line("app.js:3,5: trailing whitespace"));
+        assert_eq!(
+            collector.problems().cloned().collect::<Vec<_>>(),
+            vec![Problem {
+                path: PathBuf::from("/project/web/app.js"),
+                severity: ProblemSeverity::Warning,
+                start: ProblemPoint { row: 2, column: 4 },
+                end: None,
+                code: None,
+                message: "trailing whitespace".to_string(),
+                source: Some("lint".to_string()),
+            }]
+        );
+
+        let named: ProblemMatcherDefinition = serde_json::from_str(r#""$rustc""#).unwrap();
+        assert_eq!(named, ProblemMatcherDefinition::Named("$rustc".to_string()));
+    }
+}
@@ -2,6 +2,7 @@
 
 mod adapter_schema;
 mod debug_format;
+mod problem_matcher;
 mod serde_helpers;
 pub mod static_source;
 mod task_template;
@@ -22,9 +23,13 @@ pub use debug_format::{
     AttachRequest, BuildTaskDefinition, DebugRequest, DebugScenario, DebugTaskFile, LaunchRequest,
     Request, TcpArgumentsTemplate, ZedDebugConfig,
 };
+pub use problem_matcher::{
+    FileLocation, Problem, ProblemCollector, ProblemMatcher, ProblemMatcherDefinition,
+    ProblemPattern, ProblemPoint, ProblemSeverity,
+};
 pub use task_template::{
-    DebugArgsRequest, HideStrategy, RevealStrategy, SaveStrategy, TaskHook, TaskTemplate,
-    TaskTemplates, substitute_variables_in_map, substitute_variables_in_str,
+    DebugArgsRequest, DependsOrder, HideStrategy, RevealStrategy, SaveStrategy, TaskHook,
+    TaskTemplate, TaskTemplates, substitute_variables_in_map, substitute_variables_in_str,
 };
 pub use util::shell::{Shell, ShellKind};
 pub use util::shell_builder::ShellBuilder;
@@ -77,6 +82,8 @@ pub struct SpawnInTerminal {
     pub show_rerun: bool,
     /// Which edited buffers to save before running the task.
     pub save: SaveStrategy,
+    /// Problem matchers to run over the task's output, reporting the problems found as diagnostics.
+    pub problem_matchers: Vec<ProblemMatcher>,
 }
 
 impl SpawnInTerminal {
@@ -124,6 +131,8 @@ pub struct ResolvedTask {
     pub resolved_label: String,
     /// Variables that were substituted during the task template resolution.
     substituted_variables: HashSet<VariableName>,
+    /// A context the task got resolved with, kept to resolve the tasks it depends on, if there are any.
+    dependencies_context: Option<TaskContext>,
     /// Further actions that need to take place after the resolved task is spawned,
     /// with all task variables resolved.
     pub resolved: SpawnInTerminal,
@@ -144,6 +153,11 @@ impl ResolvedTask {
     pub fn display_label(&self) -> &str {
         self.resolved.label.as_str()
     }
+
+    /// A context to resolve the tasks from [`TaskTemplate::depends_on`] with, same as the task was resolved with.
+    pub fn dependencies_context(&self) -> Option<&TaskContext> {
+        self.dependencies_context.as_ref()
+    }
 }
 
 /// Variables, available for use in [`TaskContext`] when a Zed's [`TaskTemplate`] gets resolved into a [`ResolvedTask`].
@@ -9,8 +9,9 @@ use util::serde::default_true;
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
-    AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX, serde_helpers::non_empty_string_vec,
+    AttachRequest, ProblemMatcher, ProblemMatcherDefinition, ResolvedTask, RevealTarget, Shell,
+    SpawnInTerminal, TaskContext, TaskId, VariableName, ZED_VARIABLE_NAME_PREFIX,
+    serde_helpers::non_empty_string_vec,
 };
 
 /// A template definition of a Zed task to run.
@@ -78,6 +79,20 @@ pub struct TaskTemplate {
     /// Hooks that this task runs when emitted.
     #[serde(default)]
     pub hooks: HashSet<TaskHook>,
+    /// Labels of the tasks to run before this one, each in its own terminal.
+    /// The task only runs if all of them succeed, and may omit `command` to only run its dependencies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// How to run the tasks this one depends on:
+    /// * `parallel` — start all of them at once (default)
+    /// * `sequence` — start each of them after the previous one succeeded, in the order listed
+    #[serde(default, skip_serializing_if = "DependsOrder::is_parallel")]
+    pub depends_order: DependsOrder,
+    /// Problem matchers to find errors and warnings in the task's output with, reporting them as diagnostics.
+    /// Each one is either the name of a built-in matcher (`$tsc`, `$tsc-watch`, `$gcc`, `$rustc`,
+    /// `$eslint-compact`, `$eslint-stylish`) or a custom matcher definition.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problem_matchers: Vec<ProblemMatcherDefinition>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -136,6 +151,23 @@ pub enum SaveStrategy {
     None,
 }
 
+/// How to run the tasks a task depends on.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Start all dependencies at once.
+    #[default]
+    Parallel,
+    /// Start each dependency after the previous one succeeded.
+    Sequence,
+}
+
+impl DependsOrder {
+    pub fn is_parallel(&self) -> bool {
+        *self == DependsOrder::Parallel
+    }
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -160,7 +192,9 @@ impl TaskTemplate {
     /// Every [`ResolvedTask`] gets a [`TaskId`], based on the `id_base` (to avoid collision with various task sources),
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
-        if self.label.trim().is_empty() || self.command.trim().is_empty() {
+        if self.label.trim().is_empty()
+            || (self.command.trim().is_empty() && self.depends_on.is_empty())
+        {
             return None;
         }
 
@@ -240,6 +274,29 @@ impl TaskTemplate {
             &mut substituted_variables,
         )?;
 
+        let problem_matchers = self
+            .problem_matchers
+            .iter()
+            .filter_map(|definition| {
+                let matcher = definition.resolve();
+                if matcher.is_none() {
+                    log::warn!("Unknown problem matcher {definition:?} in task {full_label:?}");
+                }
+                matcher
+            })
+            .map(|mut matcher| {
+                if let Some(base_directory) = &matcher.base_directory {
+                    matcher.base_directory = Some(substitute_all_template_variables_in_str(
+                        base_directory,
+                        &task_variables,
+                        &variable_names,
+                        &mut substituted_variables,
+                    )?);
+                }
+                Some(matcher)
+            })
+            .collect::<Option<Vec<ProblemMatcher>>>()?;
+
         let task_hash = to_hex_hash(self)
             .context("hashing task template")
             .log_err()?;
@@ -271,6 +328,7 @@ impl TaskTemplate {
         Some(ResolvedTask {
             id: id.clone(),
             substituted_variables,
+            dependencies_context: (!self.depends_on.is_empty()).then(|| cx.clone()),
             original_task: self.clone(),
             resolved_label: full_label.clone(),
             resolved: SpawnInTerminal {
@@ -299,6 +357,7 @@ impl TaskTemplate {
                 show_command: self.show_command,
                 show_rerun: true,
                 save: self.save,
+                problem_matchers,
             },
         })
     }
@@ -327,6 +386,16 @@ impl TaskTemplate {
             Self::collect_unknown_variables(cwd, &mut variables);
         }
 
+        for definition in &self.problem_matchers {
+            if let ProblemMatcherDefinition::Custom(ProblemMatcher {
+                base_directory: Some(base_directory),
+                ..
+            }) = definition
+            {
+                Self::collect_unknown_variables(base_directory, &mut variables);
+            }
+        }
+
         variables.into_iter().collect()
     }
 
@@ -552,6 +621,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolving_templates_with_dependencies() {
+        let task_context = TaskContext {
+            cwd: Some(PathBuf::from("/dir")),
+            ..TaskContext::default()
+        };
+        let task_with_dependencies = TaskTemplate {
+            label: "all".to_string(),
+            depends_on: vec!["build".to_string(), "test".to_string()],
+            ..TaskTemplate::default()
+        };
+        let resolved_task = task_with_dependencies
+            .resolve_task(TEST_ID_BASE, &task_context)
+            .expect("should resolve a task without command that has dependencies");
+        assert_eq!(resolved_task.dependencies_context(), Some(&task_context));
+
+        let task_without_dependencies = TaskTemplate {
+            command: "echo".to_string(),
+            depends_on: Vec::new(),
+            ..task_with_dependencies
+        };
+        let resolved_task = task_without_dependencies
+            .resolve_task(TEST_ID_BASE, &task_context)
+            .unwrap();
+        assert_eq!(resolved_task.dependencies_context(), None);
+    }
+
+    #[test]
+    fn test_resolving_problem_matchers() {
+        let task_context = TaskContext {
+            cwd: Some(PathBuf::from("/dir")),
+            task_variables: TaskVariables::from_iter([(
+                VariableName::WorktreeRoot,
+                "/worktree".to_string(),
+            )]),
+            ..TaskContext::default()
+        };
+        let task = TaskTemplate {
+            label: "lint".to_string(),
+            command: "lint".to_string(),
+            problem_matchers: vec![
+                ProblemMatcherDefinition::Named("$rustc".to_string()),
+                ProblemMatcherDefinition::Named("$unknown".to_string()),
+                ProblemMatcherDefinition::Custom(ProblemMatcher {
+                    base_directory: Some("$ZED_WORKTREE_ROOT/web".to_string()),
+                    ..ProblemMatcher::builtin("$eslint-compact").unwrap()
+                }),
+            ],
+            ..TaskTemplate::default()
+        };
+        let resolved_task = task.resolve_task(TEST_ID_BASE, &task_context).unwrap();
+        assert_eq!(
+            resolved_task.resolved.problem_matchers,
+            vec![
+                ProblemMatcher::builtin("$rustc").unwrap(),
+                ProblemMatcher {
+                    base_directory: Some("/worktree/web".to_string()),
+                    ..ProblemMatcher::builtin("$eslint-compact").unwrap()
+                },
+            ],
+            "Unknown matchers should be skipped and task variables substituted in base directories"
+        );
+    }
+
     #[test]
     fn test_template_cwd_resolution() {
         let task_without_cwd = TaskTemplate {
@@ -3,7 +3,10 @@ use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    DependsOrder, EnvVariableReplacer, FileLocation, ProblemMatcher, ProblemMatcherDefinition,
+    ProblemPattern, ProblemSeverity, TaskTemplate, TaskTemplates, VariableName,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -13,10 +16,205 @@ struct TaskOptions {
     env: HashMap<String, String>,
 }
 
+/// A task referenced in `dependsOn`, either by its label or by its definition
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum TaskReference {
+    Label(String),
+    Definition(Command),
+}
+
+impl TaskReference {
+    fn label(self) -> String {
+        match self {
+            TaskReference::Label(label) => label,
+            TaskReference::Definition(command) => generate_label(&Some(command)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum DependsOn {
+    Single(TaskReference),
+    Multiple(Vec<TaskReference>),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum ProblemMatchers {
+    Single(VsCodeProblemMatcher),
+    Multiple(Vec<VsCodeProblemMatcher>),
+}
+
+/// A problem matcher, either a name of a built-in one or a custom definition
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodeProblemMatcher {
+    Named(String),
+    Custom(CustomProblemMatcher),
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct CustomProblemMatcher {
+    /// Built-in matcher that the other fields override
+    base: Option<String>,
+    owner: Option<String>,
+    source: Option<String>,
+    file_location: Option<VsCodeFileLocation>,
+    severity: Option<ProblemSeverity>,
+    pattern: Option<VsCodeProblemPatterns>,
+    background: Option<VsCodeBackgroundMatcher>,
+}
+
+/// `"absolute"`, `"relative"`, `"autoDetect"`, or one of the latter two with the base directory: `["relative", "${workspaceFolder}/src"]`
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodeFileLocation {
+    Kind(String),
+    WithBase(Vec<String>),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodeProblemPatterns {
+    Named(String),
+    Single(VsCodeProblemPattern),
+    Multiple(Vec<VsCodeProblemPattern>),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemPattern {
+    regexp: String,
+    file: Option<usize>,
+    line: Option<usize>,
+    column: Option<usize>,
+    location: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+    severity: Option<usize>,
+    code: Option<usize>,
+    message: Option<usize>,
+    #[serde(default)]
+    r#loop: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeBackgroundMatcher {
+    begins_pattern: Option<VsCodeBackgroundPattern>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodeBackgroundPattern {
+    Regexp(String),
+    Object { regexp: String },
+}
+
+impl VsCodeProblemMatcher {
+    fn into_zed_format(self, replacer: &EnvVariableReplacer) -> ProblemMatcherDefinition {
+        let custom = match self {
+            VsCodeProblemMatcher::Named(name) => return ProblemMatcherDefinition::Named(name),
+            VsCodeProblemMatcher::Custom(custom) => custom,
+        };
+        let mut matcher = custom
+            .base
+            .as_deref()
+            .and_then(|base| {
+                let matcher = ProblemMatcher::builtin(base);
+                if matcher.is_none() {
+                    log::warn!("Unknown base problem matcher {base:?}");
+                }
+                matcher
+            })
+            .unwrap_or_default();
+        if let Some(source) = custom.source.or(custom.owner) {
+            matcher.source = Some(source);
+        }
+        if let Some(severity) = custom.severity {
+            matcher.severity = severity;
+        }
+        // Code resolves relative paths against the workspace folder by default, not the working directory of the task.
+        let file_location = custom.file_location.or_else(|| {
+            custom
+                .base
+                .is_none()
+                .then(|| VsCodeFileLocation::Kind("relative".to_string()))
+        });
+        if let Some(file_location) = file_location {
+            let (kind, base_directory) = match file_location {
+                VsCodeFileLocation::Kind(kind) => (kind, None),
+                VsCodeFileLocation::WithBase(values) => {
+                    let mut values = values.into_iter();
+                    (values.next().unwrap_or_default(), values.next())
+                }
+            };
+            if kind == "absolute" {
+                matcher.file_location = FileLocation::Absolute;
+                matcher.base_directory = None;
+            } else {
+                matcher.file_location = FileLocation::Relative;
+                let base_directory = base_directory.as_deref().unwrap_or("${workspaceFolder}");
+                matcher.base_directory = Some(replacer.replace(base_directory));
+            }
+        }
+        match custom.pattern {
+            Some(VsCodeProblemPatterns::Named(name)) => match ProblemMatcher::builtin(&name) {
+                Some(builtin) => matcher.pattern = builtin.pattern,
+                None => log::warn!("Unknown problem pattern {name:?}"),
+            },
+            Some(VsCodeProblemPatterns::Single(pattern)) => {
+                matcher.pattern = vec![pattern.into_zed_format()];
+            }
+            Some(VsCodeProblemPatterns::Multiple(patterns)) => {
+                matcher.pattern = patterns
+                    .into_iter()
+                    .map(VsCodeProblemPattern::into_zed_format)
+                    .collect();
+            }
+            None => {}
+        }
+        if let Some(begins_pattern) = custom
+            .background
+            .and_then(|background| background.begins_pattern)
+        {
+            matcher.begins_pattern = Some(match begins_pattern {
+                VsCodeBackgroundPattern::Regexp(regexp)
+                | VsCodeBackgroundPattern::Object { regexp } => regexp,
+            });
+        }
+        ProblemMatcherDefinition::Custom(matcher)
+    }
+}
+
+impl VsCodeProblemPattern {
+    fn into_zed_format(self) -> ProblemPattern {
+        ProblemPattern {
+            regexp: self.regexp,
+            file: self.file,
+            line: self.line,
+            column: self.column,
+            location: self.location,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            severity: self.severity,
+            code: self.code,
+            message: self.message,
+            r#loop: self.r#loop,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 struct VsCodeTaskDefinition {
     label: String,
     command: Option<Command>,
+    depends_on: Vec<String>,
+    depends_order: DependsOrder,
+    problem_matchers: Vec<VsCodeProblemMatcher>,
     other_attributes: HashMap<String, serde_json_lenient::Value>,
     options: Option<TaskOptions>,
 }
@@ -33,6 +231,12 @@ impl<'de> serde::Deserialize<'de> for VsCodeTaskDefinition {
             label: Option<String>,
             #[serde(flatten)]
             command: Option<Command>,
+            #[serde(default)]
+            depends_on: Option<DependsOn>,
+            #[serde(default)]
+            depends_order: DependsOrder,
+            #[serde(default)]
+            problem_matcher: Option<serde_json_lenient::Value>,
             #[serde(flatten)]
             other_attributes: HashMap<String, serde_json_lenient::Value>,
             options: Option<TaskOptions>,
@@ -44,9 +248,30 @@ impl<'de> serde::Deserialize<'de> for VsCodeTaskDefinition {
             .label
             .unwrap_or_else(|| generate_label(&helper.command));
 
+        let depends_on = match helper.depends_on {
+            Some(DependsOn::Single(task)) => vec![task.label()],
+            Some(DependsOn::Multiple(tasks)) => {
+                tasks.into_iter().map(TaskReference::label).collect()
+            }
+            None => Vec::new(),
+        };
+
+        // Matchers we fail to parse are skipped, as the task can still run without them.
+        let problem_matchers = match helper
+            .problem_matcher
+            .and_then(|value| serde_json_lenient::from_value(value).log_err())
+        {
+            Some(ProblemMatchers::Single(matcher)) => vec![matcher],
+            Some(ProblemMatchers::Multiple(matchers)) => matchers,
+            None => Vec::new(),
+        };
+
         Ok(VsCodeTaskDefinition {
             label,
             command: helper.command,
+            depends_on,
+            depends_order: helper.depends_order,
+            problem_matchers,
             other_attributes: helper.other_attributes,
             options: helper.options,
         })
@@ -86,29 +311,15 @@ fn generate_label(command: &Option<Command>) -> String {
 }
 
 impl VsCodeTaskDefinition {
-    fn into_zed_format(
-        self,
-        replacer: &EnvVariableReplacer,
-    ) -> anyhow::Result<Option<TaskTemplate>> {
-        if self.other_attributes.contains_key("dependsOn") {
-            log::warn!(
-                "Skipping deserializing of a task `{}` with the unsupported `dependsOn` key",
-                self.label
-            );
-            return Ok(None);
-        }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
+    fn into_zed_format(self, replacer: &EnvVariableReplacer) -> anyhow::Result<TaskTemplate> {
+        // `type` is not set in tasks that only run the tasks from their `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
         // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
-        let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
-        };
-
-        let (command, args) = match command {
-            Command::Npm { script } => ("npm".to_owned(), vec!["run".to_string(), script]),
-            Command::Shell { command, args } => (command, args),
-            Command::Gulp { task } => ("gulp".to_owned(), vec![task]),
+        let (command, args) = match self.command {
+            Some(Command::Npm { script }) => ("npm".to_owned(), vec!["run".to_string(), script]),
+            Some(Command::Shell { command, args }) => (command, args),
+            Some(Command::Gulp { task }) => ("gulp".to_owned(), vec![task]),
+            None if !self.depends_on.is_empty() => (String::new(), Vec::new()),
+            None => bail!("Missing `type` field in task"),
         };
         // Per VSC docs, only `command`, `args` and `options` support variable substitution.
         let command = replacer.replace(&command);
@@ -117,13 +328,20 @@ impl VsCodeTaskDefinition {
             label: self.label,
             command,
             args,
+            depends_on: self.depends_on,
+            depends_order: self.depends_order,
+            problem_matchers: self
+                .problem_matchers
+                .into_iter()
+                .map(|matcher| matcher.into_zed_format(replacer))
+                .collect(),
             ..TaskTemplate::default()
         };
         if let Some(options) = self.options {
             template.cwd = options.cwd.map(|cwd| replacer.replace(&cwd));
             template.env = options.env;
         }
-        Ok(Some(template))
+        Ok(template)
     }
 }
 
@@ -152,12 +370,7 @@ impl TryFrom<VsCodeTaskFile> for TaskTemplates {
         let templates = value
             .tasks
             .into_iter()
-            .filter_map(|vscode_definition| {
-                vscode_definition
-                    .into_zed_format(&replacer)
-                    .log_err()
-                    .flatten()
-            })
+            .filter_map(|vscode_definition| vscode_definition.into_zed_format(&replacer).log_err())
             .collect();
         Ok(Self(templates))
     }
@@ -168,8 +381,12 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
+        DependsOrder, FileLocation, ProblemMatcher, ProblemMatcherDefinition, ProblemPattern,
         TaskTemplate, TaskTemplates, VsCodeTaskFile,
-        vscode_format::{Command, VsCodeTaskDefinition},
+        vscode_format::{
+            Command, CustomProblemMatcher, VsCodeFileLocation, VsCodeProblemMatcher,
+            VsCodeTaskDefinition,
+        },
     };
 
     use super::{EnvVariableReplacer, generate_label};
@@ -225,6 +442,9 @@ mod tests {
                     script: "build:tests:notypecheck".to_string(),
                 }),
                 other_attributes: Default::default(),
+                depends_on: Vec::new(),
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Named("$tsc".to_string())],
                 options: None,
             },
             VsCodeTaskDefinition {
@@ -239,6 +459,9 @@ mod tests {
                     ],
                 }),
                 other_attributes: Default::default(),
+                depends_on: Vec::new(),
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Named("$tsc-watch".to_string())],
                 options: None,
             },
             VsCodeTaskDefinition {
@@ -247,6 +470,9 @@ mod tests {
                     script: "build:compiler".to_string(),
                 }),
                 other_attributes: Default::default(),
+                depends_on: Vec::new(),
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Named("$tsc".to_string())],
                 options: None,
             },
             VsCodeTaskDefinition {
@@ -255,6 +481,9 @@ mod tests {
                     script: "build:tests:notypecheck".to_string(),
                 }),
                 other_attributes: Default::default(),
+                depends_on: Vec::new(),
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Named("$tsc".to_string())],
                 options: None,
             },
         ];
@@ -271,6 +500,7 @@ mod tests {
                 label: "gulp: tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
@@ -282,18 +512,21 @@ mod tests {
                     "${ZED_WORKTREE_ROOT}/src".to_string(),
                     "--watch".to_string(),
                 ],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$tsc-watch".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:compiler".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:compiler".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$tsc".to_string())],
                 ..Default::default()
             },
         ];
@@ -313,6 +546,16 @@ mod tests {
                 command: Some(Command::Npm {
                     script: "watch".to_string(),
                 }),
+                depends_on: Vec::new(),
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Custom(CustomProblemMatcher {
+                    base: Some("$tsc-watch".to_string()),
+                    file_location: Some(VsCodeFileLocation::WithBase(vec![
+                        "relative".to_string(),
+                        "${workspaceFolder}/editors/code/".to_string(),
+                    ])),
+                    ..CustomProblemMatcher::default()
+                })],
                 options: None,
                 other_attributes: Default::default(),
             },
@@ -321,6 +564,16 @@ mod tests {
                 command: Some(Command::Npm {
                     script: "build".to_string(),
                 }),
+                depends_on: Vec::new(),
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Custom(CustomProblemMatcher {
+                    base: Some("$tsc".to_string()),
+                    file_location: Some(VsCodeFileLocation::WithBase(vec![
+                        "relative".to_string(),
+                        "${workspaceFolder}/editors/code/".to_string(),
+                    ])),
+                    ..CustomProblemMatcher::default()
+                })],
                 options: None,
                 other_attributes: Default::default(),
             },
@@ -330,6 +583,9 @@ mod tests {
                     command: "cargo build --package rust-analyzer".to_string(),
                     args: Default::default(),
                 }),
+                depends_on: Vec::new(),
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Named("$rustc".to_string())],
                 options: None,
                 other_attributes: Default::default(),
             },
@@ -339,6 +595,9 @@ mod tests {
                     command: "cargo build --release --package rust-analyzer".to_string(),
                     args: Default::default(),
                 }),
+                depends_on: Vec::new(),
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Named("$rustc".to_string())],
                 options: None,
                 other_attributes: Default::default(),
             },
@@ -347,18 +606,37 @@ mod tests {
                 command: Some(Command::Npm {
                     script: "pretest".to_string(),
                 }),
+                depends_on: Vec::new(),
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Custom(CustomProblemMatcher {
+                    base: Some("$tsc".to_string()),
+                    file_location: Some(VsCodeFileLocation::WithBase(vec![
+                        "relative".to_string(),
+                        "${workspaceFolder}/editors/code/".to_string(),
+                    ])),
+                    ..CustomProblemMatcher::default()
+                })],
                 options: None,
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
                 label: "Build Server and Extension".to_string(),
                 command: None,
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Named("$rustc".to_string())],
                 options: None,
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
                 label: "Build Server (Release) and Extension".to_string(),
                 command: None,
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                depends_order: DependsOrder::default(),
+                problem_matchers: vec![VsCodeProblemMatcher::Named("$rustc".to_string())],
                 options: None,
                 other_attributes: Default::default(),
             },
@@ -374,28 +652,57 @@ mod tests {
                 label: "Build Extension in Background".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "watch".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Custom(ProblemMatcher {
+                    base_directory: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..ProblemMatcher::builtin("$tsc-watch").unwrap()
+                })],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Custom(ProblemMatcher {
+                    base_directory: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..ProblemMatcher::builtin("$tsc").unwrap()
+                })],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Custom(ProblemMatcher {
+                    base_directory: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..ProblemMatcher::builtin("$tsc").unwrap()
+                })],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$rustc".to_string())],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                problem_matchers: vec![ProblemMatcherDefinition::Named("$rustc".to_string())],
                 ..Default::default()
             },
         ];
@@ -403,6 +710,111 @@ mod tests {
         assert_eq!(tasks.0, expected);
     }
 
+    #[test]
+    fn can_deserialize_task_dependencies() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "tasks": [
+                    { "label": "single", "type": "shell", "command": "make", "dependsOn": "lint" },
+                    {
+                        "label": "sequence",
+                        "dependsOn": ["lint", { "type": "npm", "script": "build" }],
+                        "dependsOrder": "sequence"
+                    },
+                    { "label": "missing type" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0,
+            vec![
+                TaskTemplate {
+                    label: "single".to_string(),
+                    command: "make".to_string(),
+                    depends_on: vec!["lint".to_string()],
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "sequence".to_string(),
+                    depends_on: vec!["lint".to_string(), "npm: build".to_string()],
+                    depends_order: DependsOrder::Sequence,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn can_deserialize_custom_problem_matchers() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "tasks": [
+                    {
+                        "label": "lint",
+                        "type": "shell",
+                        "command": "lint",
+                        "problemMatcher": {
+                            "owner": "lint",
+                            "fileLocation": "absolute",
+                            "pattern": [
+                                { "regexp": "^(.*)$", "file": 1 },
+                                { "regexp": "^(\\d+,\\d+) (.*)$", "location": 1, "message": 2, "loop": true }
+                            ],
+                            "background": { "beginsPattern": "^Linting" }
+                        }
+                    },
+                    {
+                        "label": "broken matcher",
+                        "type": "shell",
+                        "command": "make",
+                        "problemMatcher": { "pattern": { "file": 1 } }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0,
+            vec![
+                TaskTemplate {
+                    label: "lint".to_string(),
+                    command: "lint".to_string(),
+                    problem_matchers: vec![ProblemMatcherDefinition::Custom(ProblemMatcher {
+                        source: Some("lint".to_string()),
+                        file_location: FileLocation::Absolute,
+                        pattern: vec![
+                            ProblemPattern {
+                                regexp: "^(.*)$".to_string(),
+                                file: Some(1),
+                                ..ProblemPattern::default()
+                            },
+                            ProblemPattern {
+                                regexp: "^(\\d+,\\d+) (.*)$".to_string(),
+                                location: Some(1),
+                                message: Some(2),
+                                r#loop: true,
+                                ..ProblemPattern::default()
+                            },
+                        ],
+                        begins_pattern: Some("^Linting".to_string()),
+                        ..ProblemMatcher::default()
+                    })],
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "broken matcher".to_string(),
+                    command: "make".to_string(),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
     #[test]
     fn can_deserialize_tasks_without_labels() {
         const TASKS_WITHOUT_LABELS: &str = include_str!("../test_data/tasks-without-labels.json");
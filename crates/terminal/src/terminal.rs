@@ -2341,6 +2341,67 @@ pub fn row_to_string(row: &Row<Cell>) -> String {
         .collect::<String>()
 }
 
+/// Reads the output of a terminal line by line as it arrives, e.g. to match the output of a task.
+#[derive(Debug, Default)]
+pub struct TerminalOutputReader {
+    /// Index of the next row to read, counting from the top of the scrollback.
+    next_row: usize,
+    /// Text of the last row read, to find the rows that were read already
+    /// once the scrollback is full and starts dropping its oldest rows.
+    last_row: Option<String>,
+    /// Start of a line that wraps onto the rows not read yet.
+    pending_line: String,
+}
+
+impl TerminalOutputReader {
+    /// Returns the lines completed since the previous call, joining the wrapped rows.
+    /// The line with the cursor is only returned if `include_cursor_line` is set, e.g. after the task has finished.
+    pub fn read_lines(&mut self, terminal: &Terminal, include_cursor_line: bool) -> Vec<String> {
+        let term = terminal.term.lock_unfair();
+        let grid = term.grid();
+        let history_size = grid.history_size();
+        let grid_row = move |row: usize| &grid[Line(row as i32 - history_size as i32)];
+        let cursor_row = history_size + grid.cursor.point.line.0.max(0) as usize;
+        let end_row = if include_cursor_line {
+            cursor_row + 1
+        } else {
+            cursor_row
+        };
+
+        let total_rows = history_size + grid.screen_lines();
+        if let Some(last_row) = &self.last_row
+            && self.next_row > 0
+            && (self.next_row > total_rows
+                || row_to_string(grid_row(self.next_row - 1)) != *last_row)
+        {
+            self.next_row = (0..self.next_row.min(total_rows))
+                .rev()
+                .find(|row| row_to_string(grid_row(*row)) == *last_row)
+                .map_or(0, |row| row + 1);
+        }
+
+        let mut lines = Vec::new();
+        for row in self.next_row..end_row {
+            let cells = grid_row(row);
+            let text = row_to_string(cells);
+            self.pending_line.push_str(&text);
+            let wraps = cells[Column(grid.columns() - 1)]
+                .flags
+                .contains(Flags::WRAPLINE);
+            if !wraps || row + 1 == end_row && include_cursor_line {
+                lines.push(
+                    std::mem::take(&mut self.pending_line)
+                        .trim_end()
+                        .to_string(),
+                );
+            }
+            self.last_row = Some(text);
+        }
+        self.next_row = self.next_row.max(end_row);
+        lines
+    }
+}
+
 const TASK_DELIMITER: &str = "⏵ ";
 fn task_summary(task: &TaskState, exit_status: Option<ExitStatus>) -> (bool, String, String) {
     let escaped_full_label = task
@@ -3207,6 +3268,43 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_output_reader(cx: &mut TestAppContext) {
+        let terminal = cx.new(|cx| {
+            TerminalBuilder::new_display_only(
+                CursorShape::default(),
+                AlternateScroll::On,
+                None,
+                0,
+                cx.background_executor(),
+                PathStyle::local(),
+            )
+            .unwrap()
+            .subscribe(cx)
+        });
+        let mut reader = TerminalOutputReader::default();
+
+        terminal.update(cx, |terminal, cx| {
+            terminal.write_output(b"first\nsecond\nthi", cx);
+            assert_eq!(
+                reader.read_lines(terminal, false),
+                vec!["first".to_string(), "second".to_string()]
+            );
+            assert!(reader.read_lines(terminal, false).is_empty());
+
+            terminal.write_output(b"rd\nfourth", cx);
+            assert_eq!(
+                reader.read_lines(terminal, false),
+                vec!["third".to_string()]
+            );
+            assert_eq!(
+                reader.read_lines(terminal, true),
+                vec!["fourth".to_string()]
+            );
+            assert!(reader.read_lines(terminal, true).is_empty());
+        });
+    }
+
     #[gpui::test]
     async fn test_hyperlink_ctrl_click_same_position(cx: &mut TestAppContext) {
         let terminal = init_ctrl_click_hyperlink_test(cx, b"Visit https://zed.dev/ for more\r\n");
@@ -378,12 +378,15 @@ pub async fn write_default_dock_state(
 #[derive(Debug)]
 pub struct Bookmark {
     pub row: u32,
+    pub label: Option<Arc<str>>,
+    pub note: Option<Arc<str>>,
+    pub sort_order: u32,
 }
 
 impl sqlez::bindable::StaticColumnCount for Bookmark {
     fn column_count() -> usize {
-        // row
-        1
+        // row, label, note and sort order
+        4
     }
 }
 
@@ -393,7 +396,10 @@ impl sqlez::bindable::Bind for Bookmark {
         statement: &sqlez::statement::Statement,
         start_index: i32,
     ) -> anyhow::Result<i32> {
-        statement.bind(&self.row, start_index)
+        let next_index = statement.bind(&self.row, start_index)?;
+        let next_index = statement.bind(&self.label, next_index)?;
+        let next_index = statement.bind(&self.note, next_index)?;
+        statement.bind(&self.sort_order, next_index)
     }
 }
 
@@ -403,8 +409,22 @@ impl Column for Bookmark {
             .column_int(start_index)
             .with_context(|| format!("Failed to read bookmark at index {start_index}"))?
             as u32;
+        let (label, next_index) = Option::<String>::column(statement, start_index + 1)?;
+        let (note, next_index) = Option::<String>::column(statement, next_index)?;
+        let sort_order = statement
+            .column_int(next_index)
+            .with_context(|| format!("Failed to read bookmark order at index {next_index}"))?
+            as u32;
 
-        Ok((Bookmark { row }, start_index + 1))
+        Ok((
+            Bookmark {
+                row,
+                label: label.map(Arc::from),
+                note: note.map(Arc::from),
+                sort_order,
+            },
+            next_index + 1,
+        ))
     }
 }
 
@@ -1024,6 +1044,11 @@ impl Domain for WorkspaceDb {
                 ON UPDATE CASCADE
             );
         ),
+        sql!(
+            ALTER TABLE bookmarks ADD COLUMN label TEXT;
+            ALTER TABLE bookmarks ADD COLUMN note TEXT;
+            ALTER TABLE bookmarks ADD COLUMN sort_order INTEGER NOT NULL DEFAULT 0;
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -1259,10 +1284,10 @@ impl WorkspaceDb {
     fn bookmarks(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
         let bookmarks: Result<Vec<(PathBuf, Bookmark)>> = self
             .select_bound(sql! {
-                SELECT path, row
+                SELECT path, row, label, note, sort_order
                 FROM bookmarks
                 WHERE workspace_id = ?
-                ORDER BY path, row
+                ORDER BY path, sort_order, row
             })
             .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
 
@@ -1278,7 +1303,12 @@ impl WorkspaceDb {
                     let path: Arc<Path> = path.into();
                     map.entry(path.clone())
                         .or_default()
-                        .push(SerializedBookmark(bookmark.row))
+                        .push(SerializedBookmark {
+                            row: bookmark.row,
+                            label: bookmark.label,
+                            note: bookmark.note,
+                            order: bookmark.sort_order,
+                        })
                 }
 
                 map
@@ -1440,9 +1470,16 @@ impl WorkspaceDb {
                 for (path, bookmarks) in workspace.bookmarks {
                     for bookmark in bookmarks {
                         conn.exec_bound(sql!(
-                            INSERT INTO bookmarks (workspace_id, path, row)
-                            VALUES (?1, ?2, ?3);
-                        ))?((workspace.id, path.as_ref(), bookmark.0)).context("Inserting bookmark")?;
+                            INSERT INTO bookmarks (workspace_id, path, row, label, note, sort_order)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6);
+                        ))?((
+                            workspace.id,
+                            path.as_ref(),
+                            bookmark.row,
+                            bookmark.label,
+                            bookmark.note,
+                            bookmark.order,
+                        )).context("Inserting bookmark")?;
                     }
                 }
 
@@ -2983,6 +3020,55 @@ mod tests {
         assert!(empty_breakpoints.is_none());
     }
 
+    #[gpui::test]
+    async fn test_bookmarks() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_bookmarks").await;
+        let id = db.next_id().await.unwrap();
+
+        let path = Path::new("/tmp/test_bookmarks.rs");
+        let bookmarks = vec![
+            SerializedBookmark {
+                row: 12,
+                label: Some(Arc::from("Parser entry")),
+                note: Some(Arc::from("Handles the BOM")),
+                order: 0,
+            },
+            SerializedBookmark {
+                row: 3,
+                label: None,
+                note: None,
+                order: 1,
+            },
+        ];
+
+        let workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            bookmarks: {
+                let mut map = collections::BTreeMap::default();
+                map.insert(Arc::from(path), bookmarks.clone());
+                map
+            },
+            breakpoints: Default::default(),
+            session_id: None,
+            window_id: None,
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.bookmarks.get(&Arc::from(path)), Some(&bookmarks));
+    }
+
     #[gpui::test]
     async fn test_next_id_stability() {
         zlog::init_test();
@@ -1,14 +1,18 @@
-use std::process::ExitStatus;
+use std::{cell::RefCell, process::ExitStatus, rc::Rc, sync::Arc};
 
-use anyhow::Result;
-use collections::HashSet;
-use gpui::{AppContext, Context, Entity, Task};
+use anyhow::{Context as _, Result, anyhow, bail};
+use collections::{HashMap, HashSet};
+use futures::{
+    FutureExt as _, TryFutureExt as _,
+    future::{LocalBoxFuture, Shared},
+};
+use gpui::{App, AppContext, AsyncWindowContext, Context, Entity, Task, WeakEntity};
 use language::Buffer;
-use project::{TaskSourceKind, WorktreeId};
+use project::{TaskDependency, TaskSourceKind, WorktreeId};
 use remote::ConnectionState;
 use task::{
-    DebugScenario, ResolvedTask, SaveStrategy, SharedTaskContext, SpawnInTerminal, TaskContext,
-    TaskHook, TaskTemplate, TaskVariables, VariableName,
+    DebugScenario, DependsOrder, ResolvedTask, SaveStrategy, SharedTaskContext, SpawnInTerminal,
+    TaskContext, TaskHook, TaskId, TaskTemplate, TaskVariables, VariableName,
 };
 use ui::Window;
 use util::TryFutureExt;
@@ -60,6 +64,27 @@ impl Workspace {
         cx: &mut Context<Workspace>,
     ) {
         let spawn_in_terminal = resolved_task.resolved.clone();
+        let task_label = resolved_task.resolved_label.clone();
+        let dependencies = match self.task_dependencies(&task_source_kind, &resolved_task, cx) {
+            Ok(dependencies) => dependencies,
+            Err(e) => {
+                log::error!("Failed to resolve dependencies of task `{task_label}`: {e:#}");
+                let id = NotificationId::unique::<TaskDependency>();
+                self.show_toast(
+                    Toast::new(id, format!("Task `{task_label}` not run: {e}")),
+                    cx,
+                );
+                return;
+            }
+        };
+        let depends_order = resolved_task.original_task().depends_order;
+        let dependencies_context = Arc::new(
+            resolved_task
+                .dependencies_context()
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let has_command = !resolved_task.original_task().command.trim().is_empty();
         if !omit_history {
             if let Some(debugger_provider) = self.debugger_provider.as_ref() {
                 debugger_provider.task_scheduled(cx);
@@ -99,6 +124,30 @@ impl Workspace {
                     save_action.log_err().await;
                 }
 
+                if let Err(e) = run_task_dependencies(
+                    workspace.clone(),
+                    dependencies,
+                    depends_order,
+                    dependencies_context,
+                    DependencyRuns::default(),
+                    cx.clone(),
+                )
+                .await
+                {
+                    log::error!("Task `{task_label}` not run: {e:#}");
+                    _ = workspace.update(cx, |w, cx| {
+                        let id = NotificationId::unique::<TaskDependency>();
+                        w.show_toast(
+                            Toast::new(id, format!("Task `{task_label}` not run: {e}")),
+                            cx,
+                        );
+                    });
+                    return;
+                }
+                if !has_command {
+                    return;
+                }
+
                 let spawn_task = workspace.update_in(cx, |workspace, window, cx| {
                     workspace
                         .terminal_provider
@@ -132,6 +181,31 @@ impl Workspace {
         }
     }
 
+    /// Tasks to run before the resolved one, looked up among the tasks of its worktree and the global ones.
+    fn task_dependencies(
+        &self,
+        task_source_kind: &TaskSourceKind,
+        resolved_task: &ResolvedTask,
+        cx: &App,
+    ) -> Result<Vec<TaskDependency>> {
+        let template = resolved_task.original_task();
+        if template.depends_on.is_empty() {
+            return Ok(Vec::new());
+        }
+        let inventory = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .context("task dependencies are not available in this project")?;
+        let worktree = match task_source_kind {
+            TaskSourceKind::Worktree { id, .. } => Some(*id),
+            _ => None,
+        };
+        inventory.read(cx).task_dependencies(template, worktree)
+    }
+
     pub fn start_debug_session(
         &mut self,
         scenario: DebugScenario,
@@ -272,6 +346,110 @@ impl Workspace {
     }
 }
 
+/// Runs of the dependencies of a task, by the id of the resolved dependency,
+/// so that a dependency reached through several paths of the graph runs once.
+type DependencyRuns =
+    Rc<RefCell<HashMap<TaskId, Shared<LocalBoxFuture<'static, Result<(), Arc<anyhow::Error>>>>>>>;
+
+/// Runs the dependencies of a task, each after its own dependencies, failing as soon as any of them fails.
+fn run_task_dependencies(
+    workspace: WeakEntity<Workspace>,
+    dependencies: Vec<TaskDependency>,
+    order: DependsOrder,
+    task_context: Arc<TaskContext>,
+    dependency_runs: DependencyRuns,
+    cx: AsyncWindowContext,
+) -> LocalBoxFuture<'static, Result<()>> {
+    async move {
+        let runs = dependencies.into_iter().map(|dependency| {
+            run_task_dependency(
+                workspace.clone(),
+                dependency,
+                task_context.clone(),
+                dependency_runs.clone(),
+                cx.clone(),
+            )
+        });
+        match order {
+            DependsOrder::Parallel => {
+                futures::future::try_join_all(runs).await?;
+            }
+            DependsOrder::Sequence => {
+                for run in runs {
+                    run.await?;
+                }
+            }
+        }
+        Ok(())
+    }
+    .boxed_local()
+}
+
+async fn run_task_dependency(
+    workspace: WeakEntity<Workspace>,
+    dependency: TaskDependency,
+    task_context: Arc<TaskContext>,
+    dependency_runs: DependencyRuns,
+    mut cx: AsyncWindowContext,
+) -> Result<()> {
+    let template = dependency.template;
+    if template.command.trim().is_empty() {
+        return run_task_dependencies(
+            workspace,
+            dependency.dependencies,
+            template.depends_order,
+            task_context,
+            dependency_runs,
+            cx,
+        )
+        .await;
+    }
+
+    let resolved_task = template
+        .resolve_task(&dependency.source_kind.to_id_base(), &task_context)
+        .with_context(|| format!("failed to resolve task `{}`", template.label))?;
+    let existing_run = dependency_runs.borrow().get(&resolved_task.id).cloned();
+    let run = match existing_run {
+        Some(run) => run,
+        None => {
+            let task_id = resolved_task.id.clone();
+            let run = {
+                let dependency_runs = dependency_runs.clone();
+                async move {
+                    run_task_dependencies(
+                        workspace.clone(),
+                        dependency.dependencies,
+                        template.depends_order,
+                        task_context,
+                        dependency_runs,
+                        cx.clone(),
+                    )
+                    .await?;
+                    let status = workspace
+                        .update_in(&mut cx, |workspace, window, cx| {
+                            workspace.spawn_in_terminal(resolved_task.resolved, window, cx)
+                        })?
+                        .await;
+                    match status {
+                        Some(Ok(status)) if status.success() => Ok(()),
+                        Some(Ok(status)) => bail!("task `{}` failed with {status}", template.label),
+                        Some(Err(e)) => {
+                            Err(e.context(format!("failed to spawn task `{}`", template.label)))
+                        }
+                        None => bail!("task `{}` was cancelled", template.label),
+                    }
+                }
+                .map_err(Arc::new)
+                .boxed_local()
+                .shared()
+            };
+            dependency_runs.borrow_mut().insert(task_id, run.clone());
+            run
+        }
+    };
+    run.await.map_err(|error| anyhow!("{error:#}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -282,9 +460,9 @@ mod tests {
     };
     use gpui::{App, TestAppContext};
     use parking_lot::Mutex;
-    use project::{FakeFs, Project, TaskSourceKind};
+    use project::{FakeFs, Project, TaskSourceKind, task_store::TaskSettingsLocation};
     use serde_json::json;
-    use std::sync::Arc;
+    use std::{path::Path, sync::Arc};
     use task::TaskTemplate;
 
     struct Fixture {
@@ -353,6 +531,143 @@ mod tests {
         assert!(cx.read(|cx| fixture.item.read(cx).is_dirty));
     }
 
+    #[gpui::test]
+    async fn test_schedule_task_with_dependencies(cx: &mut TestAppContext) {
+        let (workspace, spawned, cx) = create_dependencies_fixture(cx).await;
+
+        schedule_task_by_label(&workspace, "all", cx).await;
+        assert_eq!(
+            *spawned.lock(),
+            ["lint", "build", "test"],
+            "dependencies should run in sequence, each after its own dependencies, \
+            and the task without command should not spawn anything"
+        );
+
+        spawned.lock().clear();
+        schedule_task_by_label(&workspace, "deploy", cx).await;
+        assert_eq!(
+            *spawned.lock(),
+            ["failing check"],
+            "a failing dependency should prevent the following ones and the task itself from running"
+        );
+
+        spawned.lock().clear();
+        schedule_task_by_label(&workspace, "ping", cx).await;
+        assert!(
+            spawned.lock().is_empty(),
+            "no task should run when the dependencies form a cycle"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_schedule_task_with_shared_dependency(cx: &mut TestAppContext) {
+        let (workspace, spawned, cx) = create_dependencies_fixture(cx).await;
+
+        schedule_task_by_label(&workspace, "release", cx).await;
+        let spawned = spawned.lock().clone();
+        assert_eq!(
+            spawned.iter().filter(|label| *label == "fetch").count(),
+            1,
+            "a dependency shared by several dependencies should run once, got {spawned:?}"
+        );
+        assert_eq!(spawned.first().map(String::as_str), Some("fetch"));
+        assert_eq!(spawned.last().map(String::as_str), Some("release"));
+        assert_eq!(spawned.len(), 4, "unexpected tasks spawned: {spawned:?}");
+    }
+
+    async fn create_dependencies_fixture(
+        cx: &mut TestAppContext,
+    ) -> (
+        Entity<Workspace>,
+        Arc<Mutex<Vec<String>>>,
+        &mut gpui::VisualTestContext,
+    ) {
+        cx.update(|cx| {
+            let settings_store = settings::SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+        });
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({ "file.txt": "" })).await;
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        let inventory = project.read_with(cx, |project, cx| {
+            project
+                .task_store()
+                .read(cx)
+                .task_inventory()
+                .cloned()
+                .unwrap()
+        });
+        inventory.update(cx, |inventory, _| {
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Global(Path::new("/tasks.json")),
+                    Some(
+                        &json!([
+                            { "label": "lint", "command": "lint" },
+                            { "label": "build", "command": "build", "depends_on": ["lint"] },
+                            { "label": "test", "command": "test" },
+                            { "label": "all", "depends_on": ["build", "test"], "depends_order": "sequence" },
+                            { "label": "failing check", "command": "check" },
+                            {
+                                "label": "deploy",
+                                "command": "deploy",
+                                "depends_on": ["failing check", "lint"],
+                                "depends_order": "sequence"
+                            },
+                            { "label": "ping", "command": "ping", "depends_on": ["pong"] },
+                            { "label": "pong", "command": "pong", "depends_on": ["ping"] },
+                            { "label": "fetch", "command": "fetch" },
+                            { "label": "compile", "command": "compile", "depends_on": ["fetch"] },
+                            { "label": "docs", "command": "docs", "depends_on": ["fetch"] },
+                            { "label": "release", "command": "release", "depends_on": ["compile", "docs"] },
+                        ])
+                        .to_string(),
+                    ),
+                )
+                .unwrap();
+        });
+
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let spawned: Arc<Mutex<Vec<String>>> = Arc::default();
+        let terminal_provider = Box::new(RecordingTerminalProvider {
+            spawned: spawned.clone(),
+        });
+        workspace.update(cx, |workspace, _| {
+            workspace.terminal_provider = Some(terminal_provider);
+        });
+        (workspace, spawned, cx)
+    }
+
+    async fn schedule_task_by_label(
+        workspace: &Entity<Workspace>,
+        label: &str,
+        cx: &mut gpui::VisualTestContext,
+    ) {
+        let project = workspace.read_with(cx, |workspace, _| workspace.project().clone());
+        let templates = project.read_with(cx, |project, cx| {
+            let inventory = project.task_store().read(cx).task_inventory().cloned();
+            inventory.unwrap().read(cx).list_tasks(None, None, None, cx)
+        });
+        let (_, template) = templates
+            .await
+            .into_iter()
+            .find(|(_, template)| template.label == label)
+            .unwrap();
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.schedule_task(
+                TaskSourceKind::UserInput,
+                &template,
+                &task::TaskContext::default(),
+                true,
+                window,
+                cx,
+            );
+        });
+        cx.executor().run_until_parked();
+    }
+
     async fn create_fixture(
         cx: &mut TestAppContext,
         save_strategy: SaveStrategy,
@@ -417,6 +732,28 @@ mod tests {
         item
     }
 
+    /// Records the labels of the spawned tasks, failing the ones labeled as such.
+    struct RecordingTerminalProvider {
+        spawned: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl TerminalProvider for RecordingTerminalProvider {
+        fn spawn(
+            &self,
+            task: task::SpawnInTerminal,
+            _window: &mut ui::Window,
+            _cx: &mut App,
+        ) -> Task<Option<Result<ExitStatus>>> {
+            let failing = task.label.starts_with("failing");
+            self.spawned.lock().push(task.label);
+            if failing {
+                Task::ready(Some(Err(anyhow::anyhow!("task failed"))))
+            } else {
+                Task::ready(Some(Ok(ExitStatus::default())))
+            }
+        }
+    }
+
     struct TestTerminalProvider {
         item: Entity<TestItem>,
         dirty_before_spawn: Arc<Mutex<Option<bool>>>,
@@ -187,6 +187,8 @@ fn run_visual_tests(project_path: PathBuf, update_baseline: bool) -> Result<()>
         title_bar::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        bookmarks_panel::init(cx);
+        call_hierarchy_panel::init(cx);
         terminal_view::init(cx);
         image_viewer::init(cx);
         search::init(cx);
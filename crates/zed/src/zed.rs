@@ -17,6 +17,8 @@ use agent_ui::AgentDiffToolbar;
 use anyhow::Context as _;
 pub use app_menus::*;
 use assets::Assets;
+use bookmarks_panel::BookmarksPanel;
+use call_hierarchy_panel::CallHierarchyPanel;
 
 use breadcrumbs::Breadcrumbs;
 use client::zed_urls;
@@ -696,6 +698,8 @@ fn initialize_panels(window: &mut Window, cx: &mut Context<Workspace>) -> Task<a
     cx.spawn_in(window, async move |workspace_handle, cx| {
         let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
         let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
+        let bookmarks_panel = BookmarksPanel::load(workspace_handle.clone(), cx.clone());
+        let call_hierarchy_panel = CallHierarchyPanel::load(workspace_handle.clone(), cx.clone());
         let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
         let git_panel = GitPanel::load(workspace_handle.clone(), cx.clone());
         let channels_panel =
@@ -720,6 +724,8 @@ fn initialize_panels(window: &mut Window, cx: &mut Context<Workspace>) -> Task<a
         futures::join!(
             add_panel_when_ready(project_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(outline_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(bookmarks_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(call_hierarchy_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(terminal_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(git_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(channels_panel, workspace_handle.clone(), cx.clone()),
@@ -5104,8 +5110,11 @@ mod tests {
                 "auto_update",
                 "branch_picker",
                 "bedrock",
+                "bookmarks_panel",
                 "branches",
                 "buffer_search",
+                "call_hierarchy",
+                "call_hierarchy_panel",
                 "channel_modal",
                 "cli",
                 "client",
@@ -5176,6 +5185,7 @@ mod tests {
                 "theme_selector",
                 "toast",
                 "toolchain",
+                "type_hierarchy",
                 "variable_list",
                 "vim",
                 "window",
@@ -5365,6 +5375,8 @@ mod tests {
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
+            bookmarks_panel::init(cx);
+            call_hierarchy_panel::init(cx);
             terminal_view::init(cx);
             copilot_chat::init(
                 app_state.fs.clone(),
@@ -42,6 +42,8 @@ pub fn app_menus(cx: &mut App) -> Vec<Menu> {
         MenuItem::separator(),
         MenuItem::action("Project Panel", zed_actions::project_panel::ToggleFocus),
         MenuItem::action("Outline Panel", outline_panel::ToggleFocus),
+        MenuItem::action("Bookmarks Panel", bookmarks_panel::ToggleFocus),
+        MenuItem::action("Call Hierarchy Panel", call_hierarchy_panel::ToggleFocus),
         MenuItem::action("Collab Panel", collab_panel::ToggleFocus),
         MenuItem::action("Terminal Panel", terminal_panel::ToggleFocus),
         MenuItem::action("Debugger Panel", debug_panel::ToggleFocus),
@@ -250,6 +252,14 @@ pub fn app_menus(cx: &mut App) -> Vec<Menu> {
                     "Find All References",
                     editor::actions::FindAllReferences::default(),
                 ),
+                MenuItem::action(
+                    "Show Call Hierarchy",
+                    zed_actions::call_hierarchy::ShowCallHierarchy,
+                ),
+                MenuItem::action(
+                    "Show Type Hierarchy",
+                    zed_actions::type_hierarchy::ShowTypeHierarchy,
+                ),
                 MenuItem::separator(),
                 MenuItem::action("Next Problem", editor::actions::GoToDiagnostic::default()),
                 MenuItem::action(
@@ -698,6 +698,32 @@ pub mod outline {
     pub static TOGGLE_OUTLINE: OnceLock<fn(AnyView, &mut Window, &mut App)> = OnceLock::new();
 }
 
+pub mod call_hierarchy {
+    use gpui::actions;
+
+    actions!(
+        call_hierarchy,
+        [
+            /// Shows the callers and callees of the function under the cursor
+            /// in the call hierarchy panel.
+            ShowCallHierarchy
+        ]
+    );
+}
+
+pub mod type_hierarchy {
+    use gpui::actions;
+
+    actions!(
+        type_hierarchy,
+        [
+            /// Shows the supertypes and subtypes of the type under the cursor
+            /// in a type hierarchy view.
+            ShowTypeHierarchy
+        ]
+    );
+}
+
 actions!(
     zed_predict_onboarding,
     [